@@ -0,0 +1,281 @@
+use actix_web::{
+    delete, get,
+    http::StatusCode,
+    patch, post,
+    web::{Data, Json, Path, ServiceConfig},
+    HttpResponse, ResponseError,
+};
+use chrono::Utc;
+use log::error;
+use utoipa::OpenApi;
+use validator::Validate;
+
+use crate::{
+    db::{account_db::AccountDB, config::Database, contact_db::ContactDB},
+    error::{account_error::AccountError, api_error::ApiError},
+    models::{
+        account_model::{Account, AccountDetail, AccountUuid, Contact},
+        sales_model::LeadType,
+        users_model::ActingUser,
+    },
+    utils::{
+        account_utils::{convert_lead_to_account, find_account_detail},
+        general_utils::shuffle_id,
+        i18n_utils::RequestLocale,
+        message_utils::get_localized_validation_errors,
+        phone_utils::normalize_optional_phone,
+        role_utils::is_active_user,
+    },
+};
+
+fn error_response(account_error: AccountError, message: &str) -> HttpResponse {
+    HttpResponse::build(account_error.status_code())
+        .insert_header((
+            "HX-Trigger",
+            format!("{{ \"page_error\": {:?} }}", message.to_string()),
+        ))
+        .json(ApiError::new(account_error).with_message(message))
+}
+
+fn forbidden_response() -> HttpResponse {
+    error_response(
+        AccountError::AccountForbidden,
+        "Inicia sesión para administrar cuentas",
+    )
+}
+
+fn invalid_response(account_error: AccountError, key_errors_vec: Vec<String>) -> HttpResponse {
+    HttpResponse::BadRequest()
+        .insert_header((
+            "HX-Trigger",
+            format!("{{ \"page_error\": {:?} }}", key_errors_vec),
+        ))
+        .json(ApiError::new(account_error).with_field_errors(key_errors_vec))
+}
+
+#[utoipa::path(
+    tag = "Accounts",
+    operation_id = "convert_lead",
+    responses(
+        (status = 201, description = "Lead converted into an account", body = AccountUuid),
+        (status = 400, description = "Lead can't be converted", body = ApiError),
+        (status = 404, description = "Lead not found", body = ApiError),
+    )
+)]
+#[post("/leads/{lead_type}/{uuid}/convert")]
+#[tracing::instrument(name = "Convert Lead", skip(db, path))]
+async fn convert(
+    db: Data<Database>,
+    path: Path<(LeadType, String)>,
+    acting_user: ActingUser,
+) -> Result<HttpResponse, AccountError> {
+    let converted_by = acting_user.user_uuid;
+    if !is_active_user(&db, converted_by.clone()).await {
+        return Ok(forbidden_response());
+    }
+
+    let (lead_type, lead_uuid) = path.into_inner();
+
+    match convert_lead_to_account(&db, &lead_type, lead_uuid, converted_by).await {
+        Ok(account) => Ok(HttpResponse::Ok()
+            .insert_header(("HX-Trigger", "account_reload_page"))
+            .status(StatusCode::CREATED)
+            .json(AccountUuid {
+                uuid: account.uuid.unwrap_or_default(),
+            })),
+        Err(AccountError::LeadNotWon) => Ok(error_response(
+            AccountError::LeadNotWon,
+            "Solo un prospecto ganado puede convertirse en cuenta",
+        )),
+        Err(AccountError::LeadAlreadyConverted) => Ok(error_response(
+            AccountError::LeadAlreadyConverted,
+            "Este prospecto ya fue convertido en cuenta",
+        )),
+        Err(AccountError::LeadNotFound) => Ok(error_response(
+            AccountError::LeadNotFound,
+            "No encontramos el prospecto",
+        )),
+        Err(e) => Ok(error_response(e, "No fue posible crear la cuenta")),
+    }
+}
+
+#[utoipa::path(
+    tag = "Accounts",
+    operation_id = "find_all_accounts",
+    responses(
+        (status = 200, description = "All accounts", body = Vec<Account>),
+        (status = 404, description = "No accounts found", body = ApiError),
+    )
+)]
+#[get("/accounts")]
+#[tracing::instrument(name = "Show Accounts", skip(db))]
+async fn find_all(db: Data<Database>) -> Result<HttpResponse, AccountError> {
+    match <Database as AccountDB>::find_all_active(&db).await {
+        Some(accounts) => Ok(HttpResponse::Ok().status(StatusCode::OK).json(accounts)),
+        None => {
+            error!("Unable to find any account");
+            Ok(HttpResponse::NotFound().json(ApiError::new(AccountError::NoAccountsFound)))
+        }
+    }
+}
+
+#[utoipa::path(
+    tag = "Accounts",
+    operation_id = "find_one_account",
+    params(AccountUuid),
+    responses(
+        (status = 200, description = "Account with its contacts", body = AccountDetail),
+        (status = 404, description = "Account not found", body = ApiError),
+    )
+)]
+#[get("/accounts/{uuid}")]
+#[tracing::instrument(name = "Get One Account", skip(db), fields(uuid = %uuid.uuid))]
+async fn find_one(
+    db: Data<Database>,
+    uuid: Path<AccountUuid>,
+) -> Result<HttpResponse, AccountError> {
+    let account_uuid = uuid.into_inner().uuid;
+
+    match find_account_detail(&db, account_uuid.clone()).await {
+        Some(detail) => Ok(HttpResponse::Ok().status(StatusCode::OK).json(detail)),
+        None => {
+            error!("No account found for UUID:: {:?}", &account_uuid);
+            Ok(HttpResponse::NotFound().json(ApiError::new(AccountError::NoAccountsFound)))
+        }
+    }
+}
+
+#[utoipa::path(
+    tag = "Accounts",
+    operation_id = "update_account",
+    responses(
+        (status = 200, description = "Account updated", body = AccountUuid),
+        (status = 400, description = "Invalid account", body = ApiError),
+        (status = 404, description = "Account not found", body = ApiError),
+    )
+)]
+#[patch("/accounts")]
+#[tracing::instrument(name = "Patch Account", skip(db), fields(name = %body.name))]
+async fn update_one(
+    db: Data<Database>,
+    mut body: Json<Account>,
+    acting_user: ActingUser,
+    locale: RequestLocale,
+) -> Result<HttpResponse, AccountError> {
+    body.modified_by = acting_user.user_uuid.clone();
+    if !is_active_user(&db, body.modified_by.clone()).await {
+        return Ok(forbidden_response());
+    }
+
+    if let Err(e) = body.validate() {
+        error!("Error in account.update_one: {:?}", e);
+        return Ok(invalid_response(
+            AccountError::AccountCreationFailure,
+            get_localized_validation_errors(&e, &locale.0),
+        ));
+    }
+
+    let uuid_in_db = body.uuid.clone().unwrap_or(String::from("forbidden"));
+    let stored_account = match <Database as AccountDB>::find_one(&db, uuid_in_db.clone()).await {
+        Some(account) => account,
+        None => {
+            error!("No account found for UUID:: {:?}", &uuid_in_db);
+            return Ok(HttpResponse::NotFound().json(ApiError::new(AccountError::NoAccountsFound)));
+        }
+    };
+
+    let updated_account = body.into_inner();
+    let account_to_update = Account {
+        lead_type: stored_account.lead_type,
+        lead_uuid: stored_account.lead_uuid,
+        phone: normalize_optional_phone(updated_account.phone.clone()),
+        date_created: stored_account.date_created,
+        created_by: stored_account.created_by,
+        date_modified: Some(Utc::now()),
+        ..updated_account
+    };
+
+    match <Database as AccountDB>::update_one(&db, account_to_update).await {
+        Some(account) => Ok(HttpResponse::Ok()
+            .insert_header(("HX-Trigger", "account_reload_page"))
+            .status(StatusCode::OK)
+            .json(AccountUuid {
+                uuid: match account.uuid {
+                    Some(this_uuid) => shuffle_id(this_uuid),
+                    None => "".to_string(),
+                },
+            })),
+        None => Ok(error_response(
+            AccountError::AccountCreationFailure,
+            "Couldn't save account",
+        )),
+    }
+}
+
+#[utoipa::path(
+    tag = "Accounts",
+    operation_id = "delete_account",
+    params(AccountUuid),
+    responses(
+        (status = 200, description = "Account deleted", body = AccountUuid),
+        (status = 404, description = "Account not found", body = ApiError),
+    )
+)]
+#[delete("/accounts/{uuid}")]
+#[tracing::instrument(name = "Delete Account", skip(db), fields(uuid = %uuid.uuid))]
+async fn delete_one(
+    db: Data<Database>,
+    uuid: Path<AccountUuid>,
+    acting_user: ActingUser,
+) -> Result<HttpResponse, AccountError> {
+    let user_uuid = acting_user.user_uuid;
+    if !is_active_user(&db, user_uuid.clone()).await {
+        return Ok(forbidden_response());
+    }
+
+    let account_uuid = uuid.into_inner().uuid;
+
+    match <Database as AccountDB>::delete_one(&db, account_uuid.clone(), user_uuid).await {
+        Some(_) => Ok(HttpResponse::Ok()
+            .insert_header(("HX-Trigger", "account_reload_page"))
+            .status(StatusCode::OK)
+            .json(AccountUuid {
+                uuid: shuffle_id(account_uuid),
+            })),
+        None => Ok(HttpResponse::NotFound().json(ApiError::new(AccountError::NoAccountsFound))),
+    }
+}
+
+#[utoipa::path(
+    tag = "Accounts",
+    operation_id = "find_account_contacts",
+    params(AccountUuid),
+    responses(
+        (status = 200, description = "Account contacts", body = Vec<Contact>),
+        (status = 404, description = "No contacts found", body = ApiError),
+    )
+)]
+#[get("/accounts/{uuid}/contacts")]
+#[tracing::instrument(name = "Show Account Contacts", skip(db), fields(uuid = %uuid.uuid))]
+async fn find_contacts(
+    db: Data<Database>,
+    uuid: Path<AccountUuid>,
+) -> Result<HttpResponse, AccountError> {
+    match Database::find_by_account(&db, uuid.into_inner().uuid).await {
+        Some(contacts) => Ok(HttpResponse::Ok().status(StatusCode::OK).json(contacts)),
+        None => Ok(HttpResponse::NotFound().json(ApiError::new(AccountError::NoContactsFound))),
+    }
+}
+
+#[derive(OpenApi)]
+#[openapi(paths(convert, find_all, find_one, update_one, delete_one, find_contacts))]
+pub struct AccountApiDoc;
+
+pub fn account_api_controllers(cfg: &mut ServiceConfig) {
+    cfg.service(convert);
+    cfg.service(delete_one);
+    cfg.service(find_all);
+    cfg.service(find_contacts);
+    cfg.service(find_one);
+    cfg.service(update_one);
+}
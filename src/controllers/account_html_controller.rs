@@ -0,0 +1,207 @@
+use actix_web::{
+    web::{post, Data, Path, ServiceConfig},
+    HttpResponse,
+};
+use chrono_tz::Tz;
+use handlebars::{Handlebars, RenderError};
+use log::{error, info};
+use serde_json::json;
+
+use crate::{
+    db::{account_db::AccountDB, config::Database},
+    models::{
+        account_model::AccountHandlebarsError, locale_model::Locale, sales_model::LeadType,
+    },
+    utils::{
+        account_utils::find_account_detail,
+        env::{set_env_vars, ConfVars},
+        fs_utils::read_hbs_template,
+        i18n_utils::RequestLocale,
+        lead_utils::{find_lead_summary, get_lead_edit_path},
+        pipeline_utils::{find_stage, get_pipeline},
+        time::{format_date_in_language, RequestTimezone},
+    },
+};
+
+fn read_account_template(template_path: &str) -> String {
+    match read_hbs_template(template_path) {
+        Ok(contents) => contents,
+        Err(e) => {
+            error!("Failed to render contents for {}:: {}", template_path, e);
+            AccountHandlebarsError::new(e.to_string()).error
+        }
+    }
+}
+
+async fn account_table(db: Data<Database>) -> Result<String, RenderError> {
+    let handlebars = Handlebars::new();
+    let template_contents = read_account_template("account_table");
+    let cf: ConfVars = set_env_vars();
+
+    match <Database as AccountDB>::find_all_active(&db).await {
+        Some(accounts) => {
+            let data = json!({ "conf": cf, "accounts": accounts });
+            let render = handlebars.render_template(&template_contents, &data)?;
+            Ok(render)
+        }
+        None => {
+            let data = json!({"conf": cf, "error": "Unable to fetch accounts"});
+            let render_error = handlebars.render_template(&template_contents, &data)?;
+            Ok(render_error)
+        }
+    }
+}
+
+async fn account_detail(
+    hbs_path: Path<String>,
+    db: Data<Database>,
+    locale: Locale,
+    timezone: Tz,
+) -> Result<String, RenderError> {
+    let uuid = hbs_path.into_inner();
+    info!("Account detail for uuid:: {}", &uuid);
+
+    let handlebars = Handlebars::new();
+    let template_contents = read_account_template("account_detail");
+    let cf: ConfVars = set_env_vars();
+
+    match find_account_detail(&db, uuid.clone()).await {
+        Some(detail) => {
+            let stages = get_pipeline(&db, &detail.account.lead_type).await;
+            let stage_name = |code: &str| match find_stage(&stages, code) {
+                Some(stage) => stage.name.clone(),
+                None => code.to_string(),
+            };
+            let history: Vec<serde_json::Value> = detail
+                .history
+                .iter()
+                .map(|transition| {
+                    json!({
+                      "from": stage_name(&transition.from_stage),
+                      "to": stage_name(&transition.to_stage),
+                      "date_created": transition
+                        .date_created
+                        .map(|this_date| {
+                            format_date_in_language(
+                                &this_date,
+                                &locale,
+                                &timezone,
+                            )
+                        })
+                        .unwrap_or_default(),
+                    })
+                })
+                .collect();
+
+            let data = json!({
+              "conf": cf,
+              "a": detail.account,
+              "contacts": detail.contacts,
+              "history": history,
+              "lead_edit_path": get_lead_edit_path(&detail.account.lead_type),
+            });
+            let render = handlebars.render_template(&template_contents, &data)?;
+            Ok(render)
+        }
+        None => {
+            let data = json!({"conf": cf, "error": format!("Unable to find account {}", uuid)});
+            let render_error = handlebars.render_template(&template_contents, &data)?;
+            Ok(render_error)
+        }
+    }
+}
+
+async fn account_lead_panel(
+    hbs_path: Path<(LeadType, String)>,
+    db: Data<Database>,
+) -> Result<String, RenderError> {
+    let (lead_type, lead_uuid) = hbs_path.into_inner();
+
+    let handlebars = Handlebars::new();
+    let template_contents = read_account_template("account_lead_panel");
+
+    let account = <Database as AccountDB>::find_by_lead(&db, lead_uuid.clone()).await;
+    let is_won = match find_lead_summary(&db, &lead_type, lead_uuid.clone()).await {
+        Some(lead) => find_stage(&get_pipeline(&db, &lead_type).await, &lead.sales_funnel)
+            .map(|stage| stage.is_won)
+            .unwrap_or(false),
+        None => false,
+    };
+
+    let cf: ConfVars = set_env_vars();
+    let data = json!({
+      "conf": cf,
+      "lead_type": lead_type,
+      "lead_uuid": lead_uuid,
+      "account": account,
+      "is_won": is_won,
+    });
+
+    let render = handlebars.render_template(&template_contents, &data)?;
+    Ok(render)
+}
+
+pub fn account_html_controllers(cfg: &mut ServiceConfig) {
+    cfg.route(
+      "/htmx/account/table",
+      post().to(
+        |db: Data<Database>| async move {
+          let accounts = account_table(db).await;
+
+          match accounts {
+            Ok(a) => HttpResponse::Ok()
+              .content_type("text/html")
+              .append_header(("HX-Trigger", "activate_navbar_element"))
+              .body(a),
+            Err(e) => HttpResponse::Ok()
+              .content_type("text/html")
+              .append_header(("HX-Trigger", "error_account"))
+              .body(
+                format!("<span class=\"icon is-small is-left\"><i class=\"fas fa-ban\"></i>Failed to load accounts: {}</span>",
+                e)
+              )
+          }
+        }
+      ),
+    );
+
+    cfg.route(
+      "/htmx/account/detail/{uuid}",
+      post().to(
+        |hbs_path, db: Data<Database>, locale: RequestLocale, timezone: RequestTimezone| async move {
+          let account = account_detail(hbs_path, db, locale.0, timezone.0).await;
+
+          match account {
+            Ok(a) => HttpResponse::Ok().content_type("text/html").body(a),
+            Err(e) => HttpResponse::Ok()
+              .content_type("text/html")
+              .append_header(("HX-Trigger", "error_account"))
+              .body(
+                format!("<span class=\"icon is-small is-left\"><i class=\"fas fa-ban\"></i>Failed to load account: {}</span>",
+                e)
+              )
+          }
+        }
+      ),
+    );
+
+    cfg.route(
+      "/htmx/account/lead/{lead_type}/{uuid}",
+      post().to(
+        |hbs_path, db: Data<Database>| async move {
+          let panel = account_lead_panel(hbs_path, db).await;
+
+          match panel {
+            Ok(p) => HttpResponse::Ok().content_type("text/html").body(p),
+            Err(e) => HttpResponse::Ok()
+              .content_type("text/html")
+              .append_header(("HX-Trigger", "error_account"))
+              .body(
+                format!("<span class=\"icon is-small is-left\"><i class=\"fas fa-ban\"></i>Failed to load account: {}</span>",
+                e)
+              )
+          }
+        }
+      ),
+    );
+}
@@ -0,0 +1,75 @@
+use actix_web::{
+    web::{post, Data, Path, ServiceConfig},
+    HttpResponse,
+};
+use handlebars::{Handlebars, RenderError};
+use log::{error, info};
+use serde_json::json;
+
+use crate::{
+    db::config::Database,
+    models::{address_model::AddressHandlebarsError, sales_model::LeadType},
+    utils::{
+        address_utils::{get_state_tags, DEFAULT_ADDRESS_COUNTRY},
+        env::{set_env_vars, ConfVars},
+        fs_utils::read_hbs_template,
+        lead_utils::find_lead_summary,
+    },
+};
+
+async fn address_editor(
+    hbs_path: Path<(LeadType, String)>,
+    db: Data<Database>,
+) -> Result<String, RenderError> {
+    let (lead_type, lead_uuid) = hbs_path.into_inner();
+    info!("Address editor for lead:: {}", &lead_uuid);
+
+    let handlebars = Handlebars::new();
+    let template_path = "address_editor";
+
+    let template_contents = match read_hbs_template(template_path) {
+        Ok(contents) => contents,
+        Err(e) => {
+            error!("Couldn't render file for address editor:: {}", e);
+            AddressHandlebarsError::new(e.to_string()).error
+        }
+    };
+
+    let address = match find_lead_summary(&db, &lead_type, lead_uuid).await {
+        Some(lead) => lead.address,
+        None => Default::default(),
+    };
+
+    let cf: ConfVars = set_env_vars();
+    let data = json!({
+      "conf": cf,
+      "states": get_state_tags(address.state.as_deref()),
+      "country": address.country.clone().unwrap_or(DEFAULT_ADDRESS_COUNTRY.to_string()),
+      "address": address,
+    });
+
+    let render = handlebars.render_template(&template_contents, &data)?;
+    Ok(render)
+}
+
+pub fn address_html_controllers(cfg: &mut ServiceConfig) {
+    cfg.route(
+      "/htmx/address/editor/{lead_type}/{uuid}",
+      post().to(
+        |hbs_path, db: Data<Database>| async move {
+          let editor = address_editor(hbs_path, db).await;
+
+          match editor {
+            Ok(e) => HttpResponse::Ok().content_type("text/html").body(e),
+            Err(e) => HttpResponse::Ok()
+              .content_type("text/html")
+              .append_header(("HX-Trigger", "error_address"))
+              .body(
+                format!("<span class=\"icon is-small is-left\"><i class=\"fas fa-ban\"></i>Failed to load address: {}</span>",
+                e)
+              )
+          }
+        }
+      ),
+    );
+}
@@ -0,0 +1,74 @@
+use actix_web::web::{scope, ServiceConfig};
+
+use crate::{
+    controllers::{
+        account_api_controller::account_api_controllers,
+        api_token_api_controller::api_token_api_controllers,
+        attachment_api_controller::attachment_api_controllers,
+        campaign_api_controller::campaign_api_controllers,
+        clinics_api_controller::clinical_api_controllers,
+        contact_api_controller::contact_api_controllers,
+        email_template_api_controller::email_template_api_controllers,
+        enterprise_api_controller::enterprise_api_controllers,
+        lead_api_controller::lead_api_controllers,
+        meeting_api_controller::{calendar_feed_controllers, meeting_api_controllers},
+        notification_api_controller::notification_api_controllers,
+        pipeline_api_controller::pipeline_api_controllers,
+        quote_api_controller::quote_api_controllers,
+        school_api_controller::school_api_controllers,
+        scoring_api_controller::scoring_api_controllers,
+        segment_api_controller::segment_api_controllers,
+        service_api_controller::service_api_controllers,
+        session_api_controller::session_api_controllers,
+        tag_api_controller::tag_api_controllers,
+        territory_api_controller::territory_api_controllers,
+        users_api_controller::users_api_controllers,
+        web_lead_api_controller::web_lead_api_controllers,
+    },
+    utils::api_utils::{deprecated_alias, BearerAuth, API_V1_PREFIX},
+};
+
+fn json_api_controllers(cfg: &mut ServiceConfig) {
+    cfg.configure(account_api_controllers)
+        .configure(api_token_api_controllers)
+        .configure(attachment_api_controllers)
+        .configure(campaign_api_controllers)
+        .configure(clinical_api_controllers)
+        .configure(contact_api_controllers)
+        .configure(email_template_api_controllers)
+        .configure(enterprise_api_controllers)
+        .configure(lead_api_controllers)
+        .configure(meeting_api_controllers)
+        .configure(notification_api_controllers)
+        .configure(pipeline_api_controllers)
+        .configure(quote_api_controllers)
+        .configure(school_api_controllers)
+        .configure(scoring_api_controllers)
+        .configure(segment_api_controllers)
+        .configure(service_api_controllers)
+        .configure(tag_api_controllers)
+        .configure(territory_api_controllers)
+        .configure(users_api_controllers);
+}
+
+pub fn api_controllers(cfg: &mut ServiceConfig) {
+    cfg.service(
+        scope(API_V1_PREFIX)
+            .wrap(BearerAuth)
+            .configure(json_api_controllers)
+            .configure(session_api_controllers)
+            .configure(web_lead_api_controllers)
+            .configure(calendar_feed_controllers),
+    );
+}
+
+// The empty scope matches every path, so this has to be registered after all
+// other services to keep it from shadowing the HTMX routes.
+pub fn deprecated_api_controllers(cfg: &mut ServiceConfig) {
+    cfg.service(
+        scope("")
+            .wrap(BearerAuth)
+            .wrap_fn(deprecated_alias)
+            .configure(json_api_controllers),
+    );
+}
@@ -0,0 +1,223 @@
+use actix_web::{
+    delete, get,
+    http::StatusCode,
+    post,
+    web::{Data, Json, Path, ServiceConfig},
+    HttpResponse,
+};
+use chrono::Utc;
+use log::error;
+use utoipa::OpenApi;
+use validator::Validate;
+
+use crate::{
+    db::{api_token_db::ApiTokenDB, config::Database},
+    error::{api_error::ApiError, api_token_error::ApiTokenError},
+    models::{
+        api_token_model::{ApiToken, ApiTokenCreated, ApiTokenSummary, ApiTokenUuid, NewApiToken},
+        users_model::{ActingUser, UserUuid},
+    },
+    utils::{
+        api_token_utils::{generate_token, get_token_prefix},
+        general_utils::get_uuid,
+        i18n_utils::RequestLocale,
+        message_utils::get_localized_validation_errors,
+        pwd::pwd_hasher,
+        role_utils::{is_active_user, is_admin},
+    },
+};
+
+async fn can_manage_tokens(db: &Data<Database>, acting_user: Option<String>, owner: &str) -> bool {
+    match acting_user {
+        Some(user_uuid) if user_uuid == owner => is_active_user(db, Some(user_uuid)).await,
+        other_user => is_admin(db, other_user).await,
+    }
+}
+
+fn forbidden_response() -> HttpResponse {
+    let message = "Solo el dueño de los tokens o un ADMIN puede administrarlos";
+    HttpResponse::Forbidden()
+        .insert_header((
+            "HX-Trigger",
+            format!("{{ \"page_error\": {:?} }}", message.to_string()),
+        ))
+        .json(ApiError::new(ApiTokenError::ApiTokenForbidden).with_message(message))
+}
+
+#[utoipa::path(
+    tag = "API tokens",
+    operation_id = "find_user_api_tokens",
+    params(UserUuid),
+    responses(
+        (status = 200, description = "Tokens of the user, without secrets", body = Vec<ApiTokenSummary>),
+        (status = 403, description = "Only the owner or an ADMIN can list tokens", body = ApiError),
+        (status = 404, description = "No tokens found", body = ApiError),
+    )
+)]
+#[get("/users/{uuid}/api_tokens")]
+#[tracing::instrument(name = "Show Api Tokens", skip(db, acting_user), fields(uuid = %uuid.uuid))]
+async fn find_by_user(
+    db: Data<Database>,
+    uuid: Path<UserUuid>,
+    acting_user: ActingUser,
+) -> Result<HttpResponse, ApiTokenError> {
+    let owner = uuid.into_inner().uuid;
+    if !can_manage_tokens(&db, acting_user.user_uuid, &owner).await {
+        return Ok(forbidden_response());
+    }
+
+    match Database::find_by_user(&db, owner).await {
+        Some(tokens) => {
+            let summaries: Vec<ApiTokenSummary> =
+                tokens.into_iter().map(ApiTokenSummary::new).collect();
+            Ok(HttpResponse::Ok().status(StatusCode::OK).json(summaries))
+        }
+        None => {
+            error!("Unable to find api tokens");
+            Ok(HttpResponse::NotFound().json(ApiError::new(ApiTokenError::NoApiTokensFound)))
+        }
+    }
+}
+
+#[utoipa::path(
+    tag = "API tokens",
+    operation_id = "create_api_token",
+    params(UserUuid),
+    responses(
+        (status = 201, description = "Token created, the secret is only shown once", body = ApiTokenCreated),
+        (status = 400, description = "Invalid token", body = ApiError),
+        (status = 403, description = "Only the owner or an ADMIN can mint tokens", body = ApiError),
+        (status = 500, description = "Token couldn't be saved", body = ApiError),
+    )
+)]
+#[post("/users/{uuid}/api_tokens")]
+#[tracing::instrument(name = "Post Api Token", skip(db, body), fields(uuid = %uuid.uuid, name = %body.name))]
+async fn create(
+    db: Data<Database>,
+    uuid: Path<UserUuid>,
+    mut body: Json<NewApiToken>,
+    acting_user: ActingUser,
+    locale: RequestLocale,
+) -> Result<HttpResponse, ApiTokenError> {
+    body.created_by = acting_user.user_uuid.clone();
+    let owner = uuid.into_inner().uuid;
+    if !can_manage_tokens(&db, body.created_by.clone(), &owner).await
+        || !is_active_user(&db, Some(owner.clone())).await
+    {
+        return Ok(forbidden_response());
+    }
+
+    if let Err(e) = body.validate() {
+        error!("Error api_token.create {:?}", e);
+        let key_errors_vec: Vec<String> = get_localized_validation_errors(&e, &locale.0);
+        return Ok(HttpResponse::BadRequest()
+            .insert_header((
+                "HX-Trigger",
+                format!("{{ \"page_error\": {:?} }}", key_errors_vec),
+            ))
+            .json(
+                ApiError::new(ApiTokenError::ApiTokenCreationFailure)
+                    .with_field_errors(key_errors_vec),
+            ));
+    }
+
+    let new_token = body.into_inner();
+    let token_uuid = get_uuid();
+    let token = generate_token(&token_uuid);
+    let token_hash = match pwd_hasher(token.clone()) {
+        Ok(hash) => hash,
+        Err(e) => {
+            error!("Failed to hash api token:: {}", e);
+            return Ok(HttpResponse::InternalServerError()
+                .json(ApiError::new(ApiTokenError::ApiTokenCreationFailure)));
+        }
+    };
+
+    let date_created = Utc::now();
+    let my_token = ApiToken {
+        uuid: None,
+        user_uuid: owner,
+        name: new_token.name.trim().to_string(),
+        token_hash,
+        token_prefix: get_token_prefix(&token),
+        scopes: new_token.scopes,
+        last_used: None,
+        revoked: false,
+        date_created: Some(date_created),
+        date_modified: Some(date_created),
+        created_by: new_token.created_by.clone(),
+        modified_by: new_token.created_by,
+    };
+
+    match Database::add_one(&db, ApiToken::new(token_uuid.clone(), my_token)).await {
+        Some(_) => Ok(HttpResponse::Ok()
+            .insert_header(("HX-Trigger", "api_token_reload_page"))
+            .status(StatusCode::CREATED)
+            .json(ApiTokenCreated {
+                uuid: token_uuid,
+                token,
+            })),
+        None => {
+            error!("Error [POST] /users/{{uuid}}/api_tokens");
+            Ok(HttpResponse::InternalServerError()
+                .insert_header((
+                    "HX-Trigger",
+                    "{ \"page_error\": \"Internal server error\" }".to_string(),
+                ))
+                .json(ApiError::new(ApiTokenError::ApiTokenCreationFailure)))
+        }
+    }
+}
+
+#[utoipa::path(
+    tag = "API tokens",
+    operation_id = "revoke_api_token",
+    params(ApiTokenUuid),
+    responses(
+        (status = 200, description = "Token revoked", body = ApiTokenUuid),
+        (status = 403, description = "Only the owner or an ADMIN can revoke tokens", body = ApiError),
+        (status = 404, description = "Token not found", body = ApiError),
+    )
+)]
+#[delete("/api_tokens/{uuid}")]
+#[tracing::instrument(name = "Revoke Api Token", skip(db, acting_user), fields(uuid = %uuid.uuid))]
+async fn revoke_one(
+    db: Data<Database>,
+    uuid: Path<ApiTokenUuid>,
+    acting_user: ActingUser,
+) -> Result<HttpResponse, ApiTokenError> {
+    let token_uuid = uuid.into_inner().uuid;
+    let user_uuid = acting_user.user_uuid;
+
+    let api_token = match Database::find_one(&db, token_uuid.clone()).await {
+        Some(api_token) => api_token,
+        None => {
+            error!("No api token found for UUID:: {:?}", &token_uuid);
+            return Ok(
+                HttpResponse::NotFound().json(ApiError::new(ApiTokenError::NoApiTokensFound))
+            );
+        }
+    };
+
+    if !can_manage_tokens(&db, user_uuid.clone(), &api_token.user_uuid).await {
+        return Ok(forbidden_response());
+    }
+
+    match Database::revoke_one(&db, token_uuid.clone(), user_uuid).await {
+        Some(_) => Ok(HttpResponse::Ok()
+            .insert_header(("HX-Trigger", "api_token_reload_page"))
+            .status(StatusCode::OK)
+            .json(ApiTokenUuid { uuid: token_uuid })),
+        None => Ok(HttpResponse::NotFound().json(ApiError::new(ApiTokenError::NoApiTokensFound))),
+    }
+}
+
+#[derive(OpenApi)]
+#[openapi(paths(find_by_user, create, revoke_one))]
+pub struct ApiTokenApiDoc;
+
+pub fn api_token_api_controllers(cfg: &mut ServiceConfig) {
+    cfg.service(create);
+    cfg.service(find_by_user);
+    cfg.service(revoke_one);
+}
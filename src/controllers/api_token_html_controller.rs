@@ -0,0 +1,92 @@
+use actix_web::{
+    web::{post, Data, Path, ServiceConfig},
+    HttpResponse,
+};
+use chrono_tz::Tz;
+use handlebars::{Handlebars, RenderError};
+use log::{error, info};
+use serde_json::json;
+
+use crate::{
+    db::{api_token_db::ApiTokenDB, config::Database},
+    models::{api_token_model::ApiTokenHandlebarsError, locale_model::Locale},
+    utils::{
+        api_token_utils::API_TOKEN_RESOURCES,
+        env::{set_env_vars, ConfVars},
+        fs_utils::read_hbs_template,
+        i18n_utils::RequestLocale,
+        time::{format_date_in_language, RequestTimezone},
+    },
+};
+
+async fn api_token_list(
+    hbs_path: Path<String>,
+    db: Data<Database>,
+    locale: Locale,
+    timezone: Tz,
+) -> Result<String, RenderError> {
+    let user_uuid = hbs_path.into_inner();
+    info!("Api tokens for user:: {}", &user_uuid);
+
+    let handlebars = Handlebars::new();
+    let template_path = "api_token_list";
+
+    let template_contents = match read_hbs_template(template_path) {
+        Ok(contents) => contents,
+        Err(e) => {
+            error!("Couldn't render file for api token list:: {}", e);
+            ApiTokenHandlebarsError::new(e.to_string()).error
+        }
+    };
+
+    let api_tokens: Vec<serde_json::Value> = Database::find_by_user(&db, user_uuid.clone())
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .map(|api_token| {
+            let last_used = match api_token.last_used {
+                Some(this_date) => format_date_in_language(&this_date, &locale, &timezone),
+                None => "Nunca".to_string(),
+            };
+            let scopes: Vec<String> = api_token
+                .scopes
+                .iter()
+                .map(|scope| format!("{}:{}", scope.resource, scope.access))
+                .collect();
+            json!({ "t": api_token, "last_used": last_used, "scopes": scopes.join(", ") })
+        })
+        .collect();
+
+    let cf: ConfVars = set_env_vars();
+    let data = json!({
+      "conf": cf,
+      "user_uuid": user_uuid,
+      "api_tokens": api_tokens,
+      "resources": API_TOKEN_RESOURCES,
+    });
+
+    let render = handlebars.render_template(&template_contents, &data)?;
+    Ok(render)
+}
+
+pub fn api_token_html_controllers(cfg: &mut ServiceConfig) {
+    cfg.route(
+      "/htmx/api_tokens/{uuid}",
+      post().to(
+        |hbs_path, db: Data<Database>, locale: RequestLocale, timezone: RequestTimezone| async move {
+          let api_tokens = api_token_list(hbs_path, db, locale.0, timezone.0).await;
+
+          match api_tokens {
+            Ok(t) => HttpResponse::Ok().content_type("text/html").body(t),
+            Err(e) => HttpResponse::Ok()
+              .content_type("text/html")
+              .append_header(("HX-Trigger", "error_api_token"))
+              .body(
+                format!("<span class=\"icon is-small is-left\"><i class=\"fas fa-ban\"></i>Failed to load api tokens: {}</span>",
+                e)
+              )
+          }
+        }
+      ),
+    );
+}
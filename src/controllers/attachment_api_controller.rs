@@ -0,0 +1,270 @@
+use actix_files::NamedFile;
+use actix_multipart::form::MultipartForm;
+use actix_web::{
+    delete, get,
+    http::{
+        header::{ContentDisposition, DispositionParam, DispositionType},
+        StatusCode,
+    },
+    post,
+    web::{Data, Path, ServiceConfig},
+    HttpRequest, HttpResponse,
+};
+use chrono::Utc;
+use log::error;
+use utoipa::OpenApi;
+
+use crate::{
+    db::{attachment_db::AttachmentDB, config::Database},
+    error::{api_error::ApiError, attachment_error::AttachmentError},
+    models::{
+        attachment_model::{Attachment, AttachmentUpload, AttachmentUuid},
+        sales_model::LeadType,
+        users_model::ActingUser,
+    },
+    utils::{
+        attachment_utils::{
+            get_attachment_max_bytes, get_attachments_path, get_office_mime_type, read_file_header,
+            sanitize_file_name, sniff_mime_type, store_attachment_file,
+        },
+        general_utils::{get_uuid, shuffle_id},
+        lead_utils::find_lead_summary,
+        role_utils::is_active_user,
+    },
+};
+
+fn rejected_response(status: StatusCode, message: String, error: AttachmentError) -> HttpResponse {
+    HttpResponse::build(status)
+        .insert_header(("HX-Trigger", format!("{{ \"page_error\": {:?} }}", message)))
+        .json(ApiError::new(error).with_message(message))
+}
+
+#[utoipa::path(
+    tag = "Attachments",
+    operation_id = "upload_attachment",
+    request_body(content = AttachmentUpload, content_type = "multipart/form-data"),
+    responses(
+        (status = 201, description = "Attachment stored", body = AttachmentUuid),
+        (status = 400, description = "Rejected file", body = ApiError),
+        (status = 403, description = "Inactive user", body = ApiError),
+        (status = 404, description = "Lead not found", body = ApiError),
+        (status = 413, description = "File too large", body = ApiError),
+    )
+)]
+#[post("/attachments/{lead_type}/{uuid}")]
+#[tracing::instrument(name = "Upload Attachment", skip(db, form, path))]
+async fn upload(
+    db: Data<Database>,
+    path: Path<(LeadType, String)>,
+    MultipartForm(form): MultipartForm<AttachmentUpload>,
+    acting_user: ActingUser,
+) -> Result<HttpResponse, AttachmentError> {
+    let (lead_type, lead_uuid) = path.into_inner();
+    let created_by = acting_user.user_uuid;
+
+    if !is_active_user(&db, created_by.clone()).await {
+        return Ok(rejected_response(
+            StatusCode::FORBIDDEN,
+            "Inicia sesión para subir archivos".to_string(),
+            AttachmentError::AttachmentForbidden,
+        ));
+    }
+
+    if find_lead_summary(&db, &lead_type, lead_uuid.clone())
+        .await
+        .is_none()
+    {
+        error!("No {} lead found for UUID:: {}", &lead_type, &lead_uuid);
+        return Ok(rejected_response(
+            StatusCode::NOT_FOUND,
+            "No se encontró el registro".to_string(),
+            AttachmentError::NoAttachmentsFound,
+        ));
+    }
+
+    let max_bytes = get_attachment_max_bytes();
+    if form.file.size == 0 || form.file.size > max_bytes {
+        return Ok(rejected_response(
+            StatusCode::BAD_REQUEST,
+            format!(
+                "El archivo debe pesar entre 1 byte y {} MB",
+                max_bytes / (1024 * 1024)
+            ),
+            AttachmentError::AttachmentRejected,
+        ));
+    }
+
+    let file_name = sanitize_file_name(form.file.file_name.as_deref().unwrap_or("attachment"));
+    let header = match read_file_header(form.file.file.path()) {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            error!("Unable to read uploaded file {}:: {}", &file_name, e);
+            return Err(AttachmentError::AttachmentCreationFailure);
+        }
+    };
+
+    let mime_type = match sniff_mime_type(&header) {
+        Some(sniffed_mime) => get_office_mime_type(&file_name, sniffed_mime),
+        None => {
+            return Ok(rejected_response(
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                format!("Tipo de archivo no permitido: {}", &file_name),
+                AttachmentError::AttachmentRejected,
+            ));
+        }
+    };
+
+    let new_uuid = get_uuid();
+    if let Err(e) = store_attachment_file(form.file.file.path(), &new_uuid) {
+        error!("Unable to store attachment {}:: {}", &file_name, e);
+        return Err(AttachmentError::AttachmentCreationFailure);
+    }
+
+    let new_attachment = Attachment::new(
+        new_uuid.clone(),
+        Attachment {
+            uuid: None,
+            lead_type,
+            lead_uuid,
+            file_name,
+            storage_name: new_uuid,
+            mime_type: mime_type.to_string(),
+            size: form.file.size as u64,
+            deleted: false,
+            date_created: Some(Utc::now()),
+            date_modified: Some(Utc::now()),
+            created_by: created_by.clone(),
+            modified_by: created_by,
+        },
+    );
+
+    match Database::add_one(&db, new_attachment).await {
+        Some(attachment) => Ok(HttpResponse::Created()
+            .insert_header(("HX-Trigger", "attachment_reload_page"))
+            .json(AttachmentUuid {
+                uuid: shuffle_id(attachment.uuid.unwrap_or_default()),
+            })),
+        None => Err(AttachmentError::AttachmentCreationFailure),
+    }
+}
+
+#[utoipa::path(
+    tag = "Attachments",
+    operation_id = "find_lead_attachments",
+    responses(
+        (status = 200, description = "Lead attachments", body = Vec<Attachment>),
+        (status = 404, description = "No attachments found", body = ApiError),
+    )
+)]
+#[get("/attachments/lead/{lead_type}/{uuid}")]
+#[tracing::instrument(name = "Show Lead Attachments", skip(db, path))]
+async fn find_by_lead(
+    db: Data<Database>,
+    path: Path<(LeadType, String)>,
+) -> Result<HttpResponse, AttachmentError> {
+    let (_, lead_uuid) = path.into_inner();
+
+    match Database::find_by_lead(&db, lead_uuid).await {
+        Some(attachments) => Ok(HttpResponse::Ok().status(StatusCode::OK).json(attachments)),
+        None => Err(AttachmentError::NoAttachmentsFound),
+    }
+}
+
+#[utoipa::path(
+    tag = "Attachments",
+    operation_id = "download_attachment",
+    params(AttachmentUuid),
+    responses(
+        (status = 200, description = "Attachment file", body = Vec<u8>, content_type = "application/octet-stream"),
+        (status = 403, description = "Inactive user", body = ApiError),
+        (status = 404, description = "Attachment not found", body = ApiError),
+    )
+)]
+#[get("/attachments/{uuid}/download")]
+#[tracing::instrument(name = "Download Attachment", skip(db, req, acting_user), fields(uuid = %uuid.uuid))]
+async fn download(
+    db: Data<Database>,
+    req: HttpRequest,
+    uuid: Path<AttachmentUuid>,
+    acting_user: ActingUser,
+) -> Result<HttpResponse, AttachmentError> {
+    if !is_active_user(&db, acting_user.user_uuid).await {
+        return Err(AttachmentError::AttachmentForbidden);
+    }
+
+    let attachment = match Database::find_one(&db, uuid.into_inner().uuid).await {
+        Some(attachment) if !attachment.deleted => attachment,
+        _ => return Err(AttachmentError::NoAttachmentsFound),
+    };
+
+    let file = match NamedFile::open(get_attachments_path().join(&attachment.storage_name)) {
+        Ok(file) => file,
+        Err(e) => {
+            error!(
+                "Attachment file missing for {}:: {}",
+                &attachment.file_name, e
+            );
+            return Err(AttachmentError::NoAttachmentsFound);
+        }
+    };
+
+    let content_type = attachment
+        .mime_type
+        .parse()
+        .unwrap_or(mime::APPLICATION_OCTET_STREAM);
+
+    Ok(file
+        .set_content_type(content_type)
+        .set_content_disposition(ContentDisposition {
+            disposition: DispositionType::Attachment,
+            parameters: vec![DispositionParam::Filename(attachment.file_name)],
+        })
+        .into_response(&req))
+}
+
+#[utoipa::path(
+    tag = "Attachments",
+    operation_id = "delete_attachment",
+    params(AttachmentUuid),
+    responses(
+        (status = 200, description = "Attachment deleted", body = AttachmentUuid),
+        (status = 403, description = "Inactive user", body = ApiError),
+        (status = 404, description = "Attachment not found", body = ApiError),
+    )
+)]
+#[delete("/attachments/{uuid}")]
+#[tracing::instrument(name = "Delete Attachment", skip(db, acting_user), fields(uuid = %uuid.uuid))]
+async fn delete(
+    db: Data<Database>,
+    uuid: Path<AttachmentUuid>,
+    acting_user: ActingUser,
+) -> Result<HttpResponse, AttachmentError> {
+    let user_uuid = acting_user.user_uuid;
+    if !is_active_user(&db, user_uuid.clone()).await {
+        return Ok(rejected_response(
+            StatusCode::FORBIDDEN,
+            "Inicia sesión para borrar archivos".to_string(),
+            AttachmentError::AttachmentForbidden,
+        ));
+    }
+
+    match Database::delete_one(&db, uuid.into_inner().uuid, user_uuid).await {
+        Some(attachment) => Ok(HttpResponse::Ok()
+            .insert_header(("HX-Trigger", "attachment_reload_page"))
+            .json(AttachmentUuid {
+                uuid: shuffle_id(attachment.uuid.unwrap_or_default()),
+            })),
+        None => Err(AttachmentError::NoAttachmentsFound),
+    }
+}
+
+#[derive(OpenApi)]
+#[openapi(paths(upload, find_by_lead, download, delete))]
+pub struct AttachmentApiDoc;
+
+pub fn attachment_api_controllers(cfg: &mut ServiceConfig) {
+    cfg.service(upload)
+        .service(find_by_lead)
+        .service(download)
+        .service(delete);
+}
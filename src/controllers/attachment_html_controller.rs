@@ -0,0 +1,93 @@
+use actix_web::{
+    web::{post, Data, Path, ServiceConfig},
+    HttpResponse,
+};
+use chrono_tz::Tz;
+use handlebars::{Handlebars, RenderError};
+use log::{error, info};
+use serde_json::json;
+
+use crate::{
+    db::{attachment_db::AttachmentDB, config::Database},
+    models::{
+        attachment_model::AttachmentHandlebarsError, locale_model::Locale, sales_model::LeadType,
+    },
+    utils::{
+        attachment_utils::{format_file_size, get_attachment_max_bytes},
+        env::{set_env_vars, ConfVars},
+        fs_utils::read_hbs_template,
+        i18n_utils::RequestLocale,
+        time::{format_date_in_language, RequestTimezone},
+    },
+};
+
+handlebars_helper!(file_size: |size: u64| format_file_size(size));
+
+async fn attachment_list(
+    hbs_path: Path<(LeadType, String)>,
+    db: Data<Database>,
+    locale: Locale,
+    timezone: Tz,
+) -> Result<String, RenderError> {
+    let (lead_type, lead_uuid) = hbs_path.into_inner();
+    info!("Attachments for lead:: {}", &lead_uuid);
+
+    let mut handlebars = Handlebars::new();
+    handlebars.register_helper("file_size", Box::new(file_size));
+    let template_path = "attachment_list";
+
+    let template_contents = match read_hbs_template(template_path) {
+        Ok(contents) => contents,
+        Err(e) => {
+            error!("Couldn't render file for attachment list:: {}", e);
+            AttachmentHandlebarsError::new(e.to_string()).error
+        }
+    };
+
+    let attachments: Vec<serde_json::Value> = Database::find_by_lead(&db, lead_uuid.clone())
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .map(|attachment| {
+            let date_created = match attachment.date_created {
+                Some(this_date) => format_date_in_language(&this_date, &locale, &timezone),
+                None => "".to_string(),
+            };
+            json!({ "a": attachment, "date_created": date_created })
+        })
+        .collect();
+
+    let cf: ConfVars = set_env_vars();
+    let data = json!({
+      "conf": cf,
+      "lead_type": lead_type,
+      "lead_uuid": lead_uuid,
+      "attachments": attachments,
+      "max_size": format_file_size(get_attachment_max_bytes() as u64),
+    });
+
+    let render = handlebars.render_template(&template_contents, &data)?;
+    Ok(render)
+}
+
+pub fn attachment_html_controllers(cfg: &mut ServiceConfig) {
+    cfg.route(
+      "/htmx/attachments/{lead_type}/{uuid}",
+      post().to(
+        |hbs_path, db: Data<Database>, locale: RequestLocale, timezone: RequestTimezone| async move {
+          let attachments = attachment_list(hbs_path, db, locale.0, timezone.0).await;
+
+          match attachments {
+            Ok(a) => HttpResponse::Ok().content_type("text/html").body(a),
+            Err(e) => HttpResponse::Ok()
+              .content_type("text/html")
+              .append_header(("HX-Trigger", "error_attachment"))
+              .body(
+                format!("<span class=\"icon is-small is-left\"><i class=\"fas fa-ban\"></i>Failed to load attachments: {}</span>",
+                e)
+              )
+          }
+        }
+      ),
+    );
+}
@@ -0,0 +1,85 @@
+use actix_web::{
+    web::{post, Data, Path, ServiceConfig},
+    HttpResponse,
+};
+use handlebars::{Handlebars, RenderError};
+use log::{error, info};
+use serde_json::json;
+
+use crate::{
+    db::{config::Database, segment_db::SegmentDB, users_db::UsersDB},
+    models::{lead_model::LeadHandlebarsError, sales_model::LeadType},
+    utils::{
+        env::{set_env_vars, ConfVars},
+        fs_utils::read_hbs_template,
+        general_utils::get_funnel_tags,
+        lead_utils::get_lead_table_path,
+        pipeline_utils::get_pipeline,
+        service_utils::get_service_catalog,
+    },
+};
+
+async fn bulk_toolbar(
+    hbs_path: Path<LeadType>,
+    db: Data<Database>,
+) -> Result<String, RenderError> {
+    let lead_type = hbs_path.into_inner();
+    info!("Bulk toolbar for:: {}", &lead_type);
+
+    let handlebars = Handlebars::new();
+    let template_path = "bulk_toolbar";
+
+    let template_contents = match read_hbs_template(template_path) {
+        Ok(contents) => contents,
+        Err(e) => {
+            error!("Couldn't render file for bulk toolbar:: {}", e);
+            LeadHandlebarsError::new(e.to_string()).error
+        }
+    };
+
+    let segments: Vec<_> = <Database as SegmentDB>::find_all_active(&db)
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|segment| segment.lead_type == lead_type)
+        .collect();
+    let users = <Database as UsersDB>::find_all_non_deleted(&db)
+        .await
+        .unwrap_or_default();
+
+    let cf: ConfVars = set_env_vars();
+    let data = json!({
+      "conf": cf,
+      "lead_type": lead_type,
+      "table_path": get_lead_table_path(&lead_type),
+      "stages": get_funnel_tags(get_pipeline(&db, &lead_type).await),
+      "services": get_service_catalog(&db).await,
+      "users": users,
+      "segments": segments,
+    });
+
+    let render = handlebars.render_template(&template_contents, &data)?;
+    Ok(render)
+}
+
+pub fn bulk_html_controllers(cfg: &mut ServiceConfig) {
+    cfg.route(
+      "/htmx/bulk/{lead_type}",
+      post().to(
+        |hbs_path, db: Data<Database>| async move {
+          let toolbar = bulk_toolbar(hbs_path, db).await;
+
+          match toolbar {
+            Ok(t) => HttpResponse::Ok().content_type("text/html").body(t),
+            Err(e) => HttpResponse::Ok()
+              .content_type("text/html")
+              .append_header(("HX-Trigger", "error_bulk"))
+              .body(
+                format!("<span class=\"icon is-small is-left\"><i class=\"fas fa-ban\"></i>Failed to load bulk actions: {}</span>",
+                e)
+              )
+          }
+        }
+      ),
+    );
+}
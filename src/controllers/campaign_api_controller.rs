@@ -0,0 +1,332 @@
+use actix_web::{
+    delete, get,
+    http::StatusCode,
+    patch, post,
+    web::{Data, Json, Path, Query, ServiceConfig},
+    HttpResponse,
+};
+use chrono::Utc;
+use log::error;
+use utoipa::OpenApi;
+use validator::Validate;
+
+use crate::{
+    db::{campaign_db::CampaignDB, config::Database},
+    error::{api_error::ApiError, campaign_error::CampaignError},
+    models::{
+        campaign_model::{AttributionReport, AttributionReportQuery, Campaign, CampaignUuid},
+        locale_model::Locale,
+        sales_model::LeadType,
+        users_model::ActingUser,
+    },
+    utils::{
+        campaign_utils::{build_attribution_report, get_campaign_errors, normalize_campaign},
+        general_utils::{get_uuid, shuffle_id},
+        i18n_utils::RequestLocale,
+        message_utils::get_localized_validation_errors,
+        role_utils::{is_active_user, is_admin},
+    },
+};
+
+fn forbidden_response(message: &str) -> HttpResponse {
+    HttpResponse::Forbidden()
+        .insert_header((
+            "HX-Trigger",
+            format!("{{ \"page_error\": {:?} }}", message.to_string()),
+        ))
+        .json(ApiError::new(CampaignError::CampaignForbidden).with_message(message))
+}
+
+fn not_found_response(campaign_uuid: &str) -> HttpResponse {
+    error!("No campaign found for UUID:: {:?}", campaign_uuid);
+    HttpResponse::NotFound().json(ApiError::new(CampaignError::NoCampaignsFound))
+}
+
+fn invalid_campaign_response(key_errors_vec: Vec<String>) -> HttpResponse {
+    HttpResponse::BadRequest()
+        .insert_header((
+            "HX-Trigger",
+            format!("{{ \"page_error\": {:?} }}", key_errors_vec),
+        ))
+        .json(
+            ApiError::new(CampaignError::CampaignCreationFailure).with_field_errors(key_errors_vec),
+        )
+}
+
+async fn get_all_campaign_errors(
+    db: &Data<Database>,
+    campaign: &Campaign,
+    locale: &Locale,
+) -> Vec<String> {
+    let mut key_errors_vec: Vec<String> = match campaign.validate() {
+        Ok(_) => vec![],
+        Err(e) => get_localized_validation_errors(&e, locale),
+    };
+
+    let campaigns = Database::find_all_active(db).await.unwrap_or_default();
+    key_errors_vec.extend(get_campaign_errors(campaign, &campaigns));
+
+    key_errors_vec
+}
+
+#[utoipa::path(
+    tag = "Campaigns",
+    operation_id = "find_all_campaigns",
+    responses(
+        (status = 200, description = "Active campaigns", body = Vec<Campaign>),
+        (status = 404, description = "No campaigns found", body = ApiError),
+    )
+)]
+#[get("/campaigns")]
+#[tracing::instrument(name = "Show Campaigns", skip(db))]
+async fn find_all(db: Data<Database>) -> Result<HttpResponse, CampaignError> {
+    match Database::find_all_active(&db).await {
+        Some(campaigns) => Ok(HttpResponse::Ok().status(StatusCode::OK).json(campaigns)),
+        None => {
+            error!("Unable to find any campaign");
+            Ok(HttpResponse::NotFound().json(ApiError::new(CampaignError::NoCampaignsFound)))
+        }
+    }
+}
+
+#[utoipa::path(
+    tag = "Campaigns",
+    operation_id = "find_one_campaign",
+    params(CampaignUuid),
+    responses(
+        (status = 200, description = "Campaign found", body = Campaign),
+        (status = 404, description = "Campaign not found", body = ApiError),
+    )
+)]
+#[get("/campaigns/{uuid}")]
+#[tracing::instrument(name = "Get One Campaign", skip(db), fields(uuid = %uuid.uuid))]
+async fn find_one(
+    db: Data<Database>,
+    uuid: Path<CampaignUuid>,
+) -> Result<HttpResponse, CampaignError> {
+    let campaign_uuid = uuid.into_inner().uuid;
+
+    match Database::find_one(&db, campaign_uuid.clone()).await {
+        Some(campaign) => Ok(HttpResponse::Ok().status(StatusCode::OK).json(campaign)),
+        None => Ok(not_found_response(&campaign_uuid)),
+    }
+}
+
+#[utoipa::path(
+    tag = "Campaigns",
+    operation_id = "attribution_report",
+    params(AttributionReportQuery),
+    responses(
+        (status = 200, description = "Leads, wins and win value per campaign and source", body = AttributionReport),
+        (status = 403, description = "Inactive user", body = ApiError),
+    )
+)]
+#[get("/reports/attribution")]
+#[tracing::instrument(name = "Attribution Report", skip(db, query))]
+async fn report(
+    db: Data<Database>,
+    query: Query<AttributionReportQuery>,
+    acting_user: ActingUser,
+) -> Result<HttpResponse, CampaignError> {
+    let report_query = query.into_inner();
+    if !is_active_user(&db, acting_user.user_uuid.clone()).await {
+        return Ok(forbidden_response("Inicia sesión para ver reportes"));
+    }
+
+    let lead_types = match report_query.lead_type {
+        Some(lead_type) => vec![lead_type],
+        None => vec![LeadType::ENTERPRISE, LeadType::SCHOOL, LeadType::CLINICAL],
+    };
+
+    Ok(HttpResponse::Ok()
+        .status(StatusCode::OK)
+        .json(build_attribution_report(&db, lead_types, acting_user.user_uuid).await))
+}
+
+#[utoipa::path(
+    tag = "Campaigns",
+    operation_id = "create_campaign",
+    responses(
+        (status = 201, description = "Campaign created", body = CampaignUuid),
+        (status = 400, description = "Invalid campaign", body = ApiError),
+        (status = 403, description = "Only an ADMIN can manage campaigns", body = ApiError),
+        (status = 500, description = "Campaign couldn't be saved", body = ApiError),
+    )
+)]
+#[post("/campaigns")]
+#[tracing::instrument(name = "Post Campaign", skip(db), fields(name = %body.name))]
+async fn create(
+    db: Data<Database>,
+    mut body: Json<Campaign>,
+    acting_user: ActingUser,
+    locale: RequestLocale,
+) -> Result<HttpResponse, CampaignError> {
+    body.created_by = acting_user.user_uuid.clone();
+    if !is_admin(&db, body.created_by.clone()).await {
+        return Ok(forbidden_response(
+            "Solo un ADMIN puede administrar campañas",
+        ));
+    }
+
+    let new_campaign = normalize_campaign(body.into_inner());
+    let key_errors_vec = get_all_campaign_errors(&db, &new_campaign, &locale.0).await;
+    if !key_errors_vec.is_empty() {
+        error!("Error campaign.create {:?}", key_errors_vec);
+        return Ok(invalid_campaign_response(key_errors_vec));
+    }
+
+    let date_created = Utc::now();
+    let my_campaign = Campaign {
+        deleted: false,
+        date_created: Some(date_created),
+        date_modified: Some(date_created),
+        modified_by: new_campaign.created_by.clone(),
+        ..new_campaign
+    };
+
+    match Database::add_one(&db, Campaign::new(get_uuid(), my_campaign)).await {
+        Some(campaign) => Ok(HttpResponse::Ok()
+            .insert_header(("HX-Trigger", "campaign_reload_page"))
+            .status(StatusCode::CREATED)
+            .json(CampaignUuid {
+                uuid: match campaign.uuid {
+                    Some(this_uuid) => shuffle_id(this_uuid),
+                    None => "".to_string(),
+                },
+            })),
+        None => {
+            error!("Error [POST] /campaigns");
+            Ok(HttpResponse::InternalServerError()
+                .insert_header((
+                    "HX-Trigger",
+                    "{ \"page_error\": \"Internal server error\" }".to_string(),
+                ))
+                .json(ApiError::new(CampaignError::CampaignCreationFailure)))
+        }
+    }
+}
+
+#[utoipa::path(
+    tag = "Campaigns",
+    operation_id = "update_campaign",
+    responses(
+        (status = 200, description = "Campaign updated", body = CampaignUuid),
+        (status = 400, description = "Invalid campaign", body = ApiError),
+        (status = 403, description = "Only an ADMIN can manage campaigns", body = ApiError),
+        (status = 404, description = "Campaign not found", body = ApiError),
+        (status = 500, description = "Campaign couldn't be saved", body = ApiError),
+    )
+)]
+#[patch("/campaigns")]
+#[tracing::instrument(name = "Patch Campaign", skip(db), fields(name = %body.name))]
+async fn update_one(
+    db: Data<Database>,
+    mut body: Json<Campaign>,
+    acting_user: ActingUser,
+    locale: RequestLocale,
+) -> Result<HttpResponse, CampaignError> {
+    body.modified_by = acting_user.user_uuid.clone();
+    if !is_admin(&db, body.modified_by.clone()).await {
+        return Ok(forbidden_response(
+            "Solo un ADMIN puede administrar campañas",
+        ));
+    }
+
+    let updated_campaign = normalize_campaign(body.into_inner());
+    let key_errors_vec = get_all_campaign_errors(&db, &updated_campaign, &locale.0).await;
+    if !key_errors_vec.is_empty() {
+        error!("Error in campaign.update_one: {:?}", key_errors_vec);
+        return Ok(invalid_campaign_response(key_errors_vec));
+    }
+
+    let uuid_in_db = match updated_campaign.uuid.clone() {
+        Some(t_id) => t_id,
+        None => String::from("forbidden"),
+    };
+
+    let stored_campaign = match Database::find_one(&db, uuid_in_db.clone()).await {
+        Some(campaign) => campaign,
+        None => return Ok(not_found_response(&uuid_in_db)),
+    };
+
+    let campaign_to_update = Campaign {
+        date_created: stored_campaign.date_created,
+        created_by: stored_campaign.created_by,
+        date_modified: Some(Utc::now()),
+        ..updated_campaign
+    };
+
+    match Database::update_one(&db, campaign_to_update).await {
+        Some(campaign) => Ok(HttpResponse::Ok()
+            .insert_header(("HX-Trigger", "campaign_reload_page"))
+            .status(StatusCode::OK)
+            .json(CampaignUuid {
+                uuid: match campaign.uuid {
+                    Some(this_uuid) => shuffle_id(this_uuid),
+                    None => "".to_string(),
+                },
+            })),
+        None => {
+            error!("Error in campaign.update_one");
+            Ok(HttpResponse::InternalServerError()
+                .insert_header((
+                    "HX-Trigger",
+                    format!(
+                        "{{ \"page_error\": {:?} }}",
+                        "Couldn't save campaign".to_string()
+                    ),
+                ))
+                .json(ApiError::new(CampaignError::CampaignCreationFailure)))
+        }
+    }
+}
+
+#[utoipa::path(
+    tag = "Campaigns",
+    operation_id = "delete_campaign",
+    params(CampaignUuid),
+    responses(
+        (status = 200, description = "Campaign deleted", body = CampaignUuid),
+        (status = 403, description = "Only an ADMIN can manage campaigns", body = ApiError),
+        (status = 404, description = "Campaign not found", body = ApiError),
+    )
+)]
+#[delete("/campaigns/{uuid}")]
+#[tracing::instrument(name = "Delete Campaign", skip(db), fields(uuid = %uuid.uuid))]
+async fn delete_one(
+    db: Data<Database>,
+    uuid: Path<CampaignUuid>,
+    acting_user: ActingUser,
+) -> Result<HttpResponse, CampaignError> {
+    let user_uuid = acting_user.user_uuid;
+    if !is_admin(&db, user_uuid.clone()).await {
+        return Ok(forbidden_response(
+            "Solo un ADMIN puede administrar campañas",
+        ));
+    }
+
+    let campaign_uuid = uuid.into_inner().uuid;
+
+    match Database::delete_one(&db, campaign_uuid.clone(), user_uuid).await {
+        Some(_) => Ok(HttpResponse::Ok()
+            .insert_header(("HX-Trigger", "campaign_reload_page"))
+            .status(StatusCode::OK)
+            .json(CampaignUuid {
+                uuid: shuffle_id(campaign_uuid),
+            })),
+        None => Ok(not_found_response(&campaign_uuid)),
+    }
+}
+
+#[derive(OpenApi)]
+#[openapi(paths(find_all, find_one, report, create, update_one, delete_one))]
+pub struct CampaignApiDoc;
+
+pub fn campaign_api_controllers(cfg: &mut ServiceConfig) {
+    cfg.service(create);
+    cfg.service(delete_one);
+    cfg.service(find_all);
+    cfg.service(find_one);
+    cfg.service(report);
+    cfg.service(update_one);
+}
@@ -0,0 +1,158 @@
+use actix_web::{
+    web::{get, post, Data, ServiceConfig},
+    HttpResponse,
+};
+use chrono_tz::Tz;
+use handlebars::{Handlebars, RenderError};
+use log::error;
+use serde_json::json;
+
+use crate::{
+    db::{campaign_db::CampaignDB, config::Database},
+    models::{
+        campaign_model::CampaignHandlebarsError, sales_model::LeadType, users_model::ActingUser,
+    },
+    utils::{
+        campaign_utils::{build_attribution_report, create_lead_channel_tags},
+        env::{set_env_vars, ConfVars},
+        fs_utils::read_hbs_template,
+        quote_utils::format_currency,
+        time::RequestTimezone,
+    },
+};
+
+handlebars_helper!(currency: |amount: f64| format_currency(amount));
+
+async fn campaign_table(db: Data<Database>, timezone: Tz) -> Result<String, RenderError> {
+    let template_path = "campaign_table";
+    let mut handlebars = Handlebars::new();
+    handlebars.register_helper("currency", Box::new(currency));
+
+    let template_contents = match read_hbs_template(template_path) {
+        Ok(contents) => contents,
+        Err(e) => {
+            error!("Failed to render contents for campaign table:: {}", e);
+            CampaignHandlebarsError::new(e.to_string()).error
+        }
+    };
+
+    let cf: ConfVars = set_env_vars();
+
+    match <Database as CampaignDB>::find_all_active(&db).await {
+        Some(campaigns) => {
+            let rows: Vec<serde_json::Value> = campaigns
+                .into_iter()
+                .map(|campaign| {
+                    let channel = campaign.channel.as_ref().map(|channel| channel.to_string());
+                    let start_date = campaign.start_date.map(|date| {
+                        date.with_timezone(&timezone)
+                            .format("%Y-%m-%d")
+                            .to_string()
+                    });
+                    let end_date = campaign.end_date.map(|date| {
+                        date.with_timezone(&timezone)
+                            .format("%Y-%m-%d")
+                            .to_string()
+                    });
+                    json!({
+                      "c": campaign,
+                      "channel": channel,
+                      "start_date": start_date,
+                      "end_date": end_date,
+                    })
+                })
+                .collect();
+
+            let data = json!({
+              "conf": cf,
+              "campaigns": rows,
+              "channels": create_lead_channel_tags(None),
+            });
+            let render = handlebars.render_template(&template_contents, &data)?;
+            Ok(render)
+        }
+        None => {
+            let data = json!({"conf": cf, "error": "Unable to fetch campaigns"});
+            let render_error = handlebars.render_template(&template_contents, &data)?;
+            Ok(render_error)
+        }
+    }
+}
+
+async fn campaign_report(
+    acting_user: ActingUser,
+    db: Data<Database>,
+) -> Result<String, RenderError> {
+    let mut handlebars = Handlebars::new();
+    handlebars.register_helper("currency", Box::new(currency));
+    let template_path = "campaign_report";
+
+    let template_contents = match read_hbs_template(template_path) {
+        Ok(contents) => contents,
+        Err(e) => {
+            error!("Couldn't render file for campaign report:: {}", e);
+            CampaignHandlebarsError::new(e.to_string()).error
+        }
+    };
+
+    let report = build_attribution_report(
+        &db,
+        vec![LeadType::ENTERPRISE, LeadType::SCHOOL, LeadType::CLINICAL],
+        acting_user.user_uuid,
+    )
+    .await;
+
+    let cf: ConfVars = set_env_vars();
+    let data = json!({
+      "conf": cf,
+      "report": report,
+    });
+
+    let render = handlebars.render_template(&template_contents, &data)?;
+    Ok(render)
+}
+
+pub fn campaign_html_controllers(cfg: &mut ServiceConfig) {
+    cfg.route(
+      "/htmx/campaign/table",
+      post().to(
+        |db: Data<Database>, timezone: RequestTimezone| async move {
+          let campaigns = campaign_table(db, timezone.0).await;
+
+          match campaigns {
+            Ok(c) => HttpResponse::Ok()
+              .content_type("text/html")
+              .append_header(("HX-Trigger", "activate_navbar_element"))
+              .body(c),
+            Err(e) => HttpResponse::Ok()
+              .content_type("text/html")
+              .append_header(("HX-Trigger", "error_campaign"))
+              .body(
+                format!("<span class=\"icon is-small is-left\"><i class=\"fas fa-ban\"></i>Failed to load campaigns: {}</span>",
+                e)
+              )
+          }
+        }
+      ),
+    );
+
+    cfg.route(
+      "/htmx/campaign/report",
+      get().to(
+        |acting_user, db: Data<Database>| async move {
+          let report = campaign_report(acting_user, db).await;
+
+          match report {
+            Ok(r) => HttpResponse::Ok().content_type("text/html").body(r),
+            Err(e) => HttpResponse::Ok()
+              .content_type("text/html")
+              .append_header(("HX-Trigger", "error_campaign"))
+              .body(
+                format!("<span class=\"icon is-small is-left\"><i class=\"fas fa-ban\"></i>Failed to load campaign report: {}</span>",
+                e)
+              )
+          }
+        }
+      ),
+    );
+}
@@ -2,27 +2,65 @@ use actix_web::{
     delete, get,
     http::StatusCode,
     patch, post,
-    web::{Data, Json, Path, ServiceConfig},
+    web::{Data, Json, Path, Query, ServiceConfig},
     HttpResponse,
 };
-use chrono::Local;
+use chrono::Utc;
 use log::error;
+use utoipa::OpenApi;
 use validator::Validate;
 
 use crate::db::{clinical_db::ClinicalDB, config::Database};
-use crate::error::clinical_error::ClinicalError;
+use crate::error::{api_error::ApiError, clinical_error::ClinicalError};
 use crate::{
-    models::clinical_model::{Clinical, ClinicalUuid},
+    models::{
+        clinical_model::{Clinical, ClinicalUuid},
+        lead_model::LeadFilter,
+        sales_model::LeadType,
+        users_model::ActingUser,
+    },
     utils::{
+        address_utils::normalize_address,
+        contact_utils::{clinical_person, sync_primary_contact},
         general_utils::{get_uuid, shuffle_id},
-        message_utils::get_validation_errors,
+        i18n_utils::RequestLocale,
+        lead_utils::clinical_summary,
+        message_utils::get_localized_validation_errors,
+        notification_utils::notify_lead_changes,
+        phone_utils::normalize_required_phone,
+        pipeline_utils::check_lead_stage,
+        scoring_utils::{clinical_facts, compute_lead_score},
+        social_utils::{normalize_optional_social_profile, SocialNetwork},
+        tag_utils::{normalize_tags, register_tags},
+        territory_utils::{assign_lead_owner, is_lead_in_scope, scope_leads},
     },
 };
 
+#[utoipa::path(
+    tag = "Clinical",
+    operation_id = "find_all_clinics",
+    params(LeadFilter),
+    responses(
+        (status = 200, description = "Active clinics in the acting user's scope", body = Vec<Clinical>),
+        (status = 404, description = "No clinics found", body = ApiError),
+    )
+)]
 #[get("/clinical")]
 #[tracing::instrument(name = "Show Clinical", skip(db))]
-async fn find_all(db: Data<Database>) -> Result<HttpResponse, ClinicalError> {
-    let clinical = Database::find_all_non_deleted(&db).await;
+async fn find_all(
+    db: Data<Database>,
+    filter: Query<LeadFilter>,
+    acting_user: ActingUser,
+) -> Result<HttpResponse, ClinicalError> {
+    let clinical = match Database::find_filtered(&db, &filter).await {
+        Some(leads) => Some(
+            scope_leads(&db, acting_user.user_uuid, leads, |lead: &Clinical| {
+                &lead.address
+            })
+            .await,
+        ),
+        None => None,
+    };
 
     match clinical {
         Some(found_clinical) => Ok(HttpResponse::Ok()
@@ -30,33 +68,53 @@ async fn find_all(db: Data<Database>) -> Result<HttpResponse, ClinicalError> {
             .json(found_clinical)),
         None => {
             error!("Didn't find any Clinical data");
-            Ok(HttpResponse::NotFound().json(ClinicalUuid {
-                uuid: format!("{}", ClinicalError::NoClinicalsFound),
-            }))
+            Ok(HttpResponse::NotFound().json(ApiError::new(ClinicalError::NoClinicalsFound)))
         }
     }
 }
 
+#[utoipa::path(
+    tag = "Clinical",
+    operation_id = "find_one_clinic",
+    params(ClinicalUuid),
+    responses(
+        (status = 200, description = "Clinic found", body = Clinical),
+        (status = 404, description = "Clinic not found", body = ApiError),
+    )
+)]
 #[get("/clinical/{uuid}")]
-#[tracing::instrument(name = "Get One Clinic", skip(db), fields(uuid = %uuid.uuid))]
+#[tracing::instrument(name = "Get One Clinic", skip(db, acting_user), fields(uuid = %uuid.uuid))]
 async fn find_one(
     db: Data<Database>,
     uuid: Path<ClinicalUuid>,
+    acting_user: ActingUser,
 ) -> Result<HttpResponse, ClinicalError> {
     let clinical_uuid = uuid.into_inner().uuid;
-    let clinical_result = Database::find_one(&db, clinical_uuid.clone()).await;
+    let clinical_result = match Database::find_one(&db, clinical_uuid.clone()).await {
+        Some(lead) if is_lead_in_scope(&db, acting_user.user_uuid, &lead.address).await => {
+            Some(lead)
+        }
+        _ => None,
+    };
 
     match clinical_result {
         Some(result) => Ok(HttpResponse::Ok().status(StatusCode::OK).json(result)),
         None => {
             error!("No items found for UUID:: {:?}", &clinical_uuid);
-            Ok(HttpResponse::NotFound().json(ClinicalUuid {
-                uuid: format!("{}", ClinicalError::NoClinicalsFound),
-            }))
+            Ok(HttpResponse::NotFound().json(ApiError::new(ClinicalError::NoClinicalsFound)))
         }
     }
 }
 
+#[utoipa::path(
+    tag = "Clinical",
+    operation_id = "create_clinic",
+    responses(
+        (status = 201, description = "Clinic created", body = ClinicalUuid),
+        (status = 400, description = "Invalid stage", body = ApiError),
+        (status = 500, description = "Invalid or unsaved clinic", body = ApiError),
+    )
+)]
 #[post("/clinical")]
 #[tracing::instrument(
     name = "Post Clinic", skip(db), fields(
@@ -66,20 +124,79 @@ async fn find_one(
         clinic_name = body.clinic_name,
     )
 )]
-async fn create(db: Data<Database>, body: Json<Clinical>) -> Result<HttpResponse, ClinicalError> {
+async fn create(
+    db: Data<Database>,
+    mut body: Json<Clinical>,
+    acting_user: ActingUser,
+    locale: RequestLocale,
+) -> Result<HttpResponse, ClinicalError> {
+    body.created_by = acting_user.user_uuid.clone();
     let is_valid = body.validate();
-    let date_created = Local::now();
+
+    if let Some(response) = check_lead_stage(
+        &db,
+        &LeadType::CLINICAL,
+        &body.sales_funnel,
+        ClinicalError::InvalidStage,
+    )
+    .await
+    {
+        return Ok(response);
+    }
+
+    let date_created = Utc::now();
     let mut new_clinical = body.into_inner();
     new_clinical.date_created = Some(date_created.clone());
     new_clinical.date_modified = Some(date_created.clone());
     new_clinical.first_contact_date = Some(date_created.clone());
+    new_clinical.tags = normalize_tags(new_clinical.tags);
+    new_clinical.phone = normalize_required_phone(new_clinical.phone);
+    new_clinical.fb = normalize_optional_social_profile(SocialNetwork::Facebook, new_clinical.fb);
+    new_clinical.instagram =
+        normalize_optional_social_profile(SocialNetwork::Instagram, new_clinical.instagram);
+    new_clinical.linked_in =
+        normalize_optional_social_profile(SocialNetwork::LinkedIn, new_clinical.linked_in);
+    new_clinical.tik_tok =
+        normalize_optional_social_profile(SocialNetwork::TikTok, new_clinical.tik_tok);
+    new_clinical.twitter =
+        normalize_optional_social_profile(SocialNetwork::Twitter, new_clinical.twitter);
+    new_clinical.address = normalize_address(new_clinical.address);
+    new_clinical.account_uuid = None;
+    new_clinical.assigned_to = assign_lead_owner(
+        &db,
+        new_clinical.assigned_to.clone(),
+        &new_clinical.address,
+        new_clinical.created_by.clone(),
+    )
+    .await;
 
     match is_valid {
         Ok(_) => {
+            register_tags(&db, &new_clinical.tags, new_clinical.created_by.clone()).await;
+            new_clinical.score =
+                compute_lead_score(&db, &LeadType::CLINICAL, &clinical_facts(&new_clinical)).await;
             let new_uuid = get_uuid();
             let my_clinical =
                 Database::add_one(&db, Clinical::new(String::from(new_uuid), new_clinical)).await;
 
+            if let Some(clinical) = &my_clinical {
+                sync_primary_contact(
+                    &db,
+                    &LeadType::CLINICAL,
+                    clinical_person(clinical),
+                    clinical.account_uuid.clone(),
+                    clinical.created_by.clone(),
+                )
+                .await;
+                notify_lead_changes(
+                    &db,
+                    None,
+                    &clinical_summary(clinical.clone()),
+                    clinical.created_by.clone(),
+                )
+                .await;
+            }
+
             match my_clinical {
                 Some(clinical_result) => Ok(HttpResponse::Ok()
                     .insert_header(("HX-Trigger", "clinic_reload_page"))
@@ -97,28 +214,37 @@ async fn create(db: Data<Database>, body: Json<Clinical>) -> Result<HttpResponse
                             "HX-Trigger",
                             format!("{{ \"page_error\": \"Internal server error\" }}"),
                         ))
-                        .json(ClinicalUuid {
-                            uuid: format!("{}", ClinicalError::ClinicalCreationFailure),
-                        }))
+                        .json(ApiError::new(ClinicalError::ClinicalCreationFailure)))
                 }
             }
         }
         Err(e) => {
             error!("Error clinical.create {:?}", e);
-            let key_errors_vec: Vec<String> = get_validation_errors(&e);
+            let key_errors_vec: Vec<String> = get_localized_validation_errors(&e, &locale.0);
 
             Ok(HttpResponse::InternalServerError()
                 .insert_header((
                     "HX-Trigger",
                     format!("{{ \"page_error\": {:?} }}", key_errors_vec),
                 ))
-                .json(ClinicalUuid {
-                    uuid: format!("{}", ClinicalError::ClinicalCreationFailure),
-                }))
+                .json(
+                    ApiError::new(ClinicalError::ClinicalCreationFailure)
+                        .with_field_errors(key_errors_vec),
+                ))
         }
     }
 }
 
+#[utoipa::path(
+    tag = "Clinical",
+    operation_id = "update_clinic",
+    responses(
+        (status = 200, description = "Clinic updated", body = ClinicalUuid),
+        (status = 404, description = "Clinic not found", body = ApiError),
+        (status = 400, description = "Invalid stage", body = ApiError),
+        (status = 500, description = "Invalid or unsaved clinic", body = ApiError),
+    )
+)]
 #[patch("/clinical")]
 #[tracing::instrument(
     name = "Patch Clinic", skip(db), fields(
@@ -130,10 +256,24 @@ async fn create(db: Data<Database>, body: Json<Clinical>) -> Result<HttpResponse
 )]
 async fn update_one(
     db: Data<Database>,
-    body: Json<Clinical>,
+    mut body: Json<Clinical>,
+    acting_user: ActingUser,
+    locale: RequestLocale,
 ) -> Result<HttpResponse, ClinicalError> {
+    body.modified_by = acting_user.user_uuid.clone();
     let is_valid = body.validate();
 
+    if let Some(response) = check_lead_stage(
+        &db,
+        &LeadType::CLINICAL,
+        &body.sales_funnel,
+        ClinicalError::InvalidStage,
+    )
+    .await
+    {
+        return Ok(response);
+    }
+
     match is_valid {
         Ok(_) => {
             let uuid_id_db = match body.uuid.clone() {
@@ -141,19 +281,31 @@ async fn update_one(
                 None => String::from(""),
             };
             let stored_clinical = Database::find_one(&db, uuid_id_db).await;
-            let date_modified = Local::now();
+            let stored_summary = stored_clinical.clone().map(clinical_summary);
+            let date_modified = Utc::now();
 
             let c_cloned = stored_clinical.clone();
+            let assigned_to = match &c_cloned {
+                Some(stored) => body.assigned_to.clone().or(stored.assigned_to.clone()),
+                None => body.assigned_to.clone(),
+            };
+            let account_uuid = c_cloned
+                .as_ref()
+                .and_then(|stored| stored.account_uuid.clone());
+            let source = body
+                .source
+                .clone()
+                .or(c_cloned.as_ref().and_then(|stored| stored.source.clone()));
 
             let date_created = match stored_clinical {
                 Some(clinical) => clinical.date_created,
                 None => {
                     error!("No date found for UUID:: {:?}", body.uuid.clone());
-                    Some(Local::now())
+                    Some(Utc::now())
                 }
             };
 
-            let my_clinical = Clinical {
+            let mut my_clinical = Clinical {
                 uuid: body.uuid.clone(),
                 name: body.name.clone(),
                 is_company: body.is_company.clone(),
@@ -175,32 +327,35 @@ async fn update_one(
                     Some(email) => email.clone(),
                     None => None,
                 },
-                phone: body.phone.clone(),
+                phone: normalize_required_phone(body.phone.clone()),
                 deleted: body.deleted.clone(),
-                fb: match Some(&body.fb) {
-                    Some(fb_req) => fb_req.clone(),
-                    None => None,
-                },
-                instagram: match Some(&body.instagram) {
-                    Some(ig_req) => ig_req.clone(),
-                    None => None,
-                },
-                linked_in: match Some(&body.linked_in) {
-                    Some(li_req) => li_req.clone(),
-                    None => None,
-                },
-                tik_tok: match Some(&body.tik_tok) {
-                    Some(tt_req) => tt_req.clone(),
-                    None => None,
-                },
-                twitter: match Some(&body.twitter) {
-                    Some(tw_req) => tw_req.clone(),
-                    None => None,
-                },
+                fb: normalize_optional_social_profile(SocialNetwork::Facebook, body.fb.clone()),
+                instagram: normalize_optional_social_profile(
+                    SocialNetwork::Instagram,
+                    body.instagram.clone(),
+                ),
+                linked_in: normalize_optional_social_profile(
+                    SocialNetwork::LinkedIn,
+                    body.linked_in.clone(),
+                ),
+                tik_tok: normalize_optional_social_profile(
+                    SocialNetwork::TikTok,
+                    body.tik_tok.clone(),
+                ),
+                twitter: normalize_optional_social_profile(
+                    SocialNetwork::Twitter,
+                    body.twitter.clone(),
+                ),
                 first_contact_date: body.first_contact_date.clone(),
                 sales_funnel: body.sales_funnel.clone(),
                 notes: body.notes.clone(),
                 services_offered: body.services_offered.clone(),
+                tags: normalize_tags(body.tags.clone()),
+                assigned_to,
+                score: 0,
+                account_uuid,
+                address: normalize_address(body.address.clone()),
+                source,
                 date_created,
                 date_modified: Some(date_modified),
                 created_by: match c_cloned {
@@ -213,8 +368,30 @@ async fn update_one(
                 },
             };
 
+            register_tags(&db, &my_clinical.tags, my_clinical.modified_by.clone()).await;
+            my_clinical.score =
+                compute_lead_score(&db, &LeadType::CLINICAL, &clinical_facts(&my_clinical)).await;
+
             let updated_clinical = Database::update_one(&db, my_clinical).await;
 
+            if let Some(clinical) = &updated_clinical {
+                sync_primary_contact(
+                    &db,
+                    &LeadType::CLINICAL,
+                    clinical_person(clinical),
+                    clinical.account_uuid.clone(),
+                    clinical.modified_by.clone(),
+                )
+                .await;
+                notify_lead_changes(
+                    &db,
+                    stored_summary.as_ref(),
+                    &clinical_summary(clinical.clone()),
+                    clinical.modified_by.clone(),
+                )
+                .await;
+            }
+
             match updated_clinical {
                 Some(clinical) => Ok(HttpResponse::Ok()
                     .insert_header(("HX-Trigger", "clinic_reload_page"))
@@ -235,32 +412,50 @@ async fn update_one(
                                 "Couldn't find school".to_string()
                             ),
                         ))
-                        .json(ClinicalUuid {
-                            uuid: format!("{}", ClinicalError::NoClinicalsFound),
-                        }))
+                        .json(ApiError::new(ClinicalError::NoClinicalsFound)))
                 }
             }
         }
         Err(e) => {
             error!("Error in clinical.update_one: {:?}", e);
-            let key_errors_vec: Vec<String> = get_validation_errors(&e);
+            let key_errors_vec: Vec<String> = get_localized_validation_errors(&e, &locale.0);
 
             Ok(HttpResponse::NotFound()
                 .insert_header((
                     "HX-Trigger",
                     format!("{{ \"page_error\": {:?} }}", key_errors_vec),
                 ))
-                .json(ClinicalUuid {
-                    uuid: format!("{}", ClinicalError::NoClinicalsFound),
-                }))
+                .json(
+                    ApiError::new(ClinicalError::NoClinicalsFound)
+                        .with_field_errors(key_errors_vec),
+                ))
         }
     }
 }
 
+#[utoipa::path(
+    tag = "Clinical",
+    operation_id = "find_deleted_clinics",
+    responses(
+        (status = 200, description = "Deleted clinics", body = Vec<Clinical>),
+        (status = 404, description = "No clinics found", body = ApiError),
+    )
+)]
 #[get("/clinical/deleted")]
-#[tracing::instrument(name = "Get Deleted Clinics", skip(db))]
-async fn find_all_deleted(db: Data<Database>) -> Result<HttpResponse, ClinicalError> {
-    let clinics = Database::find_all_deleted(&db).await;
+#[tracing::instrument(name = "Get Deleted Clinics", skip(db, acting_user))]
+async fn find_all_deleted(
+    db: Data<Database>,
+    acting_user: ActingUser,
+) -> Result<HttpResponse, ClinicalError> {
+    let clinics = match Database::find_all_deleted(&db).await {
+        Some(leads) => Some(
+            scope_leads(&db, acting_user.user_uuid, leads, |lead: &Clinical| {
+                &lead.address
+            })
+            .await,
+        ),
+        None => None,
+    };
     match clinics {
         Some(found_clinics) => Ok(HttpResponse::Ok()
             .insert_header(("HX-Trigger", "clinic_fad"))
@@ -268,13 +463,21 @@ async fn find_all_deleted(db: Data<Database>) -> Result<HttpResponse, ClinicalEr
             .json(found_clinics)),
         None => {
             error!("Didn't find any deleted clinics");
-            Ok(HttpResponse::NotFound().json(ClinicalUuid {
-                uuid: format!("{}", ClinicalError::NoClinicalsFound),
-            }))
+            Ok(HttpResponse::NotFound().json(ApiError::new(ClinicalError::NoClinicalsFound)))
         }
     }
 }
 
+#[utoipa::path(
+    tag = "Clinical",
+    operation_id = "delete_clinic",
+    params(ClinicalUuid),
+    responses(
+        (status = 200, description = "Clinic deleted", body = ClinicalUuid),
+        (status = 404, description = "Clinic not found", body = ApiError),
+        (status = 500, description = "Clinic couldn't be deleted", body = ApiError),
+    )
+)]
 #[delete("/clinical/{uuid}")]
 #[tracing::instrument(name = "Post Clinic", skip(db), fields(uuid = %uuid.uuid))]
 async fn delete_one(
@@ -296,21 +499,22 @@ async fn delete_one(
                     })),
                 None => {
                     error!("Unable to update clinic :: {:?}", &clinic_uuid);
-                    Ok(HttpResponse::InternalServerError().json(ClinicalUuid {
-                        uuid: format!("{}", ClinicalError::ClinicalCreationFailure),
-                    }))
+                    Ok(HttpResponse::InternalServerError()
+                        .json(ApiError::new(ClinicalError::ClinicalCreationFailure)))
                 }
             }
         }
         None => {
             error!("Error [POST] /enterprise");
-            Ok(HttpResponse::NotFound().json(ClinicalUuid {
-                uuid: format!("{}", ClinicalError::NoClinicalsFound),
-            }))
+            Ok(HttpResponse::NotFound().json(ApiError::new(ClinicalError::NoClinicalsFound)))
         }
     }
 }
 
+#[derive(OpenApi)]
+#[openapi(paths(find_all, find_one, create, update_one, find_all_deleted, delete_one))]
+pub struct ClinicalApiDoc;
+
 pub fn clinical_api_controllers(cfg: &mut ServiceConfig) {
     cfg.service(find_all);
     cfg.service(find_all_deleted);
@@ -2,35 +2,53 @@ use actix_web::{
     web::{post, Data, Path, ServiceConfig},
     HttpRequest, HttpResponse,
 };
+use chrono_tz::Tz;
 use handlebars::{Handlebars, RenderError};
 use log::{error, info};
 use serde_json::json;
 
 use crate::db::{clinical_db::ClinicalDB, config::Database};
-use crate::models::{clinical_model::*, sales_model::GeneralTags};
+use crate::models::{
+    clinical_model::*,
+    locale_model::Locale,
+    sales_model::{GeneralTags, LeadType},
+    users_model::ActingUser,
+};
 use crate::utils::{
     env::{set_env_vars, ConfVars},
     fs_utils::read_hbs_template,
     general_utils::{create_option_tags_info_for_services_and_funnel, get_options_and_services},
-    time::format_date_in_language,
+    i18n_utils::{register_i18n_helpers, RequestLocale},
+    pipeline_utils::get_pipeline,
+    service_utils::get_service_catalog,
+    territory_utils::{is_lead_in_scope, scope_leads},
+    time::{format_date_in_language, RequestTimezone},
 };
 
 handlebars_helper!(str_equal: |s1: String, s2: String| s1 == s2);
 
-async fn clinical_edit(hbs_path: Path<String>, db: Data<Database>) -> Result<String, RenderError> {
+async fn clinical_edit(
+    hbs_path: Path<String>,
+    acting_user: ActingUser,
+    db: Data<Database>,
+    locale: Locale,
+) -> Result<String, RenderError> {
     let uuid = hbs_path.into_inner();
     let my_error = format!("Unable to find uuid {}", &uuid).to_string();
     info!("Edit user screen for uuid:: {}", &uuid);
 
     let mut handlebars = Handlebars::new();
     handlebars.register_helper("str_equal", Box::new(str_equal));
+    register_i18n_helpers(&mut handlebars);
 
     let mut template_path = "clinical_edit";
 
     let clinical_from_db: Result<Clinical, ClinicalHandlebarsError> =
         match Database::find_one(&db, uuid).await {
-            Some(clinic) => Ok(clinic),
-            None => {
+            Some(clinic) if is_lead_in_scope(&db, acting_user.user_uuid, &clinic.address).await => {
+                Ok(clinic)
+            }
+            _ => {
                 error!("Not clinics found in db");
                 template_path = "clinical_edit";
                 Err(ClinicalHandlebarsError::new(my_error))
@@ -51,6 +69,8 @@ async fn clinical_edit(hbs_path: Path<String>, db: Data<Database>) -> Result<Str
     match clinical_from_db {
         Ok(this_clinic) => {
             let (services_tag, funnel_tag) = create_option_tags_info_for_services_and_funnel(
+                get_service_catalog(&db).await,
+                get_pipeline(&db, &LeadType::CLINICAL).await,
                 this_clinic.services_offered.clone(),
                 this_clinic.sales_funnel.clone(),
             );
@@ -58,6 +78,7 @@ async fn clinical_edit(hbs_path: Path<String>, db: Data<Database>) -> Result<Str
             let cf: ConfVars = set_env_vars();
             let data = json!({
               "conf": cf,
+              "locale": locale.code(),
               "services_tag": services_tag,
               "sales_funnel": funnel_tag,
               "c": this_clinic
@@ -73,9 +94,10 @@ async fn clinical_edit(hbs_path: Path<String>, db: Data<Database>) -> Result<Str
     }
 }
 
-async fn clinical_new() -> Result<String, RenderError> {
+async fn clinical_new(db: Data<Database>, locale: Locale) -> Result<String, RenderError> {
     let mut handlebars = Handlebars::new();
     handlebars.register_helper("str_equal", Box::new(str_equal));
+    register_i18n_helpers(&mut handlebars);
 
     let template_path = "clinical_new";
 
@@ -90,21 +112,38 @@ async fn clinical_new() -> Result<String, RenderError> {
         }
     };
 
-    let (services_tag, funnel_tag) = get_options_and_services();
+    let (services_tag, funnel_tag) = get_options_and_services(
+        get_service_catalog(&db).await,
+        get_pipeline(&db, &LeadType::CLINICAL).await,
+    );
     let cf: ConfVars = set_env_vars();
-    let data = json!({ "conf": cf, "services_tag": services_tag, "sales_funnel": funnel_tag});
+    let data = json!({ "conf": cf, "locale": locale.code(), "services_tag": services_tag, "sales_funnel": funnel_tag});
 
     let handlebars_render = handlebars.render_template(&template_contents, &data)?;
 
     Ok(handlebars_render)
 }
 
-async fn clinical_table(db: Data<Database>) -> Result<String, RenderError> {
+async fn clinical_table(
+    db: Data<Database>,
+    acting_user: ActingUser,
+    locale: Locale,
+    timezone: Tz,
+) -> Result<String, RenderError> {
     let template_path = "clinical_table";
     let mut handlebars = Handlebars::new();
     handlebars.register_helper("str_equal", Box::new(str_equal));
+    register_i18n_helpers(&mut handlebars);
 
-    let clinics_from_db = Database::find_all(&db).await;
+    let clinics_from_db = match Database::find_all(&db).await {
+        Some(leads) => Some(
+            scope_leads(&db, acting_user.user_uuid, leads, |lead: &Clinical| {
+                &lead.address
+            })
+            .await,
+        ),
+        None => None,
+    };
 
     let template_contents = match read_hbs_template(&template_path) {
         Ok(contents) => contents,
@@ -119,17 +158,25 @@ async fn clinical_table(db: Data<Database>) -> Result<String, RenderError> {
     };
 
     match clinics_from_db {
-        Some(these_clinics) => {
+        Some(mut these_clinics) => {
+            these_clinics.sort_by_key(|lead| std::cmp::Reverse(lead.score));
+
             let mut clinical_tags_vector: Vec<GeneralTags<Clinical>> = Vec::new();
+            let services = get_service_catalog(&db).await;
+            let stages = get_pipeline(&db, &LeadType::CLINICAL).await;
 
             for clinic in these_clinics {
                 let (services_tag, funnel_tag) = create_option_tags_info_for_services_and_funnel(
+                    services.clone(),
+                    stages.clone(),
                     clinic.services_offered.clone(),
                     clinic.sales_funnel.clone(),
                 );
 
                 let first_contact = match clinic.first_contact_date {
-                    Some(this_date) => format_date_in_language(&this_date, "es"),
+                    Some(this_date) => {
+                        format_date_in_language(&this_date, &locale, &timezone)
+                    },
                     None => "".to_string(),
                 };
 
@@ -142,7 +189,7 @@ async fn clinical_table(db: Data<Database>) -> Result<String, RenderError> {
             }
 
             let cf: ConfVars = set_env_vars();
-            let data = json!({ "conf": cf, "clinics": clinical_tags_vector.clone() });
+            let data = json!({ "conf": cf, "locale": locale.code(), "clinics": clinical_tags_vector.clone() });
 
             let render = handlebars.render_template(&template_contents, &data)?;
             Ok(render)
@@ -159,8 +206,8 @@ pub fn clinical_html_controllers(cfg: &mut ServiceConfig) {
     cfg.route(
       "/clinics/edit/{uuid}",
       post().to(
-          |_req: HttpRequest, hbs_path, db: Data<Database>| async move {
-              let clinic_editor_screen = clinical_edit(hbs_path, db).await;
+          |_req: HttpRequest, hbs_path, acting_user, db: Data<Database>, locale: RequestLocale| async move {
+              let clinic_editor_screen = clinical_edit(hbs_path, acting_user, db, locale.0).await;
               match clinic_editor_screen {
                   Ok(ces) => HttpResponse::Ok().content_type("text/html")
                     .body(ces),
@@ -181,8 +228,8 @@ pub fn clinical_html_controllers(cfg: &mut ServiceConfig) {
     cfg.route(
     "/htmx/clinics/table",
     post().to(
-      |db: Data<Database>| async move {
-        let my_enterprise_table = clinical_table(db).await;
+      |db: Data<Database>, acting_user, locale: RequestLocale, timezone: RequestTimezone| async move {
+        let my_enterprise_table = clinical_table(db, acting_user, locale.0, timezone.0).await;
 
         match my_enterprise_table {
           Ok(et) => HttpResponse::Ok()
@@ -204,8 +251,8 @@ pub fn clinical_html_controllers(cfg: &mut ServiceConfig) {
 
     cfg.route(
         "/new_clinic",
-        post().to(|| async move {
-            let new_clinical_editor = clinical_new().await;
+        post().to(|db: Data<Database>, locale: RequestLocale| async move {
+            let new_clinical_editor = clinical_new(db, locale.0).await;
 
             match new_clinical_editor {
               Ok(new_enterprise) => HttpResponse::Ok()
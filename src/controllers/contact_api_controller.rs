@@ -0,0 +1,299 @@
+use actix_web::{
+    delete, get,
+    http::StatusCode,
+    patch, post,
+    web::{Data, Json, Path, ServiceConfig},
+    HttpResponse, ResponseError,
+};
+use chrono::Utc;
+use log::error;
+use utoipa::OpenApi;
+use validator::Validate;
+
+use crate::{
+    db::{account_db::AccountDB, config::Database, contact_db::ContactDB},
+    error::{account_error::AccountError, api_error::ApiError},
+    models::{
+        account_model::{Contact, ContactUuid},
+        sales_model::LeadType,
+        users_model::ActingUser,
+    },
+    utils::{
+        contact_utils::{find_lead_contacts, keep_single_primary, sync_lead_person},
+        general_utils::{get_uuid, shuffle_id},
+        i18n_utils::RequestLocale,
+        lead_utils::find_lead_summary,
+        message_utils::get_localized_validation_errors,
+        phone_utils::normalize_optional_phone,
+        role_utils::is_active_user,
+    },
+};
+
+fn error_response(account_error: AccountError, message: &str) -> HttpResponse {
+    HttpResponse::build(account_error.status_code())
+        .insert_header((
+            "HX-Trigger",
+            format!("{{ \"page_error\": {:?} }}", message.to_string()),
+        ))
+        .json(ApiError::new(account_error).with_message(message))
+}
+
+fn forbidden_response() -> HttpResponse {
+    error_response(
+        AccountError::AccountForbidden,
+        "Inicia sesión para administrar contactos",
+    )
+}
+
+fn invalid_response(account_error: AccountError, key_errors_vec: Vec<String>) -> HttpResponse {
+    HttpResponse::BadRequest()
+        .insert_header((
+            "HX-Trigger",
+            format!("{{ \"page_error\": {:?} }}", key_errors_vec),
+        ))
+        .json(ApiError::new(account_error).with_field_errors(key_errors_vec))
+}
+
+async fn find_contact_owner(
+    db: &Data<Database>,
+    contact: &Contact,
+) -> Option<(LeadType, String, Option<String>)> {
+    if let Some(account_uuid) = contact.account_uuid.clone() {
+        return <Database as AccountDB>::find_one(db, account_uuid.clone())
+            .await
+            .map(|account| (account.lead_type, account.lead_uuid, Some(account_uuid)));
+    }
+
+    match (contact.lead_type.clone(), contact.lead_uuid.clone()) {
+        (Some(lead_type), Some(lead_uuid)) => find_lead_summary(db, &lead_type, lead_uuid)
+            .await
+            .map(|lead| (lead_type, lead.uuid, lead.account_uuid)),
+        _ => None,
+    }
+}
+
+#[utoipa::path(
+    tag = "Contacts",
+    operation_id = "find_lead_contacts",
+    responses(
+        (status = 200, description = "Lead contacts", body = Vec<Contact>),
+        (status = 404, description = "Lead not found", body = ApiError),
+    )
+)]
+#[get("/leads/{lead_type}/{uuid}/contacts")]
+#[tracing::instrument(name = "Show Lead Contacts", skip(db, path))]
+async fn find_by_lead(
+    db: Data<Database>,
+    path: Path<(LeadType, String)>,
+) -> Result<HttpResponse, AccountError> {
+    let (lead_type, lead_uuid) = path.into_inner();
+
+    match find_lead_summary(&db, &lead_type, lead_uuid.clone()).await {
+        Some(_) => Ok(HttpResponse::Ok()
+            .status(StatusCode::OK)
+            .json(find_lead_contacts(&db, lead_uuid).await)),
+        None => Ok(HttpResponse::NotFound().json(ApiError::new(AccountError::LeadNotFound))),
+    }
+}
+
+#[utoipa::path(
+    tag = "Contacts",
+    operation_id = "create_contact",
+    responses(
+        (status = 201, description = "Contact created", body = ContactUuid),
+        (status = 400, description = "Invalid contact", body = ApiError),
+        (status = 404, description = "Lead not found", body = ApiError),
+    )
+)]
+#[post("/contacts")]
+#[tracing::instrument(name = "Post Contact", skip(db), fields(name = %body.name))]
+async fn create_one(
+    db: Data<Database>,
+    mut body: Json<Contact>,
+    acting_user: ActingUser,
+    locale: RequestLocale,
+) -> Result<HttpResponse, AccountError> {
+    body.created_by = acting_user.user_uuid.clone();
+    if !is_active_user(&db, body.created_by.clone()).await {
+        return Ok(forbidden_response());
+    }
+
+    if let Err(e) = body.validate() {
+        error!("Error contact.create {:?}", e);
+        return Ok(invalid_response(
+            AccountError::AccountCreationFailure,
+            get_localized_validation_errors(&e, &locale.0),
+        ));
+    }
+
+    let (lead_type, lead_uuid, account_uuid) = match find_contact_owner(&db, &body).await {
+        Some(owner) => owner,
+        None => {
+            return Ok(error_response(
+                AccountError::LeadNotFound,
+                "El contacto necesita un prospecto o una cuenta",
+            ))
+        }
+    };
+
+    let date_created = Utc::now();
+    let new_contact = body.into_inner();
+    let my_contact = Contact {
+        account_uuid,
+        lead_type: Some(lead_type),
+        lead_uuid: Some(lead_uuid),
+        phone: normalize_optional_phone(new_contact.phone.clone()),
+        deleted: false,
+        date_created: Some(date_created),
+        date_modified: Some(date_created),
+        modified_by: new_contact.created_by.clone(),
+        ..new_contact
+    };
+
+    match <Database as ContactDB>::add_one(&db, Contact::new(get_uuid(), my_contact)).await {
+        Some(contact) => {
+            keep_single_primary(&db, &contact).await;
+            sync_lead_person(&db, &contact).await;
+            Ok(HttpResponse::Ok()
+                .insert_header(("HX-Trigger", "contact_reload_page"))
+                .status(StatusCode::CREATED)
+                .json(ContactUuid {
+                    uuid: match contact.uuid {
+                        Some(this_uuid) => shuffle_id(this_uuid),
+                        None => "".to_string(),
+                    },
+                }))
+        }
+        None => Ok(error_response(
+            AccountError::AccountCreationFailure,
+            "Internal server error",
+        )),
+    }
+}
+
+#[utoipa::path(
+    tag = "Contacts",
+    operation_id = "update_contact",
+    responses(
+        (status = 200, description = "Contact updated", body = ContactUuid),
+        (status = 400, description = "Invalid contact", body = ApiError),
+        (status = 404, description = "Contact not found", body = ApiError),
+    )
+)]
+#[patch("/contacts")]
+#[tracing::instrument(name = "Patch Contact", skip(db), fields(name = %body.name))]
+async fn update_one(
+    db: Data<Database>,
+    mut body: Json<Contact>,
+    acting_user: ActingUser,
+    locale: RequestLocale,
+) -> Result<HttpResponse, AccountError> {
+    body.modified_by = acting_user.user_uuid.clone();
+    if !is_active_user(&db, body.modified_by.clone()).await {
+        return Ok(forbidden_response());
+    }
+
+    if let Err(e) = body.validate() {
+        error!("Error in contact.update_one: {:?}", e);
+        return Ok(invalid_response(
+            AccountError::AccountCreationFailure,
+            get_localized_validation_errors(&e, &locale.0),
+        ));
+    }
+
+    let uuid_in_db = body.uuid.clone().unwrap_or(String::from("forbidden"));
+    let stored_contact = match <Database as ContactDB>::find_one(&db, uuid_in_db.clone()).await {
+        Some(contact) => contact,
+        None => {
+            error!("No contact found for UUID:: {:?}", &uuid_in_db);
+            return Ok(HttpResponse::NotFound().json(ApiError::new(AccountError::NoContactsFound)));
+        }
+    };
+
+    let updated_contact = body.into_inner();
+    let contact_to_update = Contact {
+        account_uuid: stored_contact.account_uuid,
+        lead_type: stored_contact.lead_type,
+        lead_uuid: stored_contact.lead_uuid,
+        is_primary: stored_contact.is_primary || updated_contact.is_primary,
+        phone: normalize_optional_phone(updated_contact.phone.clone()),
+        date_created: stored_contact.date_created,
+        created_by: stored_contact.created_by,
+        date_modified: Some(Utc::now()),
+        ..updated_contact
+    };
+
+    match <Database as ContactDB>::update_one(&db, contact_to_update).await {
+        Some(contact) => {
+            keep_single_primary(&db, &contact).await;
+            sync_lead_person(&db, &contact).await;
+            Ok(HttpResponse::Ok()
+                .insert_header(("HX-Trigger", "contact_reload_page"))
+                .status(StatusCode::OK)
+                .json(ContactUuid {
+                    uuid: match contact.uuid {
+                        Some(this_uuid) => shuffle_id(this_uuid),
+                        None => "".to_string(),
+                    },
+                }))
+        }
+        None => Ok(error_response(
+            AccountError::AccountCreationFailure,
+            "Couldn't save contact",
+        )),
+    }
+}
+
+#[utoipa::path(
+    tag = "Contacts",
+    operation_id = "delete_contact",
+    params(ContactUuid),
+    responses(
+        (status = 200, description = "Contact deleted", body = ContactUuid),
+        (status = 404, description = "Contact not found", body = ApiError),
+    )
+)]
+#[delete("/contacts/{uuid}")]
+#[tracing::instrument(name = "Delete Contact", skip(db), fields(uuid = %uuid.uuid))]
+async fn delete_one(
+    db: Data<Database>,
+    uuid: Path<ContactUuid>,
+    acting_user: ActingUser,
+) -> Result<HttpResponse, AccountError> {
+    let user_uuid = acting_user.user_uuid;
+    if !is_active_user(&db, user_uuid.clone()).await {
+        return Ok(forbidden_response());
+    }
+
+    let contact_uuid = uuid.into_inner().uuid;
+
+    if let Some(contact) = <Database as ContactDB>::find_one(&db, contact_uuid.clone()).await {
+        if contact.is_primary {
+            return Ok(error_response(
+                AccountError::AccountForbidden,
+                "Marca otro contacto como principal antes de eliminar este",
+            ));
+        }
+    }
+
+    match <Database as ContactDB>::delete_one(&db, contact_uuid.clone(), user_uuid).await {
+        Some(_) => Ok(HttpResponse::Ok()
+            .insert_header(("HX-Trigger", "contact_reload_page"))
+            .status(StatusCode::OK)
+            .json(ContactUuid {
+                uuid: shuffle_id(contact_uuid),
+            })),
+        None => Ok(HttpResponse::NotFound().json(ApiError::new(AccountError::NoContactsFound))),
+    }
+}
+
+#[derive(OpenApi)]
+#[openapi(paths(find_by_lead, create_one, update_one, delete_one))]
+pub struct ContactApiDoc;
+
+pub fn contact_api_controllers(cfg: &mut ServiceConfig) {
+    cfg.service(create_one);
+    cfg.service(delete_one);
+    cfg.service(find_by_lead);
+    cfg.service(update_one);
+}
@@ -0,0 +1,69 @@
+use actix_web::{
+    web::{post, Data, Path, ServiceConfig},
+    HttpResponse,
+};
+use handlebars::{Handlebars, RenderError};
+use log::{error, info};
+use serde_json::json;
+
+use crate::{
+    db::config::Database,
+    models::{account_model::AccountHandlebarsError, sales_model::LeadType},
+    utils::{
+        contact_utils::find_lead_contacts,
+        env::{set_env_vars, ConfVars},
+        fs_utils::read_hbs_template,
+    },
+};
+
+async fn contact_list(
+    hbs_path: Path<(LeadType, String)>,
+    db: Data<Database>,
+) -> Result<String, RenderError> {
+    let (lead_type, lead_uuid) = hbs_path.into_inner();
+    info!("Contacts for lead:: {}", &lead_uuid);
+
+    let handlebars = Handlebars::new();
+    let template_path = "contact_list";
+
+    let template_contents = match read_hbs_template(template_path) {
+        Ok(contents) => contents,
+        Err(e) => {
+            error!("Couldn't render file for contact list:: {}", e);
+            AccountHandlebarsError::new(e.to_string()).error
+        }
+    };
+
+    let cf: ConfVars = set_env_vars();
+    let data = json!({
+      "conf": cf,
+      "lead_type": lead_type,
+      "lead_uuid": lead_uuid,
+      "contacts": find_lead_contacts(&db, lead_uuid.clone()).await,
+    });
+
+    let render = handlebars.render_template(&template_contents, &data)?;
+    Ok(render)
+}
+
+pub fn contact_html_controllers(cfg: &mut ServiceConfig) {
+    cfg.route(
+      "/htmx/contacts/{lead_type}/{uuid}",
+      post().to(
+        |hbs_path, db: Data<Database>| async move {
+          let contacts = contact_list(hbs_path, db).await;
+
+          match contacts {
+            Ok(c) => HttpResponse::Ok().content_type("text/html").body(c),
+            Err(e) => HttpResponse::Ok()
+              .content_type("text/html")
+              .append_header(("HX-Trigger", "error_contact"))
+              .body(
+                format!("<span class=\"icon is-small is-left\"><i class=\"fas fa-ban\"></i>Failed to load contacts: {}</span>",
+                e)
+              )
+          }
+        }
+      ),
+    );
+}
@@ -0,0 +1,406 @@
+use actix_web::{
+    delete, get,
+    http::StatusCode,
+    patch, post,
+    web::{Data, Json, Path, ServiceConfig},
+    HttpResponse,
+};
+use chrono::Utc;
+use log::error;
+use utoipa::OpenApi;
+use validator::Validate;
+
+use crate::{
+    db::{config::Database, email_template_db::EmailTemplateDB},
+    error::{api_error::ApiError, email_template_error::EmailTemplateError},
+    models::{
+        email_template_model::{EmailTemplate, EmailTemplateUuid},
+        users_model::ActingUser,
+    },
+    utils::{
+        email_template_utils::get_template_variable_errors,
+        general_utils::{get_uuid, shuffle_id},
+        i18n_utils::RequestLocale,
+        message_utils::get_localized_validation_errors,
+        role_utils::is_admin,
+    },
+};
+
+fn forbidden_response() -> HttpResponse {
+    HttpResponse::Forbidden()
+        .insert_header((
+            "HX-Trigger",
+            format!(
+                "{{ \"page_error\": {:?} }}",
+                "Solo un ADMIN puede editar plantillas de correo".to_string()
+            ),
+        ))
+        .json(ApiError::new(EmailTemplateError::EmailTemplateForbidden))
+}
+
+fn invalid_template_response(key_errors_vec: Vec<String>) -> HttpResponse {
+    HttpResponse::BadRequest()
+        .insert_header((
+            "HX-Trigger",
+            format!("{{ \"page_error\": {:?} }}", key_errors_vec),
+        ))
+        .json(
+            ApiError::new(EmailTemplateError::EmailTemplateCreationFailure)
+                .with_field_errors(key_errors_vec),
+        )
+}
+
+#[utoipa::path(
+    tag = "Email templates",
+    operation_id = "find_all_email_templates",
+    responses(
+        (status = 200, description = "All email_templates", body = Vec<EmailTemplate>),
+        (status = 404, description = "No email_templates found", body = ApiError),
+    )
+)]
+#[get("/email_templates")]
+#[tracing::instrument(name = "Show Email Templates", skip(db))]
+async fn find_all(db: Data<Database>) -> Result<HttpResponse, EmailTemplateError> {
+    let templates = Database::find_all_current(&db).await;
+
+    match templates {
+        Some(found_templates) => Ok(HttpResponse::Ok()
+            .status(StatusCode::OK)
+            .json(found_templates)),
+        None => {
+            error!("Unable to find any email template");
+            Ok(HttpResponse::NotFound()
+                .json(ApiError::new(EmailTemplateError::NoEmailTemplatesFound)))
+        }
+    }
+}
+
+#[utoipa::path(
+    tag = "Email templates",
+    operation_id = "find_one_email_template",
+    params(EmailTemplateUuid),
+    responses(
+        (status = 200, description = "Email_template found", body = EmailTemplate),
+        (status = 404, description = "Email_template not found", body = ApiError),
+    )
+)]
+#[get("/email_templates/{uuid}")]
+#[tracing::instrument(name = "Get One Email Template", skip(db), fields(uuid = %uuid.uuid))]
+async fn find_one(
+    db: Data<Database>,
+    uuid: Path<EmailTemplateUuid>,
+) -> Result<HttpResponse, EmailTemplateError> {
+    let template_uuid = uuid.into_inner().uuid;
+
+    match Database::find_one(&db, template_uuid.clone()).await {
+        Some(template) => Ok(HttpResponse::Ok().status(StatusCode::OK).json(template)),
+        None => {
+            error!("No email template found for UUID:: {:?}", &template_uuid);
+            Ok(HttpResponse::NotFound()
+                .json(ApiError::new(EmailTemplateError::NoEmailTemplatesFound)))
+        }
+    }
+}
+
+#[utoipa::path(
+    tag = "Email templates",
+    operation_id = "find_email_template_versions",
+    params(EmailTemplateUuid),
+    responses(
+        (status = 200, description = "Template version history", body = Vec<EmailTemplate>),
+        (status = 404, description = "Template not found", body = ApiError),
+    )
+)]
+#[get("/email_templates/{uuid}/versions")]
+#[tracing::instrument(name = "Get Email Template Versions", skip(db), fields(uuid = %uuid.uuid))]
+async fn find_versions(
+    db: Data<Database>,
+    uuid: Path<EmailTemplateUuid>,
+) -> Result<HttpResponse, EmailTemplateError> {
+    let template_uuid = uuid.into_inner().uuid;
+
+    let template_key = match Database::find_one(&db, template_uuid.clone()).await {
+        Some(template) => template.template_key.unwrap_or(template_uuid.clone()),
+        None => {
+            error!("No email template found for UUID:: {:?}", &template_uuid);
+            return Ok(HttpResponse::NotFound()
+                .json(ApiError::new(EmailTemplateError::NoEmailTemplatesFound)));
+        }
+    };
+
+    match Database::find_versions(&db, template_key).await {
+        Some(versions) => Ok(HttpResponse::Ok().status(StatusCode::OK).json(versions)),
+        None => {
+            Ok(HttpResponse::NotFound()
+                .json(ApiError::new(EmailTemplateError::NoEmailTemplatesFound)))
+        }
+    }
+}
+
+#[utoipa::path(
+    tag = "Email templates",
+    operation_id = "create_email_template",
+    responses(
+        (status = 201, description = "Email_template created", body = EmailTemplateUuid),
+        (status = 400, description = "Invalid email_template", body = ApiError),
+        (status = 403, description = "Only an ADMIN can manage email_templates", body = ApiError),
+        (status = 500, description = "Email_template couldn't be saved", body = ApiError),
+    )
+)]
+#[post("/email_templates")]
+#[tracing::instrument(
+    name = "Post Email Template",
+    skip(db),
+    fields(name = %body.name, language = %body.language, lead_type = %body.lead_type)
+)]
+async fn create(
+    db: Data<Database>,
+    mut body: Json<EmailTemplate>,
+    acting_user: ActingUser,
+    locale: RequestLocale,
+) -> Result<HttpResponse, EmailTemplateError> {
+    body.created_by = acting_user.user_uuid.clone();
+    if !is_admin(&db, body.created_by.clone()).await {
+        return Ok(forbidden_response());
+    }
+
+    if let Err(e) = body.validate() {
+        error!("Error email_template.create {:?}", e);
+        return Ok(invalid_template_response(get_localized_validation_errors(
+            &e, &locale.0,
+        )));
+    }
+
+    let variable_errors = get_template_variable_errors(
+        &body.subject,
+        &body.html_body,
+        &body.text_body,
+        &body.lead_type,
+    );
+
+    if !variable_errors.is_empty() {
+        error!("Error email_template.create {:?}", variable_errors);
+        return Ok(invalid_template_response(variable_errors));
+    }
+
+    let date_created = Utc::now();
+    let mut new_template = body.into_inner();
+    new_template.template_key = None;
+    new_template.version = Some(1);
+    new_template.is_current = Some(true);
+    new_template.deleted = false;
+    new_template.date_created = Some(date_created);
+    new_template.date_modified = Some(date_created);
+    new_template.modified_by = new_template.created_by.clone();
+
+    let my_template = Database::add_one(&db, EmailTemplate::new(get_uuid(), new_template)).await;
+
+    match my_template {
+        Some(template_result) => Ok(HttpResponse::Ok()
+            .insert_header(("HX-Trigger", "email_template_reload_page"))
+            .status(StatusCode::CREATED)
+            .json(EmailTemplateUuid {
+                uuid: match template_result.uuid {
+                    Some(this_uuid) => shuffle_id(this_uuid),
+                    None => "".to_string(),
+                },
+            })),
+        None => {
+            error!("Error [POST] /email_templates");
+            Ok(HttpResponse::InternalServerError()
+                .insert_header((
+                    "HX-Trigger",
+                    "{ \"page_error\": \"Internal server error\" }".to_string(),
+                ))
+                .json(ApiError::new(
+                    EmailTemplateError::EmailTemplateCreationFailure,
+                )))
+        }
+    }
+}
+
+#[utoipa::path(
+    tag = "Email templates",
+    operation_id = "update_email_template",
+    responses(
+        (status = 200, description = "Email_template updated", body = EmailTemplateUuid),
+        (status = 400, description = "Invalid email_template", body = ApiError),
+        (status = 403, description = "Only an ADMIN can manage email_templates", body = ApiError),
+        (status = 404, description = "Email_template not found", body = ApiError),
+        (status = 409, description = "Only the current version can be edited", body = ApiError),
+        (status = 500, description = "Email_template couldn't be saved", body = ApiError),
+    )
+)]
+#[patch("/email_templates")]
+#[tracing::instrument(
+    name = "Patch Email Template",
+    skip(db),
+    fields(uuid = body.uuid, name = %body.name, language = %body.language)
+)]
+async fn update_one(
+    db: Data<Database>,
+    mut body: Json<EmailTemplate>,
+    acting_user: ActingUser,
+    locale: RequestLocale,
+) -> Result<HttpResponse, EmailTemplateError> {
+    body.modified_by = acting_user.user_uuid.clone();
+    if !is_admin(&db, body.modified_by.clone()).await {
+        return Ok(forbidden_response());
+    }
+
+    if let Err(e) = body.validate() {
+        error!("Error in email_template.update_one: {:?}", e);
+        return Ok(invalid_template_response(get_localized_validation_errors(
+            &e, &locale.0,
+        )));
+    }
+
+    let variable_errors = get_template_variable_errors(
+        &body.subject,
+        &body.html_body,
+        &body.text_body,
+        &body.lead_type,
+    );
+
+    if !variable_errors.is_empty() {
+        error!("Error in email_template.update_one: {:?}", variable_errors);
+        return Ok(invalid_template_response(variable_errors));
+    }
+
+    let uuid_in_db = match body.uuid.clone() {
+        Some(t_id) => t_id,
+        None => String::from("forbidden"),
+    };
+
+    let stored_template = match Database::find_one(&db, uuid_in_db.clone()).await {
+        Some(template) => template,
+        None => {
+            error!("No email template found for UUID:: {:?}", &uuid_in_db);
+            return Ok(HttpResponse::NotFound()
+                .json(ApiError::new(EmailTemplateError::NoEmailTemplatesFound)));
+        }
+    };
+
+    // Older versions are history; editing one would fork the template.
+    if stored_template.is_current != Some(true) {
+        error!(
+            "Email template {:?} is not the current version",
+            &uuid_in_db
+        );
+        return Ok(HttpResponse::Conflict()
+            .insert_header((
+                "HX-Trigger",
+                format!(
+                    "{{ \"page_error\": {:?} }}",
+                    "Solo se puede editar la versión actual de la plantilla".to_string()
+                ),
+            ))
+            .json(ApiError::new(EmailTemplateError::EmailTemplateNotCurrent)));
+    }
+
+    let head_version = match stored_template.template_key.clone() {
+        Some(template_key) => Database::find_versions(&db, template_key)
+            .await
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|template| template.version)
+            .max(),
+        None => None,
+    };
+    let date_modified = Utc::now();
+
+    let new_version = EmailTemplate {
+        uuid: None,
+        template_key: stored_template.template_key.clone(),
+        name: body.name.clone(),
+        language: body.language.clone(),
+        lead_type: body.lead_type.clone(),
+        version: Some(head_version.or(stored_template.version).unwrap_or(1) + 1),
+        is_current: Some(true),
+        subject: body.subject.clone(),
+        html_body: body.html_body.clone(),
+        text_body: body.text_body.clone(),
+        deleted: stored_template.deleted,
+        date_created: stored_template.date_created,
+        date_modified: Some(date_modified),
+        created_by: stored_template.created_by.clone(),
+        modified_by: body.modified_by.clone(),
+    };
+
+    match Database::add_version(&db, uuid_in_db, new_version).await {
+        Some(template) => Ok(HttpResponse::Ok()
+            .insert_header(("HX-Trigger", "email_template_reload_page"))
+            .status(StatusCode::OK)
+            .json(EmailTemplateUuid {
+                uuid: match template.uuid {
+                    Some(this_uuid) => shuffle_id(this_uuid),
+                    None => "".to_string(),
+                },
+            })),
+        None => {
+            error!("Error in email_template.update_one");
+            Ok(HttpResponse::InternalServerError()
+                .insert_header((
+                    "HX-Trigger",
+                    format!(
+                        "{{ \"page_error\": {:?} }}",
+                        "Couldn't save email template".to_string()
+                    ),
+                ))
+                .json(ApiError::new(
+                    EmailTemplateError::EmailTemplateCreationFailure,
+                )))
+        }
+    }
+}
+
+#[utoipa::path(
+    tag = "Email templates",
+    operation_id = "delete_email_template",
+    params(EmailTemplateUuid),
+    responses(
+        (status = 200, description = "Email_template deleted", body = EmailTemplateUuid),
+        (status = 403, description = "Only an ADMIN can manage email_templates", body = ApiError),
+        (status = 404, description = "Email_template not found", body = ApiError),
+    )
+)]
+#[delete("/email_templates/{uuid}")]
+#[tracing::instrument(name = "Delete Email Template", skip(db), fields(uuid = %uuid.uuid))]
+async fn delete_one(
+    db: Data<Database>,
+    uuid: Path<EmailTemplateUuid>,
+    acting_user: ActingUser,
+) -> Result<HttpResponse, EmailTemplateError> {
+    if !is_admin(&db, acting_user.user_uuid).await {
+        return Ok(forbidden_response());
+    }
+
+    let template_uuid = uuid.into_inner().uuid;
+
+    match Database::delete_one(&db, template_uuid.clone()).await {
+        Some(_) => Ok(HttpResponse::Ok()
+            .insert_header(("HX-Trigger", "email_template_reload_page"))
+            .status(StatusCode::OK)
+            .json(EmailTemplateUuid {
+                uuid: shuffle_id(template_uuid),
+            })),
+        None => {
+            error!("Unable to delete email template:: {:?}", &template_uuid);
+            Ok(HttpResponse::NotFound()
+                .json(ApiError::new(EmailTemplateError::NoEmailTemplatesFound)))
+        }
+    }
+}
+
+#[derive(OpenApi)]
+#[openapi(paths(find_all, find_one, find_versions, create, update_one, delete_one))]
+pub struct EmailTemplateApiDoc;
+
+pub fn email_template_api_controllers(cfg: &mut ServiceConfig) {
+    cfg.service(create);
+    cfg.service(delete_one);
+    cfg.service(find_all);
+    cfg.service(find_one);
+    cfg.service(find_versions);
+    cfg.service(update_one);
+}
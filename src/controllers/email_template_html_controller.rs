@@ -0,0 +1,281 @@
+use actix_web::{
+    web::{post, Data, Json, Path, ServiceConfig},
+    HttpRequest, HttpResponse,
+};
+use handlebars::{Handlebars, RenderError};
+use log::{error, info};
+use serde_json::json;
+
+use crate::{
+    db::{config::Database, email_template_db::EmailTemplateDB},
+    models::email_template_model::*,
+    utils::{
+        email_template_utils::{
+            get_sample_data, get_template_variable_errors, render_email,
+        },
+        env::{set_env_vars, ConfVars},
+        fs_utils::read_hbs_template,
+        general_utils::{create_lead_type_tags, get_lead_type_tags},
+    },
+};
+
+handlebars_helper!(str_equal: |s1: String, s2: String| s1 == s2);
+
+async fn email_template_table(db: Data<Database>) -> Result<String, RenderError> {
+    let template_path = "email_template_table";
+    let mut handlebars = Handlebars::new();
+    handlebars.register_helper("str_equal", Box::new(str_equal));
+
+    let templates_from_db = Database::find_all_current(&db).await;
+
+    let template_contents = match read_hbs_template(template_path) {
+        Ok(contents) => contents,
+        Err(e) => {
+            error!(
+                "Failed to render contents for email template table:: {}",
+                e
+            );
+            EmailTemplateHandlebarsError::new(e.to_string()).error
+        }
+    };
+
+    let cf: ConfVars = set_env_vars();
+
+    match templates_from_db {
+        Some(templates) => {
+            let data = json!({"conf": cf, "templates": templates});
+            let render = handlebars.render_template(&template_contents, &data)?;
+            Ok(render)
+        }
+        None => {
+            let data = json!({"conf": cf, "error": "Unable to fetch email templates"});
+            let render_error = handlebars.render_template(&template_contents, &data)?;
+            Ok(render_error)
+        }
+    }
+}
+
+async fn email_template_new() -> Result<String, RenderError> {
+    let handlebars = Handlebars::new();
+    let template_path = "email_template_new";
+
+    let template_contents = match read_hbs_template(template_path) {
+        Ok(contents) => contents,
+        Err(e) => {
+            error!(
+                "Couldn't render file for new email template:: {}",
+                e
+            );
+            EmailTemplateHandlebarsError::new(e.to_string()).error
+        }
+    };
+
+    let cf: ConfVars = set_env_vars();
+    let data = json!({"conf": cf, "lead_types": get_lead_type_tags()});
+
+    let handlebars_render = handlebars.render_template(&template_contents, &data)?;
+    Ok(handlebars_render)
+}
+
+async fn email_template_edit(
+    hbs_path: Path<String>,
+    db: Data<Database>,
+) -> Result<String, RenderError> {
+    let uuid = hbs_path.into_inner();
+    let my_error = format!("Unable to find uuid {}", &uuid);
+    info!("Edit email template screen for uuid:: {}", &uuid);
+
+    let mut handlebars = Handlebars::new();
+    handlebars.register_helper("str_equal", Box::new(str_equal));
+    let template_path = "email_template_edit";
+
+    let template_from_db: Result<EmailTemplate, EmailTemplateHandlebarsError> =
+        match Database::find_one(&db, uuid).await {
+            Some(template) => Ok(template),
+            None => {
+                error!("No email template found in db");
+                Err(EmailTemplateHandlebarsError::new(my_error))
+            }
+        };
+
+    let template_contents = match read_hbs_template(template_path) {
+        Ok(contents) => contents,
+        Err(e) => {
+            error!(
+                "Failed to render contents for edit email template:: {}",
+                e
+            );
+            EmailTemplateHandlebarsError::new(e.to_string()).error
+        }
+    };
+
+    match template_from_db {
+        Ok(template) => {
+            let versions = match template.template_key.clone() {
+                Some(template_key) => Database::find_versions(&db, template_key)
+                    .await
+                    .unwrap_or_default(),
+                None => vec![],
+            };
+
+            let cf: ConfVars = set_env_vars();
+            let data = json!({
+              "conf": cf,
+              "lead_types": create_lead_type_tags(template.lead_type.clone()),
+              "t": template,
+              "versions": versions,
+            });
+
+            let render = handlebars.render_template(&template_contents, &data)?;
+            Ok(render)
+        }
+        Err(e) => {
+            let render_error = handlebars.render_template(&template_contents, &e)?;
+            Ok(render_error)
+        }
+    }
+}
+
+async fn email_template_preview(body: EmailTemplatePreview) -> Result<String, RenderError> {
+    let handlebars = Handlebars::new();
+    let template_path = "email_template_preview";
+
+    let template_contents = match read_hbs_template(template_path) {
+        Ok(contents) => contents,
+        Err(e) => {
+            error!(
+                "Failed to render contents for email template preview:: {}",
+                e
+            );
+            EmailTemplateHandlebarsError::new(e.to_string()).error
+        }
+    };
+
+    let sample_data = get_sample_data(&body.lead_type);
+    let variables: Vec<String> = match sample_data.as_object() {
+        Some(sample_object) => sample_object.keys().cloned().collect(),
+        None => vec![],
+    };
+
+    let key_errors_vec = get_template_variable_errors(
+        &body.subject,
+        &body.html_body,
+        &body.text_body,
+        &body.lead_type,
+    );
+
+    let data = if key_errors_vec.is_empty() {
+        match render_email(
+            &body.subject,
+            &body.html_body,
+            &body.text_body,
+            &sample_data,
+        ) {
+            Ok(rendered) => json!({
+              "email": rendered,
+              "lead_type": body.lead_type.to_string(),
+              "variables": variables,
+            }),
+            Err(e) => json!({
+              "errors": vec![e.to_string()],
+              "lead_type": body.lead_type.to_string(),
+              "variables": variables,
+            }),
+        }
+    } else {
+        json!({
+          "errors": key_errors_vec,
+          "lead_type": body.lead_type.to_string(),
+          "variables": variables,
+        })
+    };
+
+    let render = handlebars.render_template(&template_contents, &data)?;
+    Ok(render)
+}
+
+pub fn email_template_html_controllers(cfg: &mut ServiceConfig) {
+    cfg.route(
+      "/htmx/email_template/table",
+      post().to(
+        |db: Data<Database>| async move {
+          let my_template_table = email_template_table(db).await;
+
+          match my_template_table {
+            Ok(tt) => HttpResponse::Ok()
+              .content_type("text/html")
+              .append_header(("HX-Trigger", "activate_navbar_element"))
+              .body(tt),
+            Err(e) => HttpResponse::Ok()
+              .content_type("text/html")
+              .append_header(("HX-Trigger", "error_email_template_table"))
+              .body(
+                format!("<span class=\"icon is-small is-left\"><i class=\"fas fa-ban\"></i>Failed to load email templates: {}</span>",
+                e)
+              )
+          }
+        }
+      ),
+    );
+
+    cfg.route(
+        "/htmx/email_template/new",
+        post().to(|| async move {
+            let new_template_editor = email_template_new().await;
+
+            match new_template_editor {
+              Ok(new_template) => HttpResponse::Ok()
+                .content_type("text/html")
+                .body(new_template),
+              Err(e) => HttpResponse::Ok()
+                .content_type("text/html")
+                .append_header(("HX-Trigger", "error_email_template_table"))
+                .body(
+                  format!("<span class=\"icon is-small is-left\"><i class=\"fas fa-ban\"></i>Failed to load email template: {}</span>",
+                  e)
+                )
+            }
+        }),
+    );
+
+    cfg.route(
+      "/htmx/email_template/edit/{uuid}",
+      post().to(
+          |_req: HttpRequest, hbs_path, db: Data<Database>| async move {
+              let template_editor = email_template_edit(hbs_path, db).await;
+              match template_editor {
+                  Ok(te) => HttpResponse::Ok().content_type("text/html")
+                    .body(te),
+                  Err(e) => HttpResponse::Ok()
+                      .content_type("text/html")
+                      .append_header(("HX-Trigger", "error_email_template_table"))
+                      .body(
+                        format!(
+                          "<span class=\"icon is-small is-left\"><i class=\"fas fa-ban\"></i>Failed to load email template: {}</span>",
+                          e
+                        )
+                      ),
+              }
+          },
+      ),
+    );
+
+    cfg.route(
+      "/htmx/email_template/preview",
+      post().to(
+        |body: Json<EmailTemplatePreview>| async move {
+          let preview = email_template_preview(body.into_inner()).await;
+
+          match preview {
+            Ok(p) => HttpResponse::Ok().content_type("text/html").body(p),
+            Err(e) => HttpResponse::Ok()
+              .content_type("text/html")
+              .body(
+                format!("<span class=\"icon is-small is-left\"><i class=\"fas fa-ban\"></i>Failed to render preview: {}</span>",
+                e)
+              )
+          }
+        }
+      ),
+    );
+}
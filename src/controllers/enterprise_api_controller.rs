@@ -2,27 +2,65 @@ use actix_web::{
     delete, get,
     http::StatusCode,
     patch, post,
-    web::{Data, Json, Path, ServiceConfig},
+    web::{Data, Json, Path, Query, ServiceConfig},
     HttpResponse,
 };
-use chrono::Local;
+use chrono::Utc;
 use log::error;
+use utoipa::OpenApi;
 use validator::Validate;
 
 use crate::{
     db::{config::Database, enterprise_db::EnterpriseDB},
-    error::enterprise_error::EnterpriseError,
-    models::enterprise_model::{Enterprise, EnterpriseUuid},
+    error::{api_error::ApiError, enterprise_error::EnterpriseError},
+    models::{
+        enterprise_model::{Enterprise, EnterpriseUuid},
+        lead_model::LeadFilter,
+        sales_model::LeadType,
+        users_model::ActingUser,
+    },
     utils::{
+        address_utils::normalize_address,
+        contact_utils::{enterprise_person, sync_primary_contact},
         general_utils::{get_uuid, shuffle_id},
-        message_utils::get_validation_errors,
+        i18n_utils::RequestLocale,
+        lead_utils::enterprise_summary,
+        message_utils::get_localized_validation_errors,
+        notification_utils::notify_lead_changes,
+        phone_utils::normalize_optional_phone,
+        pipeline_utils::check_lead_stage,
+        scoring_utils::{compute_lead_score, enterprise_facts},
+        social_utils::{normalize_optional_social_profile, SocialNetwork},
+        tag_utils::{normalize_tags, register_tags},
+        territory_utils::{assign_lead_owner, is_lead_in_scope, scope_leads},
     },
 };
 
+#[utoipa::path(
+    tag = "Enterprises",
+    operation_id = "find_all_enterprises",
+    params(LeadFilter),
+    responses(
+        (status = 200, description = "Active enterprises in the acting user's scope", body = Vec<Enterprise>),
+        (status = 404, description = "No enterprises found", body = ApiError),
+    )
+)]
 #[get("/enterprises")]
 #[tracing::instrument(name = "Show Enterprises", skip(db))]
-async fn find_all(db: Data<Database>) -> Result<HttpResponse, EnterpriseError> {
-    let enterprise = Database::find_all_active(&db).await;
+async fn find_all(
+    db: Data<Database>,
+    filter: Query<LeadFilter>,
+    acting_user: ActingUser,
+) -> Result<HttpResponse, EnterpriseError> {
+    let enterprise = match Database::find_filtered(&db, &filter).await {
+        Some(leads) => Some(
+            scope_leads(&db, acting_user.user_uuid, leads, |lead: &Enterprise| {
+                &lead.address
+            })
+            .await,
+        ),
+        None => None,
+    };
 
     match enterprise {
         Some(found_enterprise) => Ok(HttpResponse::Ok()
@@ -30,33 +68,53 @@ async fn find_all(db: Data<Database>) -> Result<HttpResponse, EnterpriseError> {
             .json(found_enterprise)),
         None => {
             error!("Unable to find any enterprise data");
-            Ok(HttpResponse::NotFound().json(EnterpriseUuid {
-                uuid: format!("{}", EnterpriseError::NoEnterprisesFound),
-            }))
+            Ok(HttpResponse::NotFound().json(ApiError::new(EnterpriseError::NoEnterprisesFound)))
         }
     }
 }
 
+#[utoipa::path(
+    tag = "Enterprises",
+    operation_id = "find_one_enterprise",
+    params(EnterpriseUuid),
+    responses(
+        (status = 200, description = "Enterprise found", body = Enterprise),
+        (status = 404, description = "Enterprise not found", body = ApiError),
+    )
+)]
 #[get("/enterprises/{uuid}")]
-#[tracing::instrument(name = "Get One Enterprise", skip(db), fields(uuid = %uuid.uuid))]
+#[tracing::instrument(name = "Get One Enterprise", skip(db, acting_user), fields(uuid = %uuid.uuid))]
 async fn find_one(
     db: Data<Database>,
     uuid: Path<EnterpriseUuid>,
+    acting_user: ActingUser,
 ) -> Result<HttpResponse, EnterpriseError> {
     let enterprise_uuid = uuid.into_inner().uuid;
-    let enterprise_result = Database::find_one(&db, enterprise_uuid.clone()).await;
+    let enterprise_result = match Database::find_one(&db, enterprise_uuid.clone()).await {
+        Some(lead) if is_lead_in_scope(&db, acting_user.user_uuid, &lead.address).await => {
+            Some(lead)
+        }
+        _ => None,
+    };
 
     match enterprise_result {
         Some(result) => Ok(HttpResponse::Ok().status(StatusCode::OK).json(result)),
         None => {
             error!("No enterprise found for UUID:: {:?}", &enterprise_uuid);
-            Ok(HttpResponse::NotFound().json(EnterpriseUuid {
-                uuid: format!("{}", EnterpriseError::NoEnterprisesFound),
-            }))
+            Ok(HttpResponse::NotFound().json(ApiError::new(EnterpriseError::NoEnterprisesFound)))
         }
     }
 }
 
+#[utoipa::path(
+    tag = "Enterprises",
+    operation_id = "create_enterprise",
+    responses(
+        (status = 201, description = "Enterprise created", body = EnterpriseUuid),
+        (status = 400, description = "Invalid stage", body = ApiError),
+        (status = 500, description = "Invalid or unsaved enterprise", body = ApiError),
+    )
+)]
 #[post("/enterprises")]
 #[tracing::instrument(
     name = "Post Enterprise",
@@ -70,22 +128,83 @@ async fn find_one(
 )]
 async fn create(
     db: Data<Database>,
-    body: Json<Enterprise>,
+    mut body: Json<Enterprise>,
+    acting_user: ActingUser,
+    locale: RequestLocale,
 ) -> Result<HttpResponse, EnterpriseError> {
+    body.created_by = acting_user.user_uuid.clone();
     let is_valid = body.validate();
-    let date_created = Local::now();
+
+    if let Some(response) = check_lead_stage(
+        &db,
+        &LeadType::ENTERPRISE,
+        &body.sales_funnel,
+        EnterpriseError::InvalidStage,
+    )
+    .await
+    {
+        return Ok(response);
+    }
+
+    let date_created = Utc::now();
     let mut new_enterprise = body.into_inner();
     new_enterprise.date_created = Some(date_created.clone());
     new_enterprise.date_modified = Some(date_created.clone());
     new_enterprise.first_contact_date = Some(date_created.clone());
+    new_enterprise.tags = normalize_tags(new_enterprise.tags);
+    new_enterprise.phone = normalize_optional_phone(new_enterprise.phone);
+    new_enterprise.fb =
+        normalize_optional_social_profile(SocialNetwork::Facebook, new_enterprise.fb);
+    new_enterprise.instagram =
+        normalize_optional_social_profile(SocialNetwork::Instagram, new_enterprise.instagram);
+    new_enterprise.linked_in =
+        normalize_optional_social_profile(SocialNetwork::LinkedIn, new_enterprise.linked_in);
+    new_enterprise.tik_tok =
+        normalize_optional_social_profile(SocialNetwork::TikTok, new_enterprise.tik_tok);
+    new_enterprise.twitter =
+        normalize_optional_social_profile(SocialNetwork::Twitter, new_enterprise.twitter);
+    new_enterprise.address = normalize_address(new_enterprise.address);
+    new_enterprise.account_uuid = None;
+    new_enterprise.assigned_to = assign_lead_owner(
+        &db,
+        new_enterprise.assigned_to.clone(),
+        &new_enterprise.address,
+        new_enterprise.created_by.clone(),
+    )
+    .await;
 
     match is_valid {
         Ok(_) => {
+            register_tags(&db, &new_enterprise.tags, new_enterprise.created_by.clone()).await;
+            new_enterprise.score = compute_lead_score(
+                &db,
+                &LeadType::ENTERPRISE,
+                &enterprise_facts(&new_enterprise),
+            )
+            .await;
             let new_uuid = get_uuid();
             let my_enterprise =
                 Database::add_one(&db, Enterprise::new(String::from(new_uuid), new_enterprise))
                     .await;
 
+            if let Some(enterprise) = &my_enterprise {
+                sync_primary_contact(
+                    &db,
+                    &LeadType::ENTERPRISE,
+                    enterprise_person(enterprise),
+                    enterprise.account_uuid.clone(),
+                    enterprise.created_by.clone(),
+                )
+                .await;
+                notify_lead_changes(
+                    &db,
+                    None,
+                    &enterprise_summary(enterprise.clone()),
+                    enterprise.created_by.clone(),
+                )
+                .await;
+            }
+
             match my_enterprise {
                 Some(enterprise_result) => Ok(HttpResponse::Ok()
                     .insert_header(("HX-Trigger", "enterprise_reload_page"))
@@ -103,27 +222,36 @@ async fn create(
                             "HX-Trigger",
                             format!("{{ \"page_error\": \"Internal server error\" }}"),
                         ))
-                        .json(EnterpriseUuid {
-                            uuid: format!("{}", EnterpriseError::EnterpriseCreationFailure),
-                        }))
+                        .json(ApiError::new(EnterpriseError::EnterpriseCreationFailure)))
                 }
             }
         }
         Err(e) => {
             error!("Error enterprise.create {:?}", e);
-            let key_errors_vec: Vec<String> = get_validation_errors(&e);
+            let key_errors_vec: Vec<String> = get_localized_validation_errors(&e, &locale.0);
             Ok(HttpResponse::InternalServerError()
                 .insert_header((
                     "HX-Trigger",
                     format!("{{ \"page_error\": {:?} }}", key_errors_vec),
                 ))
-                .json(EnterpriseUuid {
-                    uuid: format!("{}", EnterpriseError::EnterpriseCreationFailure),
-                }))
+                .json(
+                    ApiError::new(EnterpriseError::EnterpriseCreationFailure)
+                        .with_field_errors(key_errors_vec),
+                ))
         }
     }
 }
 
+#[utoipa::path(
+    tag = "Enterprises",
+    operation_id = "update_enterprise",
+    responses(
+        (status = 200, description = "Enterprise updated", body = EnterpriseUuid),
+        (status = 404, description = "Enterprise not found", body = ApiError),
+        (status = 400, description = "Invalid stage", body = ApiError),
+        (status = 500, description = "Invalid or unsaved enterprise", body = ApiError),
+    )
+)]
 #[patch("/enterprises")]
 #[tracing::instrument(
     name = "Patch Enterprise",
@@ -137,10 +265,24 @@ async fn create(
 )]
 async fn update_one(
     db: Data<Database>,
-    body: Json<Enterprise>,
+    mut body: Json<Enterprise>,
+    acting_user: ActingUser,
+    locale: RequestLocale,
 ) -> Result<HttpResponse, EnterpriseError> {
+    body.modified_by = acting_user.user_uuid.clone();
     let is_valid = body.validate();
 
+    if let Some(response) = check_lead_stage(
+        &db,
+        &LeadType::ENTERPRISE,
+        &body.sales_funnel,
+        EnterpriseError::InvalidStage,
+    )
+    .await
+    {
+        return Ok(response);
+    }
+
     match is_valid {
         Ok(_) => {
             let uuid_id_db = match body.uuid.clone() {
@@ -149,40 +291,67 @@ async fn update_one(
             };
 
             let stored_enterprise = Database::find_one(&db, uuid_id_db).await;
-            let date_modified = Local::now();
+            let stored_summary = stored_enterprise.clone().map(enterprise_summary);
+            let date_modified = Utc::now();
 
             let e_cloned = stored_enterprise.clone();
+            let assigned_to = match &e_cloned {
+                Some(stored) => body.assigned_to.clone().or(stored.assigned_to.clone()),
+                None => body.assigned_to.clone(),
+            };
+            let account_uuid = e_cloned
+                .as_ref()
+                .and_then(|stored| stored.account_uuid.clone());
+            let source = body
+                .source
+                .clone()
+                .or(e_cloned.as_ref().and_then(|stored| stored.source.clone()));
 
             let date_created = match stored_enterprise {
                 Some(enterprise) => enterprise.date_created,
                 None => {
                     error!("No date found for UUID:: {:?}", body.uuid.clone());
-                    Some(Local::now())
+                    Some(Utc::now())
                 }
             };
 
-            let my_enterprise = Enterprise {
+            let mut my_enterprise = Enterprise {
                 uuid: body.uuid.clone(),
                 name: body.name.clone(),
                 last_name: body.last_name.clone(),
                 is_company: body.is_company.clone(),
                 company_name: body.company_name.clone(),
                 line_of_business: body.line_of_business.clone(),
-                phone: match body.phone.clone() {
-                    Some(my_phone) => Some(my_phone),
-                    None => None,
-                },
+                phone: normalize_optional_phone(body.phone.clone()),
                 email: body.email.clone(),
                 deleted: body.deleted.clone(),
-                fb: body.fb.clone(),
-                instagram: body.instagram.clone(),
-                linked_in: body.linked_in.clone(),
-                tik_tok: body.tik_tok.clone(),
-                twitter: body.twitter.clone(),
+                fb: normalize_optional_social_profile(SocialNetwork::Facebook, body.fb.clone()),
+                instagram: normalize_optional_social_profile(
+                    SocialNetwork::Instagram,
+                    body.instagram.clone(),
+                ),
+                linked_in: normalize_optional_social_profile(
+                    SocialNetwork::LinkedIn,
+                    body.linked_in.clone(),
+                ),
+                tik_tok: normalize_optional_social_profile(
+                    SocialNetwork::TikTok,
+                    body.tik_tok.clone(),
+                ),
+                twitter: normalize_optional_social_profile(
+                    SocialNetwork::Twitter,
+                    body.twitter.clone(),
+                ),
                 first_contact_date: body.first_contact_date.clone(),
                 sales_funnel: body.sales_funnel.clone(),
                 notes: body.notes.clone(),
                 services_offered: body.services_offered.clone(),
+                tags: normalize_tags(body.tags.clone()),
+                assigned_to,
+                score: 0,
+                account_uuid,
+                address: normalize_address(body.address.clone()),
+                source,
                 resolution: body.resolution.clone(),
                 date_created,
                 date_modified: Some(date_modified),
@@ -193,8 +362,34 @@ async fn update_one(
                 modified_by: body.modified_by.clone(),
             };
 
+            register_tags(&db, &my_enterprise.tags, my_enterprise.modified_by.clone()).await;
+            my_enterprise.score = compute_lead_score(
+                &db,
+                &LeadType::ENTERPRISE,
+                &enterprise_facts(&my_enterprise),
+            )
+            .await;
+
             let updated_enterprise = Database::update_one(&db, my_enterprise).await;
 
+            if let Some(enterprise) = &updated_enterprise {
+                sync_primary_contact(
+                    &db,
+                    &LeadType::ENTERPRISE,
+                    enterprise_person(enterprise),
+                    enterprise.account_uuid.clone(),
+                    enterprise.modified_by.clone(),
+                )
+                .await;
+                notify_lead_changes(
+                    &db,
+                    stored_summary.as_ref(),
+                    &enterprise_summary(enterprise.clone()),
+                    enterprise.modified_by.clone(),
+                )
+                .await;
+            }
+
             match updated_enterprise {
                 Some(enterprise) => Ok(HttpResponse::Ok()
                     .insert_header(("HX-Trigger", "enterprise_reload_page"))
@@ -215,30 +410,48 @@ async fn update_one(
                                 "Couldn't find enterprise".to_string()
                             ),
                         ))
-                        .json(EnterpriseUuid {
-                            uuid: format!("{}", EnterpriseError::NoEnterprisesFound),
-                        }))
+                        .json(ApiError::new(EnterpriseError::NoEnterprisesFound)))
                 }
             }
         }
         Err(e) => {
             error!("Error in enterprise.update_one: {:?}", e);
-            let key_errors_vec: Vec<String> = get_validation_errors(&e);
+            let key_errors_vec: Vec<String> = get_localized_validation_errors(&e, &locale.0);
             Ok(HttpResponse::InternalServerError()
                 .insert_header((
                     "HX-Trigger",
                     format!("{{ \"page_error\": {:?} }}", key_errors_vec),
                 ))
-                .json(EnterpriseUuid {
-                    uuid: format!("{}", EnterpriseError::NoEnterprisesFound),
-                }))
+                .json(
+                    ApiError::new(EnterpriseError::NoEnterprisesFound)
+                        .with_field_errors(key_errors_vec),
+                ))
         }
     }
 }
 
+#[utoipa::path(
+    tag = "Enterprises",
+    operation_id = "find_deleted_enterprises",
+    responses(
+        (status = 200, description = "Deleted enterprises", body = Vec<Enterprise>),
+        (status = 404, description = "No enterprises found", body = ApiError),
+    )
+)]
 #[get("/enterprises/deleted")]
-async fn find_all_deleted(db: Data<Database>) -> Result<HttpResponse, EnterpriseError> {
-    let enterprises = Database::find_all_deleted(&db).await;
+async fn find_all_deleted(
+    db: Data<Database>,
+    acting_user: ActingUser,
+) -> Result<HttpResponse, EnterpriseError> {
+    let enterprises = match Database::find_all_deleted(&db).await {
+        Some(leads) => Some(
+            scope_leads(&db, acting_user.user_uuid, leads, |lead: &Enterprise| {
+                &lead.address
+            })
+            .await,
+        ),
+        None => None,
+    };
 
     match enterprises {
         Some(deleted_enterprises) => Ok(HttpResponse::Ok()
@@ -247,13 +460,21 @@ async fn find_all_deleted(db: Data<Database>) -> Result<HttpResponse, Enterprise
             .json(deleted_enterprises)),
         None => {
             error!("Didnt' find any deleted enterprises");
-            Ok(HttpResponse::NotFound().json(EnterpriseUuid {
-                uuid: format!("{}", EnterpriseError::NoEnterprisesFound),
-            }))
+            Ok(HttpResponse::NotFound().json(ApiError::new(EnterpriseError::NoEnterprisesFound)))
         }
     }
 }
 
+#[utoipa::path(
+    tag = "Enterprises",
+    operation_id = "delete_enterprise",
+    params(EnterpriseUuid),
+    responses(
+        (status = 200, description = "Enterprise deleted", body = EnterpriseUuid),
+        (status = 404, description = "Enterprise not found", body = ApiError),
+        (status = 500, description = "Enterprise couldn't be deleted", body = ApiError),
+    )
+)]
 #[delete("/enterprises/{uuid}")]
 #[tracing::instrument(name = "Delete Enterprise", skip(db), fields(uuid = %uuid.uuid))]
 async fn delete_one(
@@ -276,21 +497,21 @@ async fn delete_one(
                     })),
                 None => {
                     error!("Unable to delete enterprise:: {:?}", &enterprise_uuid);
-                    Ok(HttpResponse::InternalServerError().json(EnterpriseUuid {
-                        uuid: "".to_string(),
-                    }))
+                    Err(EnterpriseError::EnterpriseDeletionFailure)
                 }
             }
         }
         None => {
-            error!("Unable to update school :: {:?}", &enterprise_uuid);
-            Ok(HttpResponse::NotFound().json(EnterpriseUuid {
-                uuid: "".to_string(),
-            }))
+            error!("Unable to find enterprise:: {:?}", &enterprise_uuid);
+            Err(EnterpriseError::NoEnterprisesFound)
         }
     }
 }
 
+#[derive(OpenApi)]
+#[openapi(paths(find_all, find_one, create, update_one, find_all_deleted, delete_one))]
+pub struct EnterpriseApiDoc;
+
 pub fn enterprise_api_controllers(cfg: &mut ServiceConfig) {
     cfg.service(create);
     cfg.service(delete_one);
@@ -2,6 +2,7 @@ use actix_web::{
     web::{post, Data, Path, ServiceConfig},
     HttpRequest, HttpResponse,
 };
+use chrono_tz::Tz;
 use handlebars::{Handlebars, RenderError};
 use log::{error, info};
 use serde_json::json;
@@ -11,18 +12,28 @@ use crate::utils::{
     env::{set_env_vars, ConfVars},
     fs_utils::read_hbs_template,
     general_utils::{create_option_tags_info_for_services_and_funnel, get_options_and_services},
-    time::format_date_in_language,
+    i18n_utils::{register_i18n_helpers, RequestLocale},
+    pipeline_utils::get_pipeline,
+    service_utils::get_service_catalog,
+    territory_utils::{is_lead_in_scope, scope_leads},
+    time::{format_date_in_language, RequestTimezone},
 };
 use crate::{
     db::{config::Database, enterprise_db::EnterpriseDB},
-    models::sales_model::GeneralTags,
+    models::{
+        locale_model::Locale,
+        sales_model::{GeneralTags, LeadType},
+        users_model::ActingUser,
+    },
 };
 
 handlebars_helper!(str_equal: |s1: String, s2: String| s1 == s2);
 
 async fn enterprise_edit(
     hbs_path: Path<String>,
+    acting_user: ActingUser,
     db: Data<Database>,
+    locale: Locale,
 ) -> Result<String, RenderError> {
     let uuid = hbs_path.into_inner();
     let my_error = format!("Unable to find uuid {}", &uuid).to_string();
@@ -30,13 +41,18 @@ async fn enterprise_edit(
 
     let mut handlebars = Handlebars::new();
     handlebars.register_helper("str_equal", Box::new(str_equal));
+    register_i18n_helpers(&mut handlebars);
 
     let mut template_path = "enterprise_edit";
 
     let enterprise_from_db: Result<Enterprise, EnterpriseHandlebarsError> =
         match Database::find_one(&db, uuid).await {
-            Some(enterprise) => Ok(enterprise),
-            None => {
+            Some(enterprise)
+                if is_lead_in_scope(&db, acting_user.user_uuid, &enterprise.address).await =>
+            {
+                Ok(enterprise)
+            }
+            _ => {
                 error!("Not enterprise found in db");
                 template_path = "edit_user";
                 Err(EnterpriseHandlebarsError::new(my_error))
@@ -57,12 +73,14 @@ async fn enterprise_edit(
     match enterprise_from_db {
         Ok(enterprise) => {
             let (services_tag, funnel_tag) = create_option_tags_info_for_services_and_funnel(
+                get_service_catalog(&db).await,
+                get_pipeline(&db, &LeadType::ENTERPRISE).await,
                 enterprise.services_offered.clone(),
                 enterprise.sales_funnel.clone(),
             );
 
             let cf: ConfVars = set_env_vars();
-            let data = json!({ "conf": cf, "services_tag": services_tag, "sales_funnel": funnel_tag, "e": enterprise});
+            let data = json!({ "conf": cf, "locale": locale.code(), "services_tag": services_tag, "sales_funnel": funnel_tag, "e": enterprise});
 
             let render_good = handlebars.render_template(&template_contents, &data)?;
             Ok(render_good)
@@ -74,9 +92,10 @@ async fn enterprise_edit(
     }
 }
 
-async fn enterprise_new() -> Result<String, RenderError> {
+async fn enterprise_new(db: Data<Database>, locale: Locale) -> Result<String, RenderError> {
     let mut handlebars = Handlebars::new();
     handlebars.register_helper("str_equal", Box::new(str_equal));
+    register_i18n_helpers(&mut handlebars);
 
     let template_path = "enterprise_new";
 
@@ -91,21 +110,38 @@ async fn enterprise_new() -> Result<String, RenderError> {
         }
     };
 
-    let (services_tag, funnel_tag) = get_options_and_services();
+    let (services_tag, funnel_tag) = get_options_and_services(
+        get_service_catalog(&db).await,
+        get_pipeline(&db, &LeadType::ENTERPRISE).await,
+    );
     let cf: ConfVars = set_env_vars();
-    let data = json!({ "conf": cf, "services_tag": services_tag, "sales_funnel": funnel_tag});
+    let data = json!({ "conf": cf, "locale": locale.code(), "services_tag": services_tag, "sales_funnel": funnel_tag});
 
     let handlebars_render = handlebars.render_template(&template_contents, &data)?;
 
     Ok(handlebars_render)
 }
 
-async fn enterprise_table(db: Data<Database>) -> Result<String, RenderError> {
+async fn enterprise_table(
+    db: Data<Database>,
+    acting_user: ActingUser,
+    locale: Locale,
+    timezone: Tz,
+) -> Result<String, RenderError> {
     let template_path = "enterprise_table";
     let mut handlebars = Handlebars::new();
     handlebars.register_helper("str_equal", Box::new(str_equal));
+    register_i18n_helpers(&mut handlebars);
 
-    let enterprises_from_db = Database::find_all(&db).await;
+    let enterprises_from_db = match Database::find_all(&db).await {
+        Some(leads) => Some(
+            scope_leads(&db, acting_user.user_uuid, leads, |lead: &Enterprise| {
+                &lead.address
+            })
+            .await,
+        ),
+        None => None,
+    };
 
     let template_contents = match read_hbs_template(&template_path) {
         Ok(contents) => contents,
@@ -120,17 +156,25 @@ async fn enterprise_table(db: Data<Database>) -> Result<String, RenderError> {
     };
 
     match enterprises_from_db {
-        Some(enterprises) => {
+        Some(mut enterprises) => {
+            enterprises.sort_by_key(|lead| std::cmp::Reverse(lead.score));
+
             let mut enterprise_tags_vector: Vec<GeneralTags<Enterprise>> = Vec::new();
+            let services = get_service_catalog(&db).await;
+            let stages = get_pipeline(&db, &LeadType::ENTERPRISE).await;
 
             for enterprise in enterprises {
                 let (services_tag, funnel_tag) = create_option_tags_info_for_services_and_funnel(
+                    services.clone(),
+                    stages.clone(),
                     enterprise.services_offered.clone(),
                     enterprise.sales_funnel.clone(),
                 );
 
                 let first_contact = match enterprise.first_contact_date {
-                    Some(this_date) => format_date_in_language(&this_date, "es"),
+                    Some(this_date) => {
+                        format_date_in_language(&this_date, &locale, &timezone)
+                    },
                     None => "".to_string(),
                 };
 
@@ -143,7 +187,7 @@ async fn enterprise_table(db: Data<Database>) -> Result<String, RenderError> {
             }
 
             let cf: ConfVars = set_env_vars();
-            let data = json!({ "conf": cf, "enterprises": enterprise_tags_vector });
+            let data = json!({ "conf": cf, "locale": locale.code(), "enterprises": enterprise_tags_vector });
 
             let render = handlebars.render_template(&template_contents, &data)?;
             Ok(render)
@@ -160,8 +204,8 @@ pub fn enterprise_html_controllers(cfg: &mut ServiceConfig) {
     cfg.route(
       "/htmx/enterprise/edit/{uuid}",
       post().to(
-          |_req: HttpRequest, hbs_path, db: Data<Database>| async move {
-              let user_editor = enterprise_edit(hbs_path, db).await;
+          |_req: HttpRequest, hbs_path, acting_user, db: Data<Database>, locale: RequestLocale| async move {
+              let user_editor = enterprise_edit(hbs_path, acting_user, db, locale.0).await;
               match user_editor {
                   Ok(ue) => HttpResponse::Ok().content_type("text/html")
                     .body(ue),
@@ -182,8 +226,8 @@ pub fn enterprise_html_controllers(cfg: &mut ServiceConfig) {
     cfg.route(
     "/htmx/enterprise/table",
     post().to(
-      |db: Data<Database>| async move {
-        let my_enterprise_table = enterprise_table(db).await;
+      |db: Data<Database>, acting_user, locale: RequestLocale, timezone: RequestTimezone| async move {
+        let my_enterprise_table = enterprise_table(db, acting_user, locale.0, timezone.0).await;
 
         match my_enterprise_table {
           Ok(et) => HttpResponse::Ok()
@@ -204,8 +248,8 @@ pub fn enterprise_html_controllers(cfg: &mut ServiceConfig) {
 
     cfg.route(
         "/htmx/enterprise/new",
-        post().to(|| async move {
-            let new_enterprise_editor = enterprise_new().await;
+        post().to(|db: Data<Database>, locale: RequestLocale| async move {
+            let new_enterprise_editor = enterprise_new(db, locale.0).await;
 
             match new_enterprise_editor {
               Ok(new_enterprise) => HttpResponse::Ok()
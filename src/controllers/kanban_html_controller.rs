@@ -0,0 +1,90 @@
+use actix_web::{
+    web::{post, Data, Path, ServiceConfig},
+    HttpResponse,
+};
+use handlebars::{Handlebars, RenderError};
+use log::{error, info};
+use serde_json::json;
+
+use crate::{
+    db::{config::Database, quote_db::QuoteDB},
+    models::{lead_model::KanbanColumn, sales_model::LeadType, users_model::ActingUser},
+    utils::{
+        env::{set_env_vars, ConfVars},
+        fs_utils::read_hbs_template,
+        general_utils::create_lead_type_tags,
+        kanban_utils::build_kanban_board,
+        lead_utils::find_lead_summaries,
+        pipeline_utils::get_pipeline,
+        quote_utils::format_currency,
+        service_utils::get_service_catalog,
+    },
+};
+
+handlebars_helper!(currency: |amount: f64| format_currency(amount));
+
+async fn kanban_board(
+    hbs_path: Path<LeadType>,
+    acting_user: ActingUser,
+    db: Data<Database>,
+) -> Result<String, RenderError> {
+    let lead_type = hbs_path.into_inner();
+    info!("Kanban board for:: {}", &lead_type);
+
+    let mut handlebars = Handlebars::new();
+    handlebars.register_helper("currency", Box::new(currency));
+    let template_path = "kanban_board";
+
+    let template_contents = match read_hbs_template(template_path) {
+        Ok(contents) => contents,
+        Err(e) => {
+            error!("Couldn't render file for kanban board:: {}", e);
+            e.to_string()
+        }
+    };
+
+    let columns: Vec<KanbanColumn> = build_kanban_board(
+        get_pipeline(&db, &lead_type).await,
+        find_lead_summaries(&db, &lead_type, acting_user.user_uuid).await,
+        get_service_catalog(&db).await,
+        Database::find_all_active(&db).await.unwrap_or_default(),
+    );
+    let board_total: f64 = columns.iter().map(|column| column.total).sum();
+
+    let cf: ConfVars = set_env_vars();
+    let data = json!({
+      "conf": cf,
+      "lead_type": lead_type,
+      "lead_types": create_lead_type_tags(lead_type.clone()),
+      "columns": columns,
+      "board_total": board_total,
+    });
+
+    let render = handlebars.render_template(&template_contents, &data)?;
+    Ok(render)
+}
+
+pub fn kanban_html_controllers(cfg: &mut ServiceConfig) {
+    cfg.route(
+      "/htmx/kanban/{lead_type}",
+      post().to(
+        |hbs_path, acting_user, db: Data<Database>| async move {
+          let board = kanban_board(hbs_path, acting_user, db).await;
+
+          match board {
+            Ok(b) => HttpResponse::Ok()
+              .content_type("text/html")
+              .append_header(("HX-Trigger", "activate_navbar_element"))
+              .body(b),
+            Err(e) => HttpResponse::Ok()
+              .content_type("text/html")
+              .append_header(("HX-Trigger", "error_kanban"))
+              .body(
+                format!("<span class=\"icon is-small is-left\"><i class=\"fas fa-ban\"></i>Failed to load kanban board: {}</span>",
+                e)
+              )
+          }
+        }
+      ),
+    );
+}
@@ -0,0 +1,263 @@
+use actix_web::{
+    get,
+    http::{
+        header::{ContentDisposition, DispositionParam, DispositionType},
+        StatusCode,
+    },
+    patch, post,
+    web::{Data, Json, Path, ServiceConfig},
+    HttpResponse,
+};
+use log::error;
+use utoipa::OpenApi;
+
+use crate::{
+    db::{config::Database, stage_transition_db::StageTransitionDB},
+    error::{api_error::ApiError, lead_error::LeadError},
+    models::{
+        lead_model::{
+            BulkExportRequest, BulkLeadRequest, BulkLeadResponse, LeadStageChange, LeadTypePath,
+            LeadUuid, StageTransition,
+        },
+        sales_model::LeadType,
+        users_model::ActingUser,
+    },
+    utils::{
+        bulk_utils::{find_bulk_summaries, resolve_bulk_uuids, run_bulk_action},
+        general_utils::shuffle_id,
+        lead_utils::move_lead_to_stage,
+        pipeline_utils::{find_stage, get_pipeline},
+        role_utils::is_active_user,
+        segment_utils::leads_to_csv,
+        service_utils::get_service_catalog,
+    },
+};
+
+#[utoipa::path(
+    tag = "Leads",
+    operation_id = "update_lead_stage",
+    responses(
+        (status = 200, description = "Lead moved to the new stage", body = LeadUuid),
+        (status = 400, description = "Invalid stage", body = ApiError),
+        (status = 403, description = "No active user", body = ApiError),
+        (status = 404, description = "Lead not found", body = ApiError),
+    )
+)]
+#[patch("/leads/{lead_type}/{uuid}/stage")]
+#[tracing::instrument(name = "Patch Lead Stage", skip(db, path), fields(sales_funnel = %body.sales_funnel))]
+async fn update_stage(
+    db: Data<Database>,
+    path: Path<(LeadType, String)>,
+    body: Json<LeadStageChange>,
+    acting_user: ActingUser,
+) -> Result<HttpResponse, LeadError> {
+    let (lead_type, lead_uuid) = path.into_inner();
+    let stage_change = body.into_inner();
+    let modified_by = acting_user.user_uuid;
+
+    if !is_active_user(&db, modified_by.clone()).await {
+        return Ok(lead_error_response(
+            StatusCode::FORBIDDEN,
+            "Inicia sesión para mover leads".to_string(),
+            LeadError::LeadForbidden,
+        ));
+    }
+
+    let stages = get_pipeline(&db, &lead_type).await;
+    if find_stage(&stages, &stage_change.sales_funnel).is_none() {
+        error!(
+            "Stage {} is not part of the {} pipeline",
+            &stage_change.sales_funnel, &lead_type
+        );
+        return Ok(HttpResponse::BadRequest()
+            .insert_header((
+                "HX-Trigger",
+                format!(
+                    "{{ \"page_error\": {:?} }}",
+                    format!("Etapa inválida: {}", &stage_change.sales_funnel)
+                ),
+            ))
+            .json(ApiError::new(LeadError::InvalidStage)));
+    }
+
+    match move_lead_to_stage(
+        &db,
+        &lead_type,
+        lead_uuid.clone(),
+        stage_change.sales_funnel,
+        modified_by,
+    )
+    .await
+    {
+        Some(_) => Ok(HttpResponse::Ok()
+            .insert_header(("HX-Trigger", "kanban_reload_page"))
+            .status(StatusCode::OK)
+            .json(LeadUuid {
+                uuid: shuffle_id(lead_uuid),
+            })),
+        None => {
+            error!("Unable to move lead:: {:?}", &lead_uuid);
+            Ok(HttpResponse::NotFound().json(ApiError::new(LeadError::NoLeadsFound)))
+        }
+    }
+}
+
+#[utoipa::path(
+    tag = "Leads",
+    operation_id = "find_lead_transitions",
+    responses(
+        (status = 200, description = "Stage history", body = Vec<StageTransition>),
+        (status = 404, description = "No transitions found", body = ApiError),
+    )
+)]
+#[get("/leads/{lead_type}/{uuid}/transitions")]
+#[tracing::instrument(name = "Get Lead Transitions", skip(db, path))]
+async fn find_transitions(
+    db: Data<Database>,
+    path: Path<(LeadType, String)>,
+) -> Result<HttpResponse, LeadError> {
+    let (lead_type, lead_uuid) = path.into_inner();
+
+    match Database::find_by_lead(&db, lead_type, lead_uuid.clone()).await {
+        Some(transitions) => Ok(HttpResponse::Ok().status(StatusCode::OK).json(transitions)),
+        None => {
+            error!("No transitions found for lead:: {:?}", &lead_uuid);
+            Ok(HttpResponse::NotFound().json(ApiError::new(LeadError::NoLeadsFound)))
+        }
+    }
+}
+
+fn get_lead_reload_trigger(lead_type: &LeadType) -> &'static str {
+    match lead_type {
+        LeadType::ENTERPRISE => "enterprise_reload_page",
+        LeadType::SCHOOL => "school_reload_page",
+        LeadType::CLINICAL => "clinic_reload_page",
+    }
+}
+
+fn lead_error_response(status: StatusCode, message: String, error: LeadError) -> HttpResponse {
+    HttpResponse::build(status)
+        .insert_header(("HX-Trigger", format!("{{ \"page_error\": {:?} }}", message)))
+        .json(ApiError::new(error).with_message(message))
+}
+
+#[utoipa::path(
+    tag = "Leads",
+    operation_id = "bulk_lead_action",
+    params(LeadTypePath),
+    responses(
+        (status = 200, description = "Per lead results", body = BulkLeadResponse),
+        (status = 400, description = "Invalid bulk request", body = ApiError),
+    )
+)]
+#[post("/leads/{lead_type}/bulk")]
+#[tracing::instrument(name = "Bulk Lead Action", skip(db, body), fields(action = ?body.action))]
+async fn bulk_action(
+    db: Data<Database>,
+    lead_type: Path<LeadTypePath>,
+    body: Json<BulkLeadRequest>,
+    acting_user: ActingUser,
+) -> Result<HttpResponse, LeadError> {
+    let lead_type = lead_type.into_inner().lead_type;
+    let mut request = body.into_inner();
+    request.modified_by = acting_user.user_uuid.clone();
+
+    if !is_active_user(&db, request.modified_by.clone()).await {
+        return Ok(lead_error_response(
+            StatusCode::FORBIDDEN,
+            "Inicia sesión para usar acciones masivas".to_string(),
+            LeadError::LeadForbidden,
+        ));
+    }
+
+    match run_bulk_action(&db, &lead_type, request).await {
+        Ok(response) => Ok(HttpResponse::Ok()
+            .insert_header(("HX-Trigger", get_lead_reload_trigger(&lead_type)))
+            .status(StatusCode::OK)
+            .json(response)),
+        Err(message) => {
+            error!("Invalid bulk request for {}:: {}", &lead_type, &message);
+            Ok(lead_error_response(
+                StatusCode::BAD_REQUEST,
+                message,
+                LeadError::InvalidBulkRequest,
+            ))
+        }
+    }
+}
+
+#[utoipa::path(
+    tag = "Leads",
+    operation_id = "bulk_lead_export",
+    params(LeadTypePath),
+    responses(
+        (status = 200, description = "Selected leads as CSV", body = String, content_type = "text/csv"),
+        (status = 400, description = "Invalid bulk request", body = ApiError),
+    )
+)]
+#[post("/leads/{lead_type}/bulk/export")]
+#[tracing::instrument(name = "Bulk Lead Export", skip(db, body))]
+async fn bulk_export(
+    db: Data<Database>,
+    lead_type: Path<LeadTypePath>,
+    body: Json<BulkExportRequest>,
+    acting_user: ActingUser,
+) -> Result<HttpResponse, LeadError> {
+    let lead_type = lead_type.into_inner().lead_type;
+    let request = body.into_inner();
+
+    if !is_active_user(&db, acting_user.user_uuid.clone()).await {
+        return Ok(lead_error_response(
+            StatusCode::FORBIDDEN,
+            "Inicia sesión para exportar".to_string(),
+            LeadError::LeadForbidden,
+        ));
+    }
+
+    let uuids = match resolve_bulk_uuids(
+        &db,
+        &lead_type,
+        request.uuids,
+        request.segment,
+        acting_user.user_uuid,
+    )
+    .await
+    {
+        Ok(uuids) => uuids,
+        Err(message) => {
+            return Ok(lead_error_response(
+                StatusCode::BAD_REQUEST,
+                message,
+                LeadError::InvalidBulkRequest,
+            ))
+        }
+    };
+
+    let csv = leads_to_csv(
+        &find_bulk_summaries(&db, &lead_type, &uuids).await,
+        &get_service_catalog(&db).await,
+        &get_pipeline(&db, &lead_type).await,
+    );
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/csv; charset=utf-8")
+        .insert_header(ContentDisposition {
+            disposition: DispositionType::Attachment,
+            parameters: vec![DispositionParam::Filename(format!(
+                "{}.csv",
+                lead_type.to_string().to_lowercase()
+            ))],
+        })
+        .body(csv))
+}
+
+#[derive(OpenApi)]
+#[openapi(paths(update_stage, find_transitions, bulk_action, bulk_export))]
+pub struct LeadApiDoc;
+
+pub fn lead_api_controllers(cfg: &mut ServiceConfig) {
+    cfg.service(bulk_action);
+    cfg.service(bulk_export);
+    cfg.service(find_transitions);
+    cfg.service(update_stage);
+}
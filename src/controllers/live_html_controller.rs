@@ -0,0 +1,75 @@
+use actix_web::{
+    web::{get, post, Path, ServiceConfig},
+    HttpResponse,
+};
+use handlebars::{Handlebars, RenderError};
+use log::{error, info};
+use serde_json::json;
+
+use crate::{
+    models::{lead_model::LeadHandlebarsError, sales_model::LeadType},
+    utils::{
+        env::{set_env_vars, ConfVars},
+        fs_utils::read_hbs_template,
+        lead_utils::get_lead_table_path,
+        live_utils::live_event_stream,
+    },
+};
+
+async fn live_listener(hbs_path: Path<LeadType>) -> Result<String, RenderError> {
+    let lead_type = hbs_path.into_inner();
+    info!("Live listener for:: {}", &lead_type);
+
+    let handlebars = Handlebars::new();
+    let template_path = "live_listener";
+
+    let template_contents = match read_hbs_template(template_path) {
+        Ok(contents) => contents,
+        Err(e) => {
+            error!("Couldn't render file for live listener:: {}", e);
+            LeadHandlebarsError::new(e.to_string()).error
+        }
+    };
+
+    let cf: ConfVars = set_env_vars();
+    let data = json!({
+      "conf": cf,
+      "lead_type": lead_type,
+      "table_path": get_lead_table_path(&lead_type),
+    });
+
+    let render = handlebars.render_template(&template_contents, &data)?;
+    Ok(render)
+}
+
+pub fn live_html_controllers(cfg: &mut ServiceConfig) {
+    cfg.route(
+      "/htmx/live/events",
+      get().to(|| async move {
+        HttpResponse::Ok()
+          .content_type("text/event-stream")
+          .append_header(("Cache-Control", "no-cache"))
+          .streaming(live_event_stream())
+      }),
+    );
+
+    cfg.route(
+      "/htmx/live/listener/{lead_type}",
+      post().to(
+        |hbs_path| async move {
+          let listener = live_listener(hbs_path).await;
+
+          match listener {
+            Ok(l) => HttpResponse::Ok().content_type("text/html").body(l),
+            Err(e) => HttpResponse::Ok()
+              .content_type("text/html")
+              .append_header(("HX-Trigger", "error_live"))
+              .body(
+                format!("<span class=\"icon is-small is-left\"><i class=\"fas fa-ban\"></i>Failed to load live updates: {}</span>",
+                e)
+              )
+          }
+        }
+      ),
+    );
+}
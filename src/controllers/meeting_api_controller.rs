@@ -0,0 +1,523 @@
+use actix_web::{
+    delete, get,
+    http::StatusCode,
+    patch, post,
+    web::{Data, Json, Path, Query, ServiceConfig},
+    HttpResponse,
+};
+use chrono::Utc;
+use log::{error, warn};
+use utoipa::OpenApi;
+use validator::Validate;
+
+use crate::{
+    db::{config::Database, meeting_db::MeetingDB, users_db::UsersDB},
+    error::{api_error::ApiError, meeting_error::MeetingError},
+    models::{
+        locale_model::Locale,
+        meeting_model::{CalendarFeedPath, CalendarFeedUrl, Meeting, MeetingQuery, MeetingUuid},
+        users_model::{ActingUser, UserUuid},
+    },
+    utils::{
+        general_utils::{get_uuid, shuffle_id},
+        i18n_utils::RequestLocale,
+        lead_utils::find_lead_summary,
+        meeting_utils::{
+            build_calendar_feed, get_calendar_feed, get_calendar_feed_url, get_meeting_errors,
+            is_in_range, is_participant, normalize_meeting, send_meeting_invites,
+        },
+        message_utils::get_localized_validation_errors,
+        role_utils::{is_active_user, is_admin},
+    },
+};
+
+fn forbidden_response(message: &str) -> HttpResponse {
+    HttpResponse::Forbidden()
+        .insert_header((
+            "HX-Trigger",
+            format!("{{ \"page_error\": {:?} }}", message.to_string()),
+        ))
+        .json(ApiError::new(MeetingError::MeetingForbidden).with_message(message))
+}
+
+fn not_found_response(meeting_uuid: &str) -> HttpResponse {
+    error!("No meeting found for UUID:: {:?}", meeting_uuid);
+    HttpResponse::NotFound().json(ApiError::new(MeetingError::NoMeetingsFound))
+}
+
+fn invalid_meeting_response(key_errors_vec: Vec<String>) -> HttpResponse {
+    HttpResponse::BadRequest()
+        .insert_header((
+            "HX-Trigger",
+            format!("{{ \"page_error\": {:?} }}", key_errors_vec),
+        ))
+        .json(ApiError::new(MeetingError::MeetingCreationFailure).with_field_errors(key_errors_vec))
+}
+
+fn feed_failure_response() -> HttpResponse {
+    HttpResponse::InternalServerError()
+        .insert_header((
+            "HX-Trigger",
+            format!(
+                "{{ \"page_error\": {:?} }}",
+                "Couldn't create calendar feed".to_string()
+            ),
+        ))
+        .json(ApiError::new(MeetingError::MeetingCreationFailure))
+}
+
+async fn get_all_meeting_errors(
+    db: &Data<Database>,
+    meeting: &Meeting,
+    locale: &Locale,
+) -> Vec<String> {
+    let mut key_errors_vec: Vec<String> = match meeting.validate() {
+        Ok(_) => vec![],
+        Err(e) => get_localized_validation_errors(&e, locale),
+    };
+
+    let users = <Database as UsersDB>::find_all_non_deleted(db)
+        .await
+        .unwrap_or_default();
+    key_errors_vec.extend(get_meeting_errors(meeting, &users));
+
+    if find_lead_summary(db, &meeting.lead_type, meeting.lead_uuid.clone())
+        .await
+        .is_none()
+    {
+        key_errors_vec.push(format!("El lead '{}' no existe", meeting.lead_uuid));
+    }
+
+    key_errors_vec
+}
+
+// Participants can see a meeting, only its organizer or an ADMIN can change it.
+async fn can_manage(db: &Data<Database>, meeting: &Meeting, user_uuid: Option<String>) -> bool {
+    match &user_uuid {
+        Some(uuid) if meeting.organizer.as_ref() == Some(uuid) => {
+            is_active_user(db, user_uuid).await
+        }
+        _ => is_admin(db, user_uuid).await,
+    }
+}
+
+async fn can_view(db: &Data<Database>, meeting: &Meeting, user_uuid: Option<String>) -> bool {
+    match &user_uuid {
+        Some(uuid) if is_participant(meeting, uuid) => is_active_user(db, user_uuid).await,
+        _ => is_admin(db, user_uuid).await,
+    }
+}
+
+#[utoipa::path(
+    tag = "Meetings",
+    operation_id = "find_user_meetings",
+    params(MeetingQuery),
+    responses(
+        (status = 200, description = "Meetings the user organizes or attends, by start date", body = Vec<Meeting>),
+        (status = 403, description = "Inactive user", body = ApiError),
+        (status = 404, description = "No meetings found", body = ApiError),
+    )
+)]
+#[get("/meetings")]
+#[tracing::instrument(name = "Show Meetings", skip(db, query, acting_user))]
+async fn find_all(
+    db: Data<Database>,
+    query: Query<MeetingQuery>,
+    acting_user: ActingUser,
+) -> Result<HttpResponse, MeetingError> {
+    let meeting_query = query.into_inner();
+    if !is_active_user(&db, acting_user.user_uuid.clone()).await {
+        return Ok(forbidden_response("Inicia sesión para ver tus reuniones"));
+    }
+
+    let user_uuid = acting_user.user_uuid.unwrap_or_default();
+    match <Database as MeetingDB>::find_by_user(&db, user_uuid).await {
+        Some(meetings) => {
+            let meetings: Vec<Meeting> = match (meeting_query.from, meeting_query.to) {
+                (Some(from), Some(to)) => meetings
+                    .into_iter()
+                    .filter(|meeting| is_in_range(meeting, &from, &to))
+                    .collect(),
+                (Some(from), None) => meetings
+                    .into_iter()
+                    .filter(|meeting| meeting.end_date > from)
+                    .collect(),
+                (None, Some(to)) => meetings
+                    .into_iter()
+                    .filter(|meeting| meeting.start_date < to)
+                    .collect(),
+                (None, None) => meetings,
+            };
+            Ok(HttpResponse::Ok().status(StatusCode::OK).json(meetings))
+        }
+        None => {
+            error!("Unable to find meetings");
+            Ok(HttpResponse::NotFound().json(ApiError::new(MeetingError::NoMeetingsFound)))
+        }
+    }
+}
+
+#[utoipa::path(
+    tag = "Meetings",
+    operation_id = "find_one_meeting",
+    params(MeetingUuid),
+    responses(
+        (status = 200, description = "Meeting found", body = Meeting),
+        (status = 403, description = "Only participants or an ADMIN can see the meeting", body = ApiError),
+        (status = 404, description = "Meeting not found", body = ApiError),
+    )
+)]
+#[get("/meetings/{uuid}")]
+#[tracing::instrument(name = "Get One Meeting", skip(db, acting_user), fields(uuid = %uuid.uuid))]
+async fn find_one(
+    db: Data<Database>,
+    uuid: Path<MeetingUuid>,
+    acting_user: ActingUser,
+) -> Result<HttpResponse, MeetingError> {
+    let meeting_uuid = uuid.into_inner().uuid;
+
+    let meeting = match <Database as MeetingDB>::find_one(&db, meeting_uuid.clone()).await {
+        Some(meeting) if !meeting.deleted => meeting,
+        _ => return Ok(not_found_response(&meeting_uuid)),
+    };
+
+    if !can_view(&db, &meeting, acting_user.user_uuid).await {
+        return Ok(forbidden_response("No participas en esta reunión"));
+    }
+
+    Ok(HttpResponse::Ok().status(StatusCode::OK).json(meeting))
+}
+
+#[utoipa::path(
+    tag = "Meetings",
+    operation_id = "create_meeting",
+    responses(
+        (status = 201, description = "Meeting created, attendees get an .ics invitation", body = MeetingUuid),
+        (status = 400, description = "Invalid meeting", body = ApiError),
+        (status = 403, description = "Inactive user or organizer is someone else", body = ApiError),
+        (status = 500, description = "Meeting couldn't be saved", body = ApiError),
+    )
+)]
+#[post("/meetings")]
+#[tracing::instrument(name = "Post Meeting", skip(db), fields(title = %body.title))]
+async fn create(
+    db: Data<Database>,
+    body: Json<Meeting>,
+    acting_user: ActingUser,
+    locale: RequestLocale,
+) -> Result<HttpResponse, MeetingError> {
+    let created_by = acting_user.user_uuid.clone();
+    if !is_active_user(&db, created_by.clone()).await {
+        return Ok(forbidden_response("Inicia sesión para agendar reuniones"));
+    }
+
+    let organizer = body.organizer.clone().or(created_by.clone());
+    if organizer != created_by && !is_admin(&db, created_by.clone()).await {
+        return Ok(forbidden_response(
+            "Solo un ADMIN puede agendar reuniones para otros",
+        ));
+    }
+
+    let new_meeting = normalize_meeting(Meeting {
+        organizer,
+        created_by: created_by.clone(),
+        ..body.into_inner()
+    });
+    let key_errors_vec = get_all_meeting_errors(&db, &new_meeting, &locale.0).await;
+    if !key_errors_vec.is_empty() {
+        error!("Error meeting.create {:?}", key_errors_vec);
+        return Ok(invalid_meeting_response(key_errors_vec));
+    }
+
+    let date_created = Utc::now();
+    let my_meeting = Meeting {
+        deleted: false,
+        date_created: Some(date_created),
+        date_modified: Some(date_created),
+        modified_by: created_by,
+        ..new_meeting
+    };
+
+    match <Database as MeetingDB>::add_one(&db, Meeting::new(get_uuid(), my_meeting)).await {
+        Some(meeting) => {
+            send_meeting_invites(&db, &meeting).await;
+            Ok(HttpResponse::Ok()
+                .insert_header(("HX-Trigger", "meeting_reload_page"))
+                .status(StatusCode::CREATED)
+                .json(MeetingUuid {
+                    uuid: match meeting.uuid {
+                        Some(this_uuid) => shuffle_id(this_uuid),
+                        None => "".to_string(),
+                    },
+                }))
+        }
+        None => {
+            error!("Error [POST] /meetings");
+            Ok(HttpResponse::InternalServerError()
+                .insert_header((
+                    "HX-Trigger",
+                    "{ \"page_error\": \"Internal server error\" }".to_string(),
+                ))
+                .json(ApiError::new(MeetingError::MeetingCreationFailure)))
+        }
+    }
+}
+
+#[utoipa::path(
+    tag = "Meetings",
+    operation_id = "update_meeting",
+    responses(
+        (status = 200, description = "Meeting updated", body = MeetingUuid),
+        (status = 400, description = "Invalid meeting", body = ApiError),
+        (status = 403, description = "Only the organizer or an ADMIN can change the meeting", body = ApiError),
+        (status = 404, description = "Meeting not found", body = ApiError),
+        (status = 500, description = "Meeting couldn't be saved", body = ApiError),
+    )
+)]
+#[patch("/meetings")]
+#[tracing::instrument(name = "Patch Meeting", skip(db), fields(title = %body.title))]
+async fn update_one(
+    db: Data<Database>,
+    body: Json<Meeting>,
+    acting_user: ActingUser,
+    locale: RequestLocale,
+) -> Result<HttpResponse, MeetingError> {
+    let modified_by = acting_user.user_uuid.clone();
+    let uuid_in_db = match body.uuid.clone() {
+        Some(t_id) => t_id,
+        None => String::from("forbidden"),
+    };
+
+    let stored_meeting = match <Database as MeetingDB>::find_one(&db, uuid_in_db.clone()).await {
+        Some(meeting) if !meeting.deleted => meeting,
+        _ => return Ok(not_found_response(&uuid_in_db)),
+    };
+
+    if !can_manage(&db, &stored_meeting, modified_by.clone()).await {
+        return Ok(forbidden_response(
+            "Solo quien organiza la reunión o un ADMIN puede cambiarla",
+        ));
+    }
+
+    // Handing the meeting over to someone else is an ADMIN decision.
+    let organizer = match is_admin(&db, modified_by.clone()).await {
+        true => body.organizer.clone().or(stored_meeting.organizer.clone()),
+        false => stored_meeting.organizer.clone(),
+    };
+    let updated_meeting = normalize_meeting(Meeting {
+        organizer,
+        modified_by,
+        ..body.into_inner()
+    });
+    let key_errors_vec = get_all_meeting_errors(&db, &updated_meeting, &locale.0).await;
+    if !key_errors_vec.is_empty() {
+        error!("Error in meeting.update_one: {:?}", key_errors_vec);
+        return Ok(invalid_meeting_response(key_errors_vec));
+    }
+
+    let meeting_to_update = Meeting {
+        deleted: false,
+        date_created: stored_meeting.date_created,
+        created_by: stored_meeting.created_by,
+        date_modified: Some(Utc::now()),
+        ..updated_meeting
+    };
+
+    match <Database as MeetingDB>::update_one(&db, meeting_to_update).await {
+        Some(meeting) => Ok(HttpResponse::Ok()
+            .insert_header(("HX-Trigger", "meeting_reload_page"))
+            .status(StatusCode::OK)
+            .json(MeetingUuid {
+                uuid: match meeting.uuid {
+                    Some(this_uuid) => shuffle_id(this_uuid),
+                    None => "".to_string(),
+                },
+            })),
+        None => {
+            error!("Error in meeting.update_one");
+            Ok(HttpResponse::InternalServerError()
+                .insert_header((
+                    "HX-Trigger",
+                    format!(
+                        "{{ \"page_error\": {:?} }}",
+                        "Couldn't save meeting".to_string()
+                    ),
+                ))
+                .json(ApiError::new(MeetingError::MeetingCreationFailure)))
+        }
+    }
+}
+
+#[utoipa::path(
+    tag = "Meetings",
+    operation_id = "delete_meeting",
+    params(MeetingUuid),
+    responses(
+        (status = 200, description = "Meeting deleted", body = MeetingUuid),
+        (status = 403, description = "Only the organizer or an ADMIN can delete the meeting", body = ApiError),
+        (status = 404, description = "Meeting not found", body = ApiError),
+    )
+)]
+#[delete("/meetings/{uuid}")]
+#[tracing::instrument(name = "Delete Meeting", skip(db), fields(uuid = %uuid.uuid))]
+async fn delete_one(
+    db: Data<Database>,
+    uuid: Path<MeetingUuid>,
+    acting_user: ActingUser,
+) -> Result<HttpResponse, MeetingError> {
+    let user_uuid = acting_user.user_uuid;
+    let meeting_uuid = uuid.into_inner().uuid;
+
+    let meeting = match <Database as MeetingDB>::find_one(&db, meeting_uuid.clone()).await {
+        Some(meeting) if !meeting.deleted => meeting,
+        _ => return Ok(not_found_response(&meeting_uuid)),
+    };
+
+    if !can_manage(&db, &meeting, user_uuid.clone()).await {
+        return Ok(forbidden_response(
+            "Solo quien organiza la reunión o un ADMIN puede borrarla",
+        ));
+    }
+
+    match <Database as MeetingDB>::delete_one(&db, meeting_uuid.clone(), user_uuid).await {
+        Some(_) => Ok(HttpResponse::Ok()
+            .insert_header(("HX-Trigger", "meeting_reload_page"))
+            .status(StatusCode::OK)
+            .json(MeetingUuid {
+                uuid: shuffle_id(meeting_uuid),
+            })),
+        None => Ok(not_found_response(&meeting_uuid)),
+    }
+}
+
+#[utoipa::path(
+    tag = "Meetings",
+    operation_id = "find_calendar_feed",
+    params(UserUuid),
+    responses(
+        (status = 200, description = "Secret iCalendar feed url of the user, created on first request", body = CalendarFeedUrl),
+        (status = 403, description = "Only the owner can see the feed url", body = ApiError),
+        (status = 500, description = "Feed couldn't be created", body = ApiError),
+    )
+)]
+#[get("/users/{uuid}/calendar_feed")]
+#[tracing::instrument(name = "Show Calendar Feed", skip(db, acting_user), fields(uuid = %uuid.uuid))]
+async fn find_feed(
+    db: Data<Database>,
+    uuid: Path<UserUuid>,
+    acting_user: ActingUser,
+) -> Result<HttpResponse, MeetingError> {
+    let owner = uuid.into_inner().uuid;
+    let user_uuid = acting_user.user_uuid;
+    if user_uuid.as_deref() != Some(owner.as_str()) || !is_active_user(&db, user_uuid).await {
+        return Ok(forbidden_response("Solo puedes ver tu propio calendario"));
+    }
+
+    match get_calendar_feed(&db, owner.clone(), false).await {
+        Some(feed) => Ok(HttpResponse::Ok()
+            .status(StatusCode::OK)
+            .json(CalendarFeedUrl {
+                url: get_calendar_feed_url(&owner, &feed.secret),
+            })),
+        None => Ok(feed_failure_response()),
+    }
+}
+
+#[utoipa::path(
+    tag = "Meetings",
+    operation_id = "regenerate_calendar_feed",
+    params(UserUuid),
+    responses(
+        (status = 200, description = "New feed url, the previous one stops working", body = CalendarFeedUrl),
+        (status = 403, description = "Only the owner can regenerate the feed url", body = ApiError),
+        (status = 500, description = "Feed couldn't be created", body = ApiError),
+    )
+)]
+#[post("/users/{uuid}/calendar_feed")]
+#[tracing::instrument(name = "Regenerate Calendar Feed", skip(db, acting_user), fields(uuid = %uuid.uuid))]
+async fn regenerate_feed(
+    db: Data<Database>,
+    uuid: Path<UserUuid>,
+    acting_user: ActingUser,
+) -> Result<HttpResponse, MeetingError> {
+    let owner = uuid.into_inner().uuid;
+    let user_uuid = acting_user.user_uuid;
+    if user_uuid.as_deref() != Some(owner.as_str()) || !is_active_user(&db, user_uuid).await {
+        return Ok(forbidden_response(
+            "Solo puedes cambiar tu propio calendario",
+        ));
+    }
+
+    match get_calendar_feed(&db, owner.clone(), true).await {
+        Some(feed) => Ok(HttpResponse::Ok()
+            .insert_header(("HX-Trigger", "calendar_feed_reload_page"))
+            .status(StatusCode::OK)
+            .json(CalendarFeedUrl {
+                url: get_calendar_feed_url(&owner, &feed.secret),
+            })),
+        None => Ok(feed_failure_response()),
+    }
+}
+
+// Calendar apps can't send a bearer token, the secret in the url is the
+// credential, so unknown users and wrong secrets look the same.
+#[utoipa::path(
+    tag = "Meetings",
+    operation_id = "calendar_feed",
+    params(CalendarFeedPath),
+    responses(
+        (status = 200, description = "iCalendar feed with the meetings of the user", content_type = "text/calendar"),
+        (status = 404, description = "Unknown feed", body = ApiError),
+    ),
+    security(())
+)]
+#[get("/calendar/{uuid}/{secret}.ics")]
+#[tracing::instrument(name = "Calendar Feed", skip(db, path))]
+async fn calendar_feed(
+    db: Data<Database>,
+    path: Path<CalendarFeedPath>,
+) -> Result<HttpResponse, MeetingError> {
+    let CalendarFeedPath { uuid, secret } = path.into_inner();
+
+    let feed = <Database as MeetingDB>::find_feed(&db, uuid.clone()).await;
+    let user = <Database as UsersDB>::find_one(&db, uuid.clone()).await;
+    match (feed, user) {
+        (Some(feed), Some(user)) if feed.secret == secret && !user.deleted => {
+            Ok(HttpResponse::Ok()
+                .content_type("text/calendar; charset=utf-8")
+                .body(build_calendar_feed(&db, &user).await))
+        }
+        _ => {
+            warn!("Rejected calendar feed request for user {}", uuid);
+            Ok(HttpResponse::NotFound().json(ApiError::new(MeetingError::CalendarFeedNotFound)))
+        }
+    }
+}
+
+#[derive(OpenApi)]
+#[openapi(paths(
+    find_all,
+    find_one,
+    create,
+    update_one,
+    delete_one,
+    find_feed,
+    regenerate_feed,
+    calendar_feed
+))]
+pub struct MeetingApiDoc;
+
+pub fn meeting_api_controllers(cfg: &mut ServiceConfig) {
+    cfg.service(create);
+    cfg.service(delete_one);
+    cfg.service(find_all);
+    cfg.service(find_feed);
+    cfg.service(find_one);
+    cfg.service(regenerate_feed);
+    cfg.service(update_one);
+}
+
+// Public like the web-to-lead endpoint, the feed is authenticated by its url.
+pub fn calendar_feed_controllers(cfg: &mut ServiceConfig) {
+    cfg.service(calendar_feed);
+}
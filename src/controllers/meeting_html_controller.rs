@@ -0,0 +1,328 @@
+use actix_web::{
+    web::{get, post, Data, Path, Query, ServiceConfig},
+    HttpResponse,
+};
+use chrono::{Datelike, Duration, NaiveDate, Utc, Weekday};
+use chrono_tz::Tz;
+use handlebars::{Handlebars, RenderError};
+use log::{error, info};
+use serde_json::json;
+
+use crate::{
+    db::{config::Database, meeting_db::MeetingDB, users_db::UsersDB},
+    models::{
+        locale_model::Locale,
+        meeting_model::{CalendarQuery, CalendarView, MeetingHandlebarsError},
+        sales_model::LeadType,
+        users_model::ActingUser,
+    },
+    utils::{
+        env::{set_env_vars, ConfVars},
+        fs_utils::read_hbs_template,
+        i18n_utils::{register_i18n_helpers, translate, translate_with, RequestLocale, Translate},
+        lead_utils::{find_lead_summary, get_lead_edit_path},
+        meeting_utils::{
+            create_meeting_kind_tags, get_calendar_feed, get_calendar_feed_url,
+            get_calendar_range, get_local_datetime, get_user_name, is_in_range,
+            shift_calendar_date,
+        },
+        role_utils::is_active_user,
+        time::{
+            format_date_in_language, format_month_in_language, format_time, get_weekday_name,
+            RequestTimezone,
+        },
+    },
+};
+
+const WEEKDAYS: [Weekday; 7] = [
+    Weekday::Mon,
+    Weekday::Tue,
+    Weekday::Wed,
+    Weekday::Thu,
+    Weekday::Fri,
+    Weekday::Sat,
+    Weekday::Sun,
+];
+
+fn read_meeting_template(template_path: &str) -> String {
+    match read_hbs_template(template_path) {
+        Ok(contents) => contents,
+        Err(e) => {
+            error!("Failed to render contents for {}:: {}", template_path, e);
+            MeetingHandlebarsError::new(e.to_string()).error
+        }
+    }
+}
+
+async fn meeting_calendar(
+    calendar_query: Query<CalendarQuery>,
+    acting_user: ActingUser,
+    db: Data<Database>,
+    locale: Locale,
+    timezone: Tz,
+) -> Result<String, RenderError> {
+    let mut handlebars = Handlebars::new();
+    register_i18n_helpers(&mut handlebars);
+    let template_contents = read_meeting_template("meeting_calendar");
+
+    let cf: ConfVars = set_env_vars();
+    let CalendarQuery { view, date } = calendar_query.into_inner();
+    let user_uuid = acting_user.user_uuid;
+
+    if !is_active_user(&db, user_uuid.clone()).await {
+        let data = json!({
+          "conf": cf,
+          "locale": locale.code(),
+          "error": translate(&locale, "calendar.login_required"),
+        });
+        return handlebars.render_template(&template_contents, &data);
+    }
+
+    let user_uuid = user_uuid.unwrap_or_default();
+    let view = view.unwrap_or(CalendarView::MONTH);
+    let today = Utc::now().with_timezone(&timezone).date_naive();
+    let date = date.unwrap_or(today);
+    let (first_day, last_day) = get_calendar_range(&view, date);
+    let from = get_local_datetime(first_day, &timezone);
+    let to = get_local_datetime(last_day + Duration::days(1), &timezone);
+
+    let meetings: Vec<_> = <Database as MeetingDB>::find_by_user(&db, user_uuid.clone())
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|meeting| is_in_range(meeting, &from, &to))
+        .collect();
+
+    let mut entries: Vec<(NaiveDate, serde_json::Value)> = vec![];
+    for meeting in meetings {
+        let lead = find_lead_summary(&db, &meeting.lead_type, meeting.lead_uuid.clone()).await;
+        let lead_name = lead.map(|lead| match lead.company {
+            Some(company) if !company.trim().is_empty() => company,
+            _ => lead.name,
+        });
+        let entry = json!({
+          "m": meeting,
+          "kind": meeting.kind.label(&locale),
+          "time": format_time(&meeting.start_date, &timezone),
+          "end_time": format_time(&meeting.end_date, &timezone),
+          "lead_name": lead_name,
+          "lead_edit_path": get_lead_edit_path(&meeting.lead_type),
+        });
+        entries.push((meeting.start_date.with_timezone(&timezone).date_naive(), entry));
+    }
+
+    let days: Vec<serde_json::Value> = first_day
+        .iter_days()
+        .take_while(|day| *day <= last_day)
+        .map(|day| {
+            let day_meetings: Vec<&serde_json::Value> = entries
+                .iter()
+                .filter(|(meeting_day, _)| *meeting_day == day)
+                .map(|(_, entry)| entry)
+                .collect();
+            json!({
+              "date": day.format("%Y-%m-%d").to_string(),
+              "day": day.format("%-d").to_string(),
+              "in_month": view == CalendarView::WEEK || day.month() == date.month(),
+              "is_today": day == today,
+              "meetings": day_meetings,
+            })
+        })
+        .collect();
+    let weeks: Vec<&[serde_json::Value]> = days.chunks(7).collect();
+
+    let title = match view {
+        CalendarView::MONTH => format_month_in_language(&date, &locale),
+        CalendarView::WEEK => translate_with(
+            &locale,
+            "calendar.week_title",
+            &[
+                ("from", &first_day.format("%d/%m").to_string()),
+                ("to", &last_day.format("%d/%m/%Y").to_string()),
+            ],
+        ),
+    };
+    let weekdays: Vec<String> = WEEKDAYS
+        .iter()
+        .map(|weekday| get_weekday_name(*weekday, &locale))
+        .collect();
+
+    let data = json!({
+      "conf": cf,
+      "locale": locale.code(),
+      "user_uuid": user_uuid,
+      "title": title,
+      "view": view,
+      "is_week": view == CalendarView::WEEK,
+      "date": date.format("%Y-%m-%d").to_string(),
+      "previous_date": shift_calendar_date(&view, date, false).format("%Y-%m-%d").to_string(),
+      "next_date": shift_calendar_date(&view, date, true).format("%Y-%m-%d").to_string(),
+      "today": today.format("%Y-%m-%d").to_string(),
+      "weekdays": weekdays,
+      "weeks": weeks,
+    });
+
+    let render = handlebars.render_template(&template_contents, &data)?;
+    Ok(render)
+}
+
+async fn meeting_feed(
+    hbs_path: Path<String>,
+    db: Data<Database>,
+    locale: Locale,
+) -> Result<String, RenderError> {
+    let user_uuid = hbs_path.into_inner();
+    info!("Calendar feed for user:: {}", &user_uuid);
+
+    let mut handlebars = Handlebars::new();
+    register_i18n_helpers(&mut handlebars);
+    let template_contents = read_meeting_template("meeting_feed");
+
+    let feed_url = match get_calendar_feed(&db, user_uuid.clone(), false).await {
+        Some(feed) => get_calendar_feed_url(&user_uuid, &feed.secret),
+        None => "".to_string(),
+    };
+
+    let cf: ConfVars = set_env_vars();
+    let data = json!({
+      "conf": cf,
+      "locale": locale.code(),
+      "user_uuid": user_uuid,
+      "feed_url": feed_url,
+    });
+
+    let render = handlebars.render_template(&template_contents, &data)?;
+    Ok(render)
+}
+
+async fn meeting_lead_panel(
+    hbs_path: Path<(LeadType, String)>,
+    db: Data<Database>,
+    locale: Locale,
+    timezone: Tz,
+) -> Result<String, RenderError> {
+    let (lead_type, lead_uuid) = hbs_path.into_inner();
+    info!("Meetings for lead:: {}", &lead_uuid);
+
+    let mut handlebars = Handlebars::new();
+    register_i18n_helpers(&mut handlebars);
+    let template_contents = read_meeting_template("meeting_lead_panel");
+
+    let users = <Database as UsersDB>::find_all_non_deleted(&db)
+        .await
+        .unwrap_or_default();
+    let meetings: Vec<serde_json::Value> =
+        <Database as MeetingDB>::find_by_lead(&db, lead_uuid.clone())
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .map(|meeting| {
+                let participants: Vec<String> = meeting
+                    .organizer
+                    .iter()
+                    .chain(meeting.attendees.iter())
+                    .map(|participant| get_user_name(&users, participant))
+                    .collect();
+                json!({
+                  "m": meeting,
+                  "kind": meeting.kind.label(&locale),
+                  "start_date": format_date_in_language(&meeting.start_date, &locale, &timezone),
+                  "participants": participants.join(", "),
+                })
+            })
+            .collect();
+    let user_options: Vec<serde_json::Value> = users
+        .iter()
+        .map(|user| json!({ "uuid": user.uuid, "name": format!("{} {}", user.name, user.last_name) }))
+        .collect();
+    let has_email = find_lead_summary(&db, &lead_type, lead_uuid.clone())
+        .await
+        .and_then(|lead| lead.email)
+        .is_some_and(|email| !email.trim().is_empty());
+
+    let cf: ConfVars = set_env_vars();
+    let data = json!({
+      "conf": cf,
+      "locale": locale.code(),
+      "lead_type": lead_type,
+      "lead_uuid": lead_uuid,
+      "meetings": meetings,
+      "kinds": create_meeting_kind_tags(None, &locale),
+      "users": user_options,
+      "has_email": has_email,
+    });
+
+    let render = handlebars.render_template(&template_contents, &data)?;
+    Ok(render)
+}
+
+pub fn meeting_html_controllers(cfg: &mut ServiceConfig) {
+    cfg.route(
+      "/htmx/meeting/calendar",
+      get().to(
+        |calendar_query, acting_user: ActingUser, db: Data<Database>, locale: RequestLocale, timezone: RequestTimezone| async move {
+          let calendar = meeting_calendar(calendar_query, acting_user, db, locale.0, timezone.0).await;
+
+          match calendar {
+            Ok(c) => HttpResponse::Ok()
+              .content_type("text/html")
+              .append_header(("HX-Trigger", "activate_navbar_element"))
+              .body(c),
+            Err(e) => HttpResponse::Ok()
+              .content_type("text/html")
+              .append_header(("HX-Trigger", "error_meeting"))
+              .body(
+                format!("<span class=\"icon is-small is-left\"><i class=\"fas fa-ban\"></i>Failed to load calendar: {}</span>",
+                e)
+              )
+          }
+        }
+      ),
+    );
+
+    cfg.route(
+      "/htmx/meeting/feed/{uuid}",
+      get().to(
+        |hbs_path: Path<String>, acting_user: ActingUser, db: Data<Database>, locale: RequestLocale| async move {
+          // Only the owner gets to see the secret url.
+          let user_uuid = acting_user.user_uuid;
+          if user_uuid.as_deref() != Some(hbs_path.as_str()) || !is_active_user(&db, user_uuid).await {
+            return HttpResponse::Ok().content_type("text/html").body("");
+          }
+
+          let feed = meeting_feed(hbs_path, db, locale.0).await;
+
+          match feed {
+            Ok(f) => HttpResponse::Ok().content_type("text/html").body(f),
+            Err(e) => HttpResponse::Ok()
+              .content_type("text/html")
+              .append_header(("HX-Trigger", "error_meeting"))
+              .body(
+                format!("<span class=\"icon is-small is-left\"><i class=\"fas fa-ban\"></i>Failed to load calendar feed: {}</span>",
+                e)
+              )
+          }
+        }
+      ),
+    );
+
+    cfg.route(
+      "/htmx/meeting/lead/{lead_type}/{uuid}",
+      post().to(
+        |hbs_path, db: Data<Database>, locale: RequestLocale, timezone: RequestTimezone| async move {
+          let meetings = meeting_lead_panel(hbs_path, db, locale.0, timezone.0).await;
+
+          match meetings {
+            Ok(m) => HttpResponse::Ok().content_type("text/html").body(m),
+            Err(e) => HttpResponse::Ok()
+              .content_type("text/html")
+              .append_header(("HX-Trigger", "error_meeting"))
+              .body(
+                format!("<span class=\"icon is-small is-left\"><i class=\"fas fa-ban\"></i>Failed to load meetings: {}</span>",
+                e)
+              )
+          }
+        }
+      ),
+    );
+}
@@ -1,9 +1,50 @@
+pub mod account_api_controller;
+pub mod account_html_controller;
+pub mod address_html_controller;
+pub mod api_controller;
+pub mod api_token_api_controller;
+pub mod api_token_html_controller;
+pub mod attachment_api_controller;
+pub mod attachment_html_controller;
+pub mod bulk_html_controller;
+pub mod campaign_api_controller;
+pub mod campaign_html_controller;
 pub mod clinics_api_controller;
 pub mod clinics_html_controller;
+pub mod contact_api_controller;
+pub mod contact_html_controller;
+pub mod email_template_api_controller;
+pub mod email_template_html_controller;
 pub mod enterprise_api_controller;
 pub mod enterprise_html_controller;
 pub mod help_html_controller;
+pub mod kanban_html_controller;
+pub mod lead_api_controller;
+pub mod live_html_controller;
+pub mod meeting_api_controller;
+pub mod meeting_html_controller;
+pub mod notification_api_controller;
+pub mod notification_html_controller;
+pub mod openapi_controller;
+pub mod pipeline_api_controller;
+pub mod pipeline_html_controller;
+pub mod quote_api_controller;
+pub mod quote_html_controller;
 pub mod school_api_controller;
 pub mod school_html_controller;
+pub mod scoring_api_controller;
+pub mod scoring_html_controller;
+pub mod segment_api_controller;
+pub mod segment_html_controller;
+pub mod service_api_controller;
+pub mod service_html_controller;
+pub mod session_api_controller;
+pub mod session_html_controller;
+pub mod source_html_controller;
+pub mod tag_api_controller;
+pub mod tag_html_controller;
+pub mod territory_api_controller;
+pub mod territory_html_controller;
 pub mod users_api_controller;
 pub mod users_html_controller;
+pub mod web_lead_api_controller;
@@ -0,0 +1,275 @@
+use actix_web::{
+    get,
+    http::StatusCode,
+    patch,
+    web::{Data, Json, Path, Query, ServiceConfig},
+    HttpResponse,
+};
+use chrono::Utc;
+use log::error;
+use utoipa::OpenApi;
+
+use crate::{
+    db::{config::Database, notification_db::NotificationDB},
+    error::{api_error::ApiError, notification_error::NotificationError},
+    models::{
+        notification_model::{
+            Notification, NotificationPreference, NotificationPreferences, NotificationQuery,
+            NotificationUuid, UnreadNotifications,
+        },
+        users_model::{ActingUser, UserUuid},
+    },
+    utils::{
+        notification_utils::{get_notification_preferences, normalize_preferences},
+        role_utils::is_active_user,
+    },
+};
+
+async fn is_owner(db: &Data<Database>, acting_user: Option<String>, owner: &str) -> bool {
+    acting_user.as_deref() == Some(owner) && is_active_user(db, acting_user).await
+}
+
+fn forbidden_response() -> HttpResponse {
+    let message = "Solo puedes ver tus propias notificaciones";
+    HttpResponse::Forbidden()
+        .insert_header((
+            "HX-Trigger",
+            format!("{{ \"page_error\": {:?} }}", message.to_string()),
+        ))
+        .json(ApiError::new(NotificationError::NotificationForbidden).with_message(message))
+}
+
+fn update_failure_response() -> HttpResponse {
+    HttpResponse::InternalServerError()
+        .insert_header((
+            "HX-Trigger",
+            format!(
+                "{{ \"page_error\": {:?} }}",
+                "Couldn't update notifications".to_string()
+            ),
+        ))
+        .json(ApiError::new(NotificationError::NotificationUpdateFailure))
+}
+
+#[utoipa::path(
+    tag = "Notifications",
+    operation_id = "find_user_notifications",
+    params(UserUuid, NotificationQuery),
+    responses(
+        (status = 200, description = "Latest notifications of the user, newest first", body = Vec<Notification>),
+        (status = 403, description = "Only the owner can read notifications", body = ApiError),
+        (status = 404, description = "No notifications found", body = ApiError),
+    )
+)]
+#[get("/users/{uuid}/notifications")]
+#[tracing::instrument(name = "Show Notifications", skip(db, query, acting_user), fields(uuid = %uuid.uuid))]
+async fn find_by_user(
+    db: Data<Database>,
+    uuid: Path<UserUuid>,
+    query: Query<NotificationQuery>,
+    acting_user: ActingUser,
+) -> Result<HttpResponse, NotificationError> {
+    let owner = uuid.into_inner().uuid;
+    if !is_owner(&db, acting_user.user_uuid, &owner).await {
+        return Ok(forbidden_response());
+    }
+
+    let unread_only = query.into_inner().unread_only.unwrap_or(false);
+    match Database::find_by_user(&db, owner, unread_only).await {
+        Some(notifications) => Ok(HttpResponse::Ok()
+            .status(StatusCode::OK)
+            .json(notifications)),
+        None => {
+            error!("Unable to find notifications");
+            Ok(HttpResponse::NotFound()
+                .json(ApiError::new(NotificationError::NoNotificationsFound)))
+        }
+    }
+}
+
+#[utoipa::path(
+    tag = "Notifications",
+    operation_id = "count_unread_notifications",
+    params(UserUuid),
+    responses(
+        (status = 200, description = "Unread notifications of the user", body = UnreadNotifications),
+        (status = 403, description = "Only the owner can read notifications", body = ApiError),
+    )
+)]
+#[get("/users/{uuid}/notifications/unread")]
+#[tracing::instrument(name = "Count Unread Notifications", skip(db, acting_user), fields(uuid = %uuid.uuid))]
+async fn count_unread(
+    db: Data<Database>,
+    uuid: Path<UserUuid>,
+    acting_user: ActingUser,
+) -> Result<HttpResponse, NotificationError> {
+    let owner = uuid.into_inner().uuid;
+    if !is_owner(&db, acting_user.user_uuid, &owner).await {
+        return Ok(forbidden_response());
+    }
+
+    Ok(HttpResponse::Ok()
+        .status(StatusCode::OK)
+        .json(UnreadNotifications {
+            unread: Database::count_unread(&db, owner).await.unwrap_or(0),
+        }))
+}
+
+#[utoipa::path(
+    tag = "Notifications",
+    operation_id = "mark_notification_read",
+    params(NotificationUuid),
+    responses(
+        (status = 200, description = "Notification marked as read", body = Notification),
+        (status = 403, description = "Only the owner can read notifications", body = ApiError),
+        (status = 404, description = "Notification not found", body = ApiError),
+        (status = 500, description = "Notification couldn't be updated", body = ApiError),
+    )
+)]
+#[patch("/notifications/{uuid}/read")]
+#[tracing::instrument(name = "Read Notification", skip(db, acting_user), fields(uuid = %uuid.uuid))]
+async fn mark_read(
+    db: Data<Database>,
+    uuid: Path<NotificationUuid>,
+    acting_user: ActingUser,
+) -> Result<HttpResponse, NotificationError> {
+    let notification_uuid = uuid.into_inner().uuid;
+
+    let notification = match Database::find_one(&db, notification_uuid.clone()).await {
+        Some(notification) => notification,
+        None => {
+            error!("No notification found for UUID:: {:?}", notification_uuid);
+            return Ok(HttpResponse::NotFound()
+                .json(ApiError::new(NotificationError::NoNotificationsFound)));
+        }
+    };
+
+    if !is_owner(&db, acting_user.user_uuid, &notification.user_uuid).await {
+        return Ok(forbidden_response());
+    }
+
+    match Database::mark_read(&db, notification_uuid).await {
+        Some(notification) => Ok(HttpResponse::Ok()
+            .insert_header(("HX-Trigger", "notification_reload_page"))
+            .status(StatusCode::OK)
+            .json(notification)),
+        None => Ok(update_failure_response()),
+    }
+}
+
+#[utoipa::path(
+    tag = "Notifications",
+    operation_id = "mark_all_notifications_read",
+    params(UserUuid),
+    responses(
+        (status = 200, description = "Every notification of the user marked as read", body = UnreadNotifications),
+        (status = 403, description = "Only the owner can read notifications", body = ApiError),
+        (status = 500, description = "Notifications couldn't be updated", body = ApiError),
+    )
+)]
+#[patch("/users/{uuid}/notifications/read")]
+#[tracing::instrument(name = "Read All Notifications", skip(db, acting_user), fields(uuid = %uuid.uuid))]
+async fn mark_all_read(
+    db: Data<Database>,
+    uuid: Path<UserUuid>,
+    acting_user: ActingUser,
+) -> Result<HttpResponse, NotificationError> {
+    let owner = uuid.into_inner().uuid;
+    if !is_owner(&db, acting_user.user_uuid, &owner).await {
+        return Ok(forbidden_response());
+    }
+
+    match Database::mark_all_read(&db, owner).await {
+        Some(_) => Ok(HttpResponse::Ok()
+            .insert_header(("HX-Trigger", "notification_reload_page"))
+            .status(StatusCode::OK)
+            .json(UnreadNotifications { unread: 0 })),
+        None => Ok(update_failure_response()),
+    }
+}
+
+#[utoipa::path(
+    tag = "Notifications",
+    operation_id = "find_notification_preferences",
+    params(UserUuid),
+    responses(
+        (status = 200, description = "In-app and email preference per notification kind", body = Vec<NotificationPreference>),
+        (status = 403, description = "Only the owner can manage preferences", body = ApiError),
+    )
+)]
+#[get("/users/{uuid}/notification_preferences")]
+#[tracing::instrument(name = "Show Notification Preferences", skip(db, acting_user), fields(uuid = %uuid.uuid))]
+async fn find_preferences(
+    db: Data<Database>,
+    uuid: Path<UserUuid>,
+    acting_user: ActingUser,
+) -> Result<HttpResponse, NotificationError> {
+    let owner = uuid.into_inner().uuid;
+    if !is_owner(&db, acting_user.user_uuid, &owner).await {
+        return Ok(forbidden_response());
+    }
+
+    Ok(HttpResponse::Ok()
+        .status(StatusCode::OK)
+        .json(get_notification_preferences(&db, owner).await))
+}
+
+#[utoipa::path(
+    tag = "Notifications",
+    operation_id = "update_notification_preferences",
+    params(UserUuid),
+    responses(
+        (status = 200, description = "Preferences saved", body = Vec<NotificationPreference>),
+        (status = 403, description = "Only the owner can manage preferences", body = ApiError),
+        (status = 500, description = "Preferences couldn't be saved", body = ApiError),
+    )
+)]
+#[patch("/users/{uuid}/notification_preferences")]
+#[tracing::instrument(name = "Patch Notification Preferences", skip(db, body, acting_user), fields(uuid = %uuid.uuid))]
+async fn update_preferences(
+    db: Data<Database>,
+    uuid: Path<UserUuid>,
+    body: Json<NotificationPreferences>,
+    acting_user: ActingUser,
+) -> Result<HttpResponse, NotificationError> {
+    let owner = uuid.into_inner().uuid;
+    let modified_by = acting_user.user_uuid.clone();
+    if !is_owner(&db, modified_by.clone(), &owner).await {
+        return Ok(forbidden_response());
+    }
+
+    let preferences = NotificationPreferences {
+        uuid: Some(owner),
+        preferences: normalize_preferences(body.preferences.clone()),
+        date_modified: Some(Utc::now()),
+        modified_by,
+    };
+
+    match Database::update_preferences(&db, preferences).await {
+        Some(saved) => Ok(HttpResponse::Ok()
+            .insert_header(("HX-Trigger", "notification_preferences_reload_page"))
+            .status(StatusCode::OK)
+            .json(saved.preferences)),
+        None => Ok(update_failure_response()),
+    }
+}
+
+#[derive(OpenApi)]
+#[openapi(paths(
+    find_by_user,
+    count_unread,
+    mark_read,
+    mark_all_read,
+    find_preferences,
+    update_preferences
+))]
+pub struct NotificationApiDoc;
+
+pub fn notification_api_controllers(cfg: &mut ServiceConfig) {
+    cfg.service(count_unread);
+    cfg.service(find_by_user);
+    cfg.service(find_preferences);
+    cfg.service(mark_all_read);
+    cfg.service(mark_read);
+    cfg.service(update_preferences);
+}
@@ -0,0 +1,167 @@
+use actix_web::{
+    web::{get, post, Data, Path, ServiceConfig},
+    HttpResponse,
+};
+use chrono_tz::Tz;
+use handlebars::{Handlebars, RenderError};
+use log::{error, info};
+use serde_json::json;
+
+use crate::{
+    db::{config::Database, notification_db::NotificationDB},
+    models::{
+        locale_model::Locale, notification_model::NotificationHandlebarsError,
+        users_model::ActingUser,
+    },
+    utils::{
+        env::{set_env_vars, ConfVars},
+        fs_utils::read_hbs_template,
+        i18n_utils::{register_i18n_helpers, RequestLocale, Translate},
+        notification_utils::get_notification_preferences,
+        role_utils::is_active_user,
+        time::{format_date_in_language, RequestTimezone},
+    },
+};
+
+const BELL_NOTIFICATIONS: usize = 10;
+
+async fn notification_bell(
+    acting_user: ActingUser,
+    db: Data<Database>,
+    locale: Locale,
+    timezone: Tz,
+) -> Result<String, RenderError> {
+    let mut handlebars = Handlebars::new();
+    register_i18n_helpers(&mut handlebars);
+    let template_path = "notification_bell";
+
+    let template_contents = match read_hbs_template(template_path) {
+        Ok(contents) => contents,
+        Err(e) => {
+            error!("Couldn't render file for notification bell:: {}", e);
+            NotificationHandlebarsError::new(e.to_string()).error
+        }
+    };
+
+    let cf: ConfVars = set_env_vars();
+    let user_uuid = acting_user.user_uuid;
+
+    if !is_active_user(&db, user_uuid.clone()).await {
+        let data = json!({"conf": cf, "locale": locale.code(), "unread": 0, "notifications": []});
+        return handlebars.render_template(&template_contents, &data);
+    }
+
+    let user_uuid = user_uuid.unwrap_or_default();
+    let unread = <Database as NotificationDB>::count_unread(&db, user_uuid.clone())
+        .await
+        .unwrap_or(0);
+    let notifications: Vec<serde_json::Value> =
+        <Database as NotificationDB>::find_by_user(&db, user_uuid.clone(), false)
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .take(BELL_NOTIFICATIONS)
+            .map(|notification| {
+                let date_created = match notification.date_created {
+                    Some(this_date) => format_date_in_language(&this_date, &locale, &timezone),
+                    None => "".to_string(),
+                };
+                let kind = notification.kind.label(&locale);
+                json!({ "n": notification, "kind": kind, "date_created": date_created })
+            })
+            .collect();
+
+    let data = json!({
+      "conf": cf,
+      "locale": locale.code(),
+      "user_uuid": user_uuid,
+      "unread": unread,
+      "notifications": notifications,
+    });
+
+    let render = handlebars.render_template(&template_contents, &data)?;
+    Ok(render)
+}
+
+async fn notification_preferences(
+    hbs_path: Path<String>,
+    db: Data<Database>,
+    locale: Locale,
+) -> Result<String, RenderError> {
+    let user_uuid = hbs_path.into_inner();
+    info!("Notification preferences for user:: {}", &user_uuid);
+
+    let mut handlebars = Handlebars::new();
+    register_i18n_helpers(&mut handlebars);
+    let template_path = "notification_preferences";
+
+    let template_contents = match read_hbs_template(template_path) {
+        Ok(contents) => contents,
+        Err(e) => {
+            error!("Couldn't render file for notification preferences:: {}", e);
+            NotificationHandlebarsError::new(e.to_string()).error
+        }
+    };
+
+    let preferences: Vec<serde_json::Value> = get_notification_preferences(&db, user_uuid.clone())
+        .await
+        .into_iter()
+        .map(|preference| {
+            let text = preference.kind.label(&locale);
+            json!({ "p": preference, "text": text })
+        })
+        .collect();
+
+    let cf: ConfVars = set_env_vars();
+    let data = json!({
+      "conf": cf,
+      "locale": locale.code(),
+      "user_uuid": user_uuid,
+      "preferences": preferences,
+    });
+
+    let render = handlebars.render_template(&template_contents, &data)?;
+    Ok(render)
+}
+
+pub fn notification_html_controllers(cfg: &mut ServiceConfig) {
+    cfg.route(
+      "/htmx/notifications/bell",
+      get().to(
+        |acting_user, db: Data<Database>, locale: RequestLocale, timezone: RequestTimezone| async move {
+          let bell = notification_bell(acting_user, db, locale.0, timezone.0).await;
+
+          match bell {
+            Ok(b) => HttpResponse::Ok().content_type("text/html").body(b),
+            Err(e) => HttpResponse::Ok()
+              .content_type("text/html")
+              .append_header(("HX-Trigger", "error_notification"))
+              .body(
+                format!("<span class=\"icon is-small is-left\"><i class=\"fas fa-ban\"></i>Failed to load notifications: {}</span>",
+                e)
+              )
+          }
+        }
+      ),
+    );
+
+    cfg.route(
+      "/htmx/notifications/preferences/{uuid}",
+      post().to(
+        |hbs_path, db: Data<Database>, locale: RequestLocale| async move {
+          let preferences = notification_preferences(hbs_path, db, locale.0).await;
+
+          match preferences {
+            Ok(p) => HttpResponse::Ok().content_type("text/html").body(p),
+            Err(e) => HttpResponse::Ok()
+              .content_type("text/html")
+              .append_header(("HX-Trigger", "error_notification"))
+              .body(
+                format!("<span class=\"icon is-small is-left\"><i class=\"fas fa-ban\"></i>Failed to load notification preferences: {}</span>",
+                e)
+              )
+          }
+        }
+      ),
+    );
+}
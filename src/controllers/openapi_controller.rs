@@ -0,0 +1,178 @@
+use actix_web::web::ServiceConfig;
+use utoipa::{
+    openapi::security::{HttpAuthScheme, HttpBuilder, SecurityRequirement, SecurityScheme},
+    OpenApi,
+};
+use utoipa_swagger_ui::SwaggerUi;
+
+use crate::{
+    controllers::{
+        account_api_controller::AccountApiDoc, api_token_api_controller::ApiTokenApiDoc,
+        attachment_api_controller::AttachmentApiDoc, campaign_api_controller::CampaignApiDoc,
+        clinics_api_controller::ClinicalApiDoc, contact_api_controller::ContactApiDoc,
+        email_template_api_controller::EmailTemplateApiDoc,
+        enterprise_api_controller::EnterpriseApiDoc, lead_api_controller::LeadApiDoc,
+        meeting_api_controller::MeetingApiDoc, notification_api_controller::NotificationApiDoc,
+        pipeline_api_controller::PipelineApiDoc, quote_api_controller::QuoteApiDoc,
+        school_api_controller::SchoolApiDoc, scoring_api_controller::ScoringApiDoc,
+        segment_api_controller::SegmentApiDoc, service_api_controller::ServiceApiDoc,
+        session_api_controller::SessionApiDoc, tag_api_controller::TagApiDoc,
+        territory_api_controller::TerritoryApiDoc, users_api_controller::UserApiDoc,
+        web_lead_api_controller::WebLeadApiDoc,
+    },
+    error::{
+        account_error::AccountError, api_error::ApiError, api_token_error::ApiTokenError,
+        attachment_error::AttachmentError, campaign_error::CampaignError,
+        clinical_error::ClinicalError, email_template_error::EmailTemplateError,
+        enterprise_error::EnterpriseError, lead_error::LeadError, meeting_error::MeetingError,
+        notification_error::NotificationError, pipeline_error::PipelineError,
+        quote_error::QuoteError, school_error::SchoolError, scoring_error::ScoringError,
+        segment_error::SegmentError, service_error::ServiceError, session_error::SessionError,
+        tag_error::TagError, territory_error::TerritoryError, user_error::UserError,
+        web_lead_error::WebLeadError,
+    },
+};
+
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "Yay CRM API",
+        description = "JSON API for leads, pipeline, quotes and CRM administration"
+    ),
+    servers((url = "/api/v1")),
+    components(schemas(
+        ApiError,
+        AccountError,
+        ApiTokenError,
+        AttachmentError,
+        CampaignError,
+        ClinicalError,
+        EmailTemplateError,
+        EnterpriseError,
+        LeadError,
+        MeetingError,
+        NotificationError,
+        PipelineError,
+        QuoteError,
+        SchoolError,
+        ScoringError,
+        SegmentError,
+        ServiceError,
+        SessionError,
+        TagError,
+        TerritoryError,
+        UserError,
+        WebLeadError
+    ))
+)]
+struct ApiDoc;
+
+pub fn get_openapi() -> utoipa::openapi::OpenApi {
+    let mut openapi = ApiDoc::openapi();
+
+    for doc in [
+        AccountApiDoc::openapi(),
+        ApiTokenApiDoc::openapi(),
+        AttachmentApiDoc::openapi(),
+        CampaignApiDoc::openapi(),
+        ClinicalApiDoc::openapi(),
+        ContactApiDoc::openapi(),
+        EmailTemplateApiDoc::openapi(),
+        EnterpriseApiDoc::openapi(),
+        LeadApiDoc::openapi(),
+        MeetingApiDoc::openapi(),
+        NotificationApiDoc::openapi(),
+        PipelineApiDoc::openapi(),
+        QuoteApiDoc::openapi(),
+        SchoolApiDoc::openapi(),
+        ScoringApiDoc::openapi(),
+        SegmentApiDoc::openapi(),
+        ServiceApiDoc::openapi(),
+        SessionApiDoc::openapi(),
+        TagApiDoc::openapi(),
+        TerritoryApiDoc::openapi(),
+        UserApiDoc::openapi(),
+        WebLeadApiDoc::openapi(),
+    ] {
+        openapi.merge(doc);
+    }
+
+    // Browsers authenticate with the session cookie, so bearer auth is optional.
+    if let Some(components) = openapi.components.as_mut() {
+        components.add_security_scheme(
+            "bearer_token",
+            SecurityScheme::Http(HttpBuilder::new().scheme(HttpAuthScheme::Bearer).build()),
+        );
+    }
+    openapi.security = Some(vec![
+        SecurityRequirement::default(),
+        SecurityRequirement::new("bearer_token", Vec::<String>::new()),
+    ]);
+
+    openapi
+}
+
+pub fn openapi_controllers(cfg: &mut ServiceConfig) {
+    cfg.service(SwaggerUi::new("/api/docs/{_:.*}").url("/api/openapi.json", get_openapi()));
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use regex::Regex;
+    use utoipa::openapi::PathItem;
+
+    use super::get_openapi;
+
+    fn has_method(item: &PathItem, method: &str) -> bool {
+        match method {
+            "get" => item.get.is_some(),
+            "post" => item.post.is_some(),
+            "patch" => item.patch.is_some(),
+            "put" => item.put.is_some(),
+            "delete" => item.delete.is_some(),
+            _ => false,
+        }
+    }
+
+    #[test]
+    fn every_api_route_is_documented() {
+        let route_regex = Regex::new(r#"#\[(get|post|patch|put|delete)\("([^"]+)"\)\]"#).unwrap();
+        let openapi = get_openapi();
+        let mut missing: Vec<String> = vec![];
+        let mut routes = 0;
+
+        for entry in fs::read_dir("src/controllers").unwrap() {
+            let path = entry.unwrap().path();
+            let is_api_controller = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.ends_with("_api_controller.rs"));
+            if !is_api_controller {
+                continue;
+            }
+
+            let source = fs::read_to_string(&path).unwrap();
+            for captures in route_regex.captures_iter(&source) {
+                routes += 1;
+                let (method, route) = (&captures[1], &captures[2]);
+                let documented = openapi
+                    .paths
+                    .paths
+                    .get(route)
+                    .is_some_and(|item| has_method(item, method));
+                if !documented {
+                    missing.push(format!("{} {}", method.to_uppercase(), route));
+                }
+            }
+        }
+
+        assert!(routes > 0, "No API routes found in src/controllers");
+        assert!(
+            missing.is_empty(),
+            "Routes missing from OpenAPI: {:?}",
+            missing
+        );
+    }
+}
@@ -0,0 +1,362 @@
+use actix_web::{
+    delete, get,
+    http::StatusCode,
+    patch, post,
+    web::{Data, Json, Path, Query, ServiceConfig},
+    HttpResponse,
+};
+use chrono::Utc;
+use log::error;
+use utoipa::OpenApi;
+use validator::Validate;
+
+use crate::{
+    db::{config::Database, lead_db::LeadDB, pipeline_db::PipelineDB},
+    error::{api_error::ApiError, pipeline_error::PipelineError},
+    models::{
+        locale_model::Locale,
+        pipeline_model::{PipelineLeadType, PipelineStage, PipelineStageUuid},
+        users_model::ActingUser,
+    },
+    utils::{
+        general_utils::{get_uuid, shuffle_id},
+        i18n_utils::RequestLocale,
+        lead_utils::get_lead_table,
+        message_utils::get_localized_validation_errors,
+        pipeline_utils::{find_stage, get_pipeline, PROSPECT_STAGE, QUOTE_STAGE},
+        role_utils::is_admin,
+    },
+};
+
+fn forbidden_response() -> HttpResponse {
+    HttpResponse::Forbidden()
+        .insert_header((
+            "HX-Trigger",
+            format!(
+                "{{ \"page_error\": {:?} }}",
+                "Solo un ADMIN puede editar las etapas de venta".to_string()
+            ),
+        ))
+        .json(ApiError::new(PipelineError::PipelineStageForbidden))
+}
+
+fn invalid_stage_response(key_errors_vec: Vec<String>) -> HttpResponse {
+    HttpResponse::BadRequest()
+        .insert_header((
+            "HX-Trigger",
+            format!("{{ \"page_error\": {:?} }}", key_errors_vec),
+        ))
+        .json(
+            ApiError::new(PipelineError::PipelineStageCreationFailure)
+                .with_field_errors(key_errors_vec),
+        )
+}
+
+fn stage_in_use_response(message: String) -> HttpResponse {
+    HttpResponse::Conflict()
+        .insert_header(("HX-Trigger", format!("{{ \"page_error\": {:?} }}", message)))
+        .json(ApiError::new(PipelineError::PipelineStageInUse))
+}
+
+// New leads start in PROSPECT and quotes move them to QUOTE, so those stages always stay.
+async fn get_stage_in_use_error(db: &Data<Database>, stage: &PipelineStage) -> Option<String> {
+    if stage.code == PROSPECT_STAGE || stage.code == QUOTE_STAGE {
+        return Some(format!("La etapa {} no se puede borrar", stage.name));
+    }
+
+    let table_name = get_lead_table(&stage.lead_type);
+    match Database::count_in_stage(db, table_name, stage.code.clone()).await {
+        Some(0) => None,
+        Some(total) => Some(format!(
+            "Hay {} registros en la etapa {}, muévelos antes de borrarla",
+            total, stage.name
+        )),
+        None => Some(format!(
+            "No se pudo revisar si la etapa {} tiene registros",
+            stage.name
+        )),
+    }
+}
+
+fn get_stage_errors(stage: &PipelineStage, locale: &Locale) -> Vec<String> {
+    let mut key_errors_vec: Vec<String> = match stage.validate() {
+        Ok(_) => vec![],
+        Err(e) => get_localized_validation_errors(&e, locale),
+    };
+
+    if stage.is_won && stage.is_lost {
+        key_errors_vec.push("A stage can't be both won and lost".to_string());
+    }
+
+    key_errors_vec
+}
+
+#[utoipa::path(
+    tag = "Pipeline",
+    operation_id = "find_all_pipeline_stages",
+    params(PipelineLeadType),
+    responses(
+        (status = 200, description = "All pipeline_stages", body = Vec<PipelineStage>),
+        (status = 404, description = "No pipeline_stages found", body = ApiError),
+    )
+)]
+#[get("/pipeline_stages")]
+#[tracing::instrument(name = "Show Pipeline Stages", skip(db))]
+async fn find_all(
+    db: Data<Database>,
+    filter: Query<PipelineLeadType>,
+) -> Result<HttpResponse, PipelineError> {
+    let stages = match filter.into_inner().lead_type {
+        Some(lead_type) => Database::find_by_lead_type(&db, lead_type).await,
+        None => Database::find_all_active(&db).await,
+    };
+
+    match stages {
+        Some(found_stages) => Ok(HttpResponse::Ok().status(StatusCode::OK).json(found_stages)),
+        None => {
+            error!("Unable to find any pipeline stage");
+            Ok(HttpResponse::NotFound().json(ApiError::new(PipelineError::NoPipelineStagesFound)))
+        }
+    }
+}
+
+#[utoipa::path(
+    tag = "Pipeline",
+    operation_id = "find_one_pipeline_stage",
+    params(PipelineStageUuid),
+    responses(
+        (status = 200, description = "Pipeline_stage found", body = PipelineStage),
+        (status = 404, description = "Pipeline_stage not found", body = ApiError),
+    )
+)]
+#[get("/pipeline_stages/{uuid}")]
+#[tracing::instrument(name = "Get One Pipeline Stage", skip(db), fields(uuid = %uuid.uuid))]
+async fn find_one(
+    db: Data<Database>,
+    uuid: Path<PipelineStageUuid>,
+) -> Result<HttpResponse, PipelineError> {
+    let stage_uuid = uuid.into_inner().uuid;
+
+    match Database::find_one(&db, stage_uuid.clone()).await {
+        Some(stage) => Ok(HttpResponse::Ok().status(StatusCode::OK).json(stage)),
+        None => {
+            error!("No pipeline stage found for UUID:: {:?}", &stage_uuid);
+            Ok(HttpResponse::NotFound().json(ApiError::new(PipelineError::NoPipelineStagesFound)))
+        }
+    }
+}
+
+#[utoipa::path(
+    tag = "Pipeline",
+    operation_id = "create_pipeline_stage",
+    responses(
+        (status = 201, description = "Pipeline_stage created", body = PipelineStageUuid),
+        (status = 400, description = "Invalid pipeline_stage", body = ApiError),
+        (status = 403, description = "Only an ADMIN can manage pipeline_stages", body = ApiError),
+        (status = 500, description = "Pipeline_stage couldn't be saved", body = ApiError),
+    )
+)]
+#[post("/pipeline_stages")]
+#[tracing::instrument(
+    name = "Post Pipeline Stage",
+    skip(db),
+    fields(lead_type = %body.lead_type, code = %body.code)
+)]
+async fn create(
+    db: Data<Database>,
+    mut body: Json<PipelineStage>,
+    acting_user: ActingUser,
+    locale: RequestLocale,
+) -> Result<HttpResponse, PipelineError> {
+    body.created_by = acting_user.user_uuid.clone();
+    if !is_admin(&db, body.created_by.clone()).await {
+        return Ok(forbidden_response());
+    }
+
+    let key_errors_vec = get_stage_errors(&body, &locale.0);
+    if !key_errors_vec.is_empty() {
+        error!("Error pipeline_stage.create {:?}", key_errors_vec);
+        return Ok(invalid_stage_response(key_errors_vec));
+    }
+
+    let date_created = Utc::now();
+    let new_stage = PipelineStage::new(get_uuid(), body.into_inner());
+
+    let stages = get_pipeline(&db, &new_stage.lead_type).await;
+    if find_stage(&stages, &new_stage.code).is_some() {
+        error!("Duplicated stage code {:?}", &new_stage.code);
+        return Ok(invalid_stage_response(vec![format!(
+            "Stage {} already exists for {}",
+            &new_stage.code, &new_stage.lead_type
+        )]));
+    }
+
+    let my_stage = PipelineStage {
+        deleted: false,
+        date_created: Some(date_created),
+        date_modified: Some(date_created),
+        modified_by: new_stage.created_by.clone(),
+        ..new_stage
+    };
+
+    match Database::add_one(&db, my_stage).await {
+        Some(stage) => Ok(HttpResponse::Ok()
+            .insert_header(("HX-Trigger", "pipeline_reload_page"))
+            .status(StatusCode::CREATED)
+            .json(PipelineStageUuid {
+                uuid: match stage.uuid {
+                    Some(this_uuid) => shuffle_id(this_uuid),
+                    None => "".to_string(),
+                },
+            })),
+        None => {
+            error!("Error [POST] /pipeline_stages");
+            Ok(HttpResponse::InternalServerError()
+                .insert_header((
+                    "HX-Trigger",
+                    "{ \"page_error\": \"Internal server error\" }".to_string(),
+                ))
+                .json(ApiError::new(PipelineError::PipelineStageCreationFailure)))
+        }
+    }
+}
+
+#[utoipa::path(
+    tag = "Pipeline",
+    operation_id = "update_pipeline_stage",
+    responses(
+        (status = 200, description = "Pipeline_stage updated", body = PipelineStageUuid),
+        (status = 400, description = "Invalid pipeline_stage", body = ApiError),
+        (status = 403, description = "Only an ADMIN can manage pipeline_stages", body = ApiError),
+        (status = 404, description = "Pipeline_stage not found", body = ApiError),
+        (status = 500, description = "Pipeline_stage couldn't be saved", body = ApiError),
+    )
+)]
+#[patch("/pipeline_stages")]
+#[tracing::instrument(
+    name = "Patch Pipeline Stage",
+    skip(db),
+    fields(uuid = body.uuid, code = %body.code)
+)]
+async fn update_one(
+    db: Data<Database>,
+    mut body: Json<PipelineStage>,
+    acting_user: ActingUser,
+    locale: RequestLocale,
+) -> Result<HttpResponse, PipelineError> {
+    body.modified_by = acting_user.user_uuid.clone();
+    if !is_admin(&db, body.modified_by.clone()).await {
+        return Ok(forbidden_response());
+    }
+
+    let key_errors_vec = get_stage_errors(&body, &locale.0);
+    if !key_errors_vec.is_empty() {
+        error!("Error in pipeline_stage.update_one: {:?}", key_errors_vec);
+        return Ok(invalid_stage_response(key_errors_vec));
+    }
+
+    let uuid_in_db = match body.uuid.clone() {
+        Some(s_id) => s_id,
+        None => String::from("forbidden"),
+    };
+
+    let stored_stage = match Database::find_one(&db, uuid_in_db.clone()).await {
+        Some(stage) => stage,
+        None => {
+            error!("No pipeline stage found for UUID:: {:?}", &uuid_in_db);
+            return Ok(
+                HttpResponse::NotFound().json(ApiError::new(PipelineError::NoPipelineStagesFound))
+            );
+        }
+    };
+
+    let stage_to_update = PipelineStage {
+        lead_type: stored_stage.lead_type,
+        code: stored_stage.code,
+        date_created: stored_stage.date_created,
+        created_by: stored_stage.created_by,
+        date_modified: Some(Utc::now()),
+        ..body.into_inner()
+    };
+
+    match Database::update_one(&db, stage_to_update).await {
+        Some(stage) => Ok(HttpResponse::Ok()
+            .insert_header(("HX-Trigger", "pipeline_reload_page"))
+            .status(StatusCode::OK)
+            .json(PipelineStageUuid {
+                uuid: match stage.uuid {
+                    Some(this_uuid) => shuffle_id(this_uuid),
+                    None => "".to_string(),
+                },
+            })),
+        None => {
+            error!("Error in pipeline_stage.update_one");
+            Ok(HttpResponse::InternalServerError()
+                .insert_header((
+                    "HX-Trigger",
+                    format!(
+                        "{{ \"page_error\": {:?} }}",
+                        "Couldn't save pipeline stage".to_string()
+                    ),
+                ))
+                .json(ApiError::new(PipelineError::PipelineStageCreationFailure)))
+        }
+    }
+}
+
+#[utoipa::path(
+    tag = "Pipeline",
+    operation_id = "delete_pipeline_stage",
+    params(PipelineStageUuid),
+    responses(
+        (status = 200, description = "Pipeline_stage deleted", body = PipelineStageUuid),
+        (status = 403, description = "Only an ADMIN can manage pipeline_stages", body = ApiError),
+        (status = 404, description = "Pipeline_stage not found", body = ApiError),
+        (status = 409, description = "Pipeline_stage still has leads or is required", body = ApiError),
+    )
+)]
+#[delete("/pipeline_stages/{uuid}")]
+#[tracing::instrument(name = "Delete Pipeline Stage", skip(db), fields(uuid = %uuid.uuid))]
+async fn delete_one(
+    db: Data<Database>,
+    uuid: Path<PipelineStageUuid>,
+    acting_user: ActingUser,
+) -> Result<HttpResponse, PipelineError> {
+    if !is_admin(&db, acting_user.user_uuid).await {
+        return Ok(forbidden_response());
+    }
+
+    let stage_uuid = uuid.into_inner().uuid;
+
+    if let Some(stage) = Database::find_one(&db, stage_uuid.clone()).await {
+        if let Some(message) = get_stage_in_use_error(&db, &stage).await {
+            error!("Pipeline stage {} is in use:: {}", &stage_uuid, &message);
+            return Ok(stage_in_use_response(message));
+        }
+    }
+
+    match Database::delete_one(&db, stage_uuid.clone()).await {
+        Some(_) => Ok(HttpResponse::Ok()
+            .insert_header(("HX-Trigger", "pipeline_reload_page"))
+            .status(StatusCode::OK)
+            .json(PipelineStageUuid {
+                uuid: shuffle_id(stage_uuid),
+            })),
+        None => {
+            error!("Unable to delete pipeline stage:: {:?}", &stage_uuid);
+            Ok(HttpResponse::NotFound().json(ApiError::new(PipelineError::NoPipelineStagesFound)))
+        }
+    }
+}
+
+#[derive(OpenApi)]
+#[openapi(paths(find_all, find_one, create, update_one, delete_one))]
+pub struct PipelineApiDoc;
+
+pub fn pipeline_api_controllers(cfg: &mut ServiceConfig) {
+    cfg.service(create);
+    cfg.service(delete_one);
+    cfg.service(find_all);
+    cfg.service(find_one);
+    cfg.service(update_one);
+}
@@ -0,0 +1,177 @@
+use actix_web::{
+    web::{post, Data, Path, ServiceConfig},
+    HttpRequest, HttpResponse,
+};
+use handlebars::{Handlebars, RenderError};
+use log::{error, info};
+use serde_json::json;
+
+use crate::{
+    db::{config::Database, pipeline_db::PipelineDB},
+    models::pipeline_model::*,
+    utils::{
+        env::{set_env_vars, ConfVars},
+        fs_utils::read_hbs_template,
+        general_utils::{create_lead_type_tags, get_lead_type_tags},
+    },
+};
+
+async fn pipeline_table(db: Data<Database>) -> Result<String, RenderError> {
+    let template_path = "pipeline_table";
+    let handlebars = Handlebars::new();
+
+    let stages_from_db = Database::find_all_active(&db).await;
+
+    let template_contents = match read_hbs_template(template_path) {
+        Ok(contents) => contents,
+        Err(e) => {
+            error!("Failed to render contents for pipeline table:: {}", e);
+            PipelineHandlebarsError::new(e.to_string()).error
+        }
+    };
+
+    let cf: ConfVars = set_env_vars();
+
+    match stages_from_db {
+        Some(stages) => {
+            let data = json!({"conf": cf, "stages": stages});
+            let render = handlebars.render_template(&template_contents, &data)?;
+            Ok(render)
+        }
+        None => {
+            let data = json!({"conf": cf, "error": "Unable to fetch pipeline stages"});
+            let render_error = handlebars.render_template(&template_contents, &data)?;
+            Ok(render_error)
+        }
+    }
+}
+
+async fn pipeline_new() -> Result<String, RenderError> {
+    let handlebars = Handlebars::new();
+    let template_path = "pipeline_new";
+
+    let template_contents = match read_hbs_template(template_path) {
+        Ok(contents) => contents,
+        Err(e) => {
+            error!("Couldn't render file for new pipeline stage:: {}", e);
+            PipelineHandlebarsError::new(e.to_string()).error
+        }
+    };
+
+    let cf: ConfVars = set_env_vars();
+    let data = json!({"conf": cf, "lead_types": get_lead_type_tags()});
+
+    let handlebars_render = handlebars.render_template(&template_contents, &data)?;
+    Ok(handlebars_render)
+}
+
+async fn pipeline_edit(hbs_path: Path<String>, db: Data<Database>) -> Result<String, RenderError> {
+    let uuid = hbs_path.into_inner();
+    let my_error = format!("Unable to find uuid {}", &uuid);
+    info!("Edit pipeline stage screen for uuid:: {}", &uuid);
+
+    let handlebars = Handlebars::new();
+    let template_path = "pipeline_edit";
+
+    let stage_from_db: Result<PipelineStage, PipelineHandlebarsError> =
+        match Database::find_one(&db, uuid).await {
+            Some(stage) => Ok(stage),
+            None => {
+                error!("No pipeline stage found in db");
+                Err(PipelineHandlebarsError::new(my_error))
+            }
+        };
+
+    let template_contents = match read_hbs_template(template_path) {
+        Ok(contents) => contents,
+        Err(e) => {
+            error!("Failed to render contents for edit pipeline stage:: {}", e);
+            PipelineHandlebarsError::new(e.to_string()).error
+        }
+    };
+
+    match stage_from_db {
+        Ok(stage) => {
+            let cf: ConfVars = set_env_vars();
+            let data = json!({
+              "conf": cf,
+              "lead_types": create_lead_type_tags(stage.lead_type.clone()),
+              "p": stage,
+            });
+
+            let render = handlebars.render_template(&template_contents, &data)?;
+            Ok(render)
+        }
+        Err(e) => {
+            let render_error = handlebars.render_template(&template_contents, &e)?;
+            Ok(render_error)
+        }
+    }
+}
+
+pub fn pipeline_html_controllers(cfg: &mut ServiceConfig) {
+    cfg.route(
+      "/htmx/pipeline/table",
+      post().to(
+        |db: Data<Database>| async move {
+          let my_pipeline_table = pipeline_table(db).await;
+
+          match my_pipeline_table {
+            Ok(pt) => HttpResponse::Ok()
+              .content_type("text/html")
+              .append_header(("HX-Trigger", "activate_navbar_element"))
+              .body(pt),
+            Err(e) => HttpResponse::Ok()
+              .content_type("text/html")
+              .append_header(("HX-Trigger", "error_pipeline_table"))
+              .body(
+                format!("<span class=\"icon is-small is-left\"><i class=\"fas fa-ban\"></i>Failed to load pipeline: {}</span>",
+                e)
+              )
+          }
+        }
+      ),
+    );
+
+    cfg.route(
+        "/htmx/pipeline/new",
+        post().to(|| async move {
+            let new_stage_editor = pipeline_new().await;
+
+            match new_stage_editor {
+              Ok(new_stage) => HttpResponse::Ok()
+                .content_type("text/html")
+                .body(new_stage),
+              Err(e) => HttpResponse::Ok()
+                .content_type("text/html")
+                .append_header(("HX-Trigger", "error_pipeline_table"))
+                .body(
+                  format!("<span class=\"icon is-small is-left\"><i class=\"fas fa-ban\"></i>Failed to load pipeline stage: {}</span>",
+                  e)
+                )
+            }
+        }),
+    );
+
+    cfg.route(
+      "/htmx/pipeline/edit/{uuid}",
+      post().to(
+          |_req: HttpRequest, hbs_path, db: Data<Database>| async move {
+              let stage_editor = pipeline_edit(hbs_path, db).await;
+              match stage_editor {
+                  Ok(se) => HttpResponse::Ok().content_type("text/html")
+                    .body(se),
+                  Err(e) => HttpResponse::Ok()
+                      .content_type("text/html")
+                      .append_header(("HX-Trigger", "error_pipeline_table"))
+                      .body(
+                        format!(
+                          "<span class=\"icon is-small is-left\"><i class=\"fas fa-ban\"></i>Failed to load pipeline stage: {}</span>",
+                          e
+                        )
+                      ),
+              }
+          },
+      ),
+    );
+}
@@ -0,0 +1,321 @@
+use actix_web::{
+    delete, get,
+    http::{
+        header::{ContentDisposition, DispositionParam, DispositionType},
+        StatusCode,
+    },
+    post,
+    web::{Data, Json, Path, ServiceConfig},
+    HttpResponse,
+};
+use chrono::{Duration, Utc};
+use log::{error, info};
+use utoipa::OpenApi;
+use validator::Validate;
+
+use crate::{
+    db::{config::Database, quote_db::QuoteDB},
+    error::{api_error::ApiError, quote_error::QuoteError},
+    models::{
+        quote_model::{Quote, QuoteUuid},
+        users_model::ActingUser,
+    },
+    utils::{
+        general_utils::{get_uuid, shuffle_id},
+        i18n_utils::RequestLocale,
+        lead_utils::{find_lead_summary, move_lead_to_stage},
+        message_utils::get_localized_validation_errors,
+        pipeline_utils::{find_stage, get_pipeline, QUOTE_STAGE},
+        quote_utils::{build_quote, get_quote_errors, render_quote_pdf},
+        service_utils::get_service_catalog,
+        time::RequestTimezone,
+    },
+};
+
+#[utoipa::path(
+    tag = "Quotes",
+    operation_id = "find_all_quotes",
+    responses(
+        (status = 200, description = "All quotes", body = Vec<Quote>),
+        (status = 404, description = "No quotes found", body = ApiError),
+    )
+)]
+#[get("/quotes")]
+#[tracing::instrument(name = "Show Quotes", skip(db))]
+async fn find_all(db: Data<Database>) -> Result<HttpResponse, QuoteError> {
+    match Database::find_all_active(&db).await {
+        Some(quotes) => Ok(HttpResponse::Ok().status(StatusCode::OK).json(quotes)),
+        None => {
+            error!("Unable to find any quote");
+            Ok(HttpResponse::NotFound().json(ApiError::new(QuoteError::NoQuotesFound)))
+        }
+    }
+}
+
+#[utoipa::path(
+    tag = "Quotes",
+    operation_id = "find_one_quote",
+    params(QuoteUuid),
+    responses(
+        (status = 200, description = "Quote found", body = Quote),
+        (status = 404, description = "Quote not found", body = ApiError),
+    )
+)]
+#[get("/quotes/{uuid}")]
+#[tracing::instrument(name = "Get One Quote", skip(db), fields(uuid = %uuid.uuid))]
+async fn find_one(db: Data<Database>, uuid: Path<QuoteUuid>) -> Result<HttpResponse, QuoteError> {
+    let quote_uuid = uuid.into_inner().uuid;
+
+    match Database::find_one(&db, quote_uuid.clone()).await {
+        Some(quote) => Ok(HttpResponse::Ok().status(StatusCode::OK).json(quote)),
+        None => {
+            error!("No quote found for UUID:: {:?}", &quote_uuid);
+            Ok(HttpResponse::NotFound().json(ApiError::new(QuoteError::NoQuotesFound)))
+        }
+    }
+}
+
+#[utoipa::path(
+    tag = "Quotes",
+    operation_id = "find_lead_quotes",
+    params(QuoteUuid),
+    responses(
+        (status = 200, description = "Lead quotes", body = Vec<Quote>),
+        (status = 404, description = "No quotes found", body = ApiError),
+    )
+)]
+#[get("/quotes/lead/{uuid}")]
+#[tracing::instrument(name = "Get Quotes For Lead", skip(db), fields(uuid = %uuid.uuid))]
+async fn find_by_lead(
+    db: Data<Database>,
+    uuid: Path<QuoteUuid>,
+) -> Result<HttpResponse, QuoteError> {
+    let lead_uuid = uuid.into_inner().uuid;
+
+    match Database::find_by_lead(&db, lead_uuid.clone()).await {
+        Some(quotes) => Ok(HttpResponse::Ok().status(StatusCode::OK).json(quotes)),
+        None => {
+            error!("No quotes found for lead:: {:?}", &lead_uuid);
+            Ok(HttpResponse::NotFound().json(ApiError::new(QuoteError::NoQuotesFound)))
+        }
+    }
+}
+
+#[utoipa::path(
+    tag = "Quotes",
+    operation_id = "find_one_quote_pdf",
+    params(QuoteUuid),
+    responses(
+        (status = 200, description = "Quote rendered as PDF", body = Vec<u8>, content_type = "application/pdf"),
+        (status = 404, description = "Quote not found", body = ApiError),
+    )
+)]
+#[get("/quotes/{uuid}/pdf")]
+#[tracing::instrument(name = "Get Quote PDF", skip(db), fields(uuid = %uuid.uuid))]
+async fn find_one_pdf(
+    db: Data<Database>,
+    uuid: Path<QuoteUuid>,
+    timezone: RequestTimezone,
+) -> Result<HttpResponse, QuoteError> {
+    let quote_uuid = uuid.into_inner().uuid;
+
+    match Database::find_one(&db, quote_uuid.clone()).await {
+        Some(quote) => {
+            let file_name = format!(
+                "{}.pdf",
+                quote.quote_number.clone().unwrap_or(quote_uuid.clone())
+            );
+
+            Ok(HttpResponse::Ok()
+                .content_type("application/pdf")
+                .insert_header(ContentDisposition {
+                    disposition: DispositionType::Inline,
+                    parameters: vec![DispositionParam::Filename(file_name)],
+                })
+                .body(render_quote_pdf(&quote, &timezone.0)))
+        }
+        None => {
+            error!("No quote found for UUID:: {:?}", &quote_uuid);
+            Ok(HttpResponse::NotFound().json(ApiError::new(QuoteError::NoQuotesFound)))
+        }
+    }
+}
+
+#[utoipa::path(
+    tag = "Quotes",
+    operation_id = "create_quote",
+    responses(
+        (status = 201, description = "Quote created", body = QuoteUuid),
+        (status = 400, description = "Invalid quote", body = ApiError),
+        (status = 404, description = "Lead not found", body = ApiError),
+        (status = 500, description = "Quote couldn't be saved", body = ApiError),
+    )
+)]
+#[post("/quotes")]
+#[tracing::instrument(
+    name = "Post Quote",
+    skip(db),
+    fields(lead_type = %body.lead_type, lead_uuid = %body.lead_uuid)
+)]
+async fn create(
+    db: Data<Database>,
+    mut body: Json<Quote>,
+    acting_user: ActingUser,
+    locale: RequestLocale,
+) -> Result<HttpResponse, QuoteError> {
+    body.created_by = acting_user.user_uuid.clone();
+    let services = get_service_catalog(&db).await;
+    let validation_errors: Vec<String> = std::iter::once(body.validate())
+        .chain(body.line_items.iter().map(|line_item| line_item.validate()))
+        .filter_map(|result| result.err())
+        .flat_map(|e| get_localized_validation_errors(&e, &locale.0))
+        .chain(get_quote_errors(&body, &services))
+        .collect();
+
+    if !validation_errors.is_empty() {
+        error!("Error quote.create {:?}", validation_errors);
+        let key_errors_vec: Vec<String> = validation_errors;
+        return Ok(HttpResponse::BadRequest()
+            .insert_header((
+                "HX-Trigger",
+                format!("{{ \"page_error\": {:?} }}", key_errors_vec),
+            ))
+            .json(
+                ApiError::new(QuoteError::QuoteCreationFailure).with_field_errors(key_errors_vec),
+            ));
+    }
+
+    let new_quote = body.into_inner();
+
+    let lead = match find_lead_summary(&db, &new_quote.lead_type, new_quote.lead_uuid.clone()).await
+    {
+        Some(lead) => lead,
+        None => {
+            error!("No lead found for quote:: {:?}", &new_quote.lead_uuid);
+            return Ok(HttpResponse::NotFound().json(ApiError::new(QuoteError::NoQuotesFound)));
+        }
+    };
+
+    let quote_number = match Database::next_quote_number(&db).await {
+        Some(number) => number,
+        None => {
+            error!("Unable to assign a quote number");
+            return Ok(HttpResponse::InternalServerError()
+                .json(ApiError::new(QuoteError::QuoteCreationFailure)));
+        }
+    };
+
+    let date_created = Utc::now();
+    let lead_stage = lead.sales_funnel.clone();
+
+    let my_quote = Quote {
+        lead: Some(lead),
+        deleted: false,
+        valid_until: match new_quote.valid_until {
+            Some(valid_until) => Some(valid_until),
+            None => Some(date_created + Duration::days(30)),
+        },
+        date_created: Some(date_created),
+        date_modified: Some(date_created),
+        modified_by: new_quote.created_by.clone(),
+        ..build_quote(new_quote, &services)
+    };
+
+    let stored_quote = Database::add_one(&db, Quote::new(get_uuid(), quote_number, my_quote)).await;
+
+    match stored_quote {
+        Some(quote) => {
+            let stages = get_pipeline(&db, &quote.lead_type).await;
+            let should_advance = quote.advance_funnel.unwrap_or(false)
+                && match (
+                    find_stage(&stages, &lead_stage),
+                    find_stage(&stages, QUOTE_STAGE),
+                ) {
+                    (Some(current_stage), Some(quote_stage)) => {
+                        !current_stage.is_won
+                            && !current_stage.is_lost
+                            && current_stage.position < quote_stage.position
+                    }
+                    _ => false,
+                };
+
+            if should_advance {
+                match move_lead_to_stage(
+                    &db,
+                    &quote.lead_type,
+                    quote.lead_uuid.clone(),
+                    QUOTE_STAGE.to_string(),
+                    quote.created_by.clone(),
+                )
+                .await
+                {
+                    Some(_) => info!("Lead {} advanced to {}", &quote.lead_uuid, QUOTE_STAGE),
+                    None => error!(
+                        "Unable to advance lead {} to {}",
+                        &quote.lead_uuid, QUOTE_STAGE
+                    ),
+                }
+            }
+
+            Ok(HttpResponse::Ok()
+                .insert_header(("HX-Trigger", "quote_reload_page"))
+                .status(StatusCode::CREATED)
+                .json(QuoteUuid {
+                    uuid: match quote.uuid {
+                        Some(this_uuid) => shuffle_id(this_uuid),
+                        None => "".to_string(),
+                    },
+                }))
+        }
+        None => {
+            error!("Error [POST] /quotes");
+            Ok(HttpResponse::InternalServerError()
+                .insert_header((
+                    "HX-Trigger",
+                    "{ \"page_error\": \"Internal server error\" }".to_string(),
+                ))
+                .json(ApiError::new(QuoteError::QuoteCreationFailure)))
+        }
+    }
+}
+
+#[utoipa::path(
+    tag = "Quotes",
+    operation_id = "delete_quote",
+    params(QuoteUuid),
+    responses(
+        (status = 200, description = "Quote deleted", body = QuoteUuid),
+        (status = 404, description = "Quote not found", body = ApiError),
+    )
+)]
+#[delete("/quotes/{uuid}")]
+#[tracing::instrument(name = "Delete Quote", skip(db), fields(uuid = %uuid.uuid))]
+async fn delete_one(db: Data<Database>, uuid: Path<QuoteUuid>) -> Result<HttpResponse, QuoteError> {
+    let quote_uuid = uuid.into_inner().uuid;
+
+    match Database::delete_one(&db, quote_uuid.clone()).await {
+        Some(_) => Ok(HttpResponse::Ok()
+            .insert_header(("HX-Trigger", "quote_reload_page"))
+            .status(StatusCode::OK)
+            .json(QuoteUuid {
+                uuid: shuffle_id(quote_uuid),
+            })),
+        None => {
+            error!("Unable to delete quote:: {:?}", &quote_uuid);
+            Ok(HttpResponse::NotFound().json(ApiError::new(QuoteError::NoQuotesFound)))
+        }
+    }
+}
+
+#[derive(OpenApi)]
+#[openapi(paths(find_all, find_one, find_by_lead, find_one_pdf, create, delete_one))]
+pub struct QuoteApiDoc;
+
+pub fn quote_api_controllers(cfg: &mut ServiceConfig) {
+    cfg.service(create);
+    cfg.service(delete_one);
+    cfg.service(find_all);
+    cfg.service(find_by_lead);
+    cfg.service(find_one);
+    cfg.service(find_one_pdf);
+}
@@ -0,0 +1,182 @@
+use actix_web::{
+    web::{get, post, Data, Path, ServiceConfig},
+    HttpResponse,
+};
+use chrono_tz::Tz;
+use handlebars::{Handlebars, RenderError};
+use log::{error, info};
+use serde_json::json;
+
+use crate::{
+    db::{config::Database, quote_db::QuoteDB},
+    models::{locale_model::Locale, quote_model::*, sales_model::LeadType},
+    utils::{
+        env::{set_env_vars, ConfVars},
+        fs_utils::read_hbs_template,
+        general_utils::create_option_tags_info_for_services_and_funnel,
+        i18n_utils::RequestLocale,
+        lead_utils::find_lead_summary,
+        quote_utils::{format_currency, get_quote_tax_percent},
+        service_utils::get_service_catalog,
+        time::{format_date_in_language, RequestTimezone},
+    },
+};
+
+handlebars_helper!(currency: |amount: f64| format_currency(amount));
+
+async fn quote_new(
+    hbs_path: Path<(LeadType, String)>,
+    db: Data<Database>,
+) -> Result<String, RenderError> {
+    let (lead_type, lead_uuid) = hbs_path.into_inner();
+    info!("Quote builder for lead:: {}", &lead_uuid);
+
+    let mut handlebars = Handlebars::new();
+    handlebars.register_helper("currency", Box::new(currency));
+    let template_path = "quote_new";
+
+    let template_contents = match read_hbs_template(template_path) {
+        Ok(contents) => contents,
+        Err(e) => {
+            error!("Couldn't render file for new quote:: {}", e);
+            QuoteHandlebarsError::new(e.to_string()).error
+        }
+    };
+
+    let cf: ConfVars = set_env_vars();
+
+    match find_lead_summary(&db, &lead_type, lead_uuid.clone()).await {
+        Some(lead) => {
+            let (services_tag, _) = create_option_tags_info_for_services_and_funnel(
+                get_service_catalog(&db).await,
+                vec![],
+                lead.services_offered.clone(),
+                lead.sales_funnel.clone(),
+            );
+            let quotes = Database::find_by_lead(&db, lead_uuid)
+                .await
+                .unwrap_or_default();
+
+            let data = json!({
+              "conf": cf,
+              "lead": lead,
+              "services_tag": services_tag,
+              "tax_percent": get_quote_tax_percent(),
+              "quotes": quotes,
+            });
+
+            let render = handlebars.render_template(&template_contents, &data)?;
+            Ok(render)
+        }
+        None => {
+            error!("No lead found for quote builder");
+            let data = json!({
+              "conf": cf,
+              "error": format!("Unable to find uuid {}", &lead_uuid),
+            });
+            let render_error = handlebars.render_template(&template_contents, &data)?;
+            Ok(render_error)
+        }
+    }
+}
+
+async fn quote_document(
+    hbs_path: Path<String>,
+    db: Data<Database>,
+    locale: Locale,
+    timezone: Tz,
+) -> Result<String, RenderError> {
+    let uuid = hbs_path.into_inner();
+
+    let mut handlebars = Handlebars::new();
+    handlebars.register_helper("currency", Box::new(currency));
+    let template_path = "quote_document";
+
+    let template_contents = match read_hbs_template(template_path) {
+        Ok(contents) => contents,
+        Err(e) => {
+            error!("Couldn't render file for quote document:: {}", e);
+            QuoteHandlebarsError::new(e.to_string()).error
+        }
+    };
+
+    let cf: ConfVars = set_env_vars();
+
+    match Database::find_one(&db, uuid.clone()).await {
+        Some(quote) => {
+            let date_created = match quote.date_created {
+                Some(this_date) => format_date_in_language(
+                    &this_date,
+                    &locale,
+                    &timezone,
+                ),
+                None => "".to_string(),
+            };
+            let valid_until = match quote.valid_until {
+                Some(this_date) => format_date_in_language(
+                    &this_date,
+                    &locale,
+                    &timezone,
+                ),
+                None => "".to_string(),
+            };
+
+            let data = json!({
+              "conf": cf,
+              "q": quote,
+              "date_created": date_created,
+              "valid_until": valid_until,
+            });
+
+            let render = handlebars.render_template(&template_contents, &data)?;
+            Ok(render)
+        }
+        None => {
+            error!("No quote found in db");
+            let data = json!({"conf": cf, "error": format!("Unable to find uuid {}", &uuid)});
+            let render_error = handlebars.render_template(&template_contents, &data)?;
+            Ok(render_error)
+        }
+    }
+}
+
+pub fn quote_html_controllers(cfg: &mut ServiceConfig) {
+    cfg.route(
+      "/htmx/quote/new/{lead_type}/{uuid}",
+      post().to(
+        |hbs_path, db: Data<Database>| async move {
+          let quote_builder = quote_new(hbs_path, db).await;
+
+          match quote_builder {
+            Ok(qb) => HttpResponse::Ok().content_type("text/html").body(qb),
+            Err(e) => HttpResponse::Ok()
+              .content_type("text/html")
+              .append_header(("HX-Trigger", "error_quote"))
+              .body(
+                format!("<span class=\"icon is-small is-left\"><i class=\"fas fa-ban\"></i>Failed to load quote: {}</span>",
+                e)
+              )
+          }
+        }
+      ),
+    );
+
+    cfg.route(
+      "/htmx/quote/document/{uuid}",
+      get().to(
+        |hbs_path, db: Data<Database>, locale: RequestLocale, timezone: RequestTimezone| async move {
+          let document = quote_document(hbs_path, db, locale.0, timezone.0).await;
+
+          match document {
+            Ok(d) => HttpResponse::Ok().content_type("text/html").body(d),
+            Err(e) => HttpResponse::Ok()
+              .content_type("text/html")
+              .body(
+                format!("<span class=\"icon is-small is-left\"><i class=\"fas fa-ban\"></i>Failed to load quote: {}</span>",
+                e)
+              )
+          }
+        }
+      ),
+    );
+}
@@ -2,27 +2,65 @@ use actix_web::{
     delete, get,
     http::StatusCode,
     patch, post,
-    web::{Data, Json, Path, ServiceConfig},
+    web::{Data, Json, Path, Query, ServiceConfig},
     HttpResponse,
 };
-use chrono::Local;
+use chrono::Utc;
 use log::error;
+use utoipa::OpenApi;
 use validator::Validate;
 
 use crate::{
     db::{config::Database, school_db::SchoolDB},
-    error::school_error::SchoolError,
-    models::school_model::{School, SchoolUuid},
+    error::{api_error::ApiError, school_error::SchoolError},
+    models::{
+        lead_model::LeadFilter,
+        sales_model::LeadType,
+        school_model::{School, SchoolUuid},
+        users_model::ActingUser,
+    },
     utils::{
+        address_utils::normalize_address,
+        contact_utils::{school_person, sync_primary_contact},
         general_utils::{get_uuid, shuffle_id},
-        message_utils::get_validation_errors,
+        i18n_utils::RequestLocale,
+        lead_utils::school_summary,
+        message_utils::get_localized_validation_errors,
+        notification_utils::notify_lead_changes,
+        phone_utils::normalize_required_phone,
+        pipeline_utils::check_lead_stage,
+        scoring_utils::{compute_lead_score, school_facts},
+        social_utils::{normalize_optional_social_profile, SocialNetwork},
+        tag_utils::{normalize_tags, register_tags},
+        territory_utils::{assign_lead_owner, is_lead_in_scope, scope_leads},
     },
 };
 
+#[utoipa::path(
+    tag = "Schools",
+    operation_id = "find_all_schools",
+    params(LeadFilter),
+    responses(
+        (status = 200, description = "Active schools in the acting user's scope", body = Vec<School>),
+        (status = 404, description = "No schools found", body = ApiError),
+    )
+)]
 #[get("/schools")]
 #[tracing::instrument(name = "Find all schools", skip(db))]
-async fn find_all(db: Data<Database>) -> Result<HttpResponse, SchoolError> {
-    let school = Database::find_all_active(&db).await;
+async fn find_all(
+    db: Data<Database>,
+    filter: Query<LeadFilter>,
+    acting_user: ActingUser,
+) -> Result<HttpResponse, SchoolError> {
+    let school = match Database::find_filtered(&db, &filter).await {
+        Some(leads) => Some(
+            scope_leads(&db, acting_user.user_uuid, leads, |lead: &School| {
+                &lead.address
+            })
+            .await,
+        ),
+        None => None,
+    };
 
     match school {
         Some(schools_found) => Ok(HttpResponse::Ok()
@@ -30,30 +68,53 @@ async fn find_all(db: Data<Database>) -> Result<HttpResponse, SchoolError> {
             .json(schools_found)),
         None => {
             error!("Didn't find any School data");
-            Ok(HttpResponse::NotFound().json(SchoolUuid {
-                uuid: format!("{}", SchoolError::NoSchoolsFound),
-            }))
+            Ok(HttpResponse::NotFound().json(ApiError::new(SchoolError::NoSchoolsFound)))
         }
     }
 }
 
+#[utoipa::path(
+    tag = "Schools",
+    operation_id = "find_one_school",
+    params(SchoolUuid),
+    responses(
+        (status = 200, description = "School found", body = School),
+        (status = 404, description = "School not found", body = ApiError),
+    )
+)]
 #[get("/schools/{uuid}")]
-#[tracing::instrument(name = "Find one school", skip(db), fields(uuid = %uuid.uuid))]
-async fn find_one(db: Data<Database>, uuid: Path<SchoolUuid>) -> Result<HttpResponse, SchoolError> {
+#[tracing::instrument(name = "Find one school", skip(db, acting_user), fields(uuid = %uuid.uuid))]
+async fn find_one(
+    db: Data<Database>,
+    uuid: Path<SchoolUuid>,
+    acting_user: ActingUser,
+) -> Result<HttpResponse, SchoolError> {
     let school_uuid = uuid.into_inner().uuid;
-    let school_result = Database::find_one(&db, school_uuid.clone()).await;
+    let school_result = match Database::find_one(&db, school_uuid.clone()).await {
+        Some(lead) if is_lead_in_scope(&db, acting_user.user_uuid, &lead.address).await => {
+            Some(lead)
+        }
+        _ => None,
+    };
 
     match school_result {
         Some(result) => Ok(HttpResponse::Ok().status(StatusCode::OK).json(result)),
         None => {
             error!("No schools found for id:: {:?}", &school_uuid);
-            Ok(HttpResponse::NotFound().json(SchoolUuid {
-                uuid: format!("{}", SchoolError::NoSchoolsFound),
-            }))
+            Ok(HttpResponse::NotFound().json(ApiError::new(SchoolError::NoSchoolsFound)))
         }
     }
 }
 
+#[utoipa::path(
+    tag = "Schools",
+    operation_id = "create_school",
+    responses(
+        (status = 201, description = "School created", body = SchoolUuid),
+        (status = 400, description = "Invalid stage", body = ApiError),
+        (status = 500, description = "Invalid or unsaved school", body = ApiError),
+    )
+)]
 #[post("/schools")]
 #[tracing::instrument(
     name = "Find one school",
@@ -64,22 +125,81 @@ async fn find_one(db: Data<Database>, uuid: Path<SchoolUuid>) -> Result<HttpResp
         school_name = %body.school_name,
     )
 )]
-async fn create(db: Data<Database>, body: Json<School>) -> Result<HttpResponse, SchoolError> {
+async fn create(
+    db: Data<Database>,
+    mut body: Json<School>,
+    acting_user: ActingUser,
+    locale: RequestLocale,
+) -> Result<HttpResponse, SchoolError> {
+    body.created_by = acting_user.user_uuid.clone();
     let is_valid = body.validate();
-    let date_created = Local::now();
+
+    if let Some(response) = check_lead_stage(
+        &db,
+        &LeadType::SCHOOL,
+        &body.sales_funnel,
+        SchoolError::InvalidStage,
+    )
+    .await
+    {
+        return Ok(response);
+    }
+
+    let date_created = Utc::now();
 
     let mut new_school = body.into_inner();
 
     new_school.date_created = Some(date_created.clone());
     new_school.date_modified = Some(date_created.clone());
     new_school.first_contact_date = Some(date_created.clone());
+    new_school.tags = normalize_tags(new_school.tags);
+    new_school.phone = normalize_required_phone(new_school.phone);
+    new_school.fb = normalize_optional_social_profile(SocialNetwork::Facebook, new_school.fb);
+    new_school.instagram =
+        normalize_optional_social_profile(SocialNetwork::Instagram, new_school.instagram);
+    new_school.linked_in =
+        normalize_optional_social_profile(SocialNetwork::LinkedIn, new_school.linked_in);
+    new_school.tik_tok =
+        normalize_optional_social_profile(SocialNetwork::TikTok, new_school.tik_tok);
+    new_school.twitter =
+        normalize_optional_social_profile(SocialNetwork::Twitter, new_school.twitter);
+    new_school.address = normalize_address(new_school.address);
+    new_school.account_uuid = None;
+    new_school.assigned_to = assign_lead_owner(
+        &db,
+        new_school.assigned_to.clone(),
+        &new_school.address,
+        new_school.created_by.clone(),
+    )
+    .await;
 
     match is_valid {
         Ok(_) => {
+            register_tags(&db, &new_school.tags, new_school.created_by.clone()).await;
+            new_school.score =
+                compute_lead_score(&db, &LeadType::SCHOOL, &school_facts(&new_school)).await;
             let new_uuid = get_uuid();
             let my_school =
                 Database::add_one(&db, School::new(String::from(new_uuid), new_school)).await;
 
+            if let Some(school) = &my_school {
+                sync_primary_contact(
+                    &db,
+                    &LeadType::SCHOOL,
+                    school_person(school),
+                    school.account_uuid.clone(),
+                    school.created_by.clone(),
+                )
+                .await;
+                notify_lead_changes(
+                    &db,
+                    None,
+                    &school_summary(school.clone()),
+                    school.created_by.clone(),
+                )
+                .await;
+            }
+
             match my_school {
                 Some(school_result) => Ok(HttpResponse::Ok()
                     .insert_header(("HX-Trigger", "school_reload_page"))
@@ -97,37 +217,63 @@ async fn create(db: Data<Database>, body: Json<School>) -> Result<HttpResponse,
                             "HX-Trigger",
                             format!("{{ \"page_error\": \"Internal server error\" }}"),
                         ))
-                        .json(SchoolUuid {
-                            uuid: "Error".to_string(),
-                        }))
+                        .json(ApiError::new(SchoolError::SchoolCreationFailure)))
                 }
             }
         }
         Err(e) => {
             error!("Error School.create {:?}", &e);
-            let key_errors_vec: Vec<String> = get_validation_errors(&e);
+            let key_errors_vec: Vec<String> = get_localized_validation_errors(&e, &locale.0);
 
             Ok(HttpResponse::InternalServerError()
                 .insert_header((
                     "HX-Trigger",
                     format!("{{ \"page_error\": {:?} }}", key_errors_vec),
                 ))
-                .json(SchoolUuid {
-                    uuid: format!("{}", SchoolError::SchoolCreationFailure),
-                }))
+                .json(
+                    ApiError::new(SchoolError::SchoolCreationFailure)
+                        .with_field_errors(key_errors_vec),
+                ))
         }
     }
 }
 
+#[utoipa::path(
+    tag = "Schools",
+    operation_id = "update_school",
+    responses(
+        (status = 200, description = "School updated", body = SchoolUuid),
+        (status = 404, description = "School not found", body = ApiError),
+        (status = 400, description = "Invalid stage", body = ApiError),
+        (status = 500, description = "Invalid or unsaved school", body = ApiError),
+    )
+)]
 #[patch("/schools")]
 #[tracing::instrument(name = "Patch School", skip(db), fields(
     name = %body.name,
     last_name = %body.last_name,
     school_name = %body.school_name,
 ))]
-async fn update_one(db: Data<Database>, body: Json<School>) -> Result<HttpResponse, SchoolError> {
+async fn update_one(
+    db: Data<Database>,
+    mut body: Json<School>,
+    acting_user: ActingUser,
+    locale: RequestLocale,
+) -> Result<HttpResponse, SchoolError> {
+    body.modified_by = acting_user.user_uuid.clone();
     let is_valid = body.validate();
 
+    if let Some(response) = check_lead_stage(
+        &db,
+        &LeadType::SCHOOL,
+        &body.sales_funnel,
+        SchoolError::InvalidStage,
+    )
+    .await
+    {
+        return Ok(response);
+    }
+
     match is_valid {
         Ok(_) => {
             let uuid_id_db = match body.uuid.clone() {
@@ -135,19 +281,30 @@ async fn update_one(db: Data<Database>, body: Json<School>) -> Result<HttpRespon
                 None => String::from(""),
             };
             let stored_school = Database::find_one(&db, uuid_id_db).await;
-            let date_modified = Local::now();
+            let stored_summary = stored_school.clone().map(school_summary);
+            let date_modified = Utc::now();
 
             let school_cloned = stored_school.clone();
+            let assigned_to = match &school_cloned {
+                Some(stored) => body.assigned_to.clone().or(stored.assigned_to.clone()),
+                None => body.assigned_to.clone(),
+            };
+            let account_uuid = school_cloned
+                .as_ref()
+                .and_then(|stored| stored.account_uuid.clone());
+            let source = body.source.clone().or(school_cloned
+                .as_ref()
+                .and_then(|stored| stored.source.clone()));
 
             let date_created = match stored_school {
                 Some(school) => school.date_created,
                 None => {
                     error!("No date found for uuid:: {:?}", body.uuid.clone());
-                    Some(Local::now())
+                    Some(Utc::now())
                 }
             };
 
-            let my_school = School {
+            let mut my_school = School {
                 uuid: body.uuid.clone(),
                 name: body.name.clone(),
                 last_name: body.last_name.clone(),
@@ -162,32 +319,35 @@ async fn update_one(db: Data<Database>, body: Json<School>) -> Result<HttpRespon
                     None => None,
                 },
                 email: body.email.clone(),
-                phone: body.phone.clone(),
+                phone: normalize_required_phone(body.phone.clone()),
                 deleted: body.deleted.clone(),
-                fb: match Some(&body.fb) {
-                    Some(fb_req) => fb_req.clone(),
-                    None => None,
-                },
-                instagram: match Some(&body.instagram) {
-                    Some(ig_req) => ig_req.clone(),
-                    None => None,
-                },
-                linked_in: match Some(&body.linked_in) {
-                    Some(li_req) => li_req.clone(),
-                    None => None,
-                },
-                tik_tok: match Some(&body.tik_tok) {
-                    Some(tt_req) => tt_req.clone(),
-                    None => None,
-                },
-                twitter: match Some(&body.twitter) {
-                    Some(tw_req) => tw_req.clone(),
-                    None => None,
-                },
+                fb: normalize_optional_social_profile(SocialNetwork::Facebook, body.fb.clone()),
+                instagram: normalize_optional_social_profile(
+                    SocialNetwork::Instagram,
+                    body.instagram.clone(),
+                ),
+                linked_in: normalize_optional_social_profile(
+                    SocialNetwork::LinkedIn,
+                    body.linked_in.clone(),
+                ),
+                tik_tok: normalize_optional_social_profile(
+                    SocialNetwork::TikTok,
+                    body.tik_tok.clone(),
+                ),
+                twitter: normalize_optional_social_profile(
+                    SocialNetwork::Twitter,
+                    body.twitter.clone(),
+                ),
                 first_contact_date: body.first_contact_date.clone(),
                 sales_funnel: body.sales_funnel.clone(),
                 notes: body.notes.clone(),
                 services_offered: body.services_offered.clone(),
+                tags: normalize_tags(body.tags.clone()),
+                assigned_to,
+                score: 0,
+                account_uuid,
+                address: normalize_address(body.address.clone()),
+                source,
                 date_created,
                 date_modified: Some(date_modified),
                 created_by: match school_cloned {
@@ -200,8 +360,30 @@ async fn update_one(db: Data<Database>, body: Json<School>) -> Result<HttpRespon
                 },
             };
 
+            register_tags(&db, &my_school.tags, my_school.modified_by.clone()).await;
+            my_school.score =
+                compute_lead_score(&db, &LeadType::SCHOOL, &school_facts(&my_school)).await;
+
             let updated_school = Database::update_one(&db, my_school).await;
 
+            if let Some(school) = &updated_school {
+                sync_primary_contact(
+                    &db,
+                    &LeadType::SCHOOL,
+                    school_person(school),
+                    school.account_uuid.clone(),
+                    school.modified_by.clone(),
+                )
+                .await;
+                notify_lead_changes(
+                    &db,
+                    stored_summary.as_ref(),
+                    &school_summary(school.clone()),
+                    school.modified_by.clone(),
+                )
+                .await;
+            }
+
             match updated_school {
                 Some(school) => Ok(HttpResponse::Ok()
                     .insert_header(("HX-Trigger", "school_reload_page"))
@@ -223,32 +405,50 @@ async fn update_one(db: Data<Database>, body: Json<School>) -> Result<HttpRespon
                                 "Couldn't find school".to_string()
                             ),
                         ))
-                        .json(SchoolUuid {
-                            uuid: format!("{}", SchoolError::NoSchoolsFound),
-                        }))
+                        .json(ApiError::new(SchoolError::NoSchoolsFound)))
                 }
             }
         }
         Err(e) => {
             error!("Error School.update_one {:?}", &e);
-            let key_errors_vec: Vec<String> = get_validation_errors(&e);
+            let key_errors_vec: Vec<String> = get_localized_validation_errors(&e, &locale.0);
 
             Ok(HttpResponse::InternalServerError()
                 .insert_header((
                     "HX-Trigger",
                     format!("{{ \"page_error\": {:?} }}", key_errors_vec),
                 ))
-                .json(SchoolUuid {
-                    uuid: format!("{}", SchoolError::SchoolCreationFailure),
-                }))
+                .json(
+                    ApiError::new(SchoolError::SchoolCreationFailure)
+                        .with_field_errors(key_errors_vec),
+                ))
         }
     }
 }
 
+#[utoipa::path(
+    tag = "Schools",
+    operation_id = "find_deleted_schools",
+    responses(
+        (status = 200, description = "Deleted schools", body = Vec<School>),
+        (status = 404, description = "No schools found", body = ApiError),
+    )
+)]
 #[get("/schools/deleted")]
-#[tracing::instrument(name = "Show Deleted Schools", skip(db))]
-async fn find_all_deleted(db: Data<Database>) -> Result<HttpResponse, SchoolError> {
-    let schools = Database::find_all_deleted(&db).await;
+#[tracing::instrument(name = "Show Deleted Schools", skip(db, acting_user))]
+async fn find_all_deleted(
+    db: Data<Database>,
+    acting_user: ActingUser,
+) -> Result<HttpResponse, SchoolError> {
+    let schools = match Database::find_all_deleted(&db).await {
+        Some(leads) => Some(
+            scope_leads(&db, acting_user.user_uuid, leads, |lead: &School| {
+                &lead.address
+            })
+            .await,
+        ),
+        None => None,
+    };
 
     match schools {
         Some(deleted_schools) => Ok(HttpResponse::Ok()
@@ -257,13 +457,21 @@ async fn find_all_deleted(db: Data<Database>) -> Result<HttpResponse, SchoolErro
             .json(deleted_schools)),
         None => {
             error!("Didn't find any deleted schools");
-            Ok(HttpResponse::NotFound().json(SchoolUuid {
-                uuid: "Error".to_string(),
-            }))
+            Err(SchoolError::NoSchoolsFound)
         }
     }
 }
 
+#[utoipa::path(
+    tag = "Schools",
+    operation_id = "delete_school",
+    params(SchoolUuid),
+    responses(
+        (status = 200, description = "School deleted", body = SchoolUuid),
+        (status = 404, description = "School not found", body = ApiError),
+        (status = 500, description = "School couldn't be deleted", body = ApiError),
+    )
+)]
 #[delete("/schools/{uuid}")]
 #[tracing::instrument(name = "Delete School", skip(db), fields(uuid = %uuid.uuid))]
 async fn delete_one(
@@ -285,21 +493,22 @@ async fn delete_one(
                     })),
                 None => {
                     error!("unable to delete school:: {:?}", &school_uuid);
-                    Ok(HttpResponse::InternalServerError().json(SchoolUuid {
-                        uuid: format!("{}", SchoolError::NoSchoolsFound),
-                    }))
+                    Ok(HttpResponse::InternalServerError()
+                        .json(ApiError::new(SchoolError::NoSchoolsFound)))
                 }
             }
         }
         None => {
             error!("Unable to update school :: {:?}", &school_uuid);
-            Ok(HttpResponse::NotFound().json(SchoolUuid {
-                uuid: format!("{}", SchoolError::NoSchoolsFound),
-            }))
+            Ok(HttpResponse::NotFound().json(ApiError::new(SchoolError::NoSchoolsFound)))
         }
     }
 }
 
+#[derive(OpenApi)]
+#[openapi(paths(find_all, find_one, create, update_one, find_all_deleted, delete_one))]
+pub struct SchoolApiDoc;
+
 pub fn school_api_controllers(cfg: &mut ServiceConfig) {
     cfg.service(create);
     cfg.service(delete_one);
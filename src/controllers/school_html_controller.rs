@@ -2,7 +2,8 @@ use actix_web::{
     web::{post, Data, Path, ServiceConfig},
     HttpRequest, HttpResponse,
 };
-use chrono::Local;
+use chrono::Utc;
+use chrono_tz::Tz;
 use handlebars::{Handlebars, RenderError};
 use log::{error, info};
 use serde_json::json;
@@ -10,8 +11,10 @@ use serde_json::json;
 use crate::{
     db::{config::Database, school_db::SchoolDB},
     models::{
-        sales_model::{GeneralTags, SchoolLevel},
+        locale_model::Locale,
+        sales_model::{GeneralTags, LeadType, SchoolLevel},
         school_model::*,
+        users_model::ActingUser,
     },
     utils::{
         env::{set_env_vars, ConfVars},
@@ -20,7 +23,11 @@ use crate::{
             create_option_tags_info_for_services_and_funnel, create_school_level_tags,
             get_options_and_services, get_school_level_tags,
         },
-        time::format_date_in_language,
+        i18n_utils::{register_i18n_helpers, RequestLocale},
+        pipeline_utils::get_pipeline,
+        service_utils::get_service_catalog,
+        territory_utils::{is_lead_in_scope, scope_leads},
+    time::{format_date_in_language, RequestTimezone},
     },
 };
 
@@ -30,20 +37,28 @@ handlebars_helper!(levels: |v1: Vec<SchoolLevel>| {
   json!(slt)
 });
 
-async fn school_edit(hbs_path: Path<String>, db: Data<Database>) -> Result<String, RenderError> {
+async fn school_edit(
+    hbs_path: Path<String>,
+    acting_user: ActingUser,
+    db: Data<Database>,
+    locale: Locale,
+) -> Result<String, RenderError> {
     let uuid = hbs_path.into_inner();
     let my_error = format!("Unable to find uuid {}", &uuid);
     info!("Edit user screen for uuid:: {}", &uuid);
 
     let mut handlebars = Handlebars::new();
     handlebars.register_helper("str_equal", Box::new(str_equal));
+    register_i18n_helpers(&mut handlebars);
 
     let mut template_path = "school_edit";
 
     let school_from_db: Result<School, SchoolHandlebarsError> =
         match Database::find_one(&db, uuid).await {
-            Some(school) => Ok(school),
-            None => {
+            Some(school) if is_lead_in_scope(&db, acting_user.user_uuid, &school.address).await => {
+                Ok(school)
+            }
+            _ => {
                 error!("No schools found in db");
                 template_path = "clinical_edit";
                 Err(SchoolHandlebarsError::new(my_error))
@@ -64,6 +79,8 @@ async fn school_edit(hbs_path: Path<String>, db: Data<Database>) -> Result<Strin
     match school_from_db {
         Ok(this_school) => {
             let (services_tag, funnel_tag) = create_option_tags_info_for_services_and_funnel(
+                get_service_catalog(&db).await,
+                get_pipeline(&db, &LeadType::SCHOOL).await,
                 this_school.services_offered.clone(),
                 this_school.sales_funnel.clone(),
             );
@@ -73,6 +90,7 @@ async fn school_edit(hbs_path: Path<String>, db: Data<Database>) -> Result<Strin
             let cf: ConfVars = set_env_vars();
             let data = json!({
               "conf": cf,
+              "locale": locale.code(),
               "services_tag": services_tag,
               "sales_funnel": funnel_tag,
               "school_level_tag": level_tag,
@@ -89,13 +107,27 @@ async fn school_edit(hbs_path: Path<String>, db: Data<Database>) -> Result<Strin
     }
 }
 
-async fn school_table(db: Data<Database>) -> Result<String, RenderError> {
+async fn school_table(
+    db: Data<Database>,
+    acting_user: ActingUser,
+    locale: Locale,
+    timezone: Tz,
+) -> Result<String, RenderError> {
     let template_path = "school_table";
     let mut handlebars = Handlebars::new();
     handlebars.register_helper("str_equal", Box::new(str_equal));
+    register_i18n_helpers(&mut handlebars);
     handlebars.register_helper("levels", Box::new(levels));
 
-    let schools_from_db = Database::find_all_active(&db).await;
+    let schools_from_db = match Database::find_all_active(&db).await {
+        Some(leads) => Some(
+            scope_leads(&db, acting_user.user_uuid, leads, |lead: &School| {
+                &lead.address
+            })
+            .await,
+        ),
+        None => None,
+    };
 
     let template_contents = match read_hbs_template(&template_path) {
         Ok(contents) => contents,
@@ -112,17 +144,25 @@ async fn school_table(db: Data<Database>) -> Result<String, RenderError> {
     let my_cf: ConfVars = set_env_vars();
 
     match schools_from_db {
-        Some(these_schools) => {
+        Some(mut these_schools) => {
+            these_schools.sort_by_key(|lead| std::cmp::Reverse(lead.score));
+
             let mut tag_vectors: Vec<GeneralTags<School>> = Vec::new();
+            let services = get_service_catalog(&db).await;
+            let stages = get_pipeline(&db, &LeadType::SCHOOL).await;
 
             for school in these_schools {
                 let (services_tag, funnel_tag) = create_option_tags_info_for_services_and_funnel(
+                    services.clone(),
+                    stages.clone(),
                     school.services_offered.clone(),
                     school.sales_funnel.clone(),
                 );
 
                 let first_contact = match school.first_contact_date {
-                    Some(this_date) => format_date_in_language(&this_date, "es"),
+                    Some(this_date) => {
+                        format_date_in_language(&this_date, &locale, &timezone)
+                    },
                     None => "".to_string(),
                 };
 
@@ -134,22 +174,23 @@ async fn school_table(db: Data<Database>) -> Result<String, RenderError> {
                 });
             }
 
-            let data = json!({"conf": my_cf, "schools": tag_vectors});
+            let data = json!({ "conf": my_cf, "locale": locale.code(), "schools": tag_vectors});
 
             let render = handlebars.render_template(&template_contents, &data)?;
             Ok(render)
         }
         None => {
-            let data = json!({"conf": my_cf, "error": "Unable to fetch schools"});
+            let data = json!({ "conf": my_cf, "locale": locale.code(), "error": "Unable to fetch schools"});
             let render_error = handlebars.render_template(&template_contents, &data)?;
             Ok(render_error)
         }
     }
 }
 
-async fn school_new() -> Result<String, RenderError> {
+async fn school_new(db: Data<Database>, locale: Locale) -> Result<String, RenderError> {
     let mut handlebars = Handlebars::new();
     handlebars.register_helper("str_equal", Box::new(str_equal));
+    register_i18n_helpers(&mut handlebars);
 
     let template_path = "school_new";
 
@@ -164,17 +205,21 @@ async fn school_new() -> Result<String, RenderError> {
         }
     };
 
-    let (services_tag, funnel_tag) = get_options_and_services();
+    let (services_tag, funnel_tag) = get_options_and_services(
+        get_service_catalog(&db).await,
+        get_pipeline(&db, &LeadType::SCHOOL).await,
+    );
     let school_level_tags = get_school_level_tags();
 
     let cf: ConfVars = set_env_vars();
 
     let data = json!({
       "conf": cf,
+      "locale": locale.code(),
       "school": school_level_tags,
       "funnel": funnel_tag,
       "services": services_tag,
-      "date": format!("{}", Local::now()),
+      "date": Utc::now().to_rfc3339(),
     });
 
     let handlebars_render = handlebars.render_template(&template_contents, &data)?;
@@ -186,8 +231,8 @@ pub fn school_html_controller(cfg: &mut ServiceConfig) {
     cfg.route(
         "/htmx/schools/edit/{uuid}",
         post().to(
-            |_req: HttpRequest, hbs_path, db: Data<Database>| async move {
-                let school_editor_screen = school_edit(hbs_path, db).await;
+            |_req: HttpRequest, hbs_path, acting_user, db: Data<Database>, locale: RequestLocale| async move {
+                let school_editor_screen = school_edit(hbs_path, acting_user, db, locale.0).await;
 
                 match school_editor_screen {
                   Ok(ses) => HttpResponse::Ok().content_type("text/html")
@@ -209,8 +254,8 @@ pub fn school_html_controller(cfg: &mut ServiceConfig) {
     cfg.route(
       "/htmx/schools/table",
       post().to(
-        |db: Data<Database>| async move {
-          let htmx_school_table = school_table(db).await;
+        |db: Data<Database>, acting_user, locale: RequestLocale, timezone: RequestTimezone| async move {
+          let htmx_school_table = school_table(db, acting_user, locale.0, timezone.0).await;
 
           match htmx_school_table {
             Ok(hst) => HttpResponse::Ok()
@@ -232,8 +277,8 @@ pub fn school_html_controller(cfg: &mut ServiceConfig) {
     cfg.route(
         "/htmx/schools/new",
         post().to(
-            || async move {
-                let school_editor_screen = school_new().await;
+            |db: Data<Database>, locale: RequestLocale| async move {
+                let school_editor_screen = school_new(db, locale.0).await;
 
                 match school_editor_screen {
                   Ok(ses) => HttpResponse::Ok().content_type("text/html")
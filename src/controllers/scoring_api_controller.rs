@@ -0,0 +1,369 @@
+use actix_web::{
+    delete, get,
+    http::StatusCode,
+    patch, post, rt,
+    web::{Data, Json, Path, ServiceConfig},
+    HttpResponse,
+};
+use chrono::Utc;
+use log::{error, info};
+use utoipa::OpenApi;
+use validator::Validate;
+
+use crate::{
+    db::{config::Database, scoring_db::ScoringDB},
+    error::{api_error::ApiError, scoring_error::ScoringError},
+    models::{
+        locale_model::Locale,
+        scoring_model::{ScoringCriterion, ScoringRecalculation, ScoringRule, ScoringRuleUuid},
+        users_model::ActingUser,
+    },
+    utils::{
+        general_utils::{get_uuid, shuffle_id},
+        i18n_utils::RequestLocale,
+        message_utils::get_localized_validation_errors,
+        role_utils::is_admin,
+        scoring_utils::rescore_all_leads,
+        tag_utils::normalize_tag,
+    },
+};
+
+fn forbidden_response() -> HttpResponse {
+    HttpResponse::Forbidden()
+        .insert_header((
+            "HX-Trigger",
+            format!(
+                "{{ \"page_error\": {:?} }}",
+                "Solo un ADMIN puede editar las reglas de puntuación".to_string()
+            ),
+        ))
+        .json(ApiError::new(ScoringError::ScoringForbidden))
+}
+
+fn invalid_rule_response(key_errors_vec: Vec<String>) -> HttpResponse {
+    HttpResponse::BadRequest()
+        .insert_header((
+            "HX-Trigger",
+            format!("{{ \"page_error\": {:?} }}", key_errors_vec),
+        ))
+        .json(
+            ApiError::new(ScoringError::ScoringRuleCreationFailure)
+                .with_field_errors(key_errors_vec),
+        )
+}
+
+fn get_rule_errors(rule: &ScoringRule, locale: &Locale) -> Vec<String> {
+    let mut key_errors_vec: Vec<String> = match rule.validate() {
+        Ok(_) => vec![],
+        Err(e) => get_localized_validation_errors(&e, locale),
+    };
+
+    if rule.criterion == ScoringCriterion::HasTag && rule.value.is_none() {
+        key_errors_vec.push("A tag rule needs a tag to look for".to_string());
+    }
+
+    key_errors_vec
+}
+
+fn normalize_rule_value(rule: &ScoringRule) -> Option<String> {
+    match rule.criterion {
+        ScoringCriterion::HasTag => rule.value.as_deref().and_then(normalize_tag),
+        _ => None,
+    }
+}
+
+fn spawn_rescore(db: Data<Database>) {
+    rt::spawn(async move {
+        info!(
+            "Rescored {} leads after a scoring rule change",
+            rescore_all_leads(&db).await
+        );
+    });
+}
+
+#[utoipa::path(
+    tag = "Scoring",
+    operation_id = "find_all_scoring_rules",
+    responses(
+        (status = 200, description = "All scoring_rules", body = Vec<ScoringRule>),
+        (status = 404, description = "No scoring_rules found", body = ApiError),
+    )
+)]
+#[get("/scoring_rules")]
+#[tracing::instrument(name = "Show Scoring Rules", skip(db))]
+async fn find_all(db: Data<Database>) -> Result<HttpResponse, ScoringError> {
+    match Database::find_all_non_deleted(&db).await {
+        Some(rules) => Ok(HttpResponse::Ok().status(StatusCode::OK).json(rules)),
+        None => {
+            error!("Unable to find any scoring rule");
+            Ok(HttpResponse::NotFound().json(ApiError::new(ScoringError::NoScoringRulesFound)))
+        }
+    }
+}
+
+#[utoipa::path(
+    tag = "Scoring",
+    operation_id = "find_one_scoring_rule",
+    params(ScoringRuleUuid),
+    responses(
+        (status = 200, description = "Scoring_rule found", body = ScoringRule),
+        (status = 404, description = "Scoring_rule not found", body = ApiError),
+    )
+)]
+#[get("/scoring_rules/{uuid}")]
+#[tracing::instrument(name = "Get One Scoring Rule", skip(db), fields(uuid = %uuid.uuid))]
+async fn find_one(
+    db: Data<Database>,
+    uuid: Path<ScoringRuleUuid>,
+) -> Result<HttpResponse, ScoringError> {
+    let rule_uuid = uuid.into_inner().uuid;
+
+    match Database::find_one(&db, rule_uuid.clone()).await {
+        Some(rule) => Ok(HttpResponse::Ok().status(StatusCode::OK).json(rule)),
+        None => {
+            error!("No scoring rule found for UUID:: {:?}", &rule_uuid);
+            Ok(HttpResponse::NotFound().json(ApiError::new(ScoringError::NoScoringRulesFound)))
+        }
+    }
+}
+
+#[utoipa::path(
+    tag = "Scoring",
+    operation_id = "create_scoring_rule",
+    responses(
+        (status = 201, description = "Scoring_rule created", body = ScoringRuleUuid),
+        (status = 400, description = "Invalid scoring_rule", body = ApiError),
+        (status = 403, description = "Only an ADMIN can manage scoring_rules", body = ApiError),
+        (status = 500, description = "Scoring_rule couldn't be saved", body = ApiError),
+    )
+)]
+#[post("/scoring_rules")]
+#[tracing::instrument(name = "Post Scoring Rule", skip(db), fields(name = %body.name))]
+async fn create(
+    db: Data<Database>,
+    mut body: Json<ScoringRule>,
+    acting_user: ActingUser,
+    locale: RequestLocale,
+) -> Result<HttpResponse, ScoringError> {
+    body.created_by = acting_user.user_uuid.clone();
+    if !is_admin(&db, body.created_by.clone()).await {
+        return Ok(forbidden_response());
+    }
+
+    let new_rule = body.into_inner();
+    let rule_value = normalize_rule_value(&new_rule);
+    let key_errors_vec = get_rule_errors(
+        &ScoringRule {
+            value: rule_value.clone(),
+            ..new_rule.clone()
+        },
+        &locale.0,
+    );
+    if !key_errors_vec.is_empty() {
+        error!("Error scoring_rule.create {:?}", key_errors_vec);
+        return Ok(invalid_rule_response(key_errors_vec));
+    }
+
+    let date_created = Utc::now();
+    let my_rule = ScoringRule {
+        value: rule_value,
+        deleted: false,
+        date_created: Some(date_created),
+        date_modified: Some(date_created),
+        modified_by: new_rule.created_by.clone(),
+        ..new_rule
+    };
+
+    match Database::add_one(&db, ScoringRule::new(get_uuid(), my_rule)).await {
+        Some(rule) => {
+            spawn_rescore(db.clone());
+            Ok(HttpResponse::Ok()
+                .insert_header(("HX-Trigger", "scoring_reload_page"))
+                .status(StatusCode::CREATED)
+                .json(ScoringRuleUuid {
+                    uuid: match rule.uuid {
+                        Some(this_uuid) => shuffle_id(this_uuid),
+                        None => "".to_string(),
+                    },
+                }))
+        }
+        None => {
+            error!("Error [POST] /scoring_rules");
+            Ok(HttpResponse::InternalServerError()
+                .insert_header((
+                    "HX-Trigger",
+                    "{ \"page_error\": \"Internal server error\" }".to_string(),
+                ))
+                .json(ApiError::new(ScoringError::ScoringRuleCreationFailure)))
+        }
+    }
+}
+
+#[utoipa::path(
+    tag = "Scoring",
+    operation_id = "update_scoring_rule",
+    responses(
+        (status = 200, description = "Scoring_rule updated", body = ScoringRuleUuid),
+        (status = 400, description = "Invalid scoring_rule", body = ApiError),
+        (status = 403, description = "Only an ADMIN can manage scoring_rules", body = ApiError),
+        (status = 404, description = "Scoring_rule not found", body = ApiError),
+        (status = 500, description = "Scoring_rule couldn't be saved", body = ApiError),
+    )
+)]
+#[patch("/scoring_rules")]
+#[tracing::instrument(name = "Patch Scoring Rule", skip(db), fields(name = %body.name))]
+async fn update_one(
+    db: Data<Database>,
+    mut body: Json<ScoringRule>,
+    acting_user: ActingUser,
+    locale: RequestLocale,
+) -> Result<HttpResponse, ScoringError> {
+    body.modified_by = acting_user.user_uuid.clone();
+    if !is_admin(&db, body.modified_by.clone()).await {
+        return Ok(forbidden_response());
+    }
+
+    let updated_rule = body.into_inner();
+    let rule_value = normalize_rule_value(&updated_rule);
+    let key_errors_vec = get_rule_errors(
+        &ScoringRule {
+            value: rule_value.clone(),
+            ..updated_rule.clone()
+        },
+        &locale.0,
+    );
+    if !key_errors_vec.is_empty() {
+        error!("Error in scoring_rule.update_one: {:?}", key_errors_vec);
+        return Ok(invalid_rule_response(key_errors_vec));
+    }
+
+    let uuid_in_db = match updated_rule.uuid.clone() {
+        Some(r_id) => r_id,
+        None => String::from("forbidden"),
+    };
+
+    let stored_rule = match Database::find_one(&db, uuid_in_db.clone()).await {
+        Some(rule) => rule,
+        None => {
+            error!("No scoring rule found for UUID:: {:?}", &uuid_in_db);
+            return Ok(
+                HttpResponse::NotFound().json(ApiError::new(ScoringError::NoScoringRulesFound))
+            );
+        }
+    };
+
+    let rule_to_update = ScoringRule {
+        value: rule_value,
+        date_created: stored_rule.date_created,
+        created_by: stored_rule.created_by,
+        date_modified: Some(Utc::now()),
+        ..updated_rule
+    };
+
+    match Database::update_one(&db, rule_to_update).await {
+        Some(rule) => {
+            spawn_rescore(db.clone());
+            Ok(HttpResponse::Ok()
+                .insert_header(("HX-Trigger", "scoring_reload_page"))
+                .status(StatusCode::OK)
+                .json(ScoringRuleUuid {
+                    uuid: match rule.uuid {
+                        Some(this_uuid) => shuffle_id(this_uuid),
+                        None => "".to_string(),
+                    },
+                }))
+        }
+        None => {
+            error!("Error in scoring_rule.update_one");
+            Ok(HttpResponse::InternalServerError()
+                .insert_header((
+                    "HX-Trigger",
+                    format!(
+                        "{{ \"page_error\": {:?} }}",
+                        "Couldn't save scoring rule".to_string()
+                    ),
+                ))
+                .json(ApiError::new(ScoringError::ScoringRuleCreationFailure)))
+        }
+    }
+}
+
+#[utoipa::path(
+    tag = "Scoring",
+    operation_id = "delete_scoring_rule",
+    params(ScoringRuleUuid),
+    responses(
+        (status = 200, description = "Scoring_rule deleted", body = ScoringRuleUuid),
+        (status = 403, description = "Only an ADMIN can manage scoring_rules", body = ApiError),
+        (status = 404, description = "Scoring_rule not found", body = ApiError),
+    )
+)]
+#[delete("/scoring_rules/{uuid}")]
+#[tracing::instrument(name = "Delete Scoring Rule", skip(db), fields(uuid = %uuid.uuid))]
+async fn delete_one(
+    db: Data<Database>,
+    uuid: Path<ScoringRuleUuid>,
+    acting_user: ActingUser,
+) -> Result<HttpResponse, ScoringError> {
+    let user_uuid = acting_user.user_uuid;
+    if !is_admin(&db, user_uuid.clone()).await {
+        return Ok(forbidden_response());
+    }
+
+    let rule_uuid = uuid.into_inner().uuid;
+
+    match Database::delete_one(&db, rule_uuid.clone(), user_uuid).await {
+        Some(_) => {
+            spawn_rescore(db.clone());
+            Ok(HttpResponse::Ok()
+                .insert_header(("HX-Trigger", "scoring_reload_page"))
+                .status(StatusCode::OK)
+                .json(ScoringRuleUuid {
+                    uuid: shuffle_id(rule_uuid),
+                }))
+        }
+        None => {
+            error!("No scoring rule found for UUID:: {:?}", &rule_uuid);
+            Ok(HttpResponse::NotFound().json(ApiError::new(ScoringError::NoScoringRulesFound)))
+        }
+    }
+}
+
+#[utoipa::path(
+    tag = "Scoring",
+    operation_id = "recalculate_scores",
+    responses(
+        (status = 200, description = "Lead scores recalculated", body = ScoringRecalculation),
+        (status = 403, description = "Only an ADMIN can recalculate scores", body = ApiError),
+    )
+)]
+#[post("/scoring_rules/recalculate")]
+#[tracing::instrument(name = "Recalculate Lead Scores", skip(db, acting_user))]
+async fn recalculate(
+    db: Data<Database>,
+    acting_user: ActingUser,
+) -> Result<HttpResponse, ScoringError> {
+    if !is_admin(&db, acting_user.user_uuid).await {
+        return Ok(forbidden_response());
+    }
+
+    let updated = rescore_all_leads(&db).await;
+
+    Ok(HttpResponse::Ok()
+        .insert_header(("HX-Trigger", "scoring_reload_page"))
+        .status(StatusCode::OK)
+        .json(ScoringRecalculation { updated }))
+}
+
+#[derive(OpenApi)]
+#[openapi(paths(find_all, find_one, create, update_one, delete_one, recalculate))]
+pub struct ScoringApiDoc;
+
+pub fn scoring_api_controllers(cfg: &mut ServiceConfig) {
+    cfg.service(create);
+    cfg.service(delete_one);
+    cfg.service(find_all);
+    cfg.service(find_one);
+    cfg.service(recalculate);
+    cfg.service(update_one);
+}
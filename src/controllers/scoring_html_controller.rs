@@ -0,0 +1,89 @@
+use actix_web::{
+    web::{post, Data, ServiceConfig},
+    HttpResponse,
+};
+use handlebars::{Handlebars, RenderError};
+use log::error;
+use serde_json::json;
+
+use crate::{
+    db::{config::Database, scoring_db::ScoringDB},
+    models::scoring_model::ScoringHandlebarsError,
+    utils::{
+        env::{set_env_vars, ConfVars},
+        fs_utils::read_hbs_template,
+        general_utils::get_lead_type_tags,
+        scoring_utils::{get_criterion_label, SCORING_CRITERIA},
+    },
+};
+
+async fn scoring_table(db: Data<Database>) -> Result<String, RenderError> {
+    let template_path = "scoring_table";
+    let handlebars = Handlebars::new();
+
+    let template_contents = match read_hbs_template(template_path) {
+        Ok(contents) => contents,
+        Err(e) => {
+            error!("Failed to render contents for scoring table:: {}", e);
+            ScoringHandlebarsError::new(e.to_string()).error
+        }
+    };
+
+    let criteria: Vec<serde_json::Value> = SCORING_CRITERIA
+        .iter()
+        .map(|criterion| json!({ "value": criterion, "text": get_criterion_label(criterion) }))
+        .collect();
+
+    let cf: ConfVars = set_env_vars();
+
+    match Database::find_all_non_deleted(&db).await {
+        Some(rules) => {
+            let rows: Vec<serde_json::Value> = rules
+                .into_iter()
+                .map(|rule| {
+                    let criterion = get_criterion_label(&rule.criterion);
+                    json!({ "r": rule, "criterion": criterion })
+                })
+                .collect();
+
+            let data = json!({
+              "conf": cf,
+              "rules": rows,
+              "criteria": criteria,
+              "lead_types": get_lead_type_tags(),
+            });
+            let render = handlebars.render_template(&template_contents, &data)?;
+            Ok(render)
+        }
+        None => {
+            let data = json!({"conf": cf, "error": "Unable to fetch scoring rules"});
+            let render_error = handlebars.render_template(&template_contents, &data)?;
+            Ok(render_error)
+        }
+    }
+}
+
+pub fn scoring_html_controllers(cfg: &mut ServiceConfig) {
+    cfg.route(
+      "/htmx/scoring/table",
+      post().to(
+        |db: Data<Database>| async move {
+          let rules = scoring_table(db).await;
+
+          match rules {
+            Ok(r) => HttpResponse::Ok()
+              .content_type("text/html")
+              .append_header(("HX-Trigger", "activate_navbar_element"))
+              .body(r),
+            Err(e) => HttpResponse::Ok()
+              .content_type("text/html")
+              .append_header(("HX-Trigger", "error_scoring"))
+              .body(
+                format!("<span class=\"icon is-small is-left\"><i class=\"fas fa-ban\"></i>Failed to load scoring rules: {}</span>",
+                e)
+              )
+          }
+        }
+      ),
+    );
+}
@@ -0,0 +1,371 @@
+use actix_web::{
+    delete, get,
+    http::{
+        header::{ContentDisposition, DispositionParam, DispositionType},
+        StatusCode,
+    },
+    patch, post,
+    web::{Data, Json, Path, ServiceConfig},
+    HttpResponse,
+};
+use chrono::Utc;
+use log::error;
+use utoipa::OpenApi;
+use validator::Validate;
+
+use crate::{
+    db::{config::Database, segment_db::SegmentDB},
+    error::{api_error::ApiError, segment_error::SegmentError},
+    models::{
+        lead_model::LeadSummary,
+        segment_model::{Segment, SegmentUuid},
+        users_model::ActingUser,
+    },
+    utils::{
+        general_utils::{get_uuid, shuffle_id},
+        i18n_utils::RequestLocale,
+        lead_utils::find_filtered_summaries,
+        message_utils::get_localized_validation_errors,
+        pipeline_utils::get_pipeline,
+        role_utils::is_active_user,
+        segment_utils::{leads_to_csv, normalize_segment_filter},
+        service_utils::get_service_catalog,
+    },
+};
+
+fn forbidden_response() -> HttpResponse {
+    HttpResponse::Forbidden()
+        .insert_header((
+            "HX-Trigger",
+            format!(
+                "{{ \"page_error\": {:?} }}",
+                "Inicia sesión para administrar segmentos".to_string()
+            ),
+        ))
+        .json(ApiError::new(SegmentError::SegmentForbidden))
+}
+
+fn not_found_response(segment_uuid: &str) -> HttpResponse {
+    error!("No segment found for UUID:: {:?}", segment_uuid);
+    HttpResponse::NotFound().json(ApiError::new(SegmentError::NoSegmentsFound))
+}
+
+#[utoipa::path(
+    tag = "Segments",
+    operation_id = "find_all_segments",
+    responses(
+        (status = 200, description = "All segments", body = Vec<Segment>),
+        (status = 404, description = "No segments found", body = ApiError),
+    )
+)]
+#[get("/segments")]
+#[tracing::instrument(name = "Show Segments", skip(db))]
+async fn find_all(db: Data<Database>) -> Result<HttpResponse, SegmentError> {
+    match Database::find_all_active(&db).await {
+        Some(segments) => Ok(HttpResponse::Ok().status(StatusCode::OK).json(segments)),
+        None => {
+            error!("Unable to find any segment");
+            Ok(HttpResponse::NotFound().json(ApiError::new(SegmentError::NoSegmentsFound)))
+        }
+    }
+}
+
+#[utoipa::path(
+    tag = "Segments",
+    operation_id = "find_one_segment",
+    params(SegmentUuid),
+    responses(
+        (status = 200, description = "Segment found", body = Segment),
+        (status = 404, description = "Segment not found", body = ApiError),
+    )
+)]
+#[get("/segments/{uuid}")]
+#[tracing::instrument(name = "Get One Segment", skip(db), fields(uuid = %uuid.uuid))]
+async fn find_one(
+    db: Data<Database>,
+    uuid: Path<SegmentUuid>,
+) -> Result<HttpResponse, SegmentError> {
+    let segment_uuid = uuid.into_inner().uuid;
+
+    match Database::find_one(&db, segment_uuid.clone()).await {
+        Some(segment) => Ok(HttpResponse::Ok().status(StatusCode::OK).json(segment)),
+        None => Ok(not_found_response(&segment_uuid)),
+    }
+}
+
+#[utoipa::path(
+    tag = "Segments",
+    operation_id = "find_segment_leads",
+    params(SegmentUuid),
+    responses(
+        (status = 200, description = "Leads matching the segment filter", body = Vec<LeadSummary>),
+        (status = 404, description = "Segment not found", body = ApiError),
+    )
+)]
+#[get("/segments/{uuid}/leads")]
+#[tracing::instrument(name = "Show Segment Leads", skip(db, acting_user), fields(uuid = %uuid.uuid))]
+async fn find_leads(
+    db: Data<Database>,
+    uuid: Path<SegmentUuid>,
+    acting_user: ActingUser,
+) -> Result<HttpResponse, SegmentError> {
+    let segment_uuid = uuid.into_inner().uuid;
+
+    match Database::find_one(&db, segment_uuid.clone()).await {
+        Some(segment) => {
+            let leads = find_filtered_summaries(
+                &db,
+                &segment.lead_type,
+                &segment.filter,
+                acting_user.user_uuid,
+            )
+            .await;
+            Ok(HttpResponse::Ok().status(StatusCode::OK).json(leads))
+        }
+        None => Ok(not_found_response(&segment_uuid)),
+    }
+}
+
+#[utoipa::path(
+    tag = "Segments",
+    operation_id = "export_segment",
+    params(SegmentUuid),
+    responses(
+        (status = 200, description = "Segment leads as CSV", body = String, content_type = "text/csv"),
+        (status = 404, description = "Segment not found", body = ApiError),
+    )
+)]
+#[get("/segments/{uuid}/export")]
+#[tracing::instrument(name = "Export Segment", skip(db, acting_user), fields(uuid = %uuid.uuid))]
+async fn export(
+    db: Data<Database>,
+    uuid: Path<SegmentUuid>,
+    acting_user: ActingUser,
+) -> Result<HttpResponse, SegmentError> {
+    let user_uuid = acting_user.user_uuid;
+    if !is_active_user(&db, user_uuid.clone()).await {
+        return Ok(forbidden_response());
+    }
+
+    let segment_uuid = uuid.into_inner().uuid;
+    let segment = match Database::find_one(&db, segment_uuid.clone()).await {
+        Some(segment) => segment,
+        None => return Ok(not_found_response(&segment_uuid)),
+    };
+
+    let leads = find_filtered_summaries(&db, &segment.lead_type, &segment.filter, user_uuid).await;
+    let csv = leads_to_csv(
+        &leads,
+        &get_service_catalog(&db).await,
+        &get_pipeline(&db, &segment.lead_type).await,
+    );
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/csv; charset=utf-8")
+        .insert_header(ContentDisposition {
+            disposition: DispositionType::Attachment,
+            parameters: vec![DispositionParam::Filename(format!("{}.csv", segment.name))],
+        })
+        .body(csv))
+}
+
+#[utoipa::path(
+    tag = "Segments",
+    operation_id = "create_segment",
+    responses(
+        (status = 201, description = "Segment created", body = SegmentUuid),
+        (status = 400, description = "Invalid segment", body = ApiError),
+        (status = 403, description = "Only an ADMIN can manage segments", body = ApiError),
+        (status = 500, description = "Segment couldn't be saved", body = ApiError),
+    )
+)]
+#[post("/segments")]
+#[tracing::instrument(name = "Post Segment", skip(db), fields(name = %body.name))]
+async fn create(
+    db: Data<Database>,
+    mut body: Json<Segment>,
+    acting_user: ActingUser,
+    locale: RequestLocale,
+) -> Result<HttpResponse, SegmentError> {
+    body.created_by = acting_user.user_uuid.clone();
+    if !is_active_user(&db, body.created_by.clone()).await {
+        return Ok(forbidden_response());
+    }
+
+    if let Err(e) = body.validate() {
+        error!("Error segment.create {:?}", e);
+        let key_errors_vec: Vec<String> = get_localized_validation_errors(&e, &locale.0);
+        return Ok(HttpResponse::BadRequest()
+            .insert_header((
+                "HX-Trigger",
+                format!("{{ \"page_error\": {:?} }}", key_errors_vec),
+            ))
+            .json(
+                ApiError::new(SegmentError::SegmentCreationFailure)
+                    .with_field_errors(key_errors_vec),
+            ));
+    }
+
+    let date_created = Utc::now();
+    let new_segment = body.into_inner();
+    let my_segment = Segment {
+        filter: normalize_segment_filter(new_segment.filter.clone()),
+        deleted: false,
+        date_created: Some(date_created),
+        date_modified: Some(date_created),
+        modified_by: new_segment.created_by.clone(),
+        ..new_segment
+    };
+
+    match Database::add_one(&db, Segment::new(get_uuid(), my_segment)).await {
+        Some(segment) => Ok(HttpResponse::Ok()
+            .insert_header(("HX-Trigger", "segment_reload_page"))
+            .status(StatusCode::CREATED)
+            .json(SegmentUuid {
+                uuid: match segment.uuid {
+                    Some(this_uuid) => shuffle_id(this_uuid),
+                    None => "".to_string(),
+                },
+            })),
+        None => {
+            error!("Error [POST] /segments");
+            Ok(HttpResponse::InternalServerError()
+                .insert_header((
+                    "HX-Trigger",
+                    "{ \"page_error\": \"Internal server error\" }".to_string(),
+                ))
+                .json(ApiError::new(SegmentError::SegmentCreationFailure)))
+        }
+    }
+}
+
+#[utoipa::path(
+    tag = "Segments",
+    operation_id = "update_segment",
+    responses(
+        (status = 200, description = "Segment updated", body = SegmentUuid),
+        (status = 400, description = "Invalid segment", body = ApiError),
+        (status = 403, description = "Only an ADMIN can manage segments", body = ApiError),
+        (status = 404, description = "Segment not found", body = ApiError),
+        (status = 500, description = "Segment couldn't be saved", body = ApiError),
+    )
+)]
+#[patch("/segments")]
+#[tracing::instrument(name = "Patch Segment", skip(db), fields(name = %body.name))]
+async fn update_one(
+    db: Data<Database>,
+    mut body: Json<Segment>,
+    acting_user: ActingUser,
+    locale: RequestLocale,
+) -> Result<HttpResponse, SegmentError> {
+    body.modified_by = acting_user.user_uuid.clone();
+    if !is_active_user(&db, body.modified_by.clone()).await {
+        return Ok(forbidden_response());
+    }
+
+    if let Err(e) = body.validate() {
+        error!("Error in segment.update_one: {:?}", e);
+        let key_errors_vec: Vec<String> = get_localized_validation_errors(&e, &locale.0);
+        return Ok(HttpResponse::BadRequest()
+            .insert_header((
+                "HX-Trigger",
+                format!("{{ \"page_error\": {:?} }}", key_errors_vec),
+            ))
+            .json(
+                ApiError::new(SegmentError::SegmentCreationFailure)
+                    .with_field_errors(key_errors_vec),
+            ));
+    }
+
+    let uuid_in_db = match body.uuid.clone() {
+        Some(s_id) => s_id,
+        None => String::from("forbidden"),
+    };
+
+    let stored_segment = match Database::find_one(&db, uuid_in_db.clone()).await {
+        Some(segment) => segment,
+        None => return Ok(not_found_response(&uuid_in_db)),
+    };
+
+    let updated_segment = body.into_inner();
+    let segment_to_update = Segment {
+        filter: normalize_segment_filter(updated_segment.filter.clone()),
+        date_created: stored_segment.date_created,
+        created_by: stored_segment.created_by,
+        date_modified: Some(Utc::now()),
+        ..updated_segment
+    };
+
+    match Database::update_one(&db, segment_to_update).await {
+        Some(segment) => Ok(HttpResponse::Ok()
+            .insert_header(("HX-Trigger", "segment_reload_page"))
+            .status(StatusCode::OK)
+            .json(SegmentUuid {
+                uuid: match segment.uuid {
+                    Some(this_uuid) => shuffle_id(this_uuid),
+                    None => "".to_string(),
+                },
+            })),
+        None => {
+            error!("Error in segment.update_one");
+            Ok(HttpResponse::InternalServerError()
+                .insert_header((
+                    "HX-Trigger",
+                    format!(
+                        "{{ \"page_error\": {:?} }}",
+                        "Couldn't save segment".to_string()
+                    ),
+                ))
+                .json(ApiError::new(SegmentError::SegmentCreationFailure)))
+        }
+    }
+}
+
+#[utoipa::path(
+    tag = "Segments",
+    operation_id = "delete_segment",
+    params(SegmentUuid),
+    responses(
+        (status = 200, description = "Segment deleted", body = SegmentUuid),
+        (status = 403, description = "Only an ADMIN can manage segments", body = ApiError),
+        (status = 404, description = "Segment not found", body = ApiError),
+    )
+)]
+#[delete("/segments/{uuid}")]
+#[tracing::instrument(name = "Delete Segment", skip(db), fields(uuid = %uuid.uuid))]
+async fn delete_one(
+    db: Data<Database>,
+    uuid: Path<SegmentUuid>,
+    acting_user: ActingUser,
+) -> Result<HttpResponse, SegmentError> {
+    let user_uuid = acting_user.user_uuid;
+    if !is_active_user(&db, user_uuid.clone()).await {
+        return Ok(forbidden_response());
+    }
+
+    let segment_uuid = uuid.into_inner().uuid;
+
+    match Database::delete_one(&db, segment_uuid.clone(), user_uuid).await {
+        Some(_) => Ok(HttpResponse::Ok()
+            .insert_header(("HX-Trigger", "segment_reload_page"))
+            .status(StatusCode::OK)
+            .json(SegmentUuid {
+                uuid: shuffle_id(segment_uuid),
+            })),
+        None => Ok(not_found_response(&segment_uuid)),
+    }
+}
+
+#[derive(OpenApi)]
+#[openapi(paths(find_all, find_one, find_leads, export, create, update_one, delete_one))]
+pub struct SegmentApiDoc;
+
+pub fn segment_api_controllers(cfg: &mut ServiceConfig) {
+    cfg.service(create);
+    cfg.service(delete_one);
+    cfg.service(export);
+    cfg.service(find_all);
+    cfg.service(find_leads);
+    cfg.service(find_one);
+    cfg.service(update_one);
+}
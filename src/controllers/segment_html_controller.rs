@@ -0,0 +1,105 @@
+use actix_web::{
+    web::{post, Data, ServiceConfig},
+    HttpResponse,
+};
+use handlebars::{Handlebars, RenderError};
+use log::error;
+use serde_json::json;
+
+use crate::{
+    db::{config::Database, pipeline_db::PipelineDB, segment_db::SegmentDB},
+    models::segment_model::SegmentHandlebarsError,
+    utils::{
+        address_utils::get_state_tags,
+        env::{set_env_vars, ConfVars},
+        fs_utils::read_hbs_template,
+        general_utils::get_lead_type_tags,
+        service_utils::{get_service_catalog, get_service_name},
+    },
+};
+
+async fn segment_table(db: Data<Database>) -> Result<String, RenderError> {
+    let template_path = "segment_table";
+    let handlebars = Handlebars::new();
+
+    let template_contents = match read_hbs_template(template_path) {
+        Ok(contents) => contents,
+        Err(e) => {
+            error!("Failed to render contents for segment table:: {}", e);
+            SegmentHandlebarsError::new(e.to_string()).error
+        }
+    };
+
+    let services = get_service_catalog(&db).await;
+    let stages = <Database as PipelineDB>::find_all_active(&db)
+        .await
+        .unwrap_or_default();
+
+    let cf: ConfVars = set_env_vars();
+
+    match <Database as SegmentDB>::find_all_active(&db).await {
+        Some(segments) => {
+            let rows: Vec<serde_json::Value> = segments
+                .into_iter()
+                .map(|segment| {
+                    let stage = segment.filter.sales_funnel.as_deref().map(|code| {
+                        stages
+                            .iter()
+                            .find(|stage| {
+                                stage.lead_type == segment.lead_type && stage.code == code
+                            })
+                            .map(|stage| stage.name.clone())
+                            .unwrap_or(code.to_string())
+                    });
+                    let service = segment
+                        .filter
+                        .service
+                        .as_deref()
+                        .map(|service_uuid| get_service_name(&services, service_uuid));
+                    json!({ "s": segment, "stage": stage, "service": service })
+                })
+                .collect();
+
+            let data = json!({
+              "conf": cf,
+              "segments": rows,
+              "lead_types": get_lead_type_tags(),
+              "stages": stages,
+              "services": services,
+              "states": get_state_tags(None),
+            });
+            let render = handlebars.render_template(&template_contents, &data)?;
+            Ok(render)
+        }
+        None => {
+            let data = json!({"conf": cf, "error": "Unable to fetch segments"});
+            let render_error = handlebars.render_template(&template_contents, &data)?;
+            Ok(render_error)
+        }
+    }
+}
+
+pub fn segment_html_controllers(cfg: &mut ServiceConfig) {
+    cfg.route(
+      "/htmx/segment/table",
+      post().to(
+        |db: Data<Database>| async move {
+          let segments = segment_table(db).await;
+
+          match segments {
+            Ok(s) => HttpResponse::Ok()
+              .content_type("text/html")
+              .append_header(("HX-Trigger", "activate_navbar_element"))
+              .body(s),
+            Err(e) => HttpResponse::Ok()
+              .content_type("text/html")
+              .append_header(("HX-Trigger", "error_segment"))
+              .body(
+                format!("<span class=\"icon is-small is-left\"><i class=\"fas fa-ban\"></i>Failed to load segments: {}</span>",
+                e)
+              )
+          }
+        }
+      ),
+    );
+}
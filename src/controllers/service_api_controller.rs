@@ -0,0 +1,287 @@
+use actix_web::{
+    delete, get,
+    http::StatusCode,
+    patch, post,
+    web::{Data, Json, Path, ServiceConfig},
+    HttpResponse,
+};
+use chrono::Utc;
+use log::error;
+use utoipa::OpenApi;
+use validator::Validate;
+
+use crate::{
+    db::{config::Database, service_db::ServiceDB},
+    error::{api_error::ApiError, service_error::ServiceError},
+    models::{
+        service_model::{Service, ServiceUuid},
+        users_model::ActingUser,
+    },
+    utils::{
+        general_utils::{get_uuid, shuffle_id},
+        i18n_utils::RequestLocale,
+        message_utils::get_localized_validation_errors,
+        role_utils::is_admin,
+    },
+};
+
+fn forbidden_response() -> HttpResponse {
+    HttpResponse::Forbidden()
+        .insert_header((
+            "HX-Trigger",
+            format!(
+                "{{ \"page_error\": {:?} }}",
+                "Solo un ADMIN puede editar el catálogo de servicios".to_string()
+            ),
+        ))
+        .json(ApiError::new(ServiceError::ServiceForbidden))
+}
+
+#[utoipa::path(
+    tag = "Services",
+    operation_id = "find_all_services",
+    responses(
+        (status = 200, description = "All services", body = Vec<Service>),
+        (status = 404, description = "No services found", body = ApiError),
+    )
+)]
+#[get("/services")]
+#[tracing::instrument(name = "Show Services", skip(db))]
+async fn find_all(db: Data<Database>) -> Result<HttpResponse, ServiceError> {
+    match Database::find_all_active(&db).await {
+        Some(services) => Ok(HttpResponse::Ok().status(StatusCode::OK).json(services)),
+        None => {
+            error!("Unable to find any service");
+            Ok(HttpResponse::NotFound().json(ApiError::new(ServiceError::NoServicesFound)))
+        }
+    }
+}
+
+#[utoipa::path(
+    tag = "Services",
+    operation_id = "find_one_service",
+    params(ServiceUuid),
+    responses(
+        (status = 200, description = "Service found", body = Service),
+        (status = 404, description = "Service not found", body = ApiError),
+    )
+)]
+#[get("/services/{uuid}")]
+#[tracing::instrument(name = "Get One Service", skip(db), fields(uuid = %uuid.uuid))]
+async fn find_one(
+    db: Data<Database>,
+    uuid: Path<ServiceUuid>,
+) -> Result<HttpResponse, ServiceError> {
+    let service_uuid = uuid.into_inner().uuid;
+
+    match Database::find_one(&db, service_uuid.clone()).await {
+        Some(service) => Ok(HttpResponse::Ok().status(StatusCode::OK).json(service)),
+        None => {
+            error!("No service found for UUID:: {:?}", &service_uuid);
+            Ok(HttpResponse::NotFound().json(ApiError::new(ServiceError::NoServicesFound)))
+        }
+    }
+}
+
+#[utoipa::path(
+    tag = "Services",
+    operation_id = "create_service",
+    responses(
+        (status = 201, description = "Service created", body = ServiceUuid),
+        (status = 400, description = "Invalid service", body = ApiError),
+        (status = 403, description = "Only an ADMIN can manage services", body = ApiError),
+        (status = 500, description = "Service couldn't be saved", body = ApiError),
+    )
+)]
+#[post("/services")]
+#[tracing::instrument(name = "Post Service", skip(db), fields(name = %body.name))]
+async fn create(
+    db: Data<Database>,
+    mut body: Json<Service>,
+    acting_user: ActingUser,
+    locale: RequestLocale,
+) -> Result<HttpResponse, ServiceError> {
+    body.created_by = acting_user.user_uuid.clone();
+    if !is_admin(&db, body.created_by.clone()).await {
+        return Ok(forbidden_response());
+    }
+
+    if let Err(e) = body.validate() {
+        error!("Error service.create {:?}", e);
+        let key_errors_vec: Vec<String> = get_localized_validation_errors(&e, &locale.0);
+        return Ok(HttpResponse::BadRequest()
+            .insert_header((
+                "HX-Trigger",
+                format!("{{ \"page_error\": {:?} }}", key_errors_vec),
+            ))
+            .json(
+                ApiError::new(ServiceError::ServiceCreationFailure)
+                    .with_field_errors(key_errors_vec),
+            ));
+    }
+
+    let date_created = Utc::now();
+    let new_service = body.into_inner();
+    let my_service = Service {
+        legacy_code: None,
+        deleted: false,
+        date_created: Some(date_created),
+        date_modified: Some(date_created),
+        modified_by: new_service.created_by.clone(),
+        ..new_service
+    };
+
+    match Database::add_one(&db, Service::new(get_uuid(), my_service)).await {
+        Some(service) => Ok(HttpResponse::Ok()
+            .insert_header(("HX-Trigger", "service_reload_page"))
+            .status(StatusCode::CREATED)
+            .json(ServiceUuid {
+                uuid: match service.uuid {
+                    Some(this_uuid) => shuffle_id(this_uuid),
+                    None => "".to_string(),
+                },
+            })),
+        None => {
+            error!("Error [POST] /services");
+            Ok(HttpResponse::InternalServerError()
+                .insert_header((
+                    "HX-Trigger",
+                    "{ \"page_error\": \"Internal server error\" }".to_string(),
+                ))
+                .json(ApiError::new(ServiceError::ServiceCreationFailure)))
+        }
+    }
+}
+
+#[utoipa::path(
+    tag = "Services",
+    operation_id = "update_service",
+    responses(
+        (status = 200, description = "Service updated", body = ServiceUuid),
+        (status = 400, description = "Invalid service", body = ApiError),
+        (status = 403, description = "Only an ADMIN can manage services", body = ApiError),
+        (status = 404, description = "Service not found", body = ApiError),
+        (status = 500, description = "Service couldn't be saved", body = ApiError),
+    )
+)]
+#[patch("/services")]
+#[tracing::instrument(name = "Patch Service", skip(db), fields(uuid = body.uuid, name = %body.name))]
+async fn update_one(
+    db: Data<Database>,
+    mut body: Json<Service>,
+    acting_user: ActingUser,
+    locale: RequestLocale,
+) -> Result<HttpResponse, ServiceError> {
+    body.modified_by = acting_user.user_uuid.clone();
+    if !is_admin(&db, body.modified_by.clone()).await {
+        return Ok(forbidden_response());
+    }
+
+    if let Err(e) = body.validate() {
+        error!("Error in service.update_one: {:?}", e);
+        let key_errors_vec: Vec<String> = get_localized_validation_errors(&e, &locale.0);
+        return Ok(HttpResponse::BadRequest()
+            .insert_header((
+                "HX-Trigger",
+                format!("{{ \"page_error\": {:?} }}", key_errors_vec),
+            ))
+            .json(
+                ApiError::new(ServiceError::ServiceCreationFailure)
+                    .with_field_errors(key_errors_vec),
+            ));
+    }
+
+    let uuid_in_db = match body.uuid.clone() {
+        Some(s_id) => s_id,
+        None => String::from("forbidden"),
+    };
+
+    let stored_service = match Database::find_one(&db, uuid_in_db.clone()).await {
+        Some(service) => service,
+        None => {
+            error!("No service found for UUID:: {:?}", &uuid_in_db);
+            return Ok(HttpResponse::NotFound().json(ApiError::new(ServiceError::NoServicesFound)));
+        }
+    };
+
+    let service_to_update = Service {
+        legacy_code: stored_service.legacy_code,
+        date_created: stored_service.date_created,
+        created_by: stored_service.created_by,
+        date_modified: Some(Utc::now()),
+        ..body.into_inner()
+    };
+
+    match Database::update_one(&db, service_to_update).await {
+        Some(service) => Ok(HttpResponse::Ok()
+            .insert_header(("HX-Trigger", "service_reload_page"))
+            .status(StatusCode::OK)
+            .json(ServiceUuid {
+                uuid: match service.uuid {
+                    Some(this_uuid) => shuffle_id(this_uuid),
+                    None => "".to_string(),
+                },
+            })),
+        None => {
+            error!("Error in service.update_one");
+            Ok(HttpResponse::InternalServerError()
+                .insert_header((
+                    "HX-Trigger",
+                    format!(
+                        "{{ \"page_error\": {:?} }}",
+                        "Couldn't save service".to_string()
+                    ),
+                ))
+                .json(ApiError::new(ServiceError::ServiceCreationFailure)))
+        }
+    }
+}
+
+#[utoipa::path(
+    tag = "Services",
+    operation_id = "delete_service",
+    params(ServiceUuid),
+    responses(
+        (status = 200, description = "Service deleted", body = ServiceUuid),
+        (status = 403, description = "Only an ADMIN can manage services", body = ApiError),
+        (status = 404, description = "Service not found", body = ApiError),
+    )
+)]
+#[delete("/services/{uuid}")]
+#[tracing::instrument(name = "Delete Service", skip(db), fields(uuid = %uuid.uuid))]
+async fn delete_one(
+    db: Data<Database>,
+    uuid: Path<ServiceUuid>,
+    acting_user: ActingUser,
+) -> Result<HttpResponse, ServiceError> {
+    if !is_admin(&db, acting_user.user_uuid).await {
+        return Ok(forbidden_response());
+    }
+
+    let service_uuid = uuid.into_inner().uuid;
+
+    match Database::delete_one(&db, service_uuid.clone()).await {
+        Some(_) => Ok(HttpResponse::Ok()
+            .insert_header(("HX-Trigger", "service_reload_page"))
+            .status(StatusCode::OK)
+            .json(ServiceUuid {
+                uuid: shuffle_id(service_uuid),
+            })),
+        None => {
+            error!("Unable to delete service:: {:?}", &service_uuid);
+            Ok(HttpResponse::NotFound().json(ApiError::new(ServiceError::NoServicesFound)))
+        }
+    }
+}
+
+#[derive(OpenApi)]
+#[openapi(paths(find_all, find_one, create, update_one, delete_one))]
+pub struct ServiceApiDoc;
+
+pub fn service_api_controllers(cfg: &mut ServiceConfig) {
+    cfg.service(create);
+    cfg.service(delete_one);
+    cfg.service(find_all);
+    cfg.service(find_one);
+    cfg.service(update_one);
+}
@@ -0,0 +1,176 @@
+use actix_web::{
+    web::{post, Data, Path, ServiceConfig},
+    HttpRequest, HttpResponse,
+};
+use handlebars::{Handlebars, RenderError};
+use log::{error, info};
+use serde_json::json;
+
+use crate::{
+    db::{config::Database, service_db::ServiceDB},
+    models::service_model::*,
+    utils::{
+        env::{set_env_vars, ConfVars},
+        fs_utils::read_hbs_template,
+        quote_utils::format_currency,
+    },
+};
+
+handlebars_helper!(currency: |amount: f64| format_currency(amount));
+
+async fn service_table(db: Data<Database>) -> Result<String, RenderError> {
+    let template_path = "service_table";
+    let mut handlebars = Handlebars::new();
+    handlebars.register_helper("currency", Box::new(currency));
+
+    let services_from_db = Database::find_all_active(&db).await;
+
+    let template_contents = match read_hbs_template(template_path) {
+        Ok(contents) => contents,
+        Err(e) => {
+            error!("Failed to render contents for service table:: {}", e);
+            ServiceHandlebarsError::new(e.to_string()).error
+        }
+    };
+
+    let cf: ConfVars = set_env_vars();
+
+    match services_from_db {
+        Some(services) => {
+            let data = json!({"conf": cf, "services": services});
+            let render = handlebars.render_template(&template_contents, &data)?;
+            Ok(render)
+        }
+        None => {
+            let data = json!({"conf": cf, "error": "Unable to fetch services"});
+            let render_error = handlebars.render_template(&template_contents, &data)?;
+            Ok(render_error)
+        }
+    }
+}
+
+async fn service_new() -> Result<String, RenderError> {
+    let handlebars = Handlebars::new();
+    let template_path = "service_new";
+
+    let template_contents = match read_hbs_template(template_path) {
+        Ok(contents) => contents,
+        Err(e) => {
+            error!("Couldn't render file for new service:: {}", e);
+            ServiceHandlebarsError::new(e.to_string()).error
+        }
+    };
+
+    let cf: ConfVars = set_env_vars();
+    let data = json!({"conf": cf});
+
+    let handlebars_render = handlebars.render_template(&template_contents, &data)?;
+    Ok(handlebars_render)
+}
+
+async fn service_edit(hbs_path: Path<String>, db: Data<Database>) -> Result<String, RenderError> {
+    let uuid = hbs_path.into_inner();
+    let my_error = format!("Unable to find uuid {}", &uuid);
+    info!("Edit service screen for uuid:: {}", &uuid);
+
+    let handlebars = Handlebars::new();
+    let template_path = "service_edit";
+
+    let service_from_db: Result<Service, ServiceHandlebarsError> =
+        match Database::find_one(&db, uuid).await {
+            Some(service) => Ok(service),
+            None => {
+                error!("No service found in db");
+                Err(ServiceHandlebarsError::new(my_error))
+            }
+        };
+
+    let template_contents = match read_hbs_template(template_path) {
+        Ok(contents) => contents,
+        Err(e) => {
+            error!("Failed to render contents for edit service:: {}", e);
+            ServiceHandlebarsError::new(e.to_string()).error
+        }
+    };
+
+    match service_from_db {
+        Ok(service) => {
+            let cf: ConfVars = set_env_vars();
+            let data = json!({"conf": cf, "s": service});
+
+            let render = handlebars.render_template(&template_contents, &data)?;
+            Ok(render)
+        }
+        Err(e) => {
+            let render_error = handlebars.render_template(&template_contents, &e)?;
+            Ok(render_error)
+        }
+    }
+}
+
+pub fn service_html_controllers(cfg: &mut ServiceConfig) {
+    cfg.route(
+      "/htmx/service/table",
+      post().to(
+        |db: Data<Database>| async move {
+          let my_service_table = service_table(db).await;
+
+          match my_service_table {
+            Ok(st) => HttpResponse::Ok()
+              .content_type("text/html")
+              .append_header(("HX-Trigger", "activate_navbar_element"))
+              .body(st),
+            Err(e) => HttpResponse::Ok()
+              .content_type("text/html")
+              .append_header(("HX-Trigger", "error_service_table"))
+              .body(
+                format!("<span class=\"icon is-small is-left\"><i class=\"fas fa-ban\"></i>Failed to load services: {}</span>",
+                e)
+              )
+          }
+        }
+      ),
+    );
+
+    cfg.route(
+        "/htmx/service/new",
+        post().to(|| async move {
+            let new_service_editor = service_new().await;
+
+            match new_service_editor {
+              Ok(new_service) => HttpResponse::Ok()
+                .content_type("text/html")
+                .body(new_service),
+              Err(e) => HttpResponse::Ok()
+                .content_type("text/html")
+                .append_header(("HX-Trigger", "error_service_table"))
+                .body(
+                  format!("<span class=\"icon is-small is-left\"><i class=\"fas fa-ban\"></i>Failed to load service: {}</span>",
+                  e)
+                )
+            }
+        }),
+    );
+
+    cfg.route(
+      "/htmx/service/edit/{uuid}",
+      post().to(
+          |_req: HttpRequest, hbs_path, db: Data<Database>| async move {
+              let service_editor = service_edit(hbs_path, db).await;
+              match service_editor {
+                  Ok(se) => HttpResponse::Ok().content_type("text/html")
+                    .body(se),
+                  Err(e) => HttpResponse::Ok()
+                      .content_type("text/html")
+                      .append_header(("HX-Trigger", "error_service_table"))
+                      .body(
+                        format!(
+                          "<span class=\"icon is-small is-left\"><i class=\"fas fa-ban\"></i>Failed to load service: {}</span>",
+                          e
+                        )
+                      ),
+              }
+          },
+      ),
+    );
+}
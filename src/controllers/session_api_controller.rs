@@ -0,0 +1,160 @@
+use actix_web::{
+    delete, get,
+    http::{header::RETRY_AFTER, StatusCode},
+    post,
+    web::{Data, Json, ServiceConfig},
+    HttpRequest, HttpResponse,
+};
+use log::{error, warn};
+use utoipa::OpenApi;
+
+use crate::{
+    db::{config::Database, session_db::SessionDB, users_db::UsersDB},
+    error::{api_error::ApiError, session_error::SessionError},
+    models::session_model::{LoginRequest, SessionUser},
+    utils::{
+        pwd::pwd_verify,
+        role_utils::find_request_user,
+        session_utils::{
+            check_login_attempts, clear_failed_logins, create_session, expired_session_cookie,
+            get_session_uuid, record_failed_login, session_cookie,
+        },
+    },
+};
+
+fn unauthorized_response(session_error: SessionError, message: &str) -> HttpResponse {
+    HttpResponse::Unauthorized()
+        .insert_header((
+            "HX-Trigger",
+            format!("{{ \"page_error\": {:?} }}", message.to_string()),
+        ))
+        .json(ApiError::new(session_error).with_message(message))
+}
+
+#[utoipa::path(
+    tag = "Sessions",
+    operation_id = "find_current_session",
+    responses(
+        (status = 200, description = "User of the current session", body = SessionUser),
+        (status = 401, description = "No session", body = ApiError),
+    )
+)]
+#[get("/sessions")]
+#[tracing::instrument(name = "Show Session", skip(req))]
+async fn find_one(req: HttpRequest) -> Result<HttpResponse, SessionError> {
+    match find_request_user(&req).await {
+        Some(user) => Ok(HttpResponse::Ok()
+            .status(StatusCode::OK)
+            .json(SessionUser::new(user))),
+        None => Ok(unauthorized_response(
+            SessionError::NoSessionFound,
+            "Inicia sesión para continuar",
+        )),
+    }
+}
+
+#[utoipa::path(
+    tag = "Sessions",
+    operation_id = "create_session",
+    responses(
+        (status = 201, description = "Logged in, the session travels in an HttpOnly cookie", body = SessionUser),
+        (status = 401, description = "Wrong email or password", body = ApiError),
+        (status = 429, description = "Too many failed logins for this email", body = ApiError),
+        (status = 500, description = "Session couldn't be saved", body = ApiError),
+    )
+)]
+#[post("/sessions")]
+#[tracing::instrument(name = "Post Session", skip(db, body))]
+async fn create(
+    db: Data<Database>,
+    body: Json<LoginRequest>,
+) -> Result<HttpResponse, SessionError> {
+    let login = body.into_inner();
+    let email = login.email.trim().to_lowercase();
+
+    if let Err(retry_after) = check_login_attempts(&email) {
+        warn!("Too many failed logins for {}", &email);
+        let message = "Demasiados intentos, espera unos minutos";
+        return Ok(HttpResponse::TooManyRequests()
+            .insert_header((RETRY_AFTER, retry_after.to_string()))
+            .insert_header((
+                "HX-Trigger",
+                format!("{{ \"page_error\": {:?} }}", message.to_string()),
+            ))
+            .json(ApiError::new(SessionError::TooManyLoginAttempts).with_message(message)));
+    }
+
+    let user = match <Database as UsersDB>::find_by_email(&db, email.clone()).await {
+        Some(user) => user,
+        None => {
+            warn!("Login attempt for unknown email {}", &email);
+            record_failed_login(&email);
+            return Ok(unauthorized_response(
+                SessionError::InvalidCredentials,
+                "Correo o contraseña incorrectos",
+            ));
+        }
+    };
+
+    let valid_password = user
+        .password
+        .as_deref()
+        .is_some_and(|password_hash| pwd_verify(&login.password, password_hash));
+    if !valid_password {
+        warn!("Wrong password for user {}", &user.uuid);
+        record_failed_login(&email);
+        return Ok(unauthorized_response(
+            SessionError::InvalidCredentials,
+            "Correo o contraseña incorrectos",
+        ));
+    }
+
+    clear_failed_logins(&email);
+
+    match create_session(&db, user.uuid.clone()).await {
+        Some(session) => Ok(HttpResponse::Ok()
+            .cookie(session_cookie(session.uuid.unwrap_or_default()))
+            .insert_header(("HX-Trigger", "session_reload_page"))
+            .status(StatusCode::CREATED)
+            .json(SessionUser::new(user))),
+        None => {
+            error!("Error [POST] /sessions");
+            Ok(HttpResponse::InternalServerError()
+                .insert_header((
+                    "HX-Trigger",
+                    "{ \"page_error\": \"Internal server error\" }".to_string(),
+                ))
+                .json(ApiError::new(SessionError::SessionCreationFailure)))
+        }
+    }
+}
+
+#[utoipa::path(
+    tag = "Sessions",
+    operation_id = "delete_session",
+    responses(
+        (status = 204, description = "Logged out, the session cookie is cleared"),
+    )
+)]
+#[delete("/sessions")]
+#[tracing::instrument(name = "Delete Session", skip(db, req))]
+async fn delete_one(db: Data<Database>, req: HttpRequest) -> Result<HttpResponse, SessionError> {
+    if let Some(session_uuid) = get_session_uuid(&req) {
+        <Database as SessionDB>::delete_one(&db, session_uuid).await;
+    }
+
+    Ok(HttpResponse::NoContent()
+        .cookie(expired_session_cookie())
+        .insert_header(("HX-Trigger", "session_reload_page"))
+        .finish())
+}
+
+#[derive(OpenApi)]
+#[openapi(paths(find_one, create, delete_one))]
+pub struct SessionApiDoc;
+
+pub fn session_api_controllers(cfg: &mut ServiceConfig) {
+    cfg.service(create);
+    cfg.service(delete_one);
+    cfg.service(find_one);
+}
@@ -0,0 +1,66 @@
+use actix_web::{
+    web::{get, ServiceConfig},
+    HttpRequest, HttpResponse,
+};
+use handlebars::{Handlebars, RenderError};
+use log::error;
+use serde_json::json;
+
+use crate::{
+    models::{
+        locale_model::Locale,
+        session_model::{SessionHandlebarsError, SessionUser},
+    },
+    utils::{
+        env::{set_env_vars, ConfVars},
+        fs_utils::read_hbs_template,
+        i18n_utils::{register_i18n_helpers, RequestLocale},
+        role_utils::find_request_user,
+    },
+};
+
+async fn session_panel(req: HttpRequest, locale: Locale) -> Result<String, RenderError> {
+    let mut handlebars = Handlebars::new();
+    register_i18n_helpers(&mut handlebars);
+    let template_path = "session_panel";
+
+    let template_contents = match read_hbs_template(template_path) {
+        Ok(contents) => contents,
+        Err(e) => {
+            error!("Couldn't render file for session panel:: {}", e);
+            SessionHandlebarsError::new(e.to_string()).error
+        }
+    };
+
+    let cf: ConfVars = set_env_vars();
+    let data = json!({
+      "conf": cf,
+      "locale": locale.code(),
+      "user": find_request_user(&req).await.map(SessionUser::new),
+    });
+
+    let render = handlebars.render_template(&template_contents, &data)?;
+    Ok(render)
+}
+
+pub fn session_html_controllers(cfg: &mut ServiceConfig) {
+    cfg.route(
+      "/htmx/session",
+      get().to(
+        |req: HttpRequest, locale: RequestLocale| async move {
+          let panel = session_panel(req, locale.0).await;
+
+          match panel {
+            Ok(p) => HttpResponse::Ok().content_type("text/html").body(p),
+            Err(e) => HttpResponse::Ok()
+              .content_type("text/html")
+              .append_header(("HX-Trigger", "error_session"))
+              .body(
+                format!("<span class=\"icon is-small is-left\"><i class=\"fas fa-ban\"></i>Failed to load session: {}</span>",
+                e)
+              )
+          }
+        }
+      ),
+    );
+}
@@ -0,0 +1,87 @@
+use actix_web::{
+    web::{post, Data, Path, ServiceConfig},
+    HttpResponse,
+};
+use handlebars::{Handlebars, RenderError};
+use log::{error, info};
+use serde_json::json;
+
+use crate::{
+    db::{campaign_db::CampaignDB, config::Database},
+    models::{campaign_model::CampaignHandlebarsError, sales_model::LeadType},
+    utils::{
+        campaign_utils::create_lead_channel_tags,
+        env::{set_env_vars, ConfVars},
+        fs_utils::read_hbs_template,
+        lead_utils::find_lead_summary,
+    },
+};
+
+async fn source_editor(
+    hbs_path: Path<(LeadType, String)>,
+    db: Data<Database>,
+) -> Result<String, RenderError> {
+    let (lead_type, lead_uuid) = hbs_path.into_inner();
+    info!("Source editor for lead:: {}", &lead_uuid);
+
+    let handlebars = Handlebars::new();
+    let template_path = "source_editor";
+
+    let template_contents = match read_hbs_template(template_path) {
+        Ok(contents) => contents,
+        Err(e) => {
+            error!("Couldn't render file for source editor:: {}", e);
+            CampaignHandlebarsError::new(e.to_string()).error
+        }
+    };
+
+    let source = find_lead_summary(&db, &lead_type, lead_uuid)
+        .await
+        .and_then(|lead| lead.source);
+    let campaign_uuid = source
+        .as_ref()
+        .and_then(|source| source.campaign_uuid.clone());
+
+    let campaigns: Vec<serde_json::Value> = <Database as CampaignDB>::find_all_active(&db)
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .map(|campaign| {
+            let selected = campaign.uuid.is_some() && campaign.uuid == campaign_uuid;
+            json!({ "uuid": campaign.uuid, "name": campaign.name, "selected": selected })
+        })
+        .collect();
+
+    let cf: ConfVars = set_env_vars();
+    let data = json!({
+      "conf": cf,
+      "channels": create_lead_channel_tags(source.as_ref().map(|source| &source.channel)),
+      "campaigns": campaigns,
+      "source_json": serde_json::to_string(&source).unwrap_or("null".to_string()),
+    });
+
+    let render = handlebars.render_template(&template_contents, &data)?;
+    Ok(render)
+}
+
+pub fn source_html_controllers(cfg: &mut ServiceConfig) {
+    cfg.route(
+      "/htmx/source/editor/{lead_type}/{uuid}",
+      post().to(
+        |hbs_path, db: Data<Database>| async move {
+          let editor = source_editor(hbs_path, db).await;
+
+          match editor {
+            Ok(e) => HttpResponse::Ok().content_type("text/html").body(e),
+            Err(e) => HttpResponse::Ok()
+              .content_type("text/html")
+              .append_header(("HX-Trigger", "error_source"))
+              .body(
+                format!("<span class=\"icon is-small is-left\"><i class=\"fas fa-ban\"></i>Failed to load source: {}</span>",
+                e)
+              )
+          }
+        }
+      ),
+    );
+}
@@ -0,0 +1,96 @@
+use actix_web::{
+    delete, get,
+    http::StatusCode,
+    web::{Data, Path, Query, ServiceConfig},
+    HttpResponse,
+};
+use log::error;
+use utoipa::OpenApi;
+
+use crate::{
+    db::{config::Database, tag_db::TagDB},
+    error::{api_error::ApiError, tag_error::TagError},
+    models::{
+        tag_model::{Tag, TagSearch, TagUuid},
+        users_model::ActingUser,
+    },
+    utils::{general_utils::shuffle_id, role_utils::is_admin, tag_utils::normalize_tag},
+};
+
+#[utoipa::path(
+    tag = "Tags",
+    operation_id = "find_all_tags",
+    params(TagSearch),
+    responses(
+        (status = 200, description = "Tags matching the search", body = Vec<Tag>),
+        (status = 404, description = "No tags found", body = ApiError),
+    )
+)]
+#[get("/tags")]
+#[tracing::instrument(name = "Show Tags", skip(db))]
+async fn find_all(db: Data<Database>, search: Query<TagSearch>) -> Result<HttpResponse, TagError> {
+    let tags = match search.into_inner().q.as_deref().and_then(normalize_tag) {
+        Some(query) => Database::search(&db, query).await,
+        None => Database::find_all_active(&db).await,
+    };
+
+    match tags {
+        Some(found_tags) => Ok(HttpResponse::Ok().status(StatusCode::OK).json(found_tags)),
+        None => {
+            error!("Unable to find any tag");
+            Ok(HttpResponse::NotFound().json(ApiError::new(TagError::NoTagsFound)))
+        }
+    }
+}
+
+#[utoipa::path(
+    tag = "Tags",
+    operation_id = "delete_tag",
+    params(TagUuid),
+    responses(
+        (status = 200, description = "Tag deleted", body = TagUuid),
+        (status = 403, description = "Only an ADMIN can delete tags", body = ApiError),
+        (status = 404, description = "Tag not found", body = ApiError),
+    )
+)]
+#[delete("/tags/{uuid}")]
+#[tracing::instrument(name = "Delete Tag", skip(db), fields(uuid = %uuid.uuid))]
+async fn delete_one(
+    db: Data<Database>,
+    uuid: Path<TagUuid>,
+    acting_user: ActingUser,
+) -> Result<HttpResponse, TagError> {
+    let user_uuid = acting_user.user_uuid;
+    if !is_admin(&db, user_uuid.clone()).await {
+        return Ok(HttpResponse::Forbidden()
+            .insert_header((
+                "HX-Trigger",
+                format!(
+                    "{{ \"page_error\": {:?} }}",
+                    "Solo un ADMIN puede borrar etiquetas".to_string()
+                ),
+            ))
+            .json(ApiError::new(TagError::TagForbidden)));
+    }
+
+    let tag_uuid = uuid.into_inner().uuid;
+
+    match Database::delete_one(&db, tag_uuid.clone(), user_uuid).await {
+        Some(_) => Ok(HttpResponse::Ok().status(StatusCode::OK).json(TagUuid {
+            uuid: shuffle_id(tag_uuid),
+        })),
+        None => {
+            error!("Unable to delete tag:: {:?}", &tag_uuid);
+            Ok(HttpResponse::NotFound().json(ApiError::new(TagError::NoTagsFound)))
+        }
+    }
+}
+
+#[derive(OpenApi)]
+#[openapi(paths(find_all, delete_one))]
+pub struct TagApiDoc;
+
+pub fn tag_api_controllers(cfg: &mut ServiceConfig) {
+    cfg.service(delete_one);
+    cfg.service(find_all);
+}
@@ -0,0 +1,111 @@
+use actix_web::{
+    web::{get, post, Data, Path, Query, ServiceConfig},
+    HttpResponse,
+};
+use handlebars::{Handlebars, RenderError};
+use log::{error, info};
+use serde_json::json;
+
+use crate::{
+    db::{config::Database, tag_db::TagDB},
+    models::{
+        sales_model::LeadType,
+        tag_model::{TagHandlebarsError, TagSearch},
+    },
+    utils::{
+        env::{set_env_vars, ConfVars},
+        fs_utils::read_hbs_template,
+        lead_utils::find_lead_summary,
+        tag_utils::normalize_tag,
+    },
+};
+
+async fn tag_editor(
+    hbs_path: Path<(LeadType, String)>,
+    db: Data<Database>,
+) -> Result<String, RenderError> {
+    let (lead_type, lead_uuid) = hbs_path.into_inner();
+    info!("Tag editor for lead:: {}", &lead_uuid);
+
+    let handlebars = Handlebars::new();
+    let template_path = "tag_editor";
+
+    let template_contents = match read_hbs_template(template_path) {
+        Ok(contents) => contents,
+        Err(e) => {
+            error!("Couldn't render file for tag editor:: {}", e);
+            TagHandlebarsError::new(e.to_string()).error
+        }
+    };
+
+    let tags = match find_lead_summary(&db, &lead_type, lead_uuid).await {
+        Some(lead) => lead.tags,
+        None => vec![],
+    };
+
+    let cf: ConfVars = set_env_vars();
+    let data = json!({
+      "conf": cf,
+      "tags": tags,
+    });
+
+    let render = handlebars.render_template(&template_contents, &data)?;
+    Ok(render)
+}
+
+async fn tag_suggestions(search: Query<TagSearch>, db: Data<Database>) -> String {
+    let tags = match search
+        .into_inner()
+        .tag_query
+        .as_deref()
+        .and_then(normalize_tag)
+    {
+        Some(query) => Database::search(&db, query).await,
+        None => Database::find_all_active(&db).await,
+    };
+
+    let handlebars = Handlebars::new();
+    let data = json!({ "tags": tags.unwrap_or_default() });
+
+    match handlebars.render_template(
+        "{{#each tags}}<option value=\"{{name}}\"></option>{{/each}}",
+        &data,
+    ) {
+        Ok(options) => options,
+        Err(e) => {
+            error!("Couldn't render tag suggestions:: {}", e);
+            "".to_string()
+        }
+    }
+}
+
+pub fn tag_html_controllers(cfg: &mut ServiceConfig) {
+    cfg.route(
+      "/htmx/tags/editor/{lead_type}/{uuid}",
+      post().to(
+        |hbs_path, db: Data<Database>| async move {
+          let editor = tag_editor(hbs_path, db).await;
+
+          match editor {
+            Ok(e) => HttpResponse::Ok().content_type("text/html").body(e),
+            Err(e) => HttpResponse::Ok()
+              .content_type("text/html")
+              .append_header(("HX-Trigger", "error_tag"))
+              .body(
+                format!("<span class=\"icon is-small is-left\"><i class=\"fas fa-ban\"></i>Failed to load tags: {}</span>",
+                e)
+              )
+          }
+        }
+      ),
+    );
+
+    cfg.route(
+        "/htmx/tags/suggestions",
+        get().to(|search, db: Data<Database>| async move {
+            HttpResponse::Ok()
+                .content_type("text/html")
+                .body(tag_suggestions(search, db).await)
+        }),
+    );
+}
@@ -0,0 +1,373 @@
+use actix_web::{
+    delete, get,
+    http::StatusCode,
+    patch, post,
+    web::{Data, Json, Path, Query, ServiceConfig},
+    HttpResponse,
+};
+use chrono::Utc;
+use log::error;
+use utoipa::OpenApi;
+use validator::Validate;
+
+use crate::{
+    db::{config::Database, territory_db::TerritoryDB},
+    error::{api_error::ApiError, territory_error::TerritoryError},
+    models::{
+        locale_model::Locale,
+        sales_model::LeadType,
+        territory_model::{
+            Territory, TerritoryPipelineReport, TerritoryReportQuery, TerritoryUuid,
+        },
+        users_model::ActingUser,
+    },
+    utils::{
+        general_utils::{get_uuid, shuffle_id},
+        i18n_utils::RequestLocale,
+        message_utils::get_localized_validation_errors,
+        role_utils::{is_active_user, is_admin},
+        territory_utils::{
+            build_territory_reports, find_territory_scope, get_territory_errors,
+            normalize_territory,
+        },
+    },
+};
+
+fn forbidden_response(message: &str) -> HttpResponse {
+    HttpResponse::Forbidden()
+        .insert_header((
+            "HX-Trigger",
+            format!("{{ \"page_error\": {:?} }}", message.to_string()),
+        ))
+        .json(ApiError::new(TerritoryError::TerritoryForbidden).with_message(message))
+}
+
+fn not_found_response(territory_uuid: &str) -> HttpResponse {
+    error!("No territory found for UUID:: {:?}", territory_uuid);
+    HttpResponse::NotFound().json(ApiError::new(TerritoryError::NoTerritoriesFound))
+}
+
+fn invalid_territory_response(key_errors_vec: Vec<String>) -> HttpResponse {
+    HttpResponse::BadRequest()
+        .insert_header((
+            "HX-Trigger",
+            format!("{{ \"page_error\": {:?} }}", key_errors_vec),
+        ))
+        .json(
+            ApiError::new(TerritoryError::TerritoryCreationFailure)
+                .with_field_errors(key_errors_vec),
+        )
+}
+
+async fn get_all_territory_errors(
+    db: &Data<Database>,
+    territory: &Territory,
+    locale: &Locale,
+) -> Vec<String> {
+    let mut key_errors_vec: Vec<String> = match territory.validate() {
+        Ok(_) => vec![],
+        Err(e) => get_localized_validation_errors(&e, locale),
+    };
+    key_errors_vec.extend(get_territory_errors(territory));
+
+    if territory.assigned_to.is_some() && !is_active_user(db, territory.assigned_to.clone()).await {
+        key_errors_vec.push("El representante asignado no existe".to_string());
+    }
+
+    key_errors_vec
+}
+
+#[utoipa::path(
+    tag = "Territories",
+    operation_id = "find_all_territories",
+    responses(
+        (status = 200, description = "Active territories in the acting user's scope", body = Vec<Territory>),
+        (status = 404, description = "No territories found", body = ApiError),
+    )
+)]
+#[get("/territories")]
+#[tracing::instrument(name = "Show Territories", skip(db))]
+async fn find_all(
+    db: Data<Database>,
+    acting_user: ActingUser,
+) -> Result<HttpResponse, TerritoryError> {
+    let scope = find_territory_scope(&db, acting_user.user_uuid).await;
+
+    match Database::find_all_active(&db).await {
+        Some(territories) => {
+            let visible: Vec<Territory> = match scope {
+                Some(scope) => territories
+                    .into_iter()
+                    .filter(|territory| {
+                        territory
+                            .uuid
+                            .as_ref()
+                            .is_some_and(|uuid| scope.managed_uuids.contains(uuid))
+                    })
+                    .collect(),
+                None => territories,
+            };
+            Ok(HttpResponse::Ok().status(StatusCode::OK).json(visible))
+        }
+        None => {
+            error!("Unable to find any territory");
+            Ok(HttpResponse::NotFound().json(ApiError::new(TerritoryError::NoTerritoriesFound)))
+        }
+    }
+}
+
+#[utoipa::path(
+    tag = "Territories",
+    operation_id = "find_one_territory",
+    params(TerritoryUuid),
+    responses(
+        (status = 200, description = "Territory found", body = Territory),
+        (status = 404, description = "Territory not found", body = ApiError),
+    )
+)]
+#[get("/territories/{uuid}")]
+#[tracing::instrument(name = "Get One Territory", skip(db), fields(uuid = %uuid.uuid))]
+async fn find_one(
+    db: Data<Database>,
+    uuid: Path<TerritoryUuid>,
+) -> Result<HttpResponse, TerritoryError> {
+    let territory_uuid = uuid.into_inner().uuid;
+
+    match Database::find_one(&db, territory_uuid.clone()).await {
+        Some(territory) => Ok(HttpResponse::Ok().status(StatusCode::OK).json(territory)),
+        None => Ok(not_found_response(&territory_uuid)),
+    }
+}
+
+#[utoipa::path(
+    tag = "Territories",
+    operation_id = "territory_pipeline_report",
+    params(TerritoryUuid, TerritoryReportQuery),
+    responses(
+        (status = 200, description = "Pipeline report per lead type", body = Vec<TerritoryPipelineReport>),
+        (status = 403, description = "Inactive user or territory outside the manager's scope", body = ApiError),
+        (status = 404, description = "Territory not found", body = ApiError),
+    )
+)]
+#[get("/territories/{uuid}/report")]
+#[tracing::instrument(name = "Territory Pipeline Report", skip(db, query), fields(uuid = %uuid.uuid))]
+async fn report(
+    db: Data<Database>,
+    uuid: Path<TerritoryUuid>,
+    query: Query<TerritoryReportQuery>,
+    acting_user: ActingUser,
+) -> Result<HttpResponse, TerritoryError> {
+    let report_query = query.into_inner();
+    if !is_active_user(&db, acting_user.user_uuid.clone()).await {
+        return Ok(forbidden_response("Inicia sesión para ver reportes"));
+    }
+
+    let territory_uuid = uuid.into_inner().uuid;
+    let territory = match Database::find_one(&db, territory_uuid.clone()).await {
+        Some(territory) if !territory.deleted => territory,
+        _ => return Ok(not_found_response(&territory_uuid)),
+    };
+
+    if let Some(scope) = find_territory_scope(&db, acting_user.user_uuid).await {
+        if !scope.managed_uuids.contains(&territory_uuid) {
+            return Ok(forbidden_response("Este territorio no está a tu cargo"));
+        }
+    }
+
+    let lead_types = match report_query.lead_type {
+        Some(lead_type) => vec![lead_type],
+        None => vec![LeadType::ENTERPRISE, LeadType::SCHOOL, LeadType::CLINICAL],
+    };
+
+    Ok(HttpResponse::Ok()
+        .status(StatusCode::OK)
+        .json(build_territory_reports(&db, &territory, lead_types).await))
+}
+
+#[utoipa::path(
+    tag = "Territories",
+    operation_id = "create_territory",
+    responses(
+        (status = 201, description = "Territory created", body = TerritoryUuid),
+        (status = 400, description = "Invalid territory", body = ApiError),
+        (status = 403, description = "Only an ADMIN can manage territories", body = ApiError),
+        (status = 500, description = "Territory couldn't be saved", body = ApiError),
+    )
+)]
+#[post("/territories")]
+#[tracing::instrument(name = "Post Territory", skip(db), fields(name = %body.name))]
+async fn create(
+    db: Data<Database>,
+    mut body: Json<Territory>,
+    acting_user: ActingUser,
+    locale: RequestLocale,
+) -> Result<HttpResponse, TerritoryError> {
+    body.created_by = acting_user.user_uuid.clone();
+    if !is_admin(&db, body.created_by.clone()).await {
+        return Ok(forbidden_response(
+            "Solo un ADMIN puede administrar territorios",
+        ));
+    }
+
+    let new_territory = normalize_territory(body.into_inner());
+    let key_errors_vec = get_all_territory_errors(&db, &new_territory, &locale.0).await;
+    if !key_errors_vec.is_empty() {
+        error!("Error territory.create {:?}", key_errors_vec);
+        return Ok(invalid_territory_response(key_errors_vec));
+    }
+
+    let date_created = Utc::now();
+    let my_territory = Territory {
+        deleted: false,
+        date_created: Some(date_created),
+        date_modified: Some(date_created),
+        modified_by: new_territory.created_by.clone(),
+        ..new_territory
+    };
+
+    match Database::add_one(&db, Territory::new(get_uuid(), my_territory)).await {
+        Some(territory) => Ok(HttpResponse::Ok()
+            .insert_header(("HX-Trigger", "territory_reload_page"))
+            .status(StatusCode::CREATED)
+            .json(TerritoryUuid {
+                uuid: match territory.uuid {
+                    Some(this_uuid) => shuffle_id(this_uuid),
+                    None => "".to_string(),
+                },
+            })),
+        None => {
+            error!("Error [POST] /territories");
+            Ok(HttpResponse::InternalServerError()
+                .insert_header((
+                    "HX-Trigger",
+                    "{ \"page_error\": \"Internal server error\" }".to_string(),
+                ))
+                .json(ApiError::new(TerritoryError::TerritoryCreationFailure)))
+        }
+    }
+}
+
+#[utoipa::path(
+    tag = "Territories",
+    operation_id = "update_territory",
+    responses(
+        (status = 200, description = "Territory updated", body = TerritoryUuid),
+        (status = 400, description = "Invalid territory", body = ApiError),
+        (status = 403, description = "Only an ADMIN can manage territories", body = ApiError),
+        (status = 404, description = "Territory not found", body = ApiError),
+        (status = 500, description = "Territory couldn't be saved", body = ApiError),
+    )
+)]
+#[patch("/territories")]
+#[tracing::instrument(name = "Patch Territory", skip(db), fields(name = %body.name))]
+async fn update_one(
+    db: Data<Database>,
+    mut body: Json<Territory>,
+    acting_user: ActingUser,
+    locale: RequestLocale,
+) -> Result<HttpResponse, TerritoryError> {
+    body.modified_by = acting_user.user_uuid.clone();
+    if !is_admin(&db, body.modified_by.clone()).await {
+        return Ok(forbidden_response(
+            "Solo un ADMIN puede administrar territorios",
+        ));
+    }
+
+    let updated_territory = normalize_territory(body.into_inner());
+    let key_errors_vec = get_all_territory_errors(&db, &updated_territory, &locale.0).await;
+    if !key_errors_vec.is_empty() {
+        error!("Error in territory.update_one: {:?}", key_errors_vec);
+        return Ok(invalid_territory_response(key_errors_vec));
+    }
+
+    let uuid_in_db = match updated_territory.uuid.clone() {
+        Some(t_id) => t_id,
+        None => String::from("forbidden"),
+    };
+
+    let stored_territory = match Database::find_one(&db, uuid_in_db.clone()).await {
+        Some(territory) => territory,
+        None => return Ok(not_found_response(&uuid_in_db)),
+    };
+
+    let territory_to_update = Territory {
+        date_created: stored_territory.date_created,
+        created_by: stored_territory.created_by,
+        date_modified: Some(Utc::now()),
+        ..updated_territory
+    };
+
+    match Database::update_one(&db, territory_to_update).await {
+        Some(territory) => Ok(HttpResponse::Ok()
+            .insert_header(("HX-Trigger", "territory_reload_page"))
+            .status(StatusCode::OK)
+            .json(TerritoryUuid {
+                uuid: match territory.uuid {
+                    Some(this_uuid) => shuffle_id(this_uuid),
+                    None => "".to_string(),
+                },
+            })),
+        None => {
+            error!("Error in territory.update_one");
+            Ok(HttpResponse::InternalServerError()
+                .insert_header((
+                    "HX-Trigger",
+                    format!(
+                        "{{ \"page_error\": {:?} }}",
+                        "Couldn't save territory".to_string()
+                    ),
+                ))
+                .json(ApiError::new(TerritoryError::TerritoryCreationFailure)))
+        }
+    }
+}
+
+#[utoipa::path(
+    tag = "Territories",
+    operation_id = "delete_territory",
+    params(TerritoryUuid),
+    responses(
+        (status = 200, description = "Territory deleted", body = TerritoryUuid),
+        (status = 403, description = "Only an ADMIN can manage territories", body = ApiError),
+        (status = 404, description = "Territory not found", body = ApiError),
+    )
+)]
+#[delete("/territories/{uuid}")]
+#[tracing::instrument(name = "Delete Territory", skip(db), fields(uuid = %uuid.uuid))]
+async fn delete_one(
+    db: Data<Database>,
+    uuid: Path<TerritoryUuid>,
+    acting_user: ActingUser,
+) -> Result<HttpResponse, TerritoryError> {
+    let user_uuid = acting_user.user_uuid;
+    if !is_admin(&db, user_uuid.clone()).await {
+        return Ok(forbidden_response(
+            "Solo un ADMIN puede administrar territorios",
+        ));
+    }
+
+    let territory_uuid = uuid.into_inner().uuid;
+
+    match Database::delete_one(&db, territory_uuid.clone(), user_uuid).await {
+        Some(_) => Ok(HttpResponse::Ok()
+            .insert_header(("HX-Trigger", "territory_reload_page"))
+            .status(StatusCode::OK)
+            .json(TerritoryUuid {
+                uuid: shuffle_id(territory_uuid),
+            })),
+        None => Ok(not_found_response(&territory_uuid)),
+    }
+}
+
+#[derive(OpenApi)]
+#[openapi(paths(find_all, find_one, report, create, update_one, delete_one))]
+pub struct TerritoryApiDoc;
+
+pub fn territory_api_controllers(cfg: &mut ServiceConfig) {
+    cfg.service(create);
+    cfg.service(delete_one);
+    cfg.service(find_all);
+    cfg.service(find_one);
+    cfg.service(report);
+    cfg.service(update_one);
+}
@@ -0,0 +1,177 @@
+use actix_web::{
+    web::{get, post, Data, Path, ServiceConfig},
+    HttpResponse,
+};
+use handlebars::{Handlebars, RenderError};
+use log::{error, info};
+use serde_json::json;
+
+use crate::{
+    db::{config::Database, territory_db::TerritoryDB, users_db::UsersDB},
+    models::{
+        sales_model::LeadType, territory_model::TerritoryHandlebarsError, users_model::ActingUser,
+    },
+    utils::{
+        address_utils::get_state_tags,
+        env::{set_env_vars, ConfVars},
+        fs_utils::read_hbs_template,
+        quote_utils::format_currency,
+        territory_utils::{build_territory_reports, find_territory_scope},
+    },
+};
+
+handlebars_helper!(currency: |amount: f64| format_currency(amount));
+
+async fn territory_table(db: Data<Database>) -> Result<String, RenderError> {
+    let template_path = "territory_table";
+    let handlebars = Handlebars::new();
+
+    let template_contents = match read_hbs_template(template_path) {
+        Ok(contents) => contents,
+        Err(e) => {
+            error!("Failed to render contents for territory table:: {}", e);
+            TerritoryHandlebarsError::new(e.to_string()).error
+        }
+    };
+
+    let users = <Database as UsersDB>::find_all_non_deleted(&db)
+        .await
+        .unwrap_or_default();
+
+    let cf: ConfVars = set_env_vars();
+
+    match <Database as TerritoryDB>::find_all_active(&db).await {
+        Some(territories) => {
+            let rows: Vec<serde_json::Value> = territories
+                .into_iter()
+                .map(|territory| {
+                    let rep = territory.assigned_to.as_deref().and_then(|rep_uuid| {
+                        users
+                            .iter()
+                            .find(|user| user.uuid == rep_uuid)
+                            .map(|user| format!("{} {}", user.name, user.last_name))
+                    });
+                    let managers: Vec<String> = users
+                        .iter()
+                        .filter(|user| territory.managers.contains(&user.uuid))
+                        .map(|user| format!("{} {}", user.name, user.last_name))
+                        .collect();
+                    json!({ "t": territory, "rep": rep, "managers": managers.join(", ") })
+                })
+                .collect();
+
+            let data = json!({
+              "conf": cf,
+              "territories": rows,
+              "states": get_state_tags(None),
+              "users": users,
+            });
+            let render = handlebars.render_template(&template_contents, &data)?;
+            Ok(render)
+        }
+        None => {
+            let data = json!({"conf": cf, "error": "Unable to fetch territories"});
+            let render_error = handlebars.render_template(&template_contents, &data)?;
+            Ok(render_error)
+        }
+    }
+}
+
+async fn territory_report(
+    hbs_path: Path<String>,
+    acting_user: ActingUser,
+    db: Data<Database>,
+) -> Result<String, RenderError> {
+    let territory_uuid = hbs_path.into_inner();
+    info!("Territory report for:: {}", &territory_uuid);
+
+    let mut handlebars = Handlebars::new();
+    handlebars.register_helper("currency", Box::new(currency));
+    let template_path = "territory_report";
+
+    let template_contents = match read_hbs_template(template_path) {
+        Ok(contents) => contents,
+        Err(e) => {
+            error!("Couldn't render file for territory report:: {}", e);
+            TerritoryHandlebarsError::new(e.to_string()).error
+        }
+    };
+
+    let cf: ConfVars = set_env_vars();
+
+    let territory = match <Database as TerritoryDB>::find_one(&db, territory_uuid.clone()).await {
+        Some(territory) if !territory.deleted => territory,
+        _ => {
+            let data = json!({"conf": cf, "error": "Territorio no encontrado"});
+            return handlebars.render_template(&template_contents, &data);
+        }
+    };
+
+    if let Some(scope) = find_territory_scope(&db, acting_user.user_uuid).await {
+        if !scope.managed_uuids.contains(&territory_uuid) {
+            let data = json!({"conf": cf, "error": "Este territorio no está a tu cargo"});
+            return handlebars.render_template(&template_contents, &data);
+        }
+    }
+
+    let reports = build_territory_reports(
+        &db,
+        &territory,
+        vec![LeadType::ENTERPRISE, LeadType::SCHOOL, LeadType::CLINICAL],
+    )
+    .await;
+
+    let data = json!({
+      "conf": cf,
+      "territory": territory,
+      "reports": reports,
+    });
+
+    let render = handlebars.render_template(&template_contents, &data)?;
+    Ok(render)
+}
+
+pub fn territory_html_controllers(cfg: &mut ServiceConfig) {
+    cfg.route(
+      "/htmx/territory/table",
+      post().to(
+        |db: Data<Database>| async move {
+          let territories = territory_table(db).await;
+
+          match territories {
+            Ok(t) => HttpResponse::Ok()
+              .content_type("text/html")
+              .append_header(("HX-Trigger", "activate_navbar_element"))
+              .body(t),
+            Err(e) => HttpResponse::Ok()
+              .content_type("text/html")
+              .append_header(("HX-Trigger", "error_territory"))
+              .body(
+                format!("<span class=\"icon is-small is-left\"><i class=\"fas fa-ban\"></i>Failed to load territories: {}</span>",
+                e)
+              )
+          }
+        }
+      ),
+    );
+
+    cfg.route(
+      "/htmx/territory/report/{uuid}",
+      get().to(
+        |hbs_path, acting_user, db: Data<Database>| async move {
+          let report = territory_report(hbs_path, acting_user, db).await;
+
+          match report {
+            Ok(r) => HttpResponse::Ok().content_type("text/html").body(r),
+            Err(e) => HttpResponse::Ok()
+              .content_type("text/html")
+              .append_header(("HX-Trigger", "error_territory"))
+              .body(
+                format!("<span class=\"icon is-small is-left\"><i class=\"fas fa-ban\"></i>Failed to load territory report: {}</span>",
+                e)
+              )
+          }
+        }
+      ),
+    );
+}
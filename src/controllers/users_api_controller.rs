@@ -5,21 +5,41 @@ use actix_web::{
     web::{Data, Json, Path, ServiceConfig},
     HttpResponse,
 };
-use chrono::Local;
+use chrono::Utc;
 use log::{error, info};
+use utoipa::OpenApi;
 use validator::Validate;
 
 use crate::{
     db::{config::Database, users_db::UsersDB},
-    error::user_error::UserError,
-    models::users_model::{User, UserFromJson, UserUuid},
+    error::{api_error::ApiError, user_error::UserError},
+    models::users_model::{ActingUser, User, UserFromJson, UserUuid},
     utils::{
         general_utils::{get_uuid, shuffle_id},
-        message_utils::get_validation_errors,
+        i18n_utils::RequestLocale,
+        message_utils::get_localized_validation_errors,
         pwd::pwd_hasher,
+        role_utils::is_admin,
     },
 };
 
+fn forbidden_response(message: &str) -> HttpResponse {
+    HttpResponse::Forbidden()
+        .insert_header((
+            "HX-Trigger",
+            format!("{{ \"page_error\": {:?} }}", message.to_string()),
+        ))
+        .json(ApiError::new(UserError::UserForbidden).with_message(message))
+}
+
+#[utoipa::path(
+    tag = "Users",
+    operation_id = "find_all_users",
+    responses(
+        (status = 200, description = "All users", body = Vec<User>),
+        (status = 500, description = "Users couldn't be fetched", body = ApiError),
+    )
+)]
 #[get("/users")]
 #[tracing::instrument(name = "Retrieve all users", skip(db))]
 async fn find_all(db: Data<Database>) -> Result<HttpResponse, UserError> {
@@ -29,13 +49,19 @@ async fn find_all(db: Data<Database>) -> Result<HttpResponse, UserError> {
         Some(found_users) => Ok(HttpResponse::Ok().status(StatusCode::OK).json(found_users)),
         None => {
             error!("Didn't find any User data");
-            Ok(HttpResponse::InternalServerError().json(UserUuid {
-                uuid: format!("{}", UserError::NoUsersFound),
-            }))
+            Ok(HttpResponse::InternalServerError().json(ApiError::new(UserError::NoUsersFound)))
         }
     }
 }
 
+#[utoipa::path(
+    tag = "Users",
+    operation_id = "find_deleted_users",
+    responses(
+        (status = 200, description = "Deleted users", body = Vec<User>),
+        (status = 404, description = "No users found", body = ApiError),
+    )
+)]
 #[get("/users/deleted")]
 #[tracing::instrument(name = "Find All Deleted Users", skip(db))]
 async fn find_all_deleted(db: Data<Database>) -> Result<HttpResponse, UserError> {
@@ -45,13 +71,20 @@ async fn find_all_deleted(db: Data<Database>) -> Result<HttpResponse, UserError>
         Some(found_users) => Ok(HttpResponse::Ok().status(StatusCode::OK).json(found_users)),
         None => {
             error!("Didn't find any User data");
-            Ok(HttpResponse::NotFound().json(UserUuid {
-                uuid: format!("{}", UserError::NoUsersFound),
-            }))
+            Ok(HttpResponse::NotFound().json(ApiError::new(UserError::NoUsersFound)))
         }
     }
 }
 
+#[utoipa::path(
+    tag = "Users",
+    operation_id = "find_one_user",
+    params(UserUuid),
+    responses(
+        (status = 200, description = "User found", body = User),
+        (status = 404, description = "User not found", body = ApiError),
+    )
+)]
 #[get("/users/{uuid}")]
 #[tracing::instrument(name = "Find one user", skip(db), fields(uuid = %user_id.uuid))]
 async fn find_one(db: Data<Database>, user_id: Path<UserUuid>) -> Result<HttpResponse, UserError> {
@@ -62,13 +95,21 @@ async fn find_one(db: Data<Database>, user_id: Path<UserUuid>) -> Result<HttpRes
         Some(result) => Ok(HttpResponse::Ok().status(StatusCode::OK).json(result)),
         None => {
             error!("No users found for UUID:: {:?}", &user_uuid);
-            Ok(HttpResponse::NotFound().json(UserUuid {
-                uuid: format!("{}", UserError::NoUsersFound),
-            }))
+            Ok(HttpResponse::NotFound().json(ApiError::new(UserError::NoUsersFound)))
         }
     }
 }
 
+#[utoipa::path(
+    tag = "Users",
+    operation_id = "create_user",
+    request_body = UserFromJson,
+    responses(
+        (status = 201, description = "User created", body = UserUuid),
+        (status = 403, description = "Only an ADMIN can create users", body = ApiError),
+        (status = 500, description = "Invalid or unsaved user", body = ApiError),
+    )
+)]
 #[post("/users")]
 #[tracing::instrument(
     name = "Find one user",
@@ -80,7 +121,16 @@ async fn find_one(db: Data<Database>, user_id: Path<UserUuid>) -> Result<HttpRes
         role = %user.role
     )
 )]
-async fn create(db: Data<Database>, user: Json<UserFromJson>) -> Result<HttpResponse, UserError> {
+async fn create(
+    db: Data<Database>,
+    user: Json<UserFromJson>,
+    acting_user: ActingUser,
+    locale: RequestLocale,
+) -> Result<HttpResponse, UserError> {
+    if !is_admin(&db, acting_user.user_uuid).await {
+        return Ok(forbidden_response("Solo un ADMIN puede crear usuarios"));
+    }
+
     let is_valid = user.validate();
     let new_user = user.into_inner();
 
@@ -114,6 +164,8 @@ async fn create(db: Data<Database>, user: Json<UserFromJson>) -> Result<HttpResp
                     Some(notes) => Some(notes),
                     None => None,
                 },
+                locale: new_user.locale.clone(),
+                timezone: new_user.timezone.clone(),
             };
 
             let my_user =
@@ -133,28 +185,36 @@ async fn create(db: Data<Database>, user: Json<UserFromJson>) -> Result<HttpResp
                             "HX-Trigger",
                             format!("{{ \"page_error\": \"Internal server error\" }}"),
                         ))
-                        .json(UserUuid {
-                            uuid: format!("{}", UserError::UserCreationFailure),
-                        }))
+                        .json(ApiError::new(UserError::UserCreationFailure)))
                 }
             }
         }
         Err(e) => {
             error!("Error users.create {:?}", e);
-            let key_errors_vec: Vec<String> = get_validation_errors(&e);
+            let key_errors_vec: Vec<String> = get_localized_validation_errors(&e, &locale.0);
 
             Ok(HttpResponse::InternalServerError()
                 .insert_header((
                     "HX-Trigger",
                     format!("{{ \"page_error\": {:?} }}", key_errors_vec),
                 ))
-                .json(UserUuid {
-                    uuid: format!("{}", UserError::UserCreationFailure),
-                }))
+                .json(
+                    ApiError::new(UserError::UserCreationFailure).with_field_errors(key_errors_vec),
+                ))
         }
     }
 }
 
+#[utoipa::path(
+    tag = "Users",
+    operation_id = "update_user",
+    request_body = UserFromJson,
+    responses(
+        (status = 200, description = "User updated", body = UserUuid),
+        (status = 403, description = "Only an ADMIN can edit other users", body = ApiError),
+        (status = 500, description = "Invalid or unsaved user", body = ApiError),
+    )
+)]
 #[patch("/users")]
 #[tracing::instrument(
     name = "Find one user",
@@ -166,14 +226,26 @@ async fn create(db: Data<Database>, user: Json<UserFromJson>) -> Result<HttpResp
         role = %user.role
     )
 )]
-async fn update_one(db: Data<Database>, user: Json<User>) -> Result<HttpResponse, UserError> {
+async fn update_one(
+    db: Data<Database>,
+    user: Json<User>,
+    acting_user: ActingUser,
+    locale: RequestLocale,
+) -> Result<HttpResponse, UserError> {
+    // Everybody may edit their own profile and password, only an ADMIN anyone
+    // else's, the role or the deleted flag.
+    let acting_is_admin = is_admin(&db, acting_user.user_uuid.clone()).await;
+    if !acting_is_admin && acting_user.user_uuid.as_deref() != Some(user.uuid.as_str()) {
+        return Ok(forbidden_response("Solo puedes editar tu propio usuario"));
+    }
+
     let is_valid = user.validate();
 
     match is_valid {
         Ok(_) => {
             let uuid_in_db = user.uuid.clone();
             let stored_user = Database::find_one(&db, uuid_in_db).await;
-            let date_modified = Local::now();
+            let date_modified = Utc::now();
 
             let mut mutable_pwd = "".to_string();
             info!("{}", &mutable_pwd);
@@ -202,6 +274,11 @@ async fn update_one(db: Data<Database>, user: Json<User>) -> Result<HttpResponse
                 },
             };
 
+            let (role, deleted) = match &stored_user {
+                Some(this_user) if !acting_is_admin => (this_user.role.clone(), this_user.deleted),
+                _ => (user.role.clone(), user.deleted),
+            };
+
             let date_created = match stored_user {
                 Some(this_user) => this_user.date_created,
                 None => {
@@ -215,8 +292,8 @@ async fn update_one(db: Data<Database>, user: Json<User>) -> Result<HttpResponse
                 name: user.name.clone(),
                 last_name: user.last_name.clone(),
                 email: user.email.clone(),
-                role: user.role.clone(),
-                deleted: user.deleted.clone(),
+                role: role.clone(),
+                deleted,
                 date_created,
                 date_modified: Some(date_modified),
                 password: Some(mutable_pwd),
@@ -224,7 +301,9 @@ async fn update_one(db: Data<Database>, user: Json<User>) -> Result<HttpResponse
                     Some(notes) => Some(String::from(notes)),
                     None => None,
                 },
-                role_string: Some(user.role.to_string()),
+                role_string: Some(role.to_string()),
+                locale: user.locale.clone(),
+                timezone: user.timezone.clone(),
             };
 
             let updated_user = Database::update_one(&db, my_user).await;
@@ -246,34 +325,48 @@ async fn update_one(db: Data<Database>, user: Json<User>) -> Result<HttpResponse
                                 "Couldn't find school".to_string()
                             ),
                         ))
-                        .json(UserUuid {
-                            uuid: format!("{}", UserError::NoUsersFound),
-                        }))
+                        .json(ApiError::new(UserError::NoUsersFound)))
                 }
             }
         }
         Err(e) => {
             error!("Error in users.update_one {:?}", e);
-            let key_errors_vec: Vec<String> = get_validation_errors(&e);
+            let key_errors_vec: Vec<String> = get_localized_validation_errors(&e, &locale.0);
 
             Ok(HttpResponse::InternalServerError()
                 .insert_header((
                     "HX-Trigger",
                     format!("{{ \"page_error\": {:?} }}", key_errors_vec),
                 ))
-                .json(UserUuid {
-                    uuid: format!("{}", UserError::UserCreationFailure),
-                }))
+                .json(
+                    ApiError::new(UserError::UserCreationFailure).with_field_errors(key_errors_vec),
+                ))
         }
     }
 }
 
+#[utoipa::path(
+    tag = "Users",
+    operation_id = "delete_user",
+    params(UserUuid),
+    responses(
+        (status = 200, description = "Deleted user", body = User),
+        (status = 403, description = "Only an ADMIN can delete users", body = ApiError),
+        (status = 404, description = "User not found", body = ApiError),
+        (status = 500, description = "User couldn't be deleted", body = ApiError),
+    )
+)]
 #[delete("/users/{uuid}")]
 #[tracing::instrument(name = "Delete User", skip(db), fields(user_uuid = %user_uuid.uuid))]
 async fn delete_user(
     db: Data<Database>,
     user_uuid: Path<UserUuid>,
+    acting_user: ActingUser,
 ) -> Result<HttpResponse, UserError> {
+    if !is_admin(&db, acting_user.user_uuid).await {
+        return Ok(forbidden_response("Solo un ADMIN puede eliminar usuarios"));
+    }
+
     let uuid = user_uuid.into_inner().uuid;
     let user_from_db = Database::delete_one(&db, uuid.clone()).await;
 
@@ -296,9 +389,7 @@ async fn delete_user(
                                 "Couldn't find school".to_string()
                             ),
                         ))
-                        .json(UserUuid {
-                            uuid: format!("{}", UserError::NoUsersFound),
-                        }))
+                        .json(ApiError::new(UserError::NoUsersFound)))
                 }
             }
         }
@@ -313,13 +404,15 @@ async fn delete_user(
                         "Couldn't find school".to_string()
                     ),
                 ))
-                .json(UserUuid {
-                    uuid: format!("{}", UserError::NoUsersFound),
-                }))
+                .json(ApiError::new(UserError::NoUsersFound)))
         }
     }
 }
 
+#[derive(OpenApi)]
+#[openapi(paths(find_all, find_all_deleted, find_one, create, update_one, delete_user))]
+pub struct UserApiDoc;
+
 pub fn users_api_controllers(cfg: &mut ServiceConfig) {
     cfg.service(find_all);
     cfg.service(find_all_deleted);
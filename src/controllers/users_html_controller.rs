@@ -2,24 +2,40 @@ use actix_web::{
     web::{post, Data, Path, ServiceConfig},
     HttpRequest, HttpResponse,
 };
-use chrono::Local;
+use chrono::Utc;
 use handlebars::{Handlebars, RenderError};
 use log::{error, info};
 use serde_json::json;
 
 use crate::{
     db::{config::Database, users_db::UsersDB},
-    models::users_model::*,
+    models::{locale_model::Locale, users_model::*},
     utils::{
         env::{set_env_vars, ConfVars},
         fs_utils::read_hbs_template,
         general_utils::{create_role_tags_for_users, get_roles_tag},
+        i18n_utils::{create_locale_tags, register_i18n_helpers, RequestLocale, Translate},
+        time::{create_timezone_tags, get_default_timezone},
     },
 };
 
 handlebars_helper!(str_equal: |s1: String, s2: String| s1 == s2);
 
-async fn user_edit(hbs_path: Path<String>, db: Data<Database>) -> Result<String, RenderError> {
+fn localize_role_tags(role_tags: Vec<RolesTag>, locale: &Locale) -> Vec<RolesTag> {
+    role_tags
+        .into_iter()
+        .map(|tag| RolesTag {
+            text: tag.value.label(locale),
+            ..tag
+        })
+        .collect()
+}
+
+async fn user_edit(
+    hbs_path: Path<String>,
+    db: Data<Database>,
+    locale: Locale,
+) -> Result<String, RenderError> {
     let uuid = hbs_path.into_inner();
     let my_error = format!("Unable to find uuid {}", &uuid).to_string();
     info!("Edit user screen for uuuid:: {}", &uuid);
@@ -29,6 +45,7 @@ async fn user_edit(hbs_path: Path<String>, db: Data<Database>) -> Result<String,
 
     let mut handlebars = Handlebars::new();
     handlebars.register_helper("str_equal", Box::new(str_equal));
+    register_i18n_helpers(&mut handlebars);
 
     let user_from_db: Result<User, UserHandlebarsError> = match Database::find_one(&db, uuid).await
     {
@@ -57,10 +74,21 @@ async fn user_edit(hbs_path: Path<String>, db: Data<Database>) -> Result<String,
 
     match user_from_db {
         Ok(user) => {
-            let user_role_tags = create_role_tags_for_users(user.role.clone());
+            let user_role_tags =
+                localize_role_tags(create_role_tags_for_users(user.role.clone()), &locale);
+            let locale_tags = create_locale_tags(user.locale.clone());
+            let timezone_tags = create_timezone_tags(user.timezone.as_deref());
             let cf: ConfVars = set_env_vars();
 
-            let data = json!({"conf": cf, "u": user, "roles": user_role_tags});
+            let data = json!({
+              "conf": cf,
+              "u": user,
+              "roles": user_role_tags,
+              "locales": locale_tags,
+              "timezones": timezone_tags,
+              "default_timezone": get_default_timezone().name(),
+              "locale": locale.code(),
+            });
             let render_good = handlebars.render_template(&template_contents, &data)?;
             Ok(render_good)
         }
@@ -71,8 +99,9 @@ async fn user_edit(hbs_path: Path<String>, db: Data<Database>) -> Result<String,
     }
 }
 
-async fn user_new() -> Result<String, RenderError> {
-    let handlebars = Handlebars::new();
+async fn user_new(locale: Locale) -> Result<String, RenderError> {
+    let mut handlebars = Handlebars::new();
+    register_i18n_helpers(&mut handlebars);
     let template_path = "user_new";
 
     let template_contents = match read_hbs_template(&template_path) {
@@ -83,9 +112,16 @@ async fn user_new() -> Result<String, RenderError> {
         }
     };
 
-    let role_tags = get_roles_tag();
+    let role_tags = localize_role_tags(get_roles_tag(), &locale);
     let cf: ConfVars = set_env_vars();
-    let data = json!({"conf": cf, "roles": role_tags});
+    let data = json!({
+      "conf": cf,
+      "roles": role_tags,
+      "locales": create_locale_tags(None),
+      "timezones": create_timezone_tags(None),
+      "default_timezone": get_default_timezone().name(),
+      "locale": locale.code(),
+    });
     let hb_render = handlebars.render_template(&template_contents, &data)?;
 
     Ok(hb_render)
@@ -131,7 +167,7 @@ async fn users_delete_modal(
     let uuid = hbs_path.into_inner();
     let handlebars = Handlebars::new();
     let template_path = "user_delete_modal";
-    let date_now = Local::now();
+    let date_now = Utc::now();
     let my_error = format!("Unable to find uuid {}", &uuid).to_string();
 
     let user_from_db: Result<User, UserHandlebarsError> =
@@ -198,8 +234,8 @@ pub fn user_html_controllers(cfg: &mut ServiceConfig) {
     cfg.route(
         "/htmx/user/edit/{uuid}",
         post().to(
-            |_req: HttpRequest, hbs_path, db: Data<Database>| async move {
-                let user_editor = user_edit(hbs_path, db).await;
+            |_req: HttpRequest, hbs_path, db: Data<Database>, locale: RequestLocale| async move {
+                let user_editor = user_edit(hbs_path, db, locale.0).await;
                 match user_editor {
                     Ok(ue) => HttpResponse::Ok().content_type("text/html").body(ue),
                     Err(e) => HttpResponse::Ok()
@@ -241,8 +277,8 @@ pub fn user_html_controllers(cfg: &mut ServiceConfig) {
     cfg.route(
       "/htmx/user/new",
       post().to(
-        || async move {
-          let new_user_form = user_new().await;
+        |locale: RequestLocale| async move {
+          let new_user_form = user_new(locale.0).await;
           match new_user_form {
             Ok(uf) => HttpResponse::Ok()
               .content_type("text/html")
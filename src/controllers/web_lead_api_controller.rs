@@ -0,0 +1,131 @@
+use actix_web::{
+    http::header::{LOCATION, RETRY_AFTER},
+    post, rt,
+    web::{Data, Form, Json, ServiceConfig},
+    Either, HttpRequest, HttpResponse,
+};
+use log::{error, warn};
+use utoipa::OpenApi;
+use validator::Validate;
+
+use crate::{
+    constants::connection::set_environment_variable,
+    db::config::Database,
+    error::{api_error::ApiError, web_lead_error::WebLeadError},
+    models::{
+        sales_model::LeadType,
+        web_lead_model::{WebLeadForm, WebLeadReceived},
+    },
+    utils::{
+        i18n_utils::RequestLocale,
+        message_utils::get_localized_validation_errors,
+        web_lead_utils::{
+            check_rate_limit, get_client_ip, is_honeypot_filled, notify_web_lead, save_web_lead,
+            verify_captcha,
+        },
+    },
+};
+
+fn received_response(is_form_post: bool) -> HttpResponse {
+    let redirect_url = set_environment_variable("WEB_LEAD_REDIRECT_URL", "");
+
+    if is_form_post && !redirect_url.is_empty() {
+        return HttpResponse::SeeOther()
+            .insert_header((LOCATION, redirect_url))
+            .finish();
+    }
+
+    HttpResponse::Created().json(WebLeadReceived { received: true })
+}
+
+#[utoipa::path(
+    tag = "Web leads",
+    operation_id = "capture_web_lead",
+    request_body(
+        content((WebLeadForm = "application/x-www-form-urlencoded"), (WebLeadForm = "application/json")),
+        description = "Website contact form, lead_type defaults to ENTERPRISE"
+    ),
+    responses(
+        (status = 201, description = "Lead received", body = WebLeadReceived),
+        (status = 303, description = "Form post received, redirects to WEB_LEAD_REDIRECT_URL"),
+        (status = 400, description = "Invalid form or captcha", body = ApiError),
+        (status = 429, description = "Too many submissions from this client", body = ApiError),
+        (status = 500, description = "Lead couldn't be saved", body = ApiError),
+    ),
+    security(())
+)]
+#[post("/web_leads")]
+#[tracing::instrument(name = "Post Web Lead", skip(db, req, body))]
+async fn capture(
+    db: Data<Database>,
+    req: HttpRequest,
+    body: Either<Form<WebLeadForm>, Json<WebLeadForm>>,
+    locale: RequestLocale,
+) -> Result<HttpResponse, WebLeadError> {
+    let (form, is_form_post) = match body {
+        Either::Left(form) => (form.into_inner(), true),
+        Either::Right(json) => (json.into_inner(), false),
+    };
+    let remote_ip = get_client_ip(&req);
+
+    if let Err(retry_after) = check_rate_limit(remote_ip.as_deref().unwrap_or("unknown")) {
+        warn!("Web lead rate limit reached for {:?}", &remote_ip);
+        return Ok(HttpResponse::TooManyRequests()
+            .insert_header((RETRY_AFTER, retry_after.to_string()))
+            .json(ApiError::new(WebLeadError::TooManyWebLeads)));
+    }
+
+    if is_honeypot_filled(&form) {
+        warn!("Web lead honeypot filled from {:?}, discarding", &remote_ip);
+        return Ok(received_response(is_form_post));
+    }
+
+    if let Err(e) = form.validate() {
+        error!("Error web_lead.capture {:?}", e);
+        let key_errors_vec: Vec<String> = get_localized_validation_errors(&e, &locale.0);
+        return Ok(HttpResponse::BadRequest()
+            .json(ApiError::new(WebLeadError::WebLeadRejected).with_field_errors(key_errors_vec)));
+    }
+
+    if !verify_captcha(form.captcha_token.as_deref(), remote_ip.as_deref()).await {
+        warn!("Web lead captcha failed from {:?}", &remote_ip);
+        return Ok(
+            HttpResponse::BadRequest().json(ApiError::new(WebLeadError::WebLeadCaptchaFailed))
+        );
+    }
+
+    let lead_type = form.lead_type.clone().unwrap_or(LeadType::ENTERPRISE);
+
+    match save_web_lead(&db, &lead_type, &form).await {
+        Ok(Some((lead_uuid, lead))) => {
+            rt::spawn(notify_web_lead(
+                db.clone(),
+                lead_type,
+                lead_uuid,
+                lead,
+                form.email.trim().to_string(),
+            ));
+            Ok(received_response(is_form_post))
+        }
+        Ok(None) => {
+            error!("Error [POST] /web_leads");
+            Ok(HttpResponse::InternalServerError()
+                .json(ApiError::new(WebLeadError::WebLeadCreationFailure)))
+        }
+        Err(e) => {
+            error!("Error web_lead.capture {:?}", e);
+            let key_errors_vec: Vec<String> = get_localized_validation_errors(&e, &locale.0);
+            Ok(HttpResponse::BadRequest().json(
+                ApiError::new(WebLeadError::WebLeadRejected).with_field_errors(key_errors_vec),
+            ))
+        }
+    }
+}
+
+#[derive(OpenApi)]
+#[openapi(paths(capture))]
+pub struct WebLeadApiDoc;
+
+pub fn web_lead_api_controllers(cfg: &mut ServiceConfig) {
+    cfg.service(capture);
+}
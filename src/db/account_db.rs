@@ -0,0 +1,185 @@
+use actix_web::web::Data;
+use async_trait::async_trait;
+use chrono::Utc;
+use log::error;
+use surrealdb::{opt::PatchOp, Error};
+
+use crate::{
+    db::{config::Database, contact_db::CONTACT_TABLE},
+    models::account_model::{Account, Contact},
+    utils::{crud::*, general_utils::get_uuid},
+};
+
+pub const ACCOUNT_TABLE: &str = "accounts";
+
+#[async_trait]
+pub trait AccountDB {
+    async fn find_one(db: &Data<Database>, uuid: String) -> Option<Account>;
+    async fn find_by_lead(db: &Data<Database>, lead_uuid: String) -> Option<Account>;
+    async fn update_one(db: &Data<Database>, account: Account) -> Option<Account>;
+    async fn find_all_active(db: &Data<Database>) -> Option<Vec<Account>>;
+    async fn convert_lead(
+        db: &Data<Database>,
+        lead_table: &str,
+        account: Account,
+        contacts: Vec<Contact>,
+    ) -> Option<Account>;
+    async fn delete_one(
+        db: &Data<Database>,
+        uuid: String,
+        modified_by: Option<String>,
+    ) -> Option<Account>;
+}
+
+#[async_trait]
+impl AccountDB for Database {
+    async fn find_one(db: &Data<Database>, uuid: String) -> Option<Account> {
+        util_find_one(db, uuid, ACCOUNT_TABLE).await
+    }
+
+    async fn find_by_lead(db: &Data<Database>, lead_uuid: String) -> Option<Account> {
+        let query = format!(
+            "SELECT * FROM {} WHERE lead_uuid = $lead_uuid AND deleted = false LIMIT 1",
+            ACCOUNT_TABLE
+        );
+        let account = db.client.query(query).bind(("lead_uuid", lead_uuid)).await;
+
+        match account {
+            Ok(mut result) => match result.take(0) {
+                Ok(lead_account) => lead_account,
+                Err(e) => {
+                    error!("Failed to retrieve account for lead {}", e);
+                    None
+                }
+            },
+            Err(e) => {
+                error!("Failed to retrieve account for lead {}", e);
+                None
+            }
+        }
+    }
+
+    async fn update_one(db: &Data<Database>, account: Account) -> Option<Account> {
+        let account_id = match account.uuid.clone() {
+            Some(this_uuid) => this_uuid,
+            None => get_uuid(),
+        };
+        util_update_one(db, account, account_id, ACCOUNT_TABLE).await
+    }
+
+    async fn find_all_active(db: &Data<Database>) -> Option<Vec<Account>> {
+        let query = format!(
+            "SELECT * FROM {} WHERE deleted = false ORDER BY name ASC",
+            ACCOUNT_TABLE
+        );
+        let accounts = db.client.query(query).await;
+
+        match accounts {
+            Ok(mut result) => match result.take(0) {
+                Ok(active_accounts) => Some(active_accounts),
+                Err(e) => {
+                    error!("Failed to retrieve accounts {}", e);
+                    None
+                }
+            },
+            Err(e) => {
+                error!("Failed to retrieve accounts {}", e);
+                None
+            }
+        }
+    }
+
+    async fn convert_lead(
+        db: &Data<Database>,
+        lead_table: &str,
+        account: Account,
+        contacts: Vec<Contact>,
+    ) -> Option<Account> {
+        let account_uuid = account.uuid.clone().unwrap_or_default();
+        let mut statements = vec![
+            "BEGIN TRANSACTION;".to_string(),
+            format!(
+                "CREATE type::thing('{}', $account_uuid) CONTENT $account;",
+                ACCOUNT_TABLE
+            ),
+        ];
+
+        for index in 0..contacts.len() {
+            statements.push(format!(
+                "UPSERT type::thing('{}', $contact_uuid_{}) CONTENT $contact_{};",
+                CONTACT_TABLE, index, index
+            ));
+        }
+        statements.push(format!(
+            "UPDATE type::thing('{}', $lead_uuid) SET account_uuid = $account_uuid, modified_by = $modified_by, date_modified = $date_modified;",
+            lead_table
+        ));
+        statements.push("COMMIT TRANSACTION;".to_string());
+
+        let mut query = db
+            .client
+            .query(statements.join("\n"))
+            .bind(("account_uuid", account_uuid))
+            .bind(("lead_uuid", account.lead_uuid.clone()))
+            .bind(("modified_by", account.created_by.clone()))
+            .bind(("date_modified", Utc::now()))
+            .bind(("account", account.clone()));
+
+        for (index, contact) in contacts.into_iter().enumerate() {
+            query = query
+                .bind((
+                    format!("contact_uuid_{}", index),
+                    contact.uuid.clone().unwrap_or_default(),
+                ))
+                .bind((format!("contact_{}", index), contact));
+        }
+
+        match query.await {
+            Ok(response) => match response.check() {
+                Ok(_) => Some(account),
+                Err(e) => {
+                    error!("Lead conversion was rolled back:: {}", e);
+                    None
+                }
+            },
+            Err(e) => {
+                error!("Failed to convert lead:: {}", e);
+                None
+            }
+        }
+    }
+
+    async fn delete_one(
+        db: &Data<Database>,
+        uuid: String,
+        modified_by: Option<String>,
+    ) -> Option<Account> {
+        let account_exists: Result<Option<Account>, Error> =
+            db.client.select((ACCOUNT_TABLE, uuid.clone())).await;
+
+        match account_exists {
+            Ok(Some(_)) => {
+                let deleted_account: Result<Option<Account>, Error> = db
+                    .client
+                    .update((ACCOUNT_TABLE, &uuid))
+                    .patch(PatchOp::replace("/deleted", true))
+                    .patch(PatchOp::replace("/modified_by", modified_by))
+                    .patch(PatchOp::replace("/date_modified", Utc::now()))
+                    .await;
+
+                match deleted_account {
+                    Ok(account) => account,
+                    Err(e) => {
+                        error!("Failed to delete account:: {}", e);
+                        None
+                    }
+                }
+            }
+            Ok(None) => None,
+            Err(e) => {
+                error!("Failed to delete account: {}", e);
+                None
+            }
+        }
+    }
+}
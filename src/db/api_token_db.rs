@@ -0,0 +1,113 @@
+use actix_web::web::Data;
+use async_trait::async_trait;
+use chrono::Utc;
+use log::error;
+use surrealdb::{opt::PatchOp, Error};
+
+use crate::{
+    db::config::Database,
+    models::api_token_model::ApiToken,
+    utils::{crud::*, general_utils::get_uuid},
+};
+
+pub const API_TOKEN_TABLE: &str = "api_tokens";
+
+#[async_trait]
+pub trait ApiTokenDB {
+    async fn find_one(db: &Data<Database>, uuid: String) -> Option<ApiToken>;
+    async fn add_one(db: &Data<Database>, new_token: ApiToken) -> Option<ApiToken>;
+    async fn find_by_user(db: &Data<Database>, user_uuid: String) -> Option<Vec<ApiToken>>;
+    async fn touch_last_used(db: &Data<Database>, uuid: String) -> Option<ApiToken>;
+    async fn revoke_one(
+        db: &Data<Database>,
+        uuid: String,
+        modified_by: Option<String>,
+    ) -> Option<ApiToken>;
+}
+
+#[async_trait]
+impl ApiTokenDB for Database {
+    async fn find_one(db: &Data<Database>, uuid: String) -> Option<ApiToken> {
+        util_find_one(db, uuid, API_TOKEN_TABLE).await
+    }
+
+    async fn add_one(db: &Data<Database>, new_token: ApiToken) -> Option<ApiToken> {
+        let token_id = match new_token.uuid.clone() {
+            Some(this_uuid) => this_uuid,
+            None => get_uuid(),
+        };
+        util_add_one(db, new_token, token_id, API_TOKEN_TABLE).await
+    }
+
+    async fn find_by_user(db: &Data<Database>, user_uuid: String) -> Option<Vec<ApiToken>> {
+        let query = format!(
+            "SELECT * FROM {} WHERE user_uuid = $user_uuid ORDER BY date_created DESC",
+            API_TOKEN_TABLE
+        );
+        let tokens = db.client.query(query).bind(("user_uuid", user_uuid)).await;
+
+        match tokens {
+            Ok(mut result) => match result.take(0) {
+                Ok(user_tokens) => Some(user_tokens),
+                Err(e) => {
+                    error!("Failed to retrieve api tokens {}", e);
+                    None
+                }
+            },
+            Err(e) => {
+                error!("Failed to retrieve api tokens {}", e);
+                None
+            }
+        }
+    }
+
+    async fn touch_last_used(db: &Data<Database>, uuid: String) -> Option<ApiToken> {
+        let touched_token: Result<Option<ApiToken>, Error> = db
+            .client
+            .update((API_TOKEN_TABLE, &uuid))
+            .patch(PatchOp::replace("/last_used", Utc::now()))
+            .await;
+
+        match touched_token {
+            Ok(token) => token,
+            Err(e) => {
+                error!("Failed to update api token last use:: {}", e);
+                None
+            }
+        }
+    }
+
+    async fn revoke_one(
+        db: &Data<Database>,
+        uuid: String,
+        modified_by: Option<String>,
+    ) -> Option<ApiToken> {
+        let token_exists: Result<Option<ApiToken>, Error> =
+            db.client.select((API_TOKEN_TABLE, uuid.clone())).await;
+
+        match token_exists {
+            Ok(Some(_)) => {
+                let revoked_token: Result<Option<ApiToken>, Error> = db
+                    .client
+                    .update((API_TOKEN_TABLE, &uuid))
+                    .patch(PatchOp::replace("/revoked", true))
+                    .patch(PatchOp::replace("/modified_by", modified_by))
+                    .patch(PatchOp::replace("/date_modified", Utc::now()))
+                    .await;
+
+                match revoked_token {
+                    Ok(token) => token,
+                    Err(e) => {
+                        error!("Failed to revoke api token:: {}", e);
+                        None
+                    }
+                }
+            }
+            Ok(None) => None,
+            Err(e) => {
+                error!("Failed to revoke api token: {}", e);
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,96 @@
+use actix_web::web::Data;
+use async_trait::async_trait;
+use chrono::Utc;
+use log::error;
+use surrealdb::{opt::PatchOp, Error};
+
+use crate::{
+    db::config::Database,
+    models::attachment_model::Attachment,
+    utils::{crud::*, general_utils::get_uuid},
+};
+
+const ATTACHMENT_TABLE: &str = "attachments";
+
+#[async_trait]
+pub trait AttachmentDB {
+    async fn find_one(db: &Data<Database>, uuid: String) -> Option<Attachment>;
+    async fn add_one(db: &Data<Database>, new_attachment: Attachment) -> Option<Attachment>;
+    async fn find_by_lead(db: &Data<Database>, lead_uuid: String) -> Option<Vec<Attachment>>;
+    async fn delete_one(
+        db: &Data<Database>,
+        uuid: String,
+        modified_by: Option<String>,
+    ) -> Option<Attachment>;
+}
+
+#[async_trait]
+impl AttachmentDB for Database {
+    async fn find_one(db: &Data<Database>, uuid: String) -> Option<Attachment> {
+        util_find_one(db, uuid, ATTACHMENT_TABLE).await
+    }
+
+    async fn add_one(db: &Data<Database>, new_attachment: Attachment) -> Option<Attachment> {
+        let attachment_id = match new_attachment.uuid.clone() {
+            Some(this_uuid) => this_uuid,
+            None => get_uuid(),
+        };
+        util_add_one(db, new_attachment, attachment_id, ATTACHMENT_TABLE).await
+    }
+
+    async fn find_by_lead(db: &Data<Database>, lead_uuid: String) -> Option<Vec<Attachment>> {
+        let query = format!(
+            "SELECT * FROM {} WHERE deleted = false AND lead_uuid = $lead_uuid ORDER BY date_created DESC",
+            ATTACHMENT_TABLE
+        );
+        let attachments = db.client.query(query).bind(("lead_uuid", lead_uuid)).await;
+
+        match attachments {
+            Ok(mut result) => match result.take(0) {
+                Ok(lead_attachments) => Some(lead_attachments),
+                Err(e) => {
+                    error!("Failed to retrieve attachments {}", e);
+                    None
+                }
+            },
+            Err(e) => {
+                error!("Failed to retrieve attachments {}", e);
+                None
+            }
+        }
+    }
+
+    async fn delete_one(
+        db: &Data<Database>,
+        uuid: String,
+        modified_by: Option<String>,
+    ) -> Option<Attachment> {
+        let attachment_exists: Result<Option<Attachment>, Error> =
+            db.client.select((ATTACHMENT_TABLE, uuid.clone())).await;
+
+        match attachment_exists {
+            Ok(Some(_)) => {
+                let deleted_attachment: Result<Option<Attachment>, Error> = db
+                    .client
+                    .update((ATTACHMENT_TABLE, &uuid))
+                    .patch(PatchOp::replace("/deleted", true))
+                    .patch(PatchOp::replace("/modified_by", modified_by))
+                    .patch(PatchOp::replace("/date_modified", Utc::now()))
+                    .await;
+
+                match deleted_attachment {
+                    Ok(attachment) => attachment,
+                    Err(e) => {
+                        error!("Failed to delete attachment:: {}", e);
+                        None
+                    }
+                }
+            }
+            Ok(None) => None,
+            Err(e) => {
+                error!("Failed to delete attachment: {}", e);
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use actix_web::web::Data;
+use async_trait::async_trait;
+use chrono::Utc;
+use log::error;
+use surrealdb::{opt::PatchOp, Error};
+
+use crate::{
+    db::config::Database,
+    models::campaign_model::Campaign,
+    utils::{crud::*, general_utils::get_uuid},
+};
+
+pub const CAMPAIGN_TABLE: &str = "campaigns";
+
+#[async_trait]
+pub trait CampaignDB {
+    async fn find_one(db: &Data<Database>, uuid: String) -> Option<Campaign>;
+    async fn add_one(db: &Data<Database>, new_campaign: Campaign) -> Option<Campaign>;
+    async fn update_one(db: &Data<Database>, campaign: Campaign) -> Option<Campaign>;
+    async fn find_all_active(db: &Data<Database>) -> Option<Vec<Campaign>>;
+    async fn delete_one(
+        db: &Data<Database>,
+        uuid: String,
+        modified_by: Option<String>,
+    ) -> Option<Campaign>;
+}
+
+#[async_trait]
+impl CampaignDB for Database {
+    async fn find_one(db: &Data<Database>, uuid: String) -> Option<Campaign> {
+        util_find_one(db, uuid, CAMPAIGN_TABLE).await
+    }
+
+    async fn add_one(db: &Data<Database>, new_campaign: Campaign) -> Option<Campaign> {
+        let campaign_id = match new_campaign.uuid.clone() {
+            Some(this_uuid) => this_uuid,
+            None => get_uuid(),
+        };
+        util_add_one(db, new_campaign, campaign_id, CAMPAIGN_TABLE).await
+    }
+
+    async fn update_one(db: &Data<Database>, campaign: Campaign) -> Option<Campaign> {
+        let campaign_id = match campaign.uuid.clone() {
+            Some(this_uuid) => this_uuid,
+            None => get_uuid(),
+        };
+        util_update_one(db, campaign, campaign_id, CAMPAIGN_TABLE).await
+    }
+
+    async fn find_all_active(db: &Data<Database>) -> Option<Vec<Campaign>> {
+        let query = format!(
+            "SELECT * FROM {} WHERE deleted = false ORDER BY start_date DESC",
+            CAMPAIGN_TABLE
+        );
+        let campaigns = db.client.query(query).await;
+
+        match campaigns {
+            Ok(mut result) => match result.take(0) {
+                Ok(active_campaigns) => Some(active_campaigns),
+                Err(e) => {
+                    error!("Failed to retrieve campaigns {}", e);
+                    None
+                }
+            },
+            Err(e) => {
+                error!("Failed to retrieve campaigns {}", e);
+                None
+            }
+        }
+    }
+
+    async fn delete_one(
+        db: &Data<Database>,
+        uuid: String,
+        modified_by: Option<String>,
+    ) -> Option<Campaign> {
+        let campaign_exists: Result<Option<Campaign>, Error> =
+            db.client.select((CAMPAIGN_TABLE, uuid.clone())).await;
+
+        match campaign_exists {
+            Ok(Some(_)) => {
+                let deleted_campaign: Result<Option<Campaign>, Error> = db
+                    .client
+                    .update((CAMPAIGN_TABLE, &uuid))
+                    .patch(PatchOp::replace("/deleted", true))
+                    .patch(PatchOp::replace("/modified_by", modified_by))
+                    .patch(PatchOp::replace("/date_modified", Utc::now()))
+                    .await;
+
+                match deleted_campaign {
+                    Ok(campaign) => campaign,
+                    Err(e) => {
+                        error!("Failed to delete campaign:: {}", e);
+                        None
+                    }
+                }
+            }
+            Ok(None) => None,
+            Err(e) => {
+                error!("Failed to delete campaign: {}", e);
+                None
+            }
+        }
+    }
+}
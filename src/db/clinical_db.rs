@@ -1,15 +1,16 @@
 use actix_web::web::Data;
 use async_trait::async_trait;
-use chrono::Local;
+use chrono::Utc;
 use log::error;
 use surrealdb::{opt::PatchOp, Error};
 
 use crate::db::config::Database;
 use crate::models::clinical_model::Clinical;
+use crate::models::lead_model::LeadFilter;
 use crate::utils::crud::*;
 use crate::utils::general_utils::get_uuid;
 
-const CLINICAL_TABLE: &str = "clinical";
+pub const CLINICAL_TABLE: &str = "clinical";
 
 #[async_trait]
 pub trait ClinicalDB {
@@ -19,6 +20,7 @@ pub trait ClinicalDB {
     async fn update_one(db: &Data<Database>, clinical: Clinical) -> Option<Clinical>;
     async fn find_all_non_deleted(db: &Data<Database>) -> Option<Vec<Clinical>>;
     async fn find_all_deleted(db: &Data<Database>) -> Option<Vec<Clinical>>;
+    async fn find_filtered(db: &Data<Database>, filter: &LeadFilter) -> Option<Vec<Clinical>>;
     async fn delete_one(db: &Data<Database>, uuid: String) -> Option<Clinical>;
 }
 
@@ -54,6 +56,10 @@ impl ClinicalDB for Database {
         util_find_all_non_deleted(&db, CLINICAL_TABLE).await
     }
 
+    async fn find_filtered(db: &Data<Database>, filter: &LeadFilter) -> Option<Vec<Clinical>> {
+        util_find_filtered(db, CLINICAL_TABLE, filter).await
+    }
+
     async fn find_all_deleted(db: &Data<Database>) -> Option<Vec<Clinical>> {
         let surreal_query = format!("SELECT * FROM {} WHERE deleted = true", CLINICAL_TABLE);
         let clinics = db.client.query(surreal_query).await;
@@ -82,7 +88,7 @@ impl ClinicalDB for Database {
                 .client
                 .update((CLINICAL_TABLE, uuid))
                 .patch(PatchOp::replace("/deleted", true))
-                .patch(PatchOp::replace("/date_modified", Local::now()))
+                .patch(PatchOp::replace("/date_modified", Utc::now()))
                 .await;
 
             match clinic {
@@ -0,0 +1,217 @@
+use actix_web::web::Data;
+use async_trait::async_trait;
+use chrono::Utc;
+use log::error;
+use surrealdb::{opt::PatchOp, Error};
+
+use crate::{
+    db::config::Database,
+    models::{account_model::Contact, lead_model::LeadPerson},
+    utils::{crud::*, general_utils::get_uuid},
+};
+
+pub const CONTACT_TABLE: &str = "contacts";
+
+#[async_trait]
+pub trait ContactDB {
+    async fn find_one(db: &Data<Database>, uuid: String) -> Option<Contact>;
+    async fn add_one(db: &Data<Database>, new_contact: Contact) -> Option<Contact>;
+    async fn update_one(db: &Data<Database>, contact: Contact) -> Option<Contact>;
+    async fn find_by_account(db: &Data<Database>, account_uuid: String) -> Option<Vec<Contact>>;
+    async fn find_by_lead(db: &Data<Database>, lead_uuid: String) -> Option<Vec<Contact>>;
+    async fn unset_primary(db: &Data<Database>, account_uuid: String, keep_uuid: String) -> bool;
+    async fn unset_lead_primary(db: &Data<Database>, lead_uuid: String, keep_uuid: String) -> bool;
+    async fn find_leads_without_contacts(
+        db: &Data<Database>,
+        table_name: &str,
+    ) -> Option<Vec<LeadPerson>>;
+    async fn delete_one(
+        db: &Data<Database>,
+        uuid: String,
+        modified_by: Option<String>,
+    ) -> Option<Contact>;
+}
+
+#[async_trait]
+impl ContactDB for Database {
+    async fn find_one(db: &Data<Database>, uuid: String) -> Option<Contact> {
+        util_find_one(db, uuid, CONTACT_TABLE).await
+    }
+
+    async fn add_one(db: &Data<Database>, new_contact: Contact) -> Option<Contact> {
+        let contact_id = match new_contact.uuid.clone() {
+            Some(this_uuid) => this_uuid,
+            None => get_uuid(),
+        };
+        util_add_one(db, new_contact, contact_id, CONTACT_TABLE).await
+    }
+
+    async fn update_one(db: &Data<Database>, contact: Contact) -> Option<Contact> {
+        let contact_id = match contact.uuid.clone() {
+            Some(this_uuid) => this_uuid,
+            None => get_uuid(),
+        };
+        util_update_one(db, contact, contact_id, CONTACT_TABLE).await
+    }
+
+    async fn find_by_account(db: &Data<Database>, account_uuid: String) -> Option<Vec<Contact>> {
+        let query = format!(
+            "SELECT * FROM {} WHERE account_uuid = $account_uuid AND deleted = false ORDER BY is_primary DESC, name ASC",
+            CONTACT_TABLE
+        );
+        let contacts = db
+            .client
+            .query(query)
+            .bind(("account_uuid", account_uuid))
+            .await;
+
+        match contacts {
+            Ok(mut result) => match result.take(0) {
+                Ok(account_contacts) => Some(account_contacts),
+                Err(e) => {
+                    error!("Failed to retrieve contacts {}", e);
+                    None
+                }
+            },
+            Err(e) => {
+                error!("Failed to retrieve contacts {}", e);
+                None
+            }
+        }
+    }
+
+    async fn find_by_lead(db: &Data<Database>, lead_uuid: String) -> Option<Vec<Contact>> {
+        let query = format!(
+            "SELECT * FROM {} WHERE lead_uuid = $lead_uuid AND deleted = false ORDER BY is_primary DESC, name ASC",
+            CONTACT_TABLE
+        );
+        let contacts = db.client.query(query).bind(("lead_uuid", lead_uuid)).await;
+
+        match contacts {
+            Ok(mut result) => match result.take(0) {
+                Ok(lead_contacts) => Some(lead_contacts),
+                Err(e) => {
+                    error!("Failed to retrieve lead contacts {}", e);
+                    None
+                }
+            },
+            Err(e) => {
+                error!("Failed to retrieve lead contacts {}", e);
+                None
+            }
+        }
+    }
+
+    async fn unset_primary(db: &Data<Database>, account_uuid: String, keep_uuid: String) -> bool {
+        let query = format!(
+            "UPDATE {} SET is_primary = false WHERE account_uuid = $account_uuid AND uuid != $keep_uuid",
+            CONTACT_TABLE
+        );
+        let updated = db
+            .client
+            .query(query)
+            .bind(("account_uuid", account_uuid))
+            .bind(("keep_uuid", keep_uuid))
+            .await;
+
+        match updated {
+            Ok(response) => match response.check() {
+                Ok(_) => true,
+                Err(e) => {
+                    error!("Failed to update primary contact {}", e);
+                    false
+                }
+            },
+            Err(e) => {
+                error!("Failed to update primary contact {}", e);
+                false
+            }
+        }
+    }
+
+    async fn unset_lead_primary(db: &Data<Database>, lead_uuid: String, keep_uuid: String) -> bool {
+        let query = format!(
+            "UPDATE {} SET is_primary = false WHERE lead_uuid = $lead_uuid AND uuid != $keep_uuid",
+            CONTACT_TABLE
+        );
+        let updated = db
+            .client
+            .query(query)
+            .bind(("lead_uuid", lead_uuid))
+            .bind(("keep_uuid", keep_uuid))
+            .await;
+
+        match updated {
+            Ok(response) => match response.check() {
+                Ok(_) => true,
+                Err(e) => {
+                    error!("Failed to update primary lead contact {}", e);
+                    false
+                }
+            },
+            Err(e) => {
+                error!("Failed to update primary lead contact {}", e);
+                false
+            }
+        }
+    }
+
+    async fn find_leads_without_contacts(
+        db: &Data<Database>,
+        table_name: &str,
+    ) -> Option<Vec<LeadPerson>> {
+        let query = format!(
+            "SELECT uuid, name, last_name, email, phone FROM {} WHERE uuid NOTINSIDE (SELECT VALUE lead_uuid FROM {} WHERE lead_uuid != NONE)",
+            table_name, CONTACT_TABLE
+        );
+        let leads = db.client.query(query).await;
+
+        match leads {
+            Ok(mut result) => match result.take(0) {
+                Ok(lead_people) => Some(lead_people),
+                Err(e) => {
+                    error!("Failed to retrieve leads without contacts {}", e);
+                    None
+                }
+            },
+            Err(e) => {
+                error!("Failed to retrieve leads without contacts {}", e);
+                None
+            }
+        }
+    }
+
+    async fn delete_one(
+        db: &Data<Database>,
+        uuid: String,
+        modified_by: Option<String>,
+    ) -> Option<Contact> {
+        let contact_exists: Result<Option<Contact>, Error> =
+            db.client.select((CONTACT_TABLE, uuid.clone())).await;
+
+        match contact_exists {
+            Ok(Some(_)) => {
+                let deleted_contact: Result<Option<Contact>, Error> = db
+                    .client
+                    .update((CONTACT_TABLE, &uuid))
+                    .patch(PatchOp::replace("/deleted", true))
+                    .patch(PatchOp::replace("/modified_by", modified_by))
+                    .patch(PatchOp::replace("/date_modified", Utc::now()))
+                    .await;
+
+                match deleted_contact {
+                    Ok(contact) => contact,
+                    Err(e) => {
+                        error!("Failed to delete contact:: {}", e);
+                        None
+                    }
+                }
+            }
+            Ok(None) => None,
+            Err(e) => {
+                error!("Failed to delete contact: {}", e);
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,168 @@
+use actix_web::web::Data;
+use async_trait::async_trait;
+use chrono::Utc;
+use log::error;
+use surrealdb::{opt::PatchOp, Error};
+
+use crate::{
+    db::config::Database,
+    models::email_template_model::EmailTemplate,
+    utils::{crud::*, general_utils::get_uuid},
+};
+
+const EMAIL_TEMPLATE_TABLE: &str = "email_templates";
+
+#[async_trait]
+pub trait EmailTemplateDB {
+    async fn find_one(db: &Data<Database>, uuid: String) -> Option<EmailTemplate>;
+    async fn add_one(db: &Data<Database>, new_template: EmailTemplate) -> Option<EmailTemplate>;
+    async fn add_version(
+        db: &Data<Database>,
+        retired_uuid: String,
+        new_version: EmailTemplate,
+    ) -> Option<EmailTemplate>;
+    async fn find_all_current(db: &Data<Database>) -> Option<Vec<EmailTemplate>>;
+    async fn find_versions(db: &Data<Database>, template_key: String)
+        -> Option<Vec<EmailTemplate>>;
+    async fn delete_one(db: &Data<Database>, uuid: String) -> Option<EmailTemplate>;
+}
+
+#[async_trait]
+impl EmailTemplateDB for Database {
+    async fn find_one(db: &Data<Database>, uuid: String) -> Option<EmailTemplate> {
+        util_find_one(db, uuid, EMAIL_TEMPLATE_TABLE).await
+    }
+
+    async fn add_one(db: &Data<Database>, new_template: EmailTemplate) -> Option<EmailTemplate> {
+        let template_id = match new_template.uuid.clone() {
+            Some(this_uuid) => this_uuid,
+            None => get_uuid(),
+        };
+        util_add_one(db, new_template, template_id, EMAIL_TEMPLATE_TABLE).await
+    }
+
+    // Retiring the current version and storing its successor go together, so a
+    // template is never left without a current version. When the retired one is
+    // no longer current, someone else saved first and nothing is written.
+    async fn add_version(
+        db: &Data<Database>,
+        retired_uuid: String,
+        new_version: EmailTemplate,
+    ) -> Option<EmailTemplate> {
+        let template_id = match new_version.uuid.clone() {
+            Some(this_uuid) => this_uuid,
+            None => get_uuid(),
+        };
+        let new_version = EmailTemplate::new(template_id.clone(), new_version);
+        let query = format!(
+            "BEGIN TRANSACTION;
+            LET $retired = UPDATE type::thing('{}', $retired_uuid) SET is_current = false, date_modified = $date_modified WHERE is_current = true;
+            IF array::len($retired) = 0 {{ THROW 'Email template version is not current' }};
+            CREATE type::thing('{}', $new_uuid) CONTENT $new_version;
+            COMMIT TRANSACTION;",
+            EMAIL_TEMPLATE_TABLE, EMAIL_TEMPLATE_TABLE
+        );
+        let versioned = db
+            .client
+            .query(query)
+            .bind(("retired_uuid", retired_uuid))
+            .bind(("date_modified", new_version.date_modified))
+            .bind(("new_uuid", template_id))
+            .bind(("new_version", new_version.clone()))
+            .await;
+
+        match versioned {
+            Ok(response) => match response.check() {
+                Ok(_) => Some(new_version),
+                Err(e) => {
+                    error!("Email template version was rolled back:: {}", e);
+                    None
+                }
+            },
+            Err(e) => {
+                error!("Failed to add email template version:: {}", e);
+                None
+            }
+        }
+    }
+
+    async fn find_all_current(db: &Data<Database>) -> Option<Vec<EmailTemplate>> {
+        let query = format!(
+            "SELECT * FROM {} WHERE deleted = false AND is_current = true ORDER BY name ASC",
+            EMAIL_TEMPLATE_TABLE
+        );
+        let templates = db.client.query(query).await;
+
+        match templates {
+            Ok(mut result) => match result.take(0) {
+                Ok(current_templates) => Some(current_templates),
+                Err(e) => {
+                    error!("Failed to retrieve email templates {}", e);
+                    None
+                }
+            },
+            Err(e) => {
+                error!("Failed to retrieve email templates {}", e);
+                None
+            }
+        }
+    }
+
+    async fn find_versions(
+        db: &Data<Database>,
+        template_key: String,
+    ) -> Option<Vec<EmailTemplate>> {
+        let query = format!(
+            "SELECT * FROM {} WHERE template_key = $template_key ORDER BY version DESC",
+            EMAIL_TEMPLATE_TABLE
+        );
+        let templates = db
+            .client
+            .query(query)
+            .bind(("template_key", template_key))
+            .await;
+
+        match templates {
+            Ok(mut result) => match result.take(0) {
+                Ok(template_versions) => Some(template_versions),
+                Err(e) => {
+                    error!("Failed to retrieve email template versions {}", e);
+                    None
+                }
+            },
+            Err(e) => {
+                error!("Failed to retrieve email template versions {}", e);
+                None
+            }
+        }
+    }
+
+    async fn delete_one(db: &Data<Database>, uuid: String) -> Option<EmailTemplate> {
+        let template_exists: Result<Option<EmailTemplate>, Error> =
+            db.client.select((EMAIL_TEMPLATE_TABLE, uuid.clone())).await;
+
+        match template_exists {
+            Ok(Some(_)) => {
+                let deleted_template: Result<Option<EmailTemplate>, Error> = db
+                    .client
+                    .update((EMAIL_TEMPLATE_TABLE, &uuid))
+                    .patch(PatchOp::replace("/deleted", true))
+                    .patch(PatchOp::replace("/date_modified", Utc::now()))
+                    .await;
+
+                match deleted_template {
+                    Ok(template) => template,
+                    Err(e) => {
+                        error!("Failed to delete email template:: {}", e);
+                        None
+                    }
+                }
+            }
+            Ok(None) => None,
+            Err(e) => {
+                error!("Failed to delete email template: {}", e);
+                None
+            }
+        }
+    }
+}
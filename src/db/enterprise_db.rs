@@ -1,19 +1,16 @@
 use actix_web::web::Data;
 use async_trait::async_trait;
-use chrono::Local;
+use chrono::Utc;
 use log::error;
 use surrealdb::opt::PatchOp;
 use surrealdb::Error;
 
-// use surrealdb::opt::Resource;
-// use surrealdb::Notification;
-// use surrealdb::{engine::remote::ws::Client, method::Stream, Error, Notification};
-
 use crate::db::config::Database;
 use crate::models::enterprise_model::Enterprise;
+use crate::models::lead_model::LeadFilter;
 use crate::utils::{crud::*, general_utils::get_uuid};
 
-const ENTERPRISE_TABLE: &str = "enterprise";
+pub const ENTERPRISE_TABLE: &str = "enterprise";
 
 #[async_trait]
 pub trait EnterpriseDB {
@@ -23,6 +20,7 @@ pub trait EnterpriseDB {
     async fn update_one(db: &Data<Database>, enterprise: Enterprise) -> Option<Enterprise>;
     async fn find_all_active(db: &Data<Database>) -> Option<Vec<Enterprise>>;
     async fn find_all_deleted(db: &Data<Database>) -> Option<Vec<Enterprise>>;
+    async fn find_filtered(db: &Data<Database>, filter: &LeadFilter) -> Option<Vec<Enterprise>>;
     async fn delete_one(db: &Data<Database>, uuid: String) -> Option<Enterprise>;
 }
 
@@ -58,6 +56,10 @@ impl EnterpriseDB for Database {
         util_find_all_non_deleted(&db, ENTERPRISE_TABLE).await
     }
 
+    async fn find_filtered(db: &Data<Database>, filter: &LeadFilter) -> Option<Vec<Enterprise>> {
+        util_find_filtered(db, ENTERPRISE_TABLE, filter).await
+    }
+
     async fn find_all_deleted(db: &Data<Database>) -> Option<Vec<Enterprise>> {
         let query = format!("SELECT * FROM {} WHERE deleted = true", ENTERPRISE_TABLE);
         let enterprises = db.client.query(query).await;
@@ -87,7 +89,7 @@ impl EnterpriseDB for Database {
                     .client
                     .update((ENTERPRISE_TABLE, &uuid))
                     .patch(PatchOp::replace("/deleted", true))
-                    .patch(PatchOp::replace("/date_modified", Local::now()))
+                    .patch(PatchOp::replace("/date_modified", Utc::now()))
                     .await;
 
                 match my_enterprise {
@@ -0,0 +1,347 @@
+use actix_web::web::Data;
+use async_trait::async_trait;
+use chrono::Utc;
+use log::error;
+use serde_json::Value;
+
+use crate::{
+    db::{config::Database, stage_transition_db::STAGE_TRANSITION_TABLE},
+    models::lead_model::{
+        BulkAction, BulkTarget, LeadPerson, LeadPhone, LeadSocialProfiles, StageTransition,
+    },
+};
+
+fn bulk_set_clause(action: &BulkAction) -> &'static str {
+    match action {
+        BulkAction::SetStage => "sales_funnel = $value",
+        BulkAction::AddServices => "services_offered = array::union(services_offered, $value)",
+        BulkAction::RemoveServices => {
+            "services_offered = array::complement(services_offered, $value)"
+        }
+        BulkAction::Reassign => "assigned_to = $value",
+        BulkAction::Delete => "deleted = true",
+    }
+}
+
+#[async_trait]
+pub trait LeadDB {
+    async fn find_bulk_targets(
+        db: &Data<Database>,
+        table_name: &str,
+        uuids: Vec<String>,
+    ) -> Option<Vec<BulkTarget>>;
+    async fn apply_bulk_update(
+        db: &Data<Database>,
+        table_name: &str,
+        action: &BulkAction,
+        value: Value,
+        uuids: Vec<String>,
+        transitions: Vec<StageTransition>,
+        modified_by: Option<String>,
+    ) -> Option<usize>;
+    async fn set_score(db: &Data<Database>, table_name: &str, uuid: String, score: i32) -> bool;
+    async fn set_person(db: &Data<Database>, table_name: &str, person: LeadPerson) -> bool;
+    async fn find_phones(db: &Data<Database>, table_name: &str) -> Option<Vec<LeadPhone>>;
+    async fn set_phone(db: &Data<Database>, table_name: &str, uuid: String, phone: String) -> bool;
+    async fn find_social_profiles(
+        db: &Data<Database>,
+        table_name: &str,
+    ) -> Option<Vec<LeadSocialProfiles>>;
+    async fn set_social_profiles(
+        db: &Data<Database>,
+        table_name: &str,
+        profiles: LeadSocialProfiles,
+    ) -> bool;
+    async fn count_in_stage(db: &Data<Database>, table_name: &str, code: String) -> Option<usize>;
+}
+
+#[async_trait]
+impl LeadDB for Database {
+    async fn find_bulk_targets(
+        db: &Data<Database>,
+        table_name: &str,
+        uuids: Vec<String>,
+    ) -> Option<Vec<BulkTarget>> {
+        let query = format!(
+            "SELECT uuid, sales_funnel, deleted FROM {} WHERE uuid IN $uuids",
+            table_name
+        );
+        let targets = db.client.query(query).bind(("uuids", uuids)).await;
+
+        match targets {
+            Ok(mut result) => match result.take(0) {
+                Ok(bulk_targets) => Some(bulk_targets),
+                Err(e) => {
+                    error!(
+                        "Failed to retrieve bulk targets from {}:: {}",
+                        table_name, e
+                    );
+                    None
+                }
+            },
+            Err(e) => {
+                error!(
+                    "Failed to retrieve bulk targets from {}:: {}",
+                    table_name, e
+                );
+                None
+            }
+        }
+    }
+
+    async fn apply_bulk_update(
+        db: &Data<Database>,
+        table_name: &str,
+        action: &BulkAction,
+        value: Value,
+        uuids: Vec<String>,
+        transitions: Vec<StageTransition>,
+        modified_by: Option<String>,
+    ) -> Option<usize> {
+        let updated_count = uuids.len();
+        let mut statements = vec!["BEGIN TRANSACTION;".to_string()];
+
+        for index in 0..uuids.len() {
+            statements.push(format!(
+                "UPDATE type::thing('{}', $uuid_{}) SET {}, modified_by = $modified_by, date_modified = $date_modified;",
+                table_name,
+                index,
+                bulk_set_clause(action)
+            ));
+        }
+        for index in 0..transitions.len() {
+            statements.push(format!(
+                "CREATE type::thing('{}', $transition_uuid_{}) CONTENT $transition_{};",
+                STAGE_TRANSITION_TABLE, index, index
+            ));
+        }
+        statements.push("COMMIT TRANSACTION;".to_string());
+
+        let mut query = db
+            .client
+            .query(statements.join("\n"))
+            .bind(("value", value))
+            .bind(("modified_by", modified_by))
+            .bind(("date_modified", Utc::now()));
+
+        for (index, uuid) in uuids.into_iter().enumerate() {
+            query = query.bind((format!("uuid_{}", index), uuid));
+        }
+        for (index, transition) in transitions.into_iter().enumerate() {
+            query = query
+                .bind((
+                    format!("transition_uuid_{}", index),
+                    transition.uuid.clone().unwrap_or_default(),
+                ))
+                .bind((format!("transition_{}", index), transition));
+        }
+
+        match query.await {
+            Ok(response) => match response.check() {
+                Ok(_) => Some(updated_count),
+                Err(e) => {
+                    error!("Bulk update on {} was rolled back:: {}", table_name, e);
+                    None
+                }
+            },
+            Err(e) => {
+                error!("Failed to run bulk update on {}:: {}", table_name, e);
+                None
+            }
+        }
+    }
+
+    async fn set_score(db: &Data<Database>, table_name: &str, uuid: String, score: i32) -> bool {
+        let query = format!(
+            "UPDATE type::thing('{}', $uuid) SET score = $score",
+            table_name
+        );
+        let updated = db
+            .client
+            .query(query)
+            .bind(("uuid", uuid))
+            .bind(("score", score))
+            .await;
+
+        match updated {
+            Ok(response) => match response.check() {
+                Ok(_) => true,
+                Err(e) => {
+                    error!("Failed to store score on {}:: {}", table_name, e);
+                    false
+                }
+            },
+            Err(e) => {
+                error!("Failed to store score on {}:: {}", table_name, e);
+                false
+            }
+        }
+    }
+
+    async fn set_person(db: &Data<Database>, table_name: &str, person: LeadPerson) -> bool {
+        let query = format!(
+            "UPDATE type::thing('{}', $uuid) SET name = $name, last_name = $last_name, email = $email ?? email, phone = $phone ?? phone",
+            table_name
+        );
+        let updated = db
+            .client
+            .query(query)
+            .bind(("uuid", person.uuid))
+            .bind(("name", person.name))
+            .bind(("last_name", person.last_name))
+            .bind(("email", person.email))
+            .bind(("phone", person.phone))
+            .await;
+
+        match updated {
+            Ok(response) => match response.check() {
+                Ok(_) => true,
+                Err(e) => {
+                    error!("Failed to store primary contact on {}:: {}", table_name, e);
+                    false
+                }
+            },
+            Err(e) => {
+                error!("Failed to store primary contact on {}:: {}", table_name, e);
+                false
+            }
+        }
+    }
+
+    async fn find_phones(db: &Data<Database>, table_name: &str) -> Option<Vec<LeadPhone>> {
+        let query = format!(
+            "SELECT uuid, phone FROM {} WHERE phone != NONE AND phone != ''",
+            table_name
+        );
+        let phones = db.client.query(query).await;
+
+        match phones {
+            Ok(mut result) => match result.take(0) {
+                Ok(lead_phones) => Some(lead_phones),
+                Err(e) => {
+                    error!("Failed to retrieve phones from {}:: {}", table_name, e);
+                    None
+                }
+            },
+            Err(e) => {
+                error!("Failed to retrieve phones from {}:: {}", table_name, e);
+                None
+            }
+        }
+    }
+
+    async fn set_phone(db: &Data<Database>, table_name: &str, uuid: String, phone: String) -> bool {
+        let query = format!(
+            "UPDATE type::thing('{}', $uuid) SET phone = $phone",
+            table_name
+        );
+        let updated = db
+            .client
+            .query(query)
+            .bind(("uuid", uuid))
+            .bind(("phone", phone))
+            .await;
+
+        match updated {
+            Ok(response) => match response.check() {
+                Ok(_) => true,
+                Err(e) => {
+                    error!("Failed to store phone on {}:: {}", table_name, e);
+                    false
+                }
+            },
+            Err(e) => {
+                error!("Failed to store phone on {}:: {}", table_name, e);
+                false
+            }
+        }
+    }
+
+    async fn find_social_profiles(
+        db: &Data<Database>,
+        table_name: &str,
+    ) -> Option<Vec<LeadSocialProfiles>> {
+        let query = format!(
+            "SELECT uuid, fb, instagram, linked_in, tik_tok, twitter FROM {}",
+            table_name
+        );
+        let profiles = db.client.query(query).await;
+
+        match profiles {
+            Ok(mut result) => match result.take(0) {
+                Ok(social_profiles) => Some(social_profiles),
+                Err(e) => {
+                    error!(
+                        "Failed to retrieve social profiles from {}:: {}",
+                        table_name, e
+                    );
+                    None
+                }
+            },
+            Err(e) => {
+                error!(
+                    "Failed to retrieve social profiles from {}:: {}",
+                    table_name, e
+                );
+                None
+            }
+        }
+    }
+
+    async fn set_social_profiles(
+        db: &Data<Database>,
+        table_name: &str,
+        profiles: LeadSocialProfiles,
+    ) -> bool {
+        let query = format!(
+            "UPDATE type::thing('{}', $uuid) SET fb = $fb, instagram = $instagram, linked_in = $linked_in, tik_tok = $tik_tok, twitter = $twitter",
+            table_name
+        );
+        let updated = db
+            .client
+            .query(query)
+            .bind(("uuid", profiles.uuid))
+            .bind(("fb", profiles.fb))
+            .bind(("instagram", profiles.instagram))
+            .bind(("linked_in", profiles.linked_in))
+            .bind(("tik_tok", profiles.tik_tok))
+            .bind(("twitter", profiles.twitter))
+            .await;
+
+        match updated {
+            Ok(response) => match response.check() {
+                Ok(_) => true,
+                Err(e) => {
+                    error!("Failed to store social profiles on {}:: {}", table_name, e);
+                    false
+                }
+            },
+            Err(e) => {
+                error!("Failed to store social profiles on {}:: {}", table_name, e);
+                false
+            }
+        }
+    }
+
+    async fn count_in_stage(db: &Data<Database>, table_name: &str, code: String) -> Option<usize> {
+        let query = format!(
+            "SELECT VALUE uuid FROM {} WHERE deleted = false AND sales_funnel = $code",
+            table_name
+        );
+        let leads = db.client.query(query).bind(("code", code)).await;
+
+        match leads {
+            Ok(mut result) => match result.take::<Vec<String>>(0) {
+                Ok(uuids) => Some(uuids.len()),
+                Err(e) => {
+                    error!("Failed to count leads by stage on {}:: {}", table_name, e);
+                    None
+                }
+            },
+            Err(e) => {
+                error!("Failed to count leads by stage on {}:: {}", table_name, e);
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,147 @@
+use actix_web::web::Data;
+use async_trait::async_trait;
+use chrono::Utc;
+use log::error;
+use surrealdb::{opt::PatchOp, Error};
+
+use crate::{
+    db::config::Database,
+    models::meeting_model::{CalendarFeed, Meeting},
+    utils::{crud::*, general_utils::get_uuid},
+};
+
+pub const MEETING_TABLE: &str = "meetings";
+pub const CALENDAR_FEED_TABLE: &str = "calendar_feeds";
+
+#[async_trait]
+pub trait MeetingDB {
+    async fn find_one(db: &Data<Database>, uuid: String) -> Option<Meeting>;
+    async fn add_one(db: &Data<Database>, new_meeting: Meeting) -> Option<Meeting>;
+    async fn update_one(db: &Data<Database>, meeting: Meeting) -> Option<Meeting>;
+    async fn find_by_user(db: &Data<Database>, user_uuid: String) -> Option<Vec<Meeting>>;
+    async fn find_by_lead(db: &Data<Database>, lead_uuid: String) -> Option<Vec<Meeting>>;
+    async fn delete_one(
+        db: &Data<Database>,
+        uuid: String,
+        modified_by: Option<String>,
+    ) -> Option<Meeting>;
+    async fn find_feed(db: &Data<Database>, user_uuid: String) -> Option<CalendarFeed>;
+    async fn update_feed(db: &Data<Database>, feed: CalendarFeed) -> Option<CalendarFeed>;
+}
+
+async fn query_meetings(
+    db: &Data<Database>,
+    query: String,
+    key: &'static str,
+    value: String,
+) -> Option<Vec<Meeting>> {
+    let meetings = db.client.query(query).bind((key, value)).await;
+
+    match meetings {
+        Ok(mut result) => match result.take(0) {
+            Ok(found_meetings) => Some(found_meetings),
+            Err(e) => {
+                error!("Failed to retrieve meetings {}", e);
+                None
+            }
+        },
+        Err(e) => {
+            error!("Failed to retrieve meetings {}", e);
+            None
+        }
+    }
+}
+
+#[async_trait]
+impl MeetingDB for Database {
+    async fn find_one(db: &Data<Database>, uuid: String) -> Option<Meeting> {
+        util_find_one(db, uuid, MEETING_TABLE).await
+    }
+
+    async fn add_one(db: &Data<Database>, new_meeting: Meeting) -> Option<Meeting> {
+        let meeting_id = match new_meeting.uuid.clone() {
+            Some(this_uuid) => this_uuid,
+            None => get_uuid(),
+        };
+        util_add_one(db, new_meeting, meeting_id, MEETING_TABLE).await
+    }
+
+    async fn update_one(db: &Data<Database>, meeting: Meeting) -> Option<Meeting> {
+        let meeting_id = match meeting.uuid.clone() {
+            Some(this_uuid) => this_uuid,
+            None => get_uuid(),
+        };
+        util_update_one(db, meeting, meeting_id, MEETING_TABLE).await
+    }
+
+    async fn find_by_user(db: &Data<Database>, user_uuid: String) -> Option<Vec<Meeting>> {
+        let query = format!(
+            "SELECT * FROM {} WHERE deleted = false AND (organizer = $user_uuid OR attendees CONTAINS $user_uuid) ORDER BY start_date",
+            MEETING_TABLE
+        );
+        query_meetings(db, query, "user_uuid", user_uuid).await
+    }
+
+    async fn find_by_lead(db: &Data<Database>, lead_uuid: String) -> Option<Vec<Meeting>> {
+        let query = format!(
+            "SELECT * FROM {} WHERE deleted = false AND lead_uuid = $lead_uuid ORDER BY start_date DESC",
+            MEETING_TABLE
+        );
+        query_meetings(db, query, "lead_uuid", lead_uuid).await
+    }
+
+    async fn delete_one(
+        db: &Data<Database>,
+        uuid: String,
+        modified_by: Option<String>,
+    ) -> Option<Meeting> {
+        let meeting_exists: Result<Option<Meeting>, Error> =
+            db.client.select((MEETING_TABLE, uuid.clone())).await;
+
+        match meeting_exists {
+            Ok(Some(_)) => {
+                let deleted_meeting: Result<Option<Meeting>, Error> = db
+                    .client
+                    .update((MEETING_TABLE, &uuid))
+                    .patch(PatchOp::replace("/deleted", true))
+                    .patch(PatchOp::replace("/modified_by", modified_by))
+                    .patch(PatchOp::replace("/date_modified", Utc::now()))
+                    .await;
+
+                match deleted_meeting {
+                    Ok(meeting) => meeting,
+                    Err(e) => {
+                        error!("Failed to delete meeting:: {}", e);
+                        None
+                    }
+                }
+            }
+            Ok(None) => None,
+            Err(e) => {
+                error!("Failed to delete meeting: {}", e);
+                None
+            }
+        }
+    }
+
+    async fn find_feed(db: &Data<Database>, user_uuid: String) -> Option<CalendarFeed> {
+        util_find_one(db, user_uuid, CALENDAR_FEED_TABLE).await
+    }
+
+    async fn update_feed(db: &Data<Database>, feed: CalendarFeed) -> Option<CalendarFeed> {
+        let user_uuid = feed.uuid.clone().unwrap_or_default();
+        let saved_feed: Result<Option<CalendarFeed>, Error> = db
+            .client
+            .upsert((CALENDAR_FEED_TABLE, user_uuid))
+            .content(feed)
+            .await;
+
+        match saved_feed {
+            Ok(feed) => feed,
+            Err(e) => {
+                error!("Failed to save calendar feed:: {}", e);
+                None
+            }
+        }
+    }
+}
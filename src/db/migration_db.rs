@@ -0,0 +1,25 @@
+use actix_web::web::Data;
+use async_trait::async_trait;
+
+use crate::{db::config::Database, models::migration_model::Migration, utils::crud::*};
+
+pub const MIGRATION_TABLE: &str = "migrations";
+
+#[async_trait]
+pub trait MigrationDB {
+    async fn find_one(db: &Data<Database>, name: String) -> Option<Migration>;
+    async fn add_one(db: &Data<Database>, migration: Migration) -> Option<Migration>;
+}
+
+// Migrations are keyed by name, so each one has at most one record.
+#[async_trait]
+impl MigrationDB for Database {
+    async fn find_one(db: &Data<Database>, name: String) -> Option<Migration> {
+        util_find_one(db, name, MIGRATION_TABLE).await
+    }
+
+    async fn add_one(db: &Data<Database>, migration: Migration) -> Option<Migration> {
+        let name = migration.name.clone();
+        util_add_one(db, migration, name, MIGRATION_TABLE).await
+    }
+}
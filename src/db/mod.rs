@@ -1,5 +1,24 @@
+pub mod account_db;
+pub mod api_token_db;
+pub mod attachment_db;
+pub mod campaign_db;
 pub mod clinical_db;
 pub mod config;
+pub mod contact_db;
+pub mod email_template_db;
 pub mod enterprise_db;
+pub mod lead_db;
+pub mod meeting_db;
+pub mod migration_db;
+pub mod notification_db;
+pub mod pipeline_db;
+pub mod quote_db;
 pub mod school_db;
+pub mod scoring_db;
+pub mod segment_db;
+pub mod service_db;
+pub mod session_db;
+pub mod stage_transition_db;
+pub mod tag_db;
+pub mod territory_db;
 pub mod users_db;
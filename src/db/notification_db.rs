@@ -0,0 +1,175 @@
+use actix_web::web::Data;
+use async_trait::async_trait;
+use chrono::Utc;
+use log::error;
+use surrealdb::{opt::PatchOp, Error};
+
+use crate::{
+    db::config::Database,
+    models::notification_model::{Notification, NotificationPreferences},
+    utils::{crud::*, general_utils::get_uuid},
+};
+
+pub const NOTIFICATION_TABLE: &str = "notifications";
+pub const NOTIFICATION_PREFERENCES_TABLE: &str = "notification_preferences";
+const MAX_LISTED_NOTIFICATIONS: usize = 50;
+
+#[async_trait]
+pub trait NotificationDB {
+    async fn find_one(db: &Data<Database>, uuid: String) -> Option<Notification>;
+    async fn add_one(db: &Data<Database>, new_notification: Notification) -> Option<Notification>;
+    async fn find_by_user(
+        db: &Data<Database>,
+        user_uuid: String,
+        unread_only: bool,
+    ) -> Option<Vec<Notification>>;
+    async fn count_unread(db: &Data<Database>, user_uuid: String) -> Option<usize>;
+    async fn mark_read(db: &Data<Database>, uuid: String) -> Option<Notification>;
+    async fn mark_all_read(db: &Data<Database>, user_uuid: String) -> Option<Vec<Notification>>;
+    async fn find_preferences(
+        db: &Data<Database>,
+        user_uuid: String,
+    ) -> Option<NotificationPreferences>;
+    async fn update_preferences(
+        db: &Data<Database>,
+        preferences: NotificationPreferences,
+    ) -> Option<NotificationPreferences>;
+}
+
+#[async_trait]
+impl NotificationDB for Database {
+    async fn find_one(db: &Data<Database>, uuid: String) -> Option<Notification> {
+        util_find_one(db, uuid, NOTIFICATION_TABLE).await
+    }
+
+    async fn add_one(db: &Data<Database>, new_notification: Notification) -> Option<Notification> {
+        let notification_id = match new_notification.uuid.clone() {
+            Some(this_uuid) => this_uuid,
+            None => get_uuid(),
+        };
+        util_add_one(db, new_notification, notification_id, NOTIFICATION_TABLE).await
+    }
+
+    async fn find_by_user(
+        db: &Data<Database>,
+        user_uuid: String,
+        unread_only: bool,
+    ) -> Option<Vec<Notification>> {
+        let unread_filter = match unread_only {
+            true => " AND read = false",
+            false => "",
+        };
+        let query = format!(
+            "SELECT * FROM {} WHERE user_uuid = $user_uuid{} ORDER BY date_created DESC LIMIT {}",
+            NOTIFICATION_TABLE, unread_filter, MAX_LISTED_NOTIFICATIONS
+        );
+        let notifications = db.client.query(query).bind(("user_uuid", user_uuid)).await;
+
+        match notifications {
+            Ok(mut result) => match result.take(0) {
+                Ok(user_notifications) => Some(user_notifications),
+                Err(e) => {
+                    error!("Failed to retrieve notifications {}", e);
+                    None
+                }
+            },
+            Err(e) => {
+                error!("Failed to retrieve notifications {}", e);
+                None
+            }
+        }
+    }
+
+    async fn count_unread(db: &Data<Database>, user_uuid: String) -> Option<usize> {
+        let query = format!(
+            "SELECT count() AS unread FROM {} WHERE user_uuid = $user_uuid AND read = false GROUP ALL",
+            NOTIFICATION_TABLE
+        );
+        let unread = db.client.query(query).bind(("user_uuid", user_uuid)).await;
+
+        match unread {
+            Ok(mut result) => match result.take::<Option<usize>>((0, "unread")) {
+                Ok(count) => Some(count.unwrap_or(0)),
+                Err(e) => {
+                    error!("Failed to count unread notifications {}", e);
+                    None
+                }
+            },
+            Err(e) => {
+                error!("Failed to count unread notifications {}", e);
+                None
+            }
+        }
+    }
+
+    async fn mark_read(db: &Data<Database>, uuid: String) -> Option<Notification> {
+        let read_notification: Result<Option<Notification>, Error> = db
+            .client
+            .update((NOTIFICATION_TABLE, &uuid))
+            .patch(PatchOp::replace("/read", true))
+            .patch(PatchOp::replace("/date_read", Utc::now()))
+            .await;
+
+        match read_notification {
+            Ok(notification) => notification,
+            Err(e) => {
+                error!("Failed to mark notification as read:: {}", e);
+                None
+            }
+        }
+    }
+
+    async fn mark_all_read(db: &Data<Database>, user_uuid: String) -> Option<Vec<Notification>> {
+        let query = format!(
+            "UPDATE {} SET read = true, date_read = $date_read WHERE user_uuid = $user_uuid AND read = false",
+            NOTIFICATION_TABLE
+        );
+        let read_notifications = db
+            .client
+            .query(query)
+            .bind(("user_uuid", user_uuid))
+            .bind(("date_read", Utc::now()))
+            .await;
+
+        match read_notifications {
+            Ok(mut result) => match result.take(0) {
+                Ok(notifications) => Some(notifications),
+                Err(e) => {
+                    error!("Failed to mark notifications as read {}", e);
+                    None
+                }
+            },
+            Err(e) => {
+                error!("Failed to mark notifications as read {}", e);
+                None
+            }
+        }
+    }
+
+    async fn find_preferences(
+        db: &Data<Database>,
+        user_uuid: String,
+    ) -> Option<NotificationPreferences> {
+        util_find_one(db, user_uuid, NOTIFICATION_PREFERENCES_TABLE).await
+    }
+
+    async fn update_preferences(
+        db: &Data<Database>,
+        preferences: NotificationPreferences,
+    ) -> Option<NotificationPreferences> {
+        let user_uuid = preferences.uuid.clone().unwrap_or_default();
+        let saved_preferences: Result<Option<NotificationPreferences>, Error> = db
+            .client
+            .upsert((NOTIFICATION_PREFERENCES_TABLE, user_uuid))
+            .content(preferences)
+            .await;
+
+        match saved_preferences {
+            Ok(preferences) => preferences,
+            Err(e) => {
+                error!("Failed to save notification preferences:: {}", e);
+                None
+            }
+        }
+    }
+}
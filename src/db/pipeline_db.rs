@@ -0,0 +1,125 @@
+use actix_web::web::Data;
+use async_trait::async_trait;
+use chrono::Utc;
+use log::error;
+use surrealdb::{opt::PatchOp, Error};
+
+use crate::{
+    db::config::Database,
+    models::{pipeline_model::PipelineStage, sales_model::LeadType},
+    utils::{crud::*, general_utils::get_uuid},
+};
+
+pub const PIPELINE_STAGE_TABLE: &str = "pipeline_stages";
+
+#[async_trait]
+pub trait PipelineDB {
+    async fn find_one(db: &Data<Database>, uuid: String) -> Option<PipelineStage>;
+    async fn add_one(db: &Data<Database>, new_stage: PipelineStage) -> Option<PipelineStage>;
+    async fn update_one(db: &Data<Database>, stage: PipelineStage) -> Option<PipelineStage>;
+    async fn find_all_active(db: &Data<Database>) -> Option<Vec<PipelineStage>>;
+    async fn find_by_lead_type(
+        db: &Data<Database>,
+        lead_type: LeadType,
+    ) -> Option<Vec<PipelineStage>>;
+    async fn delete_one(db: &Data<Database>, uuid: String) -> Option<PipelineStage>;
+}
+
+#[async_trait]
+impl PipelineDB for Database {
+    async fn find_one(db: &Data<Database>, uuid: String) -> Option<PipelineStage> {
+        util_find_one(db, uuid, PIPELINE_STAGE_TABLE).await
+    }
+
+    async fn add_one(db: &Data<Database>, new_stage: PipelineStage) -> Option<PipelineStage> {
+        let stage_id = match new_stage.uuid.clone() {
+            Some(this_uuid) => this_uuid,
+            None => get_uuid(),
+        };
+        util_add_one(db, new_stage, stage_id, PIPELINE_STAGE_TABLE).await
+    }
+
+    async fn update_one(db: &Data<Database>, stage: PipelineStage) -> Option<PipelineStage> {
+        let stage_id = match stage.uuid.clone() {
+            Some(this_uuid) => this_uuid,
+            None => get_uuid(),
+        };
+        util_update_one(db, stage, stage_id, PIPELINE_STAGE_TABLE).await
+    }
+
+    async fn find_all_active(db: &Data<Database>) -> Option<Vec<PipelineStage>> {
+        let query = format!(
+            "SELECT * FROM {} WHERE deleted = false ORDER BY lead_type ASC, position ASC",
+            PIPELINE_STAGE_TABLE
+        );
+        let stages = db.client.query(query).await;
+
+        match stages {
+            Ok(mut result) => match result.take(0) {
+                Ok(active_stages) => Some(active_stages),
+                Err(e) => {
+                    error!("Failed to retrieve pipeline stages {}", e);
+                    None
+                }
+            },
+            Err(e) => {
+                error!("Failed to retrieve pipeline stages {}", e);
+                None
+            }
+        }
+    }
+
+    async fn find_by_lead_type(
+        db: &Data<Database>,
+        lead_type: LeadType,
+    ) -> Option<Vec<PipelineStage>> {
+        let query = format!(
+            "SELECT * FROM {} WHERE deleted = false AND lead_type = $lead_type ORDER BY position ASC",
+            PIPELINE_STAGE_TABLE
+        );
+        let stages = db.client.query(query).bind(("lead_type", lead_type)).await;
+
+        match stages {
+            Ok(mut result) => match result.take(0) {
+                Ok(pipeline) => Some(pipeline),
+                Err(e) => {
+                    error!("Failed to retrieve pipeline {}", e);
+                    None
+                }
+            },
+            Err(e) => {
+                error!("Failed to retrieve pipeline {}", e);
+                None
+            }
+        }
+    }
+
+    async fn delete_one(db: &Data<Database>, uuid: String) -> Option<PipelineStage> {
+        let stage_exists: Result<Option<PipelineStage>, Error> =
+            db.client.select((PIPELINE_STAGE_TABLE, uuid.clone())).await;
+
+        match stage_exists {
+            Ok(Some(_)) => {
+                let deleted_stage: Result<Option<PipelineStage>, Error> = db
+                    .client
+                    .update((PIPELINE_STAGE_TABLE, &uuid))
+                    .patch(PatchOp::replace("/deleted", true))
+                    .patch(PatchOp::replace("/date_modified", Utc::now()))
+                    .await;
+
+                match deleted_stage {
+                    Ok(stage) => stage,
+                    Err(e) => {
+                        error!("Failed to delete pipeline stage:: {}", e);
+                        None
+                    }
+                }
+            }
+            Ok(None) => None,
+            Err(e) => {
+                error!("Failed to delete pipeline stage: {}", e);
+                None
+            }
+        }
+    }
+}
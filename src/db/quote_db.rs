@@ -0,0 +1,93 @@
+use actix_web::web::Data;
+use async_trait::async_trait;
+use chrono::{Datelike, Utc};
+use log::error;
+
+use crate::{
+    db::config::Database,
+    models::quote_model::{Quote, QuoteCounter},
+    utils::{crud::*, general_utils::get_uuid, time::get_default_timezone},
+};
+
+const QUOTE_TABLE: &str = "quotes";
+const QUOTE_COUNTER_TABLE: &str = "quote_counters";
+
+#[async_trait]
+pub trait QuoteDB {
+    async fn find_one(db: &Data<Database>, uuid: String) -> Option<Quote>;
+    async fn add_one(db: &Data<Database>, new_quote: Quote) -> Option<Quote>;
+    async fn find_all_active(db: &Data<Database>) -> Option<Vec<Quote>>;
+    async fn find_by_lead(db: &Data<Database>, lead_uuid: String) -> Option<Vec<Quote>>;
+    async fn delete_one(db: &Data<Database>, uuid: String) -> Option<Quote>;
+    async fn next_quote_number(db: &Data<Database>) -> Option<String>;
+}
+
+#[async_trait]
+impl QuoteDB for Database {
+    async fn find_one(db: &Data<Database>, uuid: String) -> Option<Quote> {
+        util_find_one(db, uuid, QUOTE_TABLE).await
+    }
+
+    async fn add_one(db: &Data<Database>, new_quote: Quote) -> Option<Quote> {
+        let quote_id = match new_quote.uuid.clone() {
+            Some(this_uuid) => this_uuid,
+            None => get_uuid(),
+        };
+        util_add_one(db, new_quote, quote_id, QUOTE_TABLE).await
+    }
+
+    async fn find_all_active(db: &Data<Database>) -> Option<Vec<Quote>> {
+        util_find_all_non_deleted(db, QUOTE_TABLE).await
+    }
+
+    async fn find_by_lead(db: &Data<Database>, lead_uuid: String) -> Option<Vec<Quote>> {
+        let query = format!(
+            "SELECT * FROM {} WHERE deleted = false AND lead_uuid = $lead_uuid ORDER BY date_created DESC",
+            QUOTE_TABLE
+        );
+        let quotes = db.client.query(query).bind(("lead_uuid", lead_uuid)).await;
+
+        match quotes {
+            Ok(mut result) => match result.take(0) {
+                Ok(lead_quotes) => Some(lead_quotes),
+                Err(e) => {
+                    error!("Failed to retrieve quotes for lead {}", e);
+                    None
+                }
+            },
+            Err(e) => {
+                error!("Failed to retrieve quotes for lead {}", e);
+                None
+            }
+        }
+    }
+
+    async fn delete_one(db: &Data<Database>, uuid: String) -> Option<Quote> {
+        util_patch_one(db, uuid, QUOTE_TABLE, "/deleted", true).await
+    }
+
+    async fn next_quote_number(db: &Data<Database>) -> Option<String> {
+        let year = Utc::now().with_timezone(&get_default_timezone()).year();
+        let query = format!(
+            "UPSERT type::thing('{}', $year) SET value += 1 RETURN AFTER",
+            QUOTE_COUNTER_TABLE
+        );
+        let counter = db.client.query(query).bind(("year", year)).await;
+
+        let counter_value: Option<QuoteCounter> = match counter {
+            Ok(mut result) => match result.take(0) {
+                Ok(value) => value,
+                Err(e) => {
+                    error!("Failed to read quote counter {}", e);
+                    None
+                }
+            },
+            Err(e) => {
+                error!("Failed to increase quote counter {}", e);
+                None
+            }
+        };
+
+        counter_value.map(|c| format!("COT-{}-{:04}", year, c.value))
+    }
+}
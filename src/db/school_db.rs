@@ -1,16 +1,16 @@
 use actix_web::web::Data;
 use async_trait::async_trait;
-use chrono::Local;
+use chrono::Utc;
 use log::error;
 use surrealdb::{opt::PatchOp, Error};
 
 use crate::{
     db::config::Database,
-    models::school_model::School,
+    models::{lead_model::LeadFilter, school_model::School},
     utils::{crud::*, general_utils::get_uuid},
 };
 
-const SCHOOL_TABLE: &str = "schools";
+pub const SCHOOL_TABLE: &str = "schools";
 
 #[async_trait]
 pub trait SchoolDB {
@@ -20,6 +20,7 @@ pub trait SchoolDB {
     async fn update_one(db: &Data<Database>, old_school: School) -> Option<School>;
     async fn find_all_active(db: &Data<Database>) -> Option<Vec<School>>;
     async fn find_all_deleted(db: &Data<Database>) -> Option<Vec<School>>;
+    async fn find_filtered(db: &Data<Database>, filter: &LeadFilter) -> Option<Vec<School>>;
     async fn delete_one(db: &Data<Database>, uuid: String) -> Option<School>;
 }
 
@@ -53,6 +54,10 @@ impl SchoolDB for Database {
         util_find_all_non_deleted(&db, SCHOOL_TABLE).await
     }
 
+    async fn find_filtered(db: &Data<Database>, filter: &LeadFilter) -> Option<Vec<School>> {
+        util_find_filtered(db, SCHOOL_TABLE, filter).await
+    }
+
     async fn find_all_deleted(db: &Data<Database>) -> Option<Vec<School>> {
         let query = format!("SELECT * FROM {} WHERE deleted = true", SCHOOL_TABLE);
         let schools = db.client.query(query).await;
@@ -82,7 +87,7 @@ impl SchoolDB for Database {
                     .client
                     .update((SCHOOL_TABLE, &uuid))
                     .patch(PatchOp::replace("/deleted", true))
-                    .patch(PatchOp::replace("/date_modified", Local::now()))
+                    .patch(PatchOp::replace("/date_modified", Utc::now()))
                     .await;
 
                 match school {
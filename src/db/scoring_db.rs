@@ -0,0 +1,105 @@
+use actix_web::web::Data;
+use async_trait::async_trait;
+use chrono::Utc;
+use log::error;
+use surrealdb::{opt::PatchOp, Error};
+
+use crate::{
+    db::config::Database,
+    models::scoring_model::ScoringRule,
+    utils::{crud::*, general_utils::get_uuid},
+};
+
+pub const SCORING_RULE_TABLE: &str = "scoring_rules";
+
+#[async_trait]
+pub trait ScoringDB {
+    async fn find_one(db: &Data<Database>, uuid: String) -> Option<ScoringRule>;
+    async fn add_one(db: &Data<Database>, new_rule: ScoringRule) -> Option<ScoringRule>;
+    async fn update_one(db: &Data<Database>, rule: ScoringRule) -> Option<ScoringRule>;
+    async fn find_all_non_deleted(db: &Data<Database>) -> Option<Vec<ScoringRule>>;
+    async fn delete_one(
+        db: &Data<Database>,
+        uuid: String,
+        modified_by: Option<String>,
+    ) -> Option<ScoringRule>;
+}
+
+#[async_trait]
+impl ScoringDB for Database {
+    async fn find_one(db: &Data<Database>, uuid: String) -> Option<ScoringRule> {
+        util_find_one(db, uuid, SCORING_RULE_TABLE).await
+    }
+
+    async fn add_one(db: &Data<Database>, new_rule: ScoringRule) -> Option<ScoringRule> {
+        let rule_id = match new_rule.uuid.clone() {
+            Some(this_uuid) => this_uuid,
+            None => get_uuid(),
+        };
+        util_add_one(db, new_rule, rule_id, SCORING_RULE_TABLE).await
+    }
+
+    async fn update_one(db: &Data<Database>, rule: ScoringRule) -> Option<ScoringRule> {
+        let rule_id = match rule.uuid.clone() {
+            Some(this_uuid) => this_uuid,
+            None => get_uuid(),
+        };
+        util_update_one(db, rule, rule_id, SCORING_RULE_TABLE).await
+    }
+
+    async fn find_all_non_deleted(db: &Data<Database>) -> Option<Vec<ScoringRule>> {
+        let query = format!(
+            "SELECT * FROM {} WHERE deleted = false ORDER BY name ASC",
+            SCORING_RULE_TABLE
+        );
+        let rules = db.client.query(query).await;
+
+        match rules {
+            Ok(mut result) => match result.take(0) {
+                Ok(scoring_rules) => Some(scoring_rules),
+                Err(e) => {
+                    error!("Failed to retrieve scoring rules {}", e);
+                    None
+                }
+            },
+            Err(e) => {
+                error!("Failed to retrieve scoring rules {}", e);
+                None
+            }
+        }
+    }
+
+    async fn delete_one(
+        db: &Data<Database>,
+        uuid: String,
+        modified_by: Option<String>,
+    ) -> Option<ScoringRule> {
+        let rule_exists: Result<Option<ScoringRule>, Error> =
+            db.client.select((SCORING_RULE_TABLE, uuid.clone())).await;
+
+        match rule_exists {
+            Ok(Some(_)) => {
+                let deleted_rule: Result<Option<ScoringRule>, Error> = db
+                    .client
+                    .update((SCORING_RULE_TABLE, &uuid))
+                    .patch(PatchOp::replace("/deleted", true))
+                    .patch(PatchOp::replace("/modified_by", modified_by))
+                    .patch(PatchOp::replace("/date_modified", Utc::now()))
+                    .await;
+
+                match deleted_rule {
+                    Ok(rule) => rule,
+                    Err(e) => {
+                        error!("Failed to delete scoring rule:: {}", e);
+                        None
+                    }
+                }
+            }
+            Ok(None) => None,
+            Err(e) => {
+                error!("Failed to delete scoring rule: {}", e);
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use actix_web::web::Data;
+use async_trait::async_trait;
+use chrono::Utc;
+use log::error;
+use surrealdb::{opt::PatchOp, Error};
+
+use crate::{
+    db::config::Database,
+    models::segment_model::Segment,
+    utils::{crud::*, general_utils::get_uuid},
+};
+
+pub const SEGMENT_TABLE: &str = "segments";
+
+#[async_trait]
+pub trait SegmentDB {
+    async fn find_one(db: &Data<Database>, uuid: String) -> Option<Segment>;
+    async fn add_one(db: &Data<Database>, new_segment: Segment) -> Option<Segment>;
+    async fn update_one(db: &Data<Database>, segment: Segment) -> Option<Segment>;
+    async fn find_all_active(db: &Data<Database>) -> Option<Vec<Segment>>;
+    async fn delete_one(
+        db: &Data<Database>,
+        uuid: String,
+        modified_by: Option<String>,
+    ) -> Option<Segment>;
+}
+
+#[async_trait]
+impl SegmentDB for Database {
+    async fn find_one(db: &Data<Database>, uuid: String) -> Option<Segment> {
+        util_find_one(db, uuid, SEGMENT_TABLE).await
+    }
+
+    async fn add_one(db: &Data<Database>, new_segment: Segment) -> Option<Segment> {
+        let segment_id = match new_segment.uuid.clone() {
+            Some(this_uuid) => this_uuid,
+            None => get_uuid(),
+        };
+        util_add_one(db, new_segment, segment_id, SEGMENT_TABLE).await
+    }
+
+    async fn update_one(db: &Data<Database>, segment: Segment) -> Option<Segment> {
+        let segment_id = match segment.uuid.clone() {
+            Some(this_uuid) => this_uuid,
+            None => get_uuid(),
+        };
+        util_update_one(db, segment, segment_id, SEGMENT_TABLE).await
+    }
+
+    async fn find_all_active(db: &Data<Database>) -> Option<Vec<Segment>> {
+        let query = format!(
+            "SELECT * FROM {} WHERE deleted = false ORDER BY name ASC",
+            SEGMENT_TABLE
+        );
+        let segments = db.client.query(query).await;
+
+        match segments {
+            Ok(mut result) => match result.take(0) {
+                Ok(active_segments) => Some(active_segments),
+                Err(e) => {
+                    error!("Failed to retrieve segments {}", e);
+                    None
+                }
+            },
+            Err(e) => {
+                error!("Failed to retrieve segments {}", e);
+                None
+            }
+        }
+    }
+
+    async fn delete_one(
+        db: &Data<Database>,
+        uuid: String,
+        modified_by: Option<String>,
+    ) -> Option<Segment> {
+        let segment_exists: Result<Option<Segment>, Error> =
+            db.client.select((SEGMENT_TABLE, uuid.clone())).await;
+
+        match segment_exists {
+            Ok(Some(_)) => {
+                let deleted_segment: Result<Option<Segment>, Error> = db
+                    .client
+                    .update((SEGMENT_TABLE, &uuid))
+                    .patch(PatchOp::replace("/deleted", true))
+                    .patch(PatchOp::replace("/modified_by", modified_by))
+                    .patch(PatchOp::replace("/date_modified", Utc::now()))
+                    .await;
+
+                match deleted_segment {
+                    Ok(segment) => segment,
+                    Err(e) => {
+                        error!("Failed to delete segment:: {}", e);
+                        None
+                    }
+                }
+            }
+            Ok(None) => None,
+            Err(e) => {
+                error!("Failed to delete segment: {}", e);
+                None
+            }
+        }
+    }
+}
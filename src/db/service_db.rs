@@ -0,0 +1,178 @@
+use actix_web::web::Data;
+use async_trait::async_trait;
+use chrono::Utc;
+use log::error;
+use surrealdb::{opt::PatchOp, Error};
+
+use crate::{
+    db::config::Database,
+    models::service_model::{LeadServices, Service},
+    utils::{crud::*, general_utils::get_uuid},
+};
+
+pub const SERVICE_TABLE: &str = "services";
+
+#[async_trait]
+pub trait ServiceDB {
+    async fn find_one(db: &Data<Database>, uuid: String) -> Option<Service>;
+    async fn add_one(db: &Data<Database>, new_service: Service) -> Option<Service>;
+    async fn update_one(db: &Data<Database>, service: Service) -> Option<Service>;
+    async fn find_all_active(db: &Data<Database>) -> Option<Vec<Service>>;
+    async fn find_by_legacy_code(db: &Data<Database>, legacy_code: String) -> Option<Service>;
+    async fn find_lead_services(db: &Data<Database>, table_name: &str)
+        -> Option<Vec<LeadServices>>;
+    async fn set_lead_services(
+        db: &Data<Database>,
+        table_name: &str,
+        uuid: String,
+        services_offered: Vec<String>,
+    ) -> Option<LeadServices>;
+    async fn delete_one(db: &Data<Database>, uuid: String) -> Option<Service>;
+}
+
+#[async_trait]
+impl ServiceDB for Database {
+    async fn find_one(db: &Data<Database>, uuid: String) -> Option<Service> {
+        util_find_one(db, uuid, SERVICE_TABLE).await
+    }
+
+    async fn add_one(db: &Data<Database>, new_service: Service) -> Option<Service> {
+        let service_id = match new_service.uuid.clone() {
+            Some(this_uuid) => this_uuid,
+            None => get_uuid(),
+        };
+        util_add_one(db, new_service, service_id, SERVICE_TABLE).await
+    }
+
+    async fn update_one(db: &Data<Database>, service: Service) -> Option<Service> {
+        let service_id = match service.uuid.clone() {
+            Some(this_uuid) => this_uuid,
+            None => get_uuid(),
+        };
+        util_update_one(db, service, service_id, SERVICE_TABLE).await
+    }
+
+    async fn find_all_active(db: &Data<Database>) -> Option<Vec<Service>> {
+        let query = format!(
+            "SELECT * FROM {} WHERE deleted = false ORDER BY name ASC",
+            SERVICE_TABLE
+        );
+        let services = db.client.query(query).await;
+
+        match services {
+            Ok(mut result) => match result.take(0) {
+                Ok(active_services) => Some(active_services),
+                Err(e) => {
+                    error!("Failed to retrieve services {}", e);
+                    None
+                }
+            },
+            Err(e) => {
+                error!("Failed to retrieve services {}", e);
+                None
+            }
+        }
+    }
+
+    async fn find_by_legacy_code(db: &Data<Database>, legacy_code: String) -> Option<Service> {
+        let query = format!(
+            "SELECT * FROM {} WHERE legacy_code = $legacy_code LIMIT 1",
+            SERVICE_TABLE
+        );
+        let services = db
+            .client
+            .query(query)
+            .bind(("legacy_code", legacy_code))
+            .await;
+
+        match services {
+            Ok(mut result) => match result.take(0) {
+                Ok(service) => service,
+                Err(e) => {
+                    error!("Failed to retrieve legacy service {}", e);
+                    None
+                }
+            },
+            Err(e) => {
+                error!("Failed to retrieve legacy service {}", e);
+                None
+            }
+        }
+    }
+
+    async fn find_lead_services(
+        db: &Data<Database>,
+        table_name: &str,
+    ) -> Option<Vec<LeadServices>> {
+        let query = format!(
+            "SELECT uuid, services_offered FROM {} WHERE uuid != NONE",
+            table_name
+        );
+        let leads = db.client.query(query).await;
+
+        match leads {
+            Ok(mut result) => match result.take(0) {
+                Ok(lead_services) => Some(lead_services),
+                Err(e) => {
+                    error!("Failed to retrieve services from {}:: {}", table_name, e);
+                    None
+                }
+            },
+            Err(e) => {
+                error!("Failed to retrieve services from {}:: {}", table_name, e);
+                None
+            }
+        }
+    }
+
+    async fn set_lead_services(
+        db: &Data<Database>,
+        table_name: &str,
+        uuid: String,
+        services_offered: Vec<String>,
+    ) -> Option<LeadServices> {
+        let updated_lead: Result<Option<LeadServices>, Error> = db
+            .client
+            .update((table_name, uuid))
+            .patch(PatchOp::replace("/services_offered", services_offered))
+            .await;
+
+        match updated_lead {
+            Ok(lead) => lead,
+            Err(e) => {
+                error!("Failed to update services in {}:: {}", table_name, e);
+                None
+            }
+        }
+    }
+
+    async fn delete_one(db: &Data<Database>, uuid: String) -> Option<Service> {
+        let service_exists: Result<Option<Service>, Error> =
+            db.client.select((SERVICE_TABLE, uuid.clone())).await;
+
+        match service_exists {
+            Ok(Some(_)) => {
+                let deleted_service: Result<Option<Service>, Error> = db
+                    .client
+                    .update((SERVICE_TABLE, &uuid))
+                    .patch(PatchOp::replace("/deleted", true))
+                    .patch(PatchOp::replace("/active", false))
+                    .patch(PatchOp::replace("/date_modified", Utc::now()))
+                    .await;
+
+                match deleted_service {
+                    Ok(service) => service,
+                    Err(e) => {
+                        error!("Failed to delete service:: {}", e);
+                        None
+                    }
+                }
+            }
+            Ok(None) => None,
+            Err(e) => {
+                error!("Failed to delete service: {}", e);
+                None
+            }
+        }
+    }
+}
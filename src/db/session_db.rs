@@ -0,0 +1,48 @@
+use actix_web::web::Data;
+use async_trait::async_trait;
+use log::error;
+use surrealdb::Error;
+
+use crate::{
+    db::config::Database,
+    models::session_model::Session,
+    utils::{crud::*, general_utils::get_uuid},
+};
+
+pub const SESSION_TABLE: &str = "sessions";
+
+#[async_trait]
+pub trait SessionDB {
+    async fn find_one(db: &Data<Database>, uuid: String) -> Option<Session>;
+    async fn add_one(db: &Data<Database>, new_session: Session) -> Option<Session>;
+    async fn delete_one(db: &Data<Database>, uuid: String) -> Option<Session>;
+}
+
+#[async_trait]
+impl SessionDB for Database {
+    async fn find_one(db: &Data<Database>, uuid: String) -> Option<Session> {
+        util_find_one(db, uuid, SESSION_TABLE).await
+    }
+
+    async fn add_one(db: &Data<Database>, new_session: Session) -> Option<Session> {
+        let session_id = match new_session.uuid.clone() {
+            Some(this_uuid) => this_uuid,
+            None => get_uuid(),
+        };
+        util_add_one(db, new_session, session_id, SESSION_TABLE).await
+    }
+
+    // Sessions are removed for good on logout, there is nothing to keep.
+    async fn delete_one(db: &Data<Database>, uuid: String) -> Option<Session> {
+        let deleted_session: Result<Option<Session>, Error> =
+            db.client.delete((SESSION_TABLE, uuid)).await;
+
+        match deleted_session {
+            Ok(session) => session,
+            Err(e) => {
+                error!("Failed to delete session:: {}", e);
+                None
+            }
+        }
+    }
+}
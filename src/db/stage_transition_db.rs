@@ -0,0 +1,63 @@
+use actix_web::web::Data;
+use async_trait::async_trait;
+use log::error;
+
+use crate::{
+    db::config::Database,
+    models::{lead_model::StageTransition, sales_model::LeadType},
+    utils::{crud::*, general_utils::get_uuid},
+};
+
+pub const STAGE_TRANSITION_TABLE: &str = "stage_transitions";
+
+#[async_trait]
+pub trait StageTransitionDB {
+    async fn add_one(db: &Data<Database>, transition: StageTransition) -> Option<StageTransition>;
+    async fn find_by_lead(
+        db: &Data<Database>,
+        lead_type: LeadType,
+        lead_uuid: String,
+    ) -> Option<Vec<StageTransition>>;
+}
+
+#[async_trait]
+impl StageTransitionDB for Database {
+    async fn add_one(db: &Data<Database>, transition: StageTransition) -> Option<StageTransition> {
+        let transition_id = match transition.uuid.clone() {
+            Some(this_uuid) => this_uuid,
+            None => get_uuid(),
+        };
+        util_add_one(db, transition, transition_id, STAGE_TRANSITION_TABLE).await
+    }
+
+    async fn find_by_lead(
+        db: &Data<Database>,
+        lead_type: LeadType,
+        lead_uuid: String,
+    ) -> Option<Vec<StageTransition>> {
+        let query = format!(
+            "SELECT * FROM {} WHERE lead_type = $lead_type AND lead_uuid = $lead_uuid ORDER BY date_created DESC",
+            STAGE_TRANSITION_TABLE
+        );
+        let transitions = db
+            .client
+            .query(query)
+            .bind(("lead_type", lead_type))
+            .bind(("lead_uuid", lead_uuid))
+            .await;
+
+        match transitions {
+            Ok(mut result) => match result.take(0) {
+                Ok(lead_transitions) => Some(lead_transitions),
+                Err(e) => {
+                    error!("Failed to retrieve stage transitions {}", e);
+                    None
+                }
+            },
+            Err(e) => {
+                error!("Failed to retrieve stage transitions {}", e);
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,135 @@
+use actix_web::web::Data;
+use async_trait::async_trait;
+use chrono::Utc;
+use log::error;
+use surrealdb::{opt::PatchOp, Error};
+
+use crate::{
+    db::config::Database,
+    models::tag_model::Tag,
+    utils::{crud::*, general_utils::get_uuid},
+};
+
+pub const TAG_TABLE: &str = "tags";
+const TAG_SEARCH_LIMIT: u8 = 10;
+
+#[async_trait]
+pub trait TagDB {
+    async fn add_one(db: &Data<Database>, new_tag: Tag) -> Option<Tag>;
+    async fn find_all_active(db: &Data<Database>) -> Option<Vec<Tag>>;
+    async fn find_by_name(db: &Data<Database>, name: String) -> Option<Tag>;
+    async fn search(db: &Data<Database>, query: String) -> Option<Vec<Tag>>;
+    async fn delete_one(
+        db: &Data<Database>,
+        uuid: String,
+        modified_by: Option<String>,
+    ) -> Option<Tag>;
+}
+
+#[async_trait]
+impl TagDB for Database {
+    async fn add_one(db: &Data<Database>, new_tag: Tag) -> Option<Tag> {
+        let tag_id = match new_tag.uuid.clone() {
+            Some(this_uuid) => this_uuid,
+            None => get_uuid(),
+        };
+        util_add_one(db, new_tag, tag_id, TAG_TABLE).await
+    }
+
+    async fn find_all_active(db: &Data<Database>) -> Option<Vec<Tag>> {
+        let query = format!(
+            "SELECT * FROM {} WHERE deleted = false ORDER BY name ASC",
+            TAG_TABLE
+        );
+        let tags = db.client.query(query).await;
+
+        match tags {
+            Ok(mut result) => match result.take(0) {
+                Ok(active_tags) => Some(active_tags),
+                Err(e) => {
+                    error!("Failed to retrieve tags {}", e);
+                    None
+                }
+            },
+            Err(e) => {
+                error!("Failed to retrieve tags {}", e);
+                None
+            }
+        }
+    }
+
+    async fn find_by_name(db: &Data<Database>, name: String) -> Option<Tag> {
+        let query = format!("SELECT * FROM {} WHERE name = $name LIMIT 1", TAG_TABLE);
+        let tag = db.client.query(query).bind(("name", name)).await;
+
+        match tag {
+            Ok(mut result) => match result.take::<Vec<Tag>>(0) {
+                Ok(tags) => tags.into_iter().next(),
+                Err(e) => {
+                    error!("Failed to retrieve tag by name {}", e);
+                    None
+                }
+            },
+            Err(e) => {
+                error!("Failed to retrieve tag by name {}", e);
+                None
+            }
+        }
+    }
+
+    async fn search(db: &Data<Database>, query: String) -> Option<Vec<Tag>> {
+        let surreal_query = format!(
+            "SELECT * FROM {} WHERE deleted = false AND string::contains(name, $query) ORDER BY name ASC LIMIT {}",
+            TAG_TABLE, TAG_SEARCH_LIMIT
+        );
+        let tags = db.client.query(surreal_query).bind(("query", query)).await;
+
+        match tags {
+            Ok(mut result) => match result.take(0) {
+                Ok(found_tags) => Some(found_tags),
+                Err(e) => {
+                    error!("Failed to search tags {}", e);
+                    None
+                }
+            },
+            Err(e) => {
+                error!("Failed to search tags {}", e);
+                None
+            }
+        }
+    }
+
+    async fn delete_one(
+        db: &Data<Database>,
+        uuid: String,
+        modified_by: Option<String>,
+    ) -> Option<Tag> {
+        let tag_exists: Result<Option<Tag>, Error> =
+            db.client.select((TAG_TABLE, uuid.clone())).await;
+
+        match tag_exists {
+            Ok(Some(_)) => {
+                let deleted_tag: Result<Option<Tag>, Error> = db
+                    .client
+                    .update((TAG_TABLE, &uuid))
+                    .patch(PatchOp::replace("/deleted", true))
+                    .patch(PatchOp::replace("/modified_by", modified_by))
+                    .patch(PatchOp::replace("/date_modified", Utc::now()))
+                    .await;
+
+                match deleted_tag {
+                    Ok(tag) => tag,
+                    Err(e) => {
+                        error!("Failed to delete tag:: {}", e);
+                        None
+                    }
+                }
+            }
+            Ok(None) => None,
+            Err(e) => {
+                error!("Failed to delete tag: {}", e);
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use actix_web::web::Data;
+use async_trait::async_trait;
+use chrono::Utc;
+use log::error;
+use surrealdb::{opt::PatchOp, Error};
+
+use crate::{
+    db::config::Database,
+    models::territory_model::Territory,
+    utils::{crud::*, general_utils::get_uuid},
+};
+
+pub const TERRITORY_TABLE: &str = "territories";
+
+#[async_trait]
+pub trait TerritoryDB {
+    async fn find_one(db: &Data<Database>, uuid: String) -> Option<Territory>;
+    async fn add_one(db: &Data<Database>, new_territory: Territory) -> Option<Territory>;
+    async fn update_one(db: &Data<Database>, territory: Territory) -> Option<Territory>;
+    async fn find_all_active(db: &Data<Database>) -> Option<Vec<Territory>>;
+    async fn delete_one(
+        db: &Data<Database>,
+        uuid: String,
+        modified_by: Option<String>,
+    ) -> Option<Territory>;
+}
+
+#[async_trait]
+impl TerritoryDB for Database {
+    async fn find_one(db: &Data<Database>, uuid: String) -> Option<Territory> {
+        util_find_one(db, uuid, TERRITORY_TABLE).await
+    }
+
+    async fn add_one(db: &Data<Database>, new_territory: Territory) -> Option<Territory> {
+        let territory_id = match new_territory.uuid.clone() {
+            Some(this_uuid) => this_uuid,
+            None => get_uuid(),
+        };
+        util_add_one(db, new_territory, territory_id, TERRITORY_TABLE).await
+    }
+
+    async fn update_one(db: &Data<Database>, territory: Territory) -> Option<Territory> {
+        let territory_id = match territory.uuid.clone() {
+            Some(this_uuid) => this_uuid,
+            None => get_uuid(),
+        };
+        util_update_one(db, territory, territory_id, TERRITORY_TABLE).await
+    }
+
+    async fn find_all_active(db: &Data<Database>) -> Option<Vec<Territory>> {
+        let query = format!(
+            "SELECT * FROM {} WHERE deleted = false ORDER BY name ASC",
+            TERRITORY_TABLE
+        );
+        let territories = db.client.query(query).await;
+
+        match territories {
+            Ok(mut result) => match result.take(0) {
+                Ok(active_territories) => Some(active_territories),
+                Err(e) => {
+                    error!("Failed to retrieve territories {}", e);
+                    None
+                }
+            },
+            Err(e) => {
+                error!("Failed to retrieve territories {}", e);
+                None
+            }
+        }
+    }
+
+    async fn delete_one(
+        db: &Data<Database>,
+        uuid: String,
+        modified_by: Option<String>,
+    ) -> Option<Territory> {
+        let territory_exists: Result<Option<Territory>, Error> =
+            db.client.select((TERRITORY_TABLE, uuid.clone())).await;
+
+        match territory_exists {
+            Ok(Some(_)) => {
+                let deleted_territory: Result<Option<Territory>, Error> = db
+                    .client
+                    .update((TERRITORY_TABLE, &uuid))
+                    .patch(PatchOp::replace("/deleted", true))
+                    .patch(PatchOp::replace("/modified_by", modified_by))
+                    .patch(PatchOp::replace("/date_modified", Utc::now()))
+                    .await;
+
+                match deleted_territory {
+                    Ok(territory) => territory,
+                    Err(e) => {
+                        error!("Failed to delete territory:: {}", e);
+                        None
+                    }
+                }
+            }
+            Ok(None) => None,
+            Err(e) => {
+                error!("Failed to delete territory: {}", e);
+                None
+            }
+        }
+    }
+}
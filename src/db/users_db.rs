@@ -1,6 +1,6 @@
 use actix_web::web::Data;
 use async_trait::async_trait;
-use chrono::Local;
+use chrono::Utc;
 use lazy_static::lazy_static;
 use log::error;
 use surrealdb::{opt::PatchOp, Error};
@@ -26,6 +26,7 @@ pub trait UsersDB {
     async fn delete_one(db: &Data<Database>, uuid: String) -> Option<User>;
     async fn find_all_non_deleted(db: &Data<Database>) -> Option<Vec<User>>;
     async fn find_all_deleted(db: &Data<Database>) -> Option<Vec<User>>;
+    async fn find_by_email(db: &Data<Database>, email: String) -> Option<User>;
 }
 
 #[async_trait]
@@ -59,7 +60,7 @@ impl UsersDB for Database {
                 .client
                 .update((users_table, uuid))
                 .patch(PatchOp::replace("/deleted", true))
-                .patch(PatchOp::replace("/date_modified", Local::now()))
+                .patch(PatchOp::replace("/date_modified", Utc::now()))
                 .await;
 
             match user {
@@ -101,4 +102,27 @@ impl UsersDB for Database {
             }
         }
     }
+
+    async fn find_by_email(db: &Data<Database>, email: String) -> Option<User> {
+        let surreal_query = format!(
+            "SELECT * FROM {} WHERE deleted = false AND string::lowercase(email) = $email LIMIT 1",
+            USERS_TABLE.as_str()
+        );
+
+        let users = db.client.query(surreal_query).bind(("email", email)).await;
+
+        match users {
+            Ok(mut response) => match response.take::<Vec<User>>(0) {
+                Ok(found_users) => found_users.into_iter().next(),
+                Err(e) => {
+                    error!("Failed to retrieve user by email {}", e);
+                    None
+                }
+            },
+            Err(e) => {
+                error!("Failed to retrieve user by email {}", e);
+                None
+            }
+        }
+    }
 }
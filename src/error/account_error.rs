@@ -0,0 +1,35 @@
+use actix_web::{http::StatusCode, HttpResponse, ResponseError};
+
+use derive_more::Display;
+use utoipa::ToSchema;
+
+use crate::error::api_error::ApiError;
+
+#[derive(Debug, Display, ToSchema)]
+pub enum AccountError {
+    NoAccountsFound,
+    NoContactsFound,
+    AccountCreationFailure,
+    AccountForbidden,
+    LeadNotFound,
+    LeadNotWon,
+    LeadAlreadyConverted,
+}
+
+impl ResponseError for AccountError {
+    fn error_response(&self) -> HttpResponse<actix_web::body::BoxBody> {
+        HttpResponse::build(self.status_code()).json(ApiError::new(self))
+    }
+
+    fn status_code(&self) -> StatusCode {
+        match self {
+            AccountError::NoAccountsFound => StatusCode::NOT_FOUND,
+            AccountError::NoContactsFound => StatusCode::NOT_FOUND,
+            AccountError::AccountCreationFailure => StatusCode::INTERNAL_SERVER_ERROR,
+            AccountError::AccountForbidden => StatusCode::FORBIDDEN,
+            AccountError::LeadNotFound => StatusCode::NOT_FOUND,
+            AccountError::LeadNotWon => StatusCode::CONFLICT,
+            AccountError::LeadAlreadyConverted => StatusCode::CONFLICT,
+        }
+    }
+}
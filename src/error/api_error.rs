@@ -0,0 +1,58 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::utils::api_utils::current_request_id;
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct ApiError {
+    pub code: String,
+    pub message: String,
+    pub field_errors: Vec<String>,
+    pub request_id: Option<String>,
+}
+
+impl ApiError {
+    pub fn new(error: impl Display) -> ApiError {
+        let code = error.to_string();
+
+        ApiError {
+            message: humanize_code(&code),
+            code,
+            field_errors: vec![],
+            request_id: current_request_id(),
+        }
+    }
+
+    pub fn with_message(self, message: impl Into<String>) -> ApiError {
+        ApiError {
+            message: message.into(),
+            ..self
+        }
+    }
+
+    pub fn with_field_errors(self, field_errors: Vec<String>) -> ApiError {
+        ApiError {
+            field_errors,
+            ..self
+        }
+    }
+}
+
+fn humanize_code(code: &str) -> String {
+    let mut message = String::with_capacity(code.len() + 8);
+
+    for (index, character) in code.chars().enumerate() {
+        if index == 0 {
+            message.push(character);
+        } else if character.is_uppercase() {
+            message.push(' ');
+            message.extend(character.to_lowercase());
+        } else {
+            message.push(character);
+        }
+    }
+
+    message
+}
@@ -0,0 +1,31 @@
+use actix_web::{http::StatusCode, HttpResponse, ResponseError};
+
+use derive_more::Display;
+use utoipa::ToSchema;
+
+use crate::error::api_error::ApiError;
+
+#[derive(Debug, Display, ToSchema)]
+pub enum ApiTokenError {
+    NoApiTokensFound,
+    ApiTokenCreationFailure,
+    ApiTokenForbidden,
+    ApiTokenUnauthorized,
+    ApiTokenScopeMissing,
+}
+
+impl ResponseError for ApiTokenError {
+    fn error_response(&self) -> HttpResponse<actix_web::body::BoxBody> {
+        HttpResponse::build(self.status_code()).json(ApiError::new(self))
+    }
+
+    fn status_code(&self) -> StatusCode {
+        match self {
+            ApiTokenError::NoApiTokensFound => StatusCode::NOT_FOUND,
+            ApiTokenError::ApiTokenCreationFailure => StatusCode::INTERNAL_SERVER_ERROR,
+            ApiTokenError::ApiTokenForbidden => StatusCode::FORBIDDEN,
+            ApiTokenError::ApiTokenUnauthorized => StatusCode::UNAUTHORIZED,
+            ApiTokenError::ApiTokenScopeMissing => StatusCode::FORBIDDEN,
+        }
+    }
+}
@@ -0,0 +1,29 @@
+use actix_web::{http::StatusCode, HttpResponse, ResponseError};
+
+use derive_more::Display;
+use utoipa::ToSchema;
+
+use crate::error::api_error::ApiError;
+
+#[derive(Debug, Display, ToSchema)]
+pub enum AttachmentError {
+    NoAttachmentsFound,
+    AttachmentCreationFailure,
+    AttachmentForbidden,
+    AttachmentRejected,
+}
+
+impl ResponseError for AttachmentError {
+    fn error_response(&self) -> HttpResponse<actix_web::body::BoxBody> {
+        HttpResponse::build(self.status_code()).json(ApiError::new(self))
+    }
+
+    fn status_code(&self) -> StatusCode {
+        match self {
+            AttachmentError::NoAttachmentsFound => StatusCode::NOT_FOUND,
+            AttachmentError::AttachmentCreationFailure => StatusCode::INTERNAL_SERVER_ERROR,
+            AttachmentError::AttachmentForbidden => StatusCode::FORBIDDEN,
+            AttachmentError::AttachmentRejected => StatusCode::BAD_REQUEST,
+        }
+    }
+}
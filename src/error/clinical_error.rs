@@ -1,27 +1,27 @@
-use actix_web::{
-    http::{header::ContentType, StatusCode},
-    HttpResponse, ResponseError,
-};
+use actix_web::{http::StatusCode, HttpResponse, ResponseError};
 
 use derive_more::Display;
+use utoipa::ToSchema;
 
-#[derive(Debug, Display)]
+use crate::error::api_error::ApiError;
+
+#[derive(Debug, Display, ToSchema)]
 pub enum ClinicalError {
     NoClinicalsFound = 0,
     ClinicalCreationFailure = 1,
+    InvalidStage = 2,
 }
 
 impl ResponseError for ClinicalError {
     fn error_response(&self) -> actix_web::HttpResponse<actix_web::body::BoxBody> {
-        HttpResponse::build(self.status_code())
-            .insert_header(ContentType::json())
-            .body(self.to_string())
+        HttpResponse::build(self.status_code()).json(ApiError::new(self))
     }
 
     fn status_code(&self) -> StatusCode {
         match self {
             ClinicalError::NoClinicalsFound => StatusCode::NOT_FOUND,
             ClinicalError::ClinicalCreationFailure => StatusCode::INTERNAL_SERVER_ERROR,
+            ClinicalError::InvalidStage => StatusCode::BAD_REQUEST,
         }
     }
 }
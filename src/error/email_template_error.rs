@@ -0,0 +1,29 @@
+use actix_web::{http::StatusCode, HttpResponse, ResponseError};
+
+use derive_more::Display;
+use utoipa::ToSchema;
+
+use crate::error::api_error::ApiError;
+
+#[derive(Debug, Display, ToSchema)]
+pub enum EmailTemplateError {
+    NoEmailTemplatesFound,
+    EmailTemplateCreationFailure,
+    EmailTemplateForbidden,
+    EmailTemplateNotCurrent,
+}
+
+impl ResponseError for EmailTemplateError {
+    fn error_response(&self) -> HttpResponse<actix_web::body::BoxBody> {
+        HttpResponse::build(self.status_code()).json(ApiError::new(self))
+    }
+
+    fn status_code(&self) -> StatusCode {
+        match self {
+            EmailTemplateError::NoEmailTemplatesFound => StatusCode::NOT_FOUND,
+            EmailTemplateError::EmailTemplateCreationFailure => StatusCode::INTERNAL_SERVER_ERROR,
+            EmailTemplateError::EmailTemplateForbidden => StatusCode::FORBIDDEN,
+            EmailTemplateError::EmailTemplateNotCurrent => StatusCode::CONFLICT,
+        }
+    }
+}
@@ -1,27 +1,29 @@
-use actix_web::{
-    http::{header::ContentType, StatusCode},
-    HttpResponse, ResponseError,
-};
+use actix_web::{http::StatusCode, HttpResponse, ResponseError};
 
 use derive_more::Display;
+use utoipa::ToSchema;
 
-#[derive(Debug, Display)]
+use crate::error::api_error::ApiError;
+
+#[derive(Debug, Display, ToSchema)]
 pub enum EnterpriseError {
     NoEnterprisesFound = 0,
     EnterpriseCreationFailure = 1,
+    EnterpriseDeletionFailure = 2,
+    InvalidStage = 3,
 }
 
 impl ResponseError for EnterpriseError {
     fn error_response(&self) -> HttpResponse<actix_web::body::BoxBody> {
-        HttpResponse::build(self.status_code())
-            .insert_header(ContentType::json())
-            .body(self.to_string())
+        HttpResponse::build(self.status_code()).json(ApiError::new(self))
     }
 
     fn status_code(&self) -> StatusCode {
         match self {
             EnterpriseError::NoEnterprisesFound => StatusCode::NOT_FOUND,
             EnterpriseError::EnterpriseCreationFailure => StatusCode::INTERNAL_SERVER_ERROR,
+            EnterpriseError::EnterpriseDeletionFailure => StatusCode::INTERNAL_SERVER_ERROR,
+            EnterpriseError::InvalidStage => StatusCode::BAD_REQUEST,
         }
     }
 }
@@ -0,0 +1,29 @@
+use actix_web::{http::StatusCode, HttpResponse, ResponseError};
+
+use derive_more::Display;
+use utoipa::ToSchema;
+
+use crate::error::api_error::ApiError;
+
+#[derive(Debug, Display, ToSchema)]
+pub enum LeadError {
+    NoLeadsFound,
+    InvalidStage,
+    InvalidBulkRequest,
+    LeadForbidden,
+}
+
+impl ResponseError for LeadError {
+    fn error_response(&self) -> HttpResponse<actix_web::body::BoxBody> {
+        HttpResponse::build(self.status_code()).json(ApiError::new(self))
+    }
+
+    fn status_code(&self) -> StatusCode {
+        match self {
+            LeadError::NoLeadsFound => StatusCode::NOT_FOUND,
+            LeadError::InvalidStage => StatusCode::BAD_REQUEST,
+            LeadError::InvalidBulkRequest => StatusCode::BAD_REQUEST,
+            LeadError::LeadForbidden => StatusCode::FORBIDDEN,
+        }
+    }
+}
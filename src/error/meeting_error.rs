@@ -0,0 +1,29 @@
+use actix_web::{http::StatusCode, HttpResponse, ResponseError};
+
+use derive_more::Display;
+use utoipa::ToSchema;
+
+use crate::error::api_error::ApiError;
+
+#[derive(Debug, Display, ToSchema)]
+pub enum MeetingError {
+    NoMeetingsFound,
+    MeetingCreationFailure,
+    MeetingForbidden,
+    CalendarFeedNotFound,
+}
+
+impl ResponseError for MeetingError {
+    fn error_response(&self) -> HttpResponse<actix_web::body::BoxBody> {
+        HttpResponse::build(self.status_code()).json(ApiError::new(self))
+    }
+
+    fn status_code(&self) -> StatusCode {
+        match self {
+            MeetingError::NoMeetingsFound => StatusCode::NOT_FOUND,
+            MeetingError::MeetingCreationFailure => StatusCode::INTERNAL_SERVER_ERROR,
+            MeetingError::MeetingForbidden => StatusCode::FORBIDDEN,
+            MeetingError::CalendarFeedNotFound => StatusCode::NOT_FOUND,
+        }
+    }
+}
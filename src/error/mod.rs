@@ -1,4 +1,22 @@
+pub mod account_error;
+pub mod api_error;
+pub mod api_token_error;
+pub mod attachment_error;
+pub mod campaign_error;
 pub mod clinical_error;
+pub mod email_template_error;
 pub mod enterprise_error;
+pub mod lead_error;
+pub mod meeting_error;
+pub mod notification_error;
+pub mod pipeline_error;
+pub mod quote_error;
 pub mod school_error;
+pub mod scoring_error;
+pub mod segment_error;
+pub mod service_error;
+pub mod session_error;
+pub mod tag_error;
+pub mod territory_error;
 pub mod user_error;
+pub mod web_lead_error;
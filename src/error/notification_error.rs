@@ -0,0 +1,27 @@
+use actix_web::{http::StatusCode, HttpResponse, ResponseError};
+
+use derive_more::Display;
+use utoipa::ToSchema;
+
+use crate::error::api_error::ApiError;
+
+#[derive(Debug, Display, ToSchema)]
+pub enum NotificationError {
+    NoNotificationsFound,
+    NotificationUpdateFailure,
+    NotificationForbidden,
+}
+
+impl ResponseError for NotificationError {
+    fn error_response(&self) -> HttpResponse<actix_web::body::BoxBody> {
+        HttpResponse::build(self.status_code()).json(ApiError::new(self))
+    }
+
+    fn status_code(&self) -> StatusCode {
+        match self {
+            NotificationError::NoNotificationsFound => StatusCode::NOT_FOUND,
+            NotificationError::NotificationUpdateFailure => StatusCode::INTERNAL_SERVER_ERROR,
+            NotificationError::NotificationForbidden => StatusCode::FORBIDDEN,
+        }
+    }
+}
@@ -0,0 +1,29 @@
+use actix_web::{http::StatusCode, HttpResponse, ResponseError};
+
+use derive_more::Display;
+use utoipa::ToSchema;
+
+use crate::error::api_error::ApiError;
+
+#[derive(Debug, Display, ToSchema)]
+pub enum PipelineError {
+    NoPipelineStagesFound,
+    PipelineStageCreationFailure,
+    PipelineStageForbidden,
+    PipelineStageInUse,
+}
+
+impl ResponseError for PipelineError {
+    fn error_response(&self) -> HttpResponse<actix_web::body::BoxBody> {
+        HttpResponse::build(self.status_code()).json(ApiError::new(self))
+    }
+
+    fn status_code(&self) -> StatusCode {
+        match self {
+            PipelineError::NoPipelineStagesFound => StatusCode::NOT_FOUND,
+            PipelineError::PipelineStageCreationFailure => StatusCode::INTERNAL_SERVER_ERROR,
+            PipelineError::PipelineStageForbidden => StatusCode::FORBIDDEN,
+            PipelineError::PipelineStageInUse => StatusCode::CONFLICT,
+        }
+    }
+}
@@ -1,26 +1,26 @@
-use actix_web::{
-    http::{header::ContentType, StatusCode},
-    HttpResponse, ResponseError,
-};
+use actix_web::{http::StatusCode, HttpResponse, ResponseError};
 
 use derive_more::Display;
+use utoipa::ToSchema;
 
-#[derive(Debug, Display)]
+use crate::error::api_error::ApiError;
+
+#[derive(Debug, Display, ToSchema)]
 pub enum SchoolError {
     NoSchoolsFound = 0,
     SchoolCreationFailure = 1,
+    InvalidStage = 2,
 }
 impl ResponseError for SchoolError {
     fn error_response(&self) -> HttpResponse<actix_web::body::BoxBody> {
-        HttpResponse::build(self.status_code())
-            .insert_header(ContentType::json())
-            .body(self.to_string())
+        HttpResponse::build(self.status_code()).json(ApiError::new(self))
     }
 
     fn status_code(&self) -> StatusCode {
         match self {
             SchoolError::NoSchoolsFound => StatusCode::NOT_FOUND,
             SchoolError::SchoolCreationFailure => StatusCode::INTERNAL_SERVER_ERROR,
+            SchoolError::InvalidStage => StatusCode::BAD_REQUEST,
         }
     }
 }
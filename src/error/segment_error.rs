@@ -0,0 +1,27 @@
+use actix_web::{http::StatusCode, HttpResponse, ResponseError};
+
+use derive_more::Display;
+use utoipa::ToSchema;
+
+use crate::error::api_error::ApiError;
+
+#[derive(Debug, Display, ToSchema)]
+pub enum SegmentError {
+    NoSegmentsFound,
+    SegmentCreationFailure,
+    SegmentForbidden,
+}
+
+impl ResponseError for SegmentError {
+    fn error_response(&self) -> HttpResponse<actix_web::body::BoxBody> {
+        HttpResponse::build(self.status_code()).json(ApiError::new(self))
+    }
+
+    fn status_code(&self) -> StatusCode {
+        match self {
+            SegmentError::NoSegmentsFound => StatusCode::NOT_FOUND,
+            SegmentError::SegmentCreationFailure => StatusCode::INTERNAL_SERVER_ERROR,
+            SegmentError::SegmentForbidden => StatusCode::FORBIDDEN,
+        }
+    }
+}
@@ -0,0 +1,27 @@
+use actix_web::{http::StatusCode, HttpResponse, ResponseError};
+
+use derive_more::Display;
+use utoipa::ToSchema;
+
+use crate::error::api_error::ApiError;
+
+#[derive(Debug, Display, ToSchema)]
+pub enum ServiceError {
+    NoServicesFound,
+    ServiceCreationFailure,
+    ServiceForbidden,
+}
+
+impl ResponseError for ServiceError {
+    fn error_response(&self) -> HttpResponse<actix_web::body::BoxBody> {
+        HttpResponse::build(self.status_code()).json(ApiError::new(self))
+    }
+
+    fn status_code(&self) -> StatusCode {
+        match self {
+            ServiceError::NoServicesFound => StatusCode::NOT_FOUND,
+            ServiceError::ServiceCreationFailure => StatusCode::INTERNAL_SERVER_ERROR,
+            ServiceError::ServiceForbidden => StatusCode::FORBIDDEN,
+        }
+    }
+}
@@ -0,0 +1,29 @@
+use actix_web::{http::StatusCode, HttpResponse, ResponseError};
+
+use derive_more::Display;
+use utoipa::ToSchema;
+
+use crate::error::api_error::ApiError;
+
+#[derive(Debug, Display, ToSchema)]
+pub enum SessionError {
+    InvalidCredentials,
+    NoSessionFound,
+    SessionCreationFailure,
+    TooManyLoginAttempts,
+}
+
+impl ResponseError for SessionError {
+    fn error_response(&self) -> HttpResponse<actix_web::body::BoxBody> {
+        HttpResponse::build(self.status_code()).json(ApiError::new(self))
+    }
+
+    fn status_code(&self) -> StatusCode {
+        match self {
+            SessionError::InvalidCredentials => StatusCode::UNAUTHORIZED,
+            SessionError::NoSessionFound => StatusCode::UNAUTHORIZED,
+            SessionError::SessionCreationFailure => StatusCode::INTERNAL_SERVER_ERROR,
+            SessionError::TooManyLoginAttempts => StatusCode::TOO_MANY_REQUESTS,
+        }
+    }
+}
@@ -0,0 +1,25 @@
+use actix_web::{http::StatusCode, HttpResponse, ResponseError};
+
+use derive_more::Display;
+use utoipa::ToSchema;
+
+use crate::error::api_error::ApiError;
+
+#[derive(Debug, Display, ToSchema)]
+pub enum TagError {
+    NoTagsFound,
+    TagForbidden,
+}
+
+impl ResponseError for TagError {
+    fn error_response(&self) -> HttpResponse<actix_web::body::BoxBody> {
+        HttpResponse::build(self.status_code()).json(ApiError::new(self))
+    }
+
+    fn status_code(&self) -> StatusCode {
+        match self {
+            TagError::NoTagsFound => StatusCode::NOT_FOUND,
+            TagError::TagForbidden => StatusCode::FORBIDDEN,
+        }
+    }
+}
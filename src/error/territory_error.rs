@@ -0,0 +1,27 @@
+use actix_web::{http::StatusCode, HttpResponse, ResponseError};
+
+use derive_more::Display;
+use utoipa::ToSchema;
+
+use crate::error::api_error::ApiError;
+
+#[derive(Debug, Display, ToSchema)]
+pub enum TerritoryError {
+    NoTerritoriesFound,
+    TerritoryCreationFailure,
+    TerritoryForbidden,
+}
+
+impl ResponseError for TerritoryError {
+    fn error_response(&self) -> HttpResponse<actix_web::body::BoxBody> {
+        HttpResponse::build(self.status_code()).json(ApiError::new(self))
+    }
+
+    fn status_code(&self) -> StatusCode {
+        match self {
+            TerritoryError::NoTerritoriesFound => StatusCode::NOT_FOUND,
+            TerritoryError::TerritoryCreationFailure => StatusCode::INTERNAL_SERVER_ERROR,
+            TerritoryError::TerritoryForbidden => StatusCode::FORBIDDEN,
+        }
+    }
+}
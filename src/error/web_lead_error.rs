@@ -0,0 +1,29 @@
+use actix_web::{http::StatusCode, HttpResponse, ResponseError};
+
+use derive_more::Display;
+use utoipa::ToSchema;
+
+use crate::error::api_error::ApiError;
+
+#[derive(Debug, Display, ToSchema)]
+pub enum WebLeadError {
+    WebLeadCreationFailure,
+    WebLeadRejected,
+    WebLeadCaptchaFailed,
+    TooManyWebLeads,
+}
+
+impl ResponseError for WebLeadError {
+    fn error_response(&self) -> HttpResponse<actix_web::body::BoxBody> {
+        HttpResponse::build(self.status_code()).json(ApiError::new(self))
+    }
+
+    fn status_code(&self) -> StatusCode {
+        match self {
+            WebLeadError::WebLeadCreationFailure => StatusCode::INTERNAL_SERVER_ERROR,
+            WebLeadError::WebLeadRejected => StatusCode::BAD_REQUEST,
+            WebLeadError::WebLeadCaptchaFailed => StatusCode::BAD_REQUEST,
+            WebLeadError::TooManyWebLeads => StatusCode::TOO_MANY_REQUESTS,
+        }
+    }
+}
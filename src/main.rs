@@ -1,4 +1,5 @@
 use actix_cors::Cors;
+use actix_multipart::form::MultipartFormConfig;
 use actix_web::{middleware, web::Data, App, HttpServer};
 use log::{info, warn};
 
@@ -16,16 +17,50 @@ mod utils;
 use crate::db::config::Database;
 use crate::{
     controllers::{
-        clinics_api_controller::clinical_api_controllers,
+        account_html_controller::account_html_controllers,
+        address_html_controller::address_html_controllers,
+        api_controller::{api_controllers, deprecated_api_controllers},
+        api_token_html_controller::api_token_html_controllers,
+        attachment_html_controller::attachment_html_controllers,
+        bulk_html_controller::bulk_html_controllers,
+        campaign_html_controller::campaign_html_controllers,
         clinics_html_controller::clinical_html_controllers,
-        enterprise_api_controller::enterprise_api_controllers,
+        contact_html_controller::contact_html_controllers,
+        email_template_html_controller::email_template_html_controllers,
         enterprise_html_controller::enterprise_html_controllers,
-        help_html_controller::help_html_controllers, school_api_controller::school_api_controllers,
+        help_html_controller::help_html_controllers,
+        kanban_html_controller::kanban_html_controllers,
+        live_html_controller::live_html_controllers,
+        meeting_html_controller::meeting_html_controllers,
+        notification_html_controller::notification_html_controllers,
+        openapi_controller::openapi_controllers,
+        pipeline_html_controller::pipeline_html_controllers,
+        quote_html_controller::quote_html_controllers,
         school_html_controller::school_html_controller,
-        users_api_controller::users_api_controllers, users_html_controller::user_html_controllers,
+        scoring_html_controller::scoring_html_controllers,
+        segment_html_controller::segment_html_controllers,
+        service_html_controller::service_html_controllers,
+        session_html_controller::session_html_controllers,
+        source_html_controller::source_html_controllers,
+        tag_html_controller::tag_html_controllers,
+        territory_html_controller::territory_html_controllers,
+        users_html_controller::user_html_controllers,
     },
     reporting::telemetry::{get_subscriber, init_subscriber},
-    utils::env::{get_cwd, set_env_vars, ConfVars},
+    utils::{
+        api_utils::with_request_id,
+        attachment_utils::get_attachment_max_bytes,
+        contact_utils::migrate_lead_contacts,
+        env::{get_cwd, set_env_vars, ConfVars},
+        live_utils::watch_lead_tables,
+        migration_utils::run_once,
+        phone_utils::normalize_existing_phones,
+        pipeline_utils::seed_default_pipelines,
+        role_utils::seed_first_admin,
+        scoring_utils::{schedule_nightly_scoring, seed_default_scoring_rules},
+        service_utils::migrate_legacy_services,
+        social_utils::normalize_existing_social_profiles,
+    },
 };
 
 #[actix_web::main]
@@ -54,6 +89,41 @@ async fn main() -> std::io::Result<()> {
     let my_db = Database::init().await.expect("CANT_CONNECT_TO_DB");
     let db_data = Data::new(my_db);
 
+    run_once(
+        &db_data,
+        "migrate_legacy_services",
+        migrate_legacy_services(&db_data),
+    )
+    .await;
+    run_once(
+        &db_data,
+        "seed_default_pipelines",
+        seed_default_pipelines(&db_data),
+    )
+    .await;
+    run_once(
+        &db_data,
+        "migrate_lead_contacts",
+        migrate_lead_contacts(&db_data),
+    )
+    .await;
+    run_once(
+        &db_data,
+        "normalize_existing_phones",
+        normalize_existing_phones(&db_data),
+    )
+    .await;
+    run_once(
+        &db_data,
+        "normalize_existing_social_profiles",
+        normalize_existing_social_profiles(&db_data),
+    )
+    .await;
+    seed_default_scoring_rules(&db_data).await;
+    run_once(&db_data, "seed_first_admin", seed_first_admin(&db_data)).await;
+    actix_web::rt::spawn(schedule_nightly_scoring(db_data.clone()));
+    watch_lead_tables(db_data.clone());
+
     info!("Welcome to Yay_CRM");
 
     HttpServer::new(move || {
@@ -62,16 +132,40 @@ async fn main() -> std::io::Result<()> {
         App::new()
             .wrap(middleware::NormalizePath::trim())
             .wrap(cors)
+            .wrap_fn(with_request_id)
             .app_data(db_data.clone())
-            .configure(clinical_api_controllers)
-            .configure(users_api_controllers)
-            .configure(enterprise_api_controllers)
+            .app_data(
+                MultipartFormConfig::default().total_limit(get_attachment_max_bytes() + 64 * 1024),
+            )
             .configure(user_html_controllers)
             .configure(enterprise_html_controllers)
             .configure(help_html_controllers)
             .configure(clinical_html_controllers)
-            .configure(school_api_controllers)
             .configure(school_html_controller)
+            .configure(email_template_html_controllers)
+            .configure(quote_html_controllers)
+            .configure(service_html_controllers)
+            .configure(pipeline_html_controllers)
+            .configure(kanban_html_controllers)
+            .configure(attachment_html_controllers)
+            .configure(tag_html_controllers)
+            .configure(segment_html_controllers)
+            .configure(bulk_html_controllers)
+            .configure(scoring_html_controllers)
+            .configure(account_html_controllers)
+            .configure(contact_html_controllers)
+            .configure(address_html_controllers)
+            .configure(territory_html_controllers)
+            .configure(campaign_html_controllers)
+            .configure(source_html_controllers)
+            .configure(live_html_controllers)
+            .configure(notification_html_controllers)
+            .configure(meeting_html_controllers)
+            .configure(api_token_html_controllers)
+            .configure(session_html_controllers)
+            .configure(api_controllers)
+            .configure(openapi_controllers)
+            .configure(deprecated_api_controllers)
     })
     .bind(server_address_conf)
     .expect("FAILED TO BIND TO PORT")
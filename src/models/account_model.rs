@@ -0,0 +1,103 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
+
+use crate::{
+    models::{lead_model::StageTransition, sales_model::LeadType},
+    utils::phone_utils::validate_optional_phone,
+};
+
+#[derive(Debug, Deserialize, Serialize, IntoParams, ToSchema)]
+#[into_params(parameter_in = Path)]
+pub struct AccountUuid {
+    pub uuid: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, IntoParams, ToSchema)]
+#[into_params(parameter_in = Path)]
+pub struct ContactUuid {
+    pub uuid: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, Validate, ToSchema)]
+pub struct Account {
+    pub uuid: Option<String>,
+    #[validate(length(min = 2, message = "Account name does not match valid length"))]
+    pub name: String,
+    pub lead_type: LeadType,
+    pub lead_uuid: String,
+    #[validate(email)]
+    pub email: Option<String>,
+    #[validate(custom(function = "validate_optional_phone"))]
+    pub phone: Option<String>,
+    #[validate(url)]
+    pub website: Option<String>,
+    pub services_offered: Vec<String>,
+    pub tags: Vec<String>,
+    pub assigned_to: Option<String>,
+    pub notes: String,
+    pub deleted: bool,
+    pub date_created: Option<DateTime<Utc>>,
+    pub date_modified: Option<DateTime<Utc>>,
+    pub created_by: Option<String>,
+    pub modified_by: Option<String>,
+}
+
+impl Account {
+    pub fn new(uuid: String, account: Account) -> Account {
+        Account {
+            uuid: Some(uuid),
+            ..account
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, Validate, ToSchema)]
+pub struct Contact {
+    pub uuid: Option<String>,
+    pub account_uuid: Option<String>,
+    pub lead_type: Option<LeadType>,
+    pub lead_uuid: Option<String>,
+    #[validate(length(min = 2, message = "Name does not match valid length"))]
+    pub name: String,
+    pub last_name: String,
+    pub role: Option<String>,
+    #[validate(email)]
+    pub email: Option<String>,
+    #[validate(custom(function = "validate_optional_phone"))]
+    pub phone: Option<String>,
+    pub is_primary: bool,
+    pub deleted: bool,
+    pub date_created: Option<DateTime<Utc>>,
+    pub date_modified: Option<DateTime<Utc>>,
+    pub created_by: Option<String>,
+    pub modified_by: Option<String>,
+}
+
+impl Contact {
+    pub fn new(uuid: String, contact: Contact) -> Contact {
+        Contact {
+            uuid: Some(uuid),
+            ..contact
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct AccountDetail {
+    pub account: Account,
+    pub contacts: Vec<Contact>,
+    pub history: Vec<StageTransition>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AccountHandlebarsError {
+    pub error: String,
+}
+
+impl AccountHandlebarsError {
+    pub fn new(error: String) -> AccountHandlebarsError {
+        AccountHandlebarsError { error }
+    }
+}
@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize, ToSchema)]
+pub struct Address {
+    pub street: Option<String>,
+    pub neighborhood: Option<String>,
+    pub municipality: Option<String>,
+    pub state: Option<String>,
+    pub postal_code: Option<String>,
+    pub country: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct AddressStateTag {
+    pub value: String,
+    pub text: String,
+    pub selected: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AddressHandlebarsError {
+    pub error: String,
+}
+
+impl AddressHandlebarsError {
+    pub fn new(error: String) -> AddressHandlebarsError {
+        AddressHandlebarsError { error }
+    }
+}
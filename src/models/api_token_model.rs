@@ -0,0 +1,114 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
+
+use crate::utils::api_token_utils::validate_token_scopes;
+
+#[derive(Debug, Deserialize, Serialize, IntoParams, ToSchema)]
+#[into_params(parameter_in = Path)]
+pub struct ApiTokenUuid {
+    pub uuid: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, ToSchema)]
+#[allow(clippy::upper_case_acronyms)]
+pub enum TokenAccess {
+    READ,
+    WRITE,
+}
+
+impl fmt::Display for TokenAccess {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TokenAccess::READ => write!(f, "READ"),
+            TokenAccess::WRITE => write!(f, "WRITE"),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, ToSchema)]
+pub struct TokenScope {
+    pub resource: String,
+    pub access: TokenAccess,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ApiToken {
+    pub uuid: Option<String>,
+    pub user_uuid: String,
+    pub name: String,
+    pub token_hash: String,
+    pub token_prefix: String,
+    pub scopes: Vec<TokenScope>,
+    pub last_used: Option<DateTime<Utc>>,
+    pub revoked: bool,
+    pub date_created: Option<DateTime<Utc>>,
+    pub date_modified: Option<DateTime<Utc>>,
+    pub created_by: Option<String>,
+    pub modified_by: Option<String>,
+}
+
+impl ApiToken {
+    pub fn new(uuid: String, api_token: ApiToken) -> ApiToken {
+        ApiToken {
+            uuid: Some(uuid),
+            ..api_token
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Validate, ToSchema)]
+pub struct NewApiToken {
+    #[validate(length(min = 2, max = 60, message = "Token name does not match valid length"))]
+    pub name: String,
+    #[validate(
+        length(min = 1, message = "A token needs at least one scope"),
+        custom(function = "validate_token_scopes")
+    )]
+    pub scopes: Vec<TokenScope>,
+    pub created_by: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct ApiTokenSummary {
+    pub uuid: String,
+    pub name: String,
+    pub token_prefix: String,
+    pub scopes: Vec<TokenScope>,
+    pub last_used: Option<DateTime<Utc>>,
+    pub revoked: bool,
+    pub date_created: Option<DateTime<Utc>>,
+}
+
+impl ApiTokenSummary {
+    pub fn new(api_token: ApiToken) -> ApiTokenSummary {
+        ApiTokenSummary {
+            uuid: api_token.uuid.unwrap_or_default(),
+            name: api_token.name,
+            token_prefix: api_token.token_prefix,
+            scopes: api_token.scopes,
+            last_used: api_token.last_used,
+            revoked: api_token.revoked,
+            date_created: api_token.date_created,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct ApiTokenCreated {
+    pub uuid: String,
+    pub token: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ApiTokenHandlebarsError {
+    pub error: String,
+}
+
+impl ApiTokenHandlebarsError {
+    pub fn new(error: String) -> ApiTokenHandlebarsError {
+        ApiTokenHandlebarsError { error }
+    }
+}
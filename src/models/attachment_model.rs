@@ -0,0 +1,54 @@
+use actix_multipart::form::{tempfile::TempFile, MultipartForm};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::models::sales_model::LeadType;
+
+#[derive(Debug, Deserialize, Serialize, IntoParams, ToSchema)]
+#[into_params(parameter_in = Path)]
+pub struct AttachmentUuid {
+    pub uuid: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+pub struct Attachment {
+    pub uuid: Option<String>,
+    pub lead_type: LeadType,
+    pub lead_uuid: String,
+    pub file_name: String,
+    pub storage_name: String,
+    pub mime_type: String,
+    pub size: u64,
+    pub deleted: bool,
+    pub date_created: Option<DateTime<Utc>>,
+    pub date_modified: Option<DateTime<Utc>>,
+    pub created_by: Option<String>,
+    pub modified_by: Option<String>,
+}
+
+impl Attachment {
+    pub fn new(uuid: String, attachment: Attachment) -> Attachment {
+        Attachment {
+            uuid: Some(uuid),
+            ..attachment
+        }
+    }
+}
+
+#[derive(Debug, MultipartForm, ToSchema)]
+pub struct AttachmentUpload {
+    #[schema(value_type = String, format = Binary)]
+    pub file: TempFile,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AttachmentHandlebarsError {
+    pub error: String,
+}
+
+impl AttachmentHandlebarsError {
+    pub fn new(error: String) -> AttachmentHandlebarsError {
+        AttachmentHandlebarsError { error }
+    }
+}
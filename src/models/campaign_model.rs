@@ -0,0 +1,80 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
+
+use crate::models::{lead_model::LeadChannel, sales_model::LeadType};
+
+#[derive(Debug, Deserialize, Serialize, IntoParams, ToSchema)]
+#[into_params(parameter_in = Path)]
+pub struct CampaignUuid {
+    pub uuid: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, Validate, ToSchema)]
+pub struct Campaign {
+    pub uuid: Option<String>,
+    #[validate(length(min = 2, message = "Campaign name does not match valid length"))]
+    pub name: String,
+    #[validate(length(min = 2, max = 60, message = "Campaign code does not match valid length"))]
+    pub code: Option<String>,
+    pub channel: Option<LeadChannel>,
+    #[validate(range(min = 0.0, message = "Budget can't be negative"))]
+    pub budget: f64,
+    pub start_date: Option<DateTime<Utc>>,
+    pub end_date: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub notes: String,
+    pub deleted: bool,
+    pub date_created: Option<DateTime<Utc>>,
+    pub date_modified: Option<DateTime<Utc>>,
+    pub created_by: Option<String>,
+    pub modified_by: Option<String>,
+}
+
+impl Campaign {
+    pub fn new(uuid: String, campaign: Campaign) -> Campaign {
+        Campaign {
+            uuid: Some(uuid),
+            name: campaign.name.trim().to_string(),
+            ..campaign
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct AttributionReportQuery {
+    pub lead_type: Option<LeadType>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+pub struct AttributionRow {
+    pub key: Option<String>,
+    pub name: String,
+    pub leads: usize,
+    pub wins: usize,
+    pub win_rate: f64,
+    pub win_value: f64,
+    pub budget: Option<f64>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+pub struct AttributionReport {
+    pub campaigns: Vec<AttributionRow>,
+    pub sources: Vec<AttributionRow>,
+    pub leads: usize,
+    pub wins: usize,
+    pub win_value: f64,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CampaignHandlebarsError {
+    pub error: String,
+}
+
+impl CampaignHandlebarsError {
+    pub fn new(error: String) -> CampaignHandlebarsError {
+        CampaignHandlebarsError { error }
+    }
+}
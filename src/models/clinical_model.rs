@@ -1,15 +1,28 @@
-use chrono::{DateTime, Local};
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
 use validator::Validate;
 
-use crate::models::sales_model::{SalesFunnel, ServicesOffered};
+use crate::{
+    models::{address_model::Address, lead_model::LeadSource},
+    utils::{
+        address_utils::validate_address,
+        phone_utils::validate_phone,
+        social_utils::{
+            validate_facebook, validate_instagram, validate_linked_in, validate_tik_tok,
+            validate_twitter,
+        },
+        time::deserialize_optional_datetime,
+    },
+};
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, Serialize, IntoParams, ToSchema)]
+#[into_params(parameter_in = Path)]
 pub struct ClinicalUuid {
     pub uuid: String,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize, Validate)]
+#[derive(Clone, Debug, Deserialize, Serialize, Validate, ToSchema)]
 pub struct Clinical {
     pub uuid: Option<String>,
     #[validate(length(min = 2, message = "Name does not match valid length"))]
@@ -25,20 +38,39 @@ pub struct Clinical {
     pub specialty: String,
     #[validate(email)]
     pub email: Option<String>,
+    #[validate(custom(function = "validate_phone"))]
     pub phone: String,
     pub deleted: bool,
-    #[validate(url)]
+    #[validate(custom(function = "validate_facebook"))]
     pub fb: Option<String>,
+    #[validate(custom(function = "validate_instagram"))]
     pub instagram: Option<String>,
+    #[validate(custom(function = "validate_linked_in"))]
     pub linked_in: Option<String>,
+    #[validate(custom(function = "validate_tik_tok"))]
     pub tik_tok: Option<String>,
+    #[validate(custom(function = "validate_twitter"))]
     pub twitter: Option<String>,
-    pub first_contact_date: Option<DateTime<Local>>,
-    pub sales_funnel: SalesFunnel,
+    #[serde(default, deserialize_with = "deserialize_optional_datetime")]
+    pub first_contact_date: Option<DateTime<Utc>>,
+    pub sales_funnel: String,
     pub notes: String,
-    pub services_offered: Vec<ServicesOffered>,
-    pub date_created: Option<DateTime<Local>>,
-    pub date_modified: Option<DateTime<Local>>,
+    pub services_offered: Vec<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub assigned_to: Option<String>,
+    #[serde(default)]
+    pub score: i32,
+    #[serde(default)]
+    pub account_uuid: Option<String>,
+    #[serde(default)]
+    #[validate(custom(function = "validate_address"))]
+    pub address: Address,
+    #[serde(default)]
+    pub source: Option<LeadSource>,
+    pub date_created: Option<DateTime<Utc>>,
+    pub date_modified: Option<DateTime<Utc>>,
     pub created_by: Option<String>,
     pub modified_by: Option<String>,
 }
@@ -48,6 +80,42 @@ impl Clinical {
         let uuid = Some(c_uuid);
         Clinical { uuid, ..clinical }
     }
+
+    pub fn sample() -> Clinical {
+        let date_created = Utc::now();
+        Clinical {
+            uuid: Some("sample".to_string()),
+            name: "Ana".to_string(),
+            last_name: "García".to_string(),
+            is_company: true,
+            clinic_name: Some("Clínica de Ejemplo".to_string()),
+            clinic_web: Some("https://www.example.com".to_string()),
+            clinic_email: Some("contacto@example.com".to_string()),
+            specialty: "Pediatría".to_string(),
+            email: Some("ana.garcia@example.com".to_string()),
+            phone: "5512345678".to_string(),
+            deleted: false,
+            fb: None,
+            instagram: None,
+            linked_in: None,
+            tik_tok: None,
+            twitter: None,
+            first_contact_date: Some(date_created),
+            sales_funnel: "PROSPECT".to_string(),
+            notes: "".to_string(),
+            services_offered: vec![],
+            tags: vec!["vip".to_string()],
+            assigned_to: None,
+            score: 0,
+            account_uuid: None,
+            address: Address::default(),
+            source: None,
+            date_created: Some(date_created),
+            date_modified: Some(date_created),
+            created_by: None,
+            modified_by: None,
+        }
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize)]
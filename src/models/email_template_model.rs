@@ -0,0 +1,75 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
+
+use crate::models::sales_model::LeadType;
+
+#[derive(Debug, Deserialize, Serialize, IntoParams, ToSchema)]
+#[into_params(parameter_in = Path)]
+pub struct EmailTemplateUuid {
+    pub uuid: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, Validate, ToSchema)]
+pub struct EmailTemplate {
+    pub uuid: Option<String>,
+    pub template_key: Option<String>,
+    #[validate(length(min = 2, message = "Template name does not match valid length"))]
+    pub name: String,
+    #[validate(length(equal = 2, message = "Language must be a two letter code"))]
+    pub language: String,
+    pub lead_type: LeadType,
+    pub version: Option<u32>,
+    pub is_current: Option<bool>,
+    #[validate(length(min = 2, message = "Subject does not match valid length"))]
+    pub subject: String,
+    pub html_body: String,
+    pub text_body: String,
+    pub deleted: bool,
+    pub date_created: Option<DateTime<Utc>>,
+    pub date_modified: Option<DateTime<Utc>>,
+    pub created_by: Option<String>,
+    pub modified_by: Option<String>,
+}
+
+impl EmailTemplate {
+    pub fn new(uuid: String, email_template: EmailTemplate) -> EmailTemplate {
+        let template_key = match email_template.template_key.clone() {
+            Some(key) => Some(key),
+            None => Some(uuid.clone()),
+        };
+
+        EmailTemplate {
+            uuid: Some(uuid),
+            template_key,
+            ..email_template
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct EmailTemplatePreview {
+    pub lead_type: LeadType,
+    pub subject: String,
+    pub html_body: String,
+    pub text_body: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct RenderedEmail {
+    pub subject: String,
+    pub html_body: String,
+    pub text_body: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct EmailTemplateHandlebarsError {
+    pub error: String,
+}
+
+impl EmailTemplateHandlebarsError {
+    pub fn new(error: String) -> EmailTemplateHandlebarsError {
+        EmailTemplateHandlebarsError { error }
+    }
+}
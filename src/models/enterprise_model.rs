@@ -1,15 +1,28 @@
 use chrono::prelude::*;
 use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
 use validator::Validate;
 
-use crate::models::sales_model::{SalesFunnel, ServicesOffered};
+use crate::{
+    models::{address_model::Address, lead_model::LeadSource},
+    utils::{
+        address_utils::validate_address,
+        phone_utils::validate_optional_phone,
+        social_utils::{
+            validate_facebook, validate_instagram, validate_linked_in, validate_tik_tok,
+            validate_twitter,
+        },
+        time::deserialize_optional_datetime,
+    },
+};
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, Serialize, IntoParams, ToSchema)]
+#[into_params(parameter_in = Path)]
 pub struct EnterpriseUuid {
     pub uuid: String,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize, Validate)]
+#[derive(Clone, Debug, Deserialize, Serialize, Validate, ToSchema)]
 pub struct Enterprise {
     pub uuid: Option<String>,
     #[validate(length(min = 2, message = "Name does not match valid length"))]
@@ -20,23 +33,42 @@ pub struct Enterprise {
     #[validate(length(min = 2, message = "Enterprise name does not match valid length"))]
     pub company_name: Option<String>,
     pub line_of_business: Option<String>,
+    #[validate(custom(function = "validate_optional_phone"))]
     pub phone: Option<String>,
     #[validate(email)]
     pub email: String,
     pub deleted: bool,
-    #[validate(url)]
+    #[validate(custom(function = "validate_facebook"))]
     pub fb: Option<String>,
+    #[validate(custom(function = "validate_instagram"))]
     pub instagram: Option<String>,
+    #[validate(custom(function = "validate_linked_in"))]
     pub linked_in: Option<String>,
+    #[validate(custom(function = "validate_tik_tok"))]
     pub tik_tok: Option<String>,
+    #[validate(custom(function = "validate_twitter"))]
     pub twitter: Option<String>,
-    pub first_contact_date: Option<DateTime<Local>>,
-    pub sales_funnel: SalesFunnel,
+    #[serde(default, deserialize_with = "deserialize_optional_datetime")]
+    pub first_contact_date: Option<DateTime<Utc>>,
+    pub sales_funnel: String,
     pub notes: String,
-    pub services_offered: Vec<ServicesOffered>,
+    pub services_offered: Vec<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub assigned_to: Option<String>,
+    #[serde(default)]
+    pub score: i32,
+    #[serde(default)]
+    pub account_uuid: Option<String>,
+    #[serde(default)]
+    #[validate(custom(function = "validate_address"))]
+    pub address: Address,
+    #[serde(default)]
+    pub source: Option<LeadSource>,
     pub resolution: Option<String>,
-    pub date_created: Option<DateTime<Local>>,
-    pub date_modified: Option<DateTime<Local>>,
+    pub date_created: Option<DateTime<Utc>>,
+    pub date_modified: Option<DateTime<Utc>>,
     pub created_by: Option<String>,
     pub modified_by: Option<String>,
 }
@@ -46,6 +78,41 @@ impl Enterprise {
         let uuid = Some(id);
         Enterprise { uuid, ..enterprise }
     }
+
+    pub fn sample() -> Enterprise {
+        let date_created = Utc::now();
+        Enterprise {
+            uuid: Some("sample".to_string()),
+            name: "Juan".to_string(),
+            last_name: "Pérez".to_string(),
+            is_company: true,
+            company_name: Some("Empresa de Ejemplo".to_string()),
+            line_of_business: Some("Consultoría".to_string()),
+            phone: Some("5512345678".to_string()),
+            email: "juan.perez@example.com".to_string(),
+            deleted: false,
+            fb: Some("https://www.facebook.com/example".to_string()),
+            instagram: None,
+            linked_in: None,
+            tik_tok: None,
+            twitter: None,
+            first_contact_date: Some(date_created),
+            sales_funnel: "PROSPECT".to_string(),
+            notes: "".to_string(),
+            services_offered: vec![],
+            tags: vec!["vip".to_string()],
+            assigned_to: None,
+            score: 0,
+            account_uuid: None,
+            address: Address::default(),
+            source: None,
+            resolution: None,
+            date_created: Some(date_created),
+            date_modified: Some(date_created),
+            created_by: None,
+            modified_by: None,
+        }
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize)]
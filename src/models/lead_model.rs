@@ -0,0 +1,232 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{
+    models::{
+        address_model::Address,
+        sales_model::{LeadType, SalesFunnelTag, ServicesOfferedTag},
+    },
+    utils::i18n_utils::{get_default_locale, Translate},
+};
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+pub struct LeadSummary {
+    pub uuid: String,
+    pub lead_type: LeadType,
+    pub name: String,
+    pub company: Option<String>,
+    pub email: Option<String>,
+    pub phone: Option<String>,
+    pub sales_funnel: String,
+    pub services_offered: Vec<String>,
+    pub tags: Vec<String>,
+    pub assigned_to: Option<String>,
+    pub score: i32,
+    pub account_uuid: Option<String>,
+    #[serde(default)]
+    pub address: Address,
+    #[serde(default)]
+    pub source: Option<LeadSource>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, ToSchema)]
+#[allow(clippy::upper_case_acronyms)]
+pub enum LeadChannel {
+    REFERRAL,
+    WEBFORM,
+    COLDCALL,
+    EVENT,
+    IMPORT,
+}
+
+impl Translate for LeadChannel {
+    fn message_key(&self) -> String {
+        format!("lead_channel.{:?}", self)
+    }
+}
+
+impl fmt::Display for LeadChannel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.label(&get_default_locale()))
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct LeadChannelTag {
+    pub value: LeadChannel,
+    pub text: String,
+    pub selected: bool,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, ToSchema)]
+pub struct LeadSource {
+    pub channel: LeadChannel,
+    pub campaign_uuid: Option<String>,
+    pub utm_source: Option<String>,
+    pub utm_medium: Option<String>,
+    pub utm_campaign: Option<String>,
+    pub utm_term: Option<String>,
+    pub utm_content: Option<String>,
+    pub referrer: Option<String>,
+    pub landing_page: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct LeadPerson {
+    pub uuid: String,
+    pub name: String,
+    pub last_name: String,
+    pub email: Option<String>,
+    pub phone: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct LeadPhone {
+    pub uuid: String,
+    pub phone: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct LeadSocialProfiles {
+    pub uuid: String,
+    pub fb: Option<String>,
+    pub instagram: Option<String>,
+    pub linked_in: Option<String>,
+    pub tik_tok: Option<String>,
+    pub twitter: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, IntoParams, ToSchema)]
+#[into_params(parameter_in = Query)]
+pub struct LeadFilter {
+    pub tag: Option<String>,
+    pub sales_funnel: Option<String>,
+    pub service: Option<String>,
+    pub min_score: Option<i32>,
+    pub max_score: Option<i32>,
+    pub state: Option<String>,
+    pub municipality: Option<String>,
+    pub sort: Option<LeadSort>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum LeadSort {
+    ScoreDesc,
+    ScoreAsc,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+pub struct StageTransition {
+    pub uuid: Option<String>,
+    pub lead_type: LeadType,
+    pub lead_uuid: String,
+    pub from_stage: String,
+    pub to_stage: String,
+    pub changed_by: Option<String>,
+    pub date_created: Option<DateTime<Utc>>,
+}
+
+impl StageTransition {
+    pub fn new(uuid: String, transition: StageTransition) -> StageTransition {
+        StageTransition {
+            uuid: Some(uuid),
+            ..transition
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct LeadStageChange {
+    pub sales_funnel: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct LeadUuid {
+    pub uuid: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, IntoParams)]
+#[into_params(parameter_in = Path)]
+pub struct LeadTypePath {
+    pub lead_type: LeadType,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct KanbanCard {
+    pub lead: LeadSummary,
+    pub services_tag: Vec<ServicesOfferedTag>,
+    pub value: f64,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct KanbanColumn {
+    pub stage: SalesFunnelTag,
+    pub cards: Vec<KanbanCard>,
+    pub count: usize,
+    pub total: f64,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, ToSchema)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum BulkAction {
+    SetStage,
+    AddServices,
+    RemoveServices,
+    Reassign,
+    Delete,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+pub struct BulkLeadRequest {
+    #[serde(default)]
+    pub uuids: Vec<String>,
+    pub segment: Option<String>,
+    pub action: BulkAction,
+    pub sales_funnel: Option<String>,
+    pub services: Option<Vec<String>>,
+    pub assigned_to: Option<String>,
+    pub modified_by: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+pub struct BulkExportRequest {
+    #[serde(default)]
+    pub uuids: Vec<String>,
+    pub segment: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct BulkTarget {
+    pub uuid: String,
+    pub sales_funnel: String,
+    pub deleted: bool,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+pub struct BulkLeadResult {
+    pub uuid: String,
+    pub success: bool,
+    pub message: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+pub struct BulkLeadResponse {
+    pub action: BulkAction,
+    pub updated: usize,
+    pub failed: usize,
+    pub results: Vec<BulkLeadResult>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct LeadHandlebarsError {
+    pub error: String,
+}
+
+impl LeadHandlebarsError {
+    pub fn new(error: String) -> LeadHandlebarsError {
+        LeadHandlebarsError { error }
+    }
+}
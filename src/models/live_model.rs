@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::sales_model::LeadType;
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[allow(clippy::upper_case_acronyms)]
+pub enum LiveAction {
+    CREATE,
+    UPDATE,
+    DELETE,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct LiveEvent {
+    pub lead_type: LeadType,
+    pub action: LiveAction,
+    pub uuid: Option<String>,
+}
+
+// Only the uuid is needed to tell the browsers which row changed.
+#[derive(Debug, Deserialize)]
+pub struct LiveRecord {
+    pub uuid: Option<String>,
+}
@@ -0,0 +1,62 @@
+use std::fmt;
+
+use serde::{Deserialize, Deserializer, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq, Hash, ToSchema)]
+#[allow(clippy::upper_case_acronyms)]
+pub enum Locale {
+    #[default]
+    ES,
+    EN,
+}
+
+impl Locale {
+    pub fn code(&self) -> &'static str {
+        match self {
+            Locale::ES => "es",
+            Locale::EN => "en",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Locale> {
+        match code.trim().to_lowercase().as_str() {
+            "es" => Some(Locale::ES),
+            "en" => Some(Locale::EN),
+            _ => None,
+        }
+    }
+}
+
+// Each language is shown in its own name so it can be found from any locale.
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Locale::ES => write!(f, "Español"),
+            Locale::EN => write!(f, "English"),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct LocaleTag {
+    pub value: Locale,
+    pub text: String,
+    pub selected: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct TimezoneTag {
+    pub value: String,
+    pub text: String,
+    pub selected: bool,
+}
+
+// Forms send "" for "follow the browser", which must not fail the whole payload.
+pub fn deserialize_locale<'de, D>(deserializer: D) -> Result<Option<Locale>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let code: Option<String> = Option::deserialize(deserializer)?;
+    Ok(code.as_deref().and_then(Locale::from_code))
+}
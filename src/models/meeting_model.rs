@@ -0,0 +1,136 @@
+use std::fmt;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
+
+use crate::{
+    models::sales_model::LeadType,
+    utils::i18n_utils::{get_default_locale, Translate},
+};
+
+#[derive(Debug, Deserialize, Serialize, IntoParams, ToSchema)]
+#[into_params(parameter_in = Path)]
+pub struct MeetingUuid {
+    pub uuid: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, ToSchema)]
+#[allow(clippy::upper_case_acronyms)]
+pub enum MeetingKind {
+    CALL,
+    MEETING,
+    VIDEO,
+}
+
+impl Translate for MeetingKind {
+    fn message_key(&self) -> String {
+        format!("meeting_kind.{:?}", self)
+    }
+}
+
+impl fmt::Display for MeetingKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.label(&get_default_locale()))
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct MeetingKindTag {
+    pub value: MeetingKind,
+    pub text: String,
+    pub selected: bool,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, Validate, ToSchema)]
+pub struct Meeting {
+    pub uuid: Option<String>,
+    #[validate(length(
+        min = 2,
+        max = 120,
+        message = "Meeting title does not match valid length"
+    ))]
+    pub title: String,
+    pub kind: MeetingKind,
+    pub lead_type: LeadType,
+    pub lead_uuid: String,
+    pub organizer: Option<String>,
+    #[serde(default)]
+    pub attendees: Vec<String>,
+    pub start_date: DateTime<Utc>,
+    pub end_date: DateTime<Utc>,
+    #[serde(default)]
+    pub location: String,
+    #[serde(default)]
+    pub notes: String,
+    // Also send the .ics invitation to the lead's email on creation.
+    #[serde(default)]
+    pub invite_lead: bool,
+    pub deleted: bool,
+    pub date_created: Option<DateTime<Utc>>,
+    pub date_modified: Option<DateTime<Utc>>,
+    pub created_by: Option<String>,
+    pub modified_by: Option<String>,
+}
+
+impl Meeting {
+    pub fn new(uuid: String, meeting: Meeting) -> Meeting {
+        Meeting {
+            uuid: Some(uuid),
+            title: meeting.title.trim().to_string(),
+            ..meeting
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct MeetingQuery {
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[allow(clippy::upper_case_acronyms)]
+pub enum CalendarView {
+    MONTH,
+    WEEK,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CalendarQuery {
+    pub view: Option<CalendarView>,
+    pub date: Option<NaiveDate>,
+}
+
+// Stored under the user's uuid, one record per user.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct CalendarFeed {
+    pub uuid: Option<String>,
+    pub secret: String,
+    pub date_created: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize, Serialize, IntoParams)]
+#[into_params(parameter_in = Path)]
+pub struct CalendarFeedPath {
+    pub uuid: String,
+    pub secret: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct CalendarFeedUrl {
+    pub url: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct MeetingHandlebarsError {
+    pub error: String,
+}
+
+impl MeetingHandlebarsError {
+    pub fn new(error: String) -> MeetingHandlebarsError {
+        MeetingHandlebarsError { error }
+    }
+}
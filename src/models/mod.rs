@@ -1,6 +1,27 @@
+pub mod account_model;
+pub mod address_model;
+pub mod api_token_model;
+pub mod attachment_model;
+pub mod campaign_model;
 pub mod clinical_model;
+pub mod email_template_model;
 pub mod enterprise_model;
+pub mod lead_model;
+pub mod live_model;
+pub mod locale_model;
+pub mod meeting_model;
+pub mod migration_model;
+pub mod notification_model;
+pub mod pipeline_model;
+pub mod quote_model;
 pub mod sales_model;
 pub mod school_model;
+pub mod scoring_model;
+pub mod segment_model;
+pub mod service_model;
+pub mod session_model;
+pub mod tag_model;
+pub mod territory_model;
 pub mod user_query_params_model;
 pub mod users_model;
+pub mod web_lead_model;
@@ -0,0 +1,133 @@
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Deserializer, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{
+    models::sales_model::LeadType,
+    utils::i18n_utils::{get_default_locale, Translate},
+};
+
+#[derive(Debug, Deserialize, Serialize, IntoParams, ToSchema)]
+#[into_params(parameter_in = Path)]
+pub struct NotificationUuid {
+    pub uuid: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, ToSchema)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum NotificationKind {
+    LeadAssigned,
+    StageChanged,
+}
+
+impl NotificationKind {
+    pub fn from_code(code: &str) -> Option<NotificationKind> {
+        match code {
+            "LEAD_ASSIGNED" => Some(NotificationKind::LeadAssigned),
+            "STAGE_CHANGED" => Some(NotificationKind::StageChanged),
+            _ => None,
+        }
+    }
+}
+
+impl Translate for NotificationKind {
+    fn message_key(&self) -> String {
+        format!("notification_kind.{:?}", self)
+    }
+}
+
+impl fmt::Display for NotificationKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.label(&get_default_locale()))
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+pub struct Notification {
+    pub uuid: Option<String>,
+    pub user_uuid: String,
+    pub kind: NotificationKind,
+    pub message: String,
+    pub lead_type: Option<LeadType>,
+    pub lead_uuid: Option<String>,
+    pub read: bool,
+    pub date_read: Option<DateTime<Utc>>,
+    pub date_created: Option<DateTime<Utc>>,
+    pub created_by: Option<String>,
+}
+
+impl Notification {
+    pub fn new(uuid: String, notification: Notification) -> Notification {
+        Notification {
+            uuid: Some(uuid),
+            ..notification
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, ToSchema)]
+pub struct NotificationPreference {
+    pub kind: NotificationKind,
+    pub in_app: bool,
+    pub email: bool,
+}
+
+#[derive(Deserialize)]
+struct StoredPreference {
+    kind: String,
+    in_app: bool,
+    email: bool,
+}
+
+// Saved preferences can name a kind that no longer exists, which must not
+// throw away the rest of them.
+fn deserialize_preferences<'de, D>(deserializer: D) -> Result<Vec<NotificationPreference>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let stored: Vec<StoredPreference> = Vec::deserialize(deserializer)?;
+    Ok(stored
+        .into_iter()
+        .filter_map(|preference| {
+            NotificationKind::from_code(&preference.kind).map(|kind| NotificationPreference {
+                kind,
+                in_app: preference.in_app,
+                email: preference.email,
+            })
+        })
+        .collect())
+}
+
+// Stored under the user's uuid, one record per user.
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+pub struct NotificationPreferences {
+    pub uuid: Option<String>,
+    #[serde(deserialize_with = "deserialize_preferences")]
+    pub preferences: Vec<NotificationPreference>,
+    pub date_modified: Option<DateTime<Utc>>,
+    pub modified_by: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct NotificationQuery {
+    pub unread_only: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct UnreadNotifications {
+    pub unread: usize,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct NotificationHandlebarsError {
+    pub error: String,
+}
+
+impl NotificationHandlebarsError {
+    pub fn new(error: String) -> NotificationHandlebarsError {
+        NotificationHandlebarsError { error }
+    }
+}
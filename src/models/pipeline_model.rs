@@ -0,0 +1,61 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
+
+use crate::models::sales_model::LeadType;
+
+#[derive(Debug, Deserialize, Serialize, IntoParams, ToSchema)]
+#[into_params(parameter_in = Path)]
+pub struct PipelineStageUuid {
+    pub uuid: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct PipelineLeadType {
+    pub lead_type: Option<LeadType>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, Validate, ToSchema)]
+pub struct PipelineStage {
+    pub uuid: Option<String>,
+    pub lead_type: LeadType,
+    #[validate(length(min = 2, message = "Stage code does not match valid length"))]
+    pub code: String,
+    #[validate(length(min = 2, message = "Stage name does not match valid length"))]
+    pub name: String,
+    #[validate(range(max = 100, message = "Probability must be between 0 and 100"))]
+    pub probability: u8,
+    pub position: u32,
+    pub icon: Option<String>,
+    pub css: Option<String>,
+    pub is_won: bool,
+    pub is_lost: bool,
+    pub deleted: bool,
+    pub date_created: Option<DateTime<Utc>>,
+    pub date_modified: Option<DateTime<Utc>>,
+    pub created_by: Option<String>,
+    pub modified_by: Option<String>,
+}
+
+impl PipelineStage {
+    pub fn new(uuid: String, stage: PipelineStage) -> PipelineStage {
+        PipelineStage {
+            uuid: Some(uuid),
+            code: stage.code.trim().to_uppercase(),
+            ..stage
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct PipelineHandlebarsError {
+    pub error: String,
+}
+
+impl PipelineHandlebarsError {
+    pub fn new(error: String) -> PipelineHandlebarsError {
+        PipelineHandlebarsError { error }
+    }
+}
@@ -0,0 +1,80 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
+
+use crate::models::{lead_model::LeadSummary, sales_model::LeadType};
+
+#[derive(Debug, Deserialize, Serialize, IntoParams, ToSchema)]
+#[into_params(parameter_in = Path)]
+pub struct QuoteUuid {
+    pub uuid: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, Validate, ToSchema)]
+pub struct QuoteLineItem {
+    pub service: String,
+    pub description: Option<String>,
+    #[validate(range(min = 1, message = "Quantity must be at least 1"))]
+    pub quantity: u32,
+    #[validate(range(min = 0.0, message = "Unit price can't be negative"))]
+    pub unit_price: Option<f64>,
+    #[validate(range(min = 0.0, max = 100.0, message = "Discount must be between 0 and 100"))]
+    pub discount_percent: Option<f64>,
+    pub line_total: Option<f64>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, Validate, ToSchema)]
+pub struct Quote {
+    pub uuid: Option<String>,
+    pub quote_number: Option<String>,
+    pub lead_type: LeadType,
+    #[validate(length(min = 1, message = "A quote must belong to a lead"))]
+    pub lead_uuid: String,
+    pub lead: Option<LeadSummary>,
+    #[validate(length(min = 1, message = "A quote needs at least one line item"))]
+    pub line_items: Vec<QuoteLineItem>,
+    #[validate(range(min = 0.0, max = 100.0, message = "Discount must be between 0 and 100"))]
+    pub discount_percent: Option<f64>,
+    #[validate(range(min = 0.0, max = 100.0, message = "Tax must be between 0 and 100"))]
+    pub tax_percent: Option<f64>,
+    pub currency: Option<String>,
+    pub subtotal: Option<f64>,
+    pub discount_total: Option<f64>,
+    pub tax_total: Option<f64>,
+    pub total: Option<f64>,
+    pub notes: Option<String>,
+    pub valid_until: Option<DateTime<Utc>>,
+    pub advance_funnel: Option<bool>,
+    pub deleted: bool,
+    pub date_created: Option<DateTime<Utc>>,
+    pub date_modified: Option<DateTime<Utc>>,
+    pub created_by: Option<String>,
+    pub modified_by: Option<String>,
+}
+
+impl Quote {
+    pub fn new(uuid: String, quote_number: String, quote: Quote) -> Quote {
+        Quote {
+            uuid: Some(uuid),
+            quote_number: Some(quote_number),
+            ..quote
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct QuoteCounter {
+    pub value: u32,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct QuoteHandlebarsError {
+    pub error: String,
+}
+
+impl QuoteHandlebarsError {
+    pub fn new(error: String) -> QuoteHandlebarsError {
+        QuoteHandlebarsError { error }
+    }
+}
@@ -1,67 +1,26 @@
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use utoipa::ToSchema;
 
-#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
-pub enum SalesFunnel {
-    LOST,
-    PROSPECT,
-    RESEARCH,
-    NEED,
-    QUOTE,
-    NEGOTIATION,
-    WIN,
-}
-
-impl fmt::Display for SalesFunnel {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            SalesFunnel::LOST => write!(f, "Perdido 0%"),
-            SalesFunnel::PROSPECT => write!(f, "Prospecto 0%"),
-            SalesFunnel::RESEARCH => write!(f, "Investigación 10%"),
-            SalesFunnel::NEED => write!(f, "Necesidad 20%"),
-            SalesFunnel::QUOTE => write!(f, "Cotización 50%"),
-            SalesFunnel::NEGOTIATION => write!(f, "Negociación 80%"),
-            SalesFunnel::WIN => write!(f, "Ganado 100%"),
-        }
-    }
-}
+use crate::utils::i18n_utils::{get_default_locale, Translate};
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct SalesFunnelTag {
-    pub value: SalesFunnel,
+    pub value: String,
     pub text: String,
     pub selected: bool,
     pub icon: Option<String>,
     pub css: Option<String>,
-}
-
-#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
-pub enum ServicesOffered {
-    BRANDING,
-    WEBSERVICES,
-    DIGITALSTRATEGY,
-    ATTRACTIONOFNEWCLIENTS,
-    SALESMANAGEMENT,
+    pub probability: u8,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct ServicesOfferedTag {
-    pub value: ServicesOffered,
+    pub value: String,
     pub text: String,
     pub selected: bool,
     pub icon: Option<String>,
-}
-
-impl fmt::Display for ServicesOffered {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            ServicesOffered::BRANDING => write!(f, "BRANDING"),
-            ServicesOffered::WEBSERVICES => write!(f, "WEB SERVICES"),
-            ServicesOffered::DIGITALSTRATEGY => write!(f, "DIGITAL STRATEGY"),
-            ServicesOffered::ATTRACTIONOFNEWCLIENTS => write!(f, "ATTRACTION OF NEW CLIENTS"),
-            ServicesOffered::SALESMANAGEMENT => write!(f, "SALES MANAGEMENT"),
-        }
-    }
+    pub price: f64,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -69,7 +28,7 @@ pub struct IsDeleted {
     pub deleted: bool,
 }
 
-#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, ToSchema)]
 pub enum SchoolLevel {
     NURSERY,
     KINDER,
@@ -87,20 +46,44 @@ pub struct SchoolLevelTag {
     pub selected: bool,
 }
 
+impl Translate for SchoolLevel {
+    fn message_key(&self) -> String {
+        format!("school_level.{:?}", self)
+    }
+}
+
 impl fmt::Display for SchoolLevel {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            SchoolLevel::NURSERY => write!(f, "Guardería"),
-            SchoolLevel::KINDER => write!(f, "Kinder"),
-            SchoolLevel::ELEMENTARY => write!(f, "Primaria"),
-            SchoolLevel::MIDDLESCHOOL => write!(f, "Secundaria"),
-            SchoolLevel::HIGHSCHOOL => write!(f, "Preparatoria"),
-            SchoolLevel::TECHNICALSCHOOL => write!(f, "Escuela Técnica"),
-            SchoolLevel::UNIVERSITY => write!(f, "Universidad"),
-        }
+        write!(f, "{}", self.label(&get_default_locale()))
     }
 }
 
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, ToSchema)]
+pub enum LeadType {
+    ENTERPRISE,
+    SCHOOL,
+    CLINICAL,
+}
+
+impl Translate for LeadType {
+    fn message_key(&self) -> String {
+        format!("lead_type.{:?}", self)
+    }
+}
+
+impl fmt::Display for LeadType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.label(&get_default_locale()))
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct LeadTypeTag {
+    pub value: LeadType,
+    pub text: String,
+    pub selected: bool,
+}
+
 #[derive(Clone, Serialize, Deserialize)]
 pub struct GeneralTags<T> {
     pub section: T,
@@ -0,0 +1,83 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
+
+use crate::models::sales_model::LeadType;
+
+#[derive(Debug, Deserialize, Serialize, IntoParams, ToSchema)]
+#[into_params(parameter_in = Path)]
+pub struct ScoringRuleUuid {
+    pub uuid: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, ToSchema)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ScoringCriterion {
+    HasEmail,
+    HasPhone,
+    HasWebsite,
+    SocialProfile,
+    StageProbability,
+    DaysSinceActivity,
+    ServiceCount,
+    HasTag,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, Validate, ToSchema)]
+pub struct ScoringRule {
+    pub uuid: Option<String>,
+    #[validate(length(min = 2, message = "Rule name does not match valid length"))]
+    pub name: String,
+    pub criterion: ScoringCriterion,
+    pub value: Option<String>,
+    #[validate(range(min = -100, max = 100, message = "Points must be between -100 and 100"))]
+    pub points: i32,
+    #[validate(range(min = 0, message = "Limit must be a positive number"))]
+    pub limit: Option<i32>,
+    pub lead_type: Option<LeadType>,
+    pub active: bool,
+    pub deleted: bool,
+    pub date_created: Option<DateTime<Utc>>,
+    pub date_modified: Option<DateTime<Utc>>,
+    pub created_by: Option<String>,
+    pub modified_by: Option<String>,
+}
+
+impl ScoringRule {
+    pub fn new(uuid: String, rule: ScoringRule) -> ScoringRule {
+        ScoringRule {
+            uuid: Some(uuid),
+            name: rule.name.trim().to_string(),
+            ..rule
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct ScoringFacts {
+    pub has_email: bool,
+    pub has_phone: bool,
+    pub has_website: bool,
+    pub social_profiles: i32,
+    pub sales_funnel: String,
+    pub services: i32,
+    pub tags: Vec<String>,
+    pub last_activity: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct ScoringRecalculation {
+    pub updated: usize,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ScoringHandlebarsError {
+    pub error: String,
+}
+
+impl ScoringHandlebarsError {
+    pub fn new(error: String) -> ScoringHandlebarsError {
+        ScoringHandlebarsError { error }
+    }
+}
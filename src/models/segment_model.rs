@@ -0,0 +1,46 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
+
+use crate::models::{lead_model::LeadFilter, sales_model::LeadType};
+
+#[derive(Debug, Deserialize, Serialize, IntoParams, ToSchema)]
+#[into_params(parameter_in = Path)]
+pub struct SegmentUuid {
+    pub uuid: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, Validate, ToSchema)]
+pub struct Segment {
+    pub uuid: Option<String>,
+    #[validate(length(min = 2, message = "Segment name does not match valid length"))]
+    pub name: String,
+    pub lead_type: LeadType,
+    pub filter: LeadFilter,
+    pub deleted: bool,
+    pub date_created: Option<DateTime<Utc>>,
+    pub date_modified: Option<DateTime<Utc>>,
+    pub created_by: Option<String>,
+    pub modified_by: Option<String>,
+}
+
+impl Segment {
+    pub fn new(uuid: String, segment: Segment) -> Segment {
+        Segment {
+            uuid: Some(uuid),
+            ..segment
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct SegmentHandlebarsError {
+    pub error: String,
+}
+
+impl SegmentHandlebarsError {
+    pub fn new(error: String) -> SegmentHandlebarsError {
+        SegmentHandlebarsError { error }
+    }
+}
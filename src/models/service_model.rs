@@ -0,0 +1,54 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
+
+#[derive(Debug, Deserialize, Serialize, IntoParams, ToSchema)]
+#[into_params(parameter_in = Path)]
+pub struct ServiceUuid {
+    pub uuid: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, Validate, ToSchema)]
+pub struct Service {
+    pub uuid: Option<String>,
+    #[validate(length(min = 2, message = "Service name does not match valid length"))]
+    pub name: String,
+    pub description: Option<String>,
+    pub icon: Option<String>,
+    #[validate(range(min = 0.0, message = "Price can't be negative"))]
+    pub price: f64,
+    pub active: bool,
+    pub legacy_code: Option<String>,
+    pub deleted: bool,
+    pub date_created: Option<DateTime<Utc>>,
+    pub date_modified: Option<DateTime<Utc>>,
+    pub created_by: Option<String>,
+    pub modified_by: Option<String>,
+}
+
+impl Service {
+    pub fn new(uuid: String, service: Service) -> Service {
+        Service {
+            uuid: Some(uuid),
+            ..service
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct LeadServices {
+    pub uuid: String,
+    pub services_offered: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ServiceHandlebarsError {
+    pub error: String,
+}
+
+impl ServiceHandlebarsError {
+    pub fn new(error: String) -> ServiceHandlebarsError {
+        ServiceHandlebarsError { error }
+    }
+}
@@ -0,0 +1,60 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::models::users_model::{Roles, User};
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Session {
+    pub uuid: Option<String>,
+    pub user_uuid: String,
+    pub expires_at: DateTime<Utc>,
+    pub date_created: Option<DateTime<Utc>>,
+}
+
+impl Session {
+    pub fn new(uuid: String, session: Session) -> Session {
+        Session {
+            uuid: Some(uuid),
+            ..session
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct LoginRequest {
+    pub email: String,
+    pub password: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct SessionUser {
+    pub uuid: String,
+    pub name: String,
+    pub last_name: String,
+    pub email: String,
+    pub role: Roles,
+}
+
+impl SessionUser {
+    pub fn new(user: User) -> SessionUser {
+        SessionUser {
+            uuid: user.uuid,
+            name: user.name,
+            last_name: user.last_name,
+            email: user.email,
+            role: user.role,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct SessionHandlebarsError {
+    pub error: String,
+}
+
+impl SessionHandlebarsError {
+    pub fn new(error: String) -> SessionHandlebarsError {
+        SessionHandlebarsError { error }
+    }
+}
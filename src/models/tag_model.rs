@@ -0,0 +1,47 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Debug, Deserialize, Serialize, IntoParams, ToSchema)]
+#[into_params(parameter_in = Path)]
+pub struct TagUuid {
+    pub uuid: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+pub struct Tag {
+    pub uuid: Option<String>,
+    pub name: String,
+    pub deleted: bool,
+    pub date_created: Option<DateTime<Utc>>,
+    pub date_modified: Option<DateTime<Utc>>,
+    pub created_by: Option<String>,
+    pub modified_by: Option<String>,
+}
+
+impl Tag {
+    pub fn new(uuid: String, tag: Tag) -> Tag {
+        Tag {
+            uuid: Some(uuid),
+            ..tag
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct TagSearch {
+    pub q: Option<String>,
+    pub tag_query: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct TagHandlebarsError {
+    pub error: String,
+}
+
+impl TagHandlebarsError {
+    pub fn new(error: String) -> TagHandlebarsError {
+        TagHandlebarsError { error }
+    }
+}
@@ -0,0 +1,91 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
+
+use crate::{models::sales_model::LeadType, utils::territory_utils::validate_postal_code_ranges};
+
+#[derive(Debug, Deserialize, Serialize, IntoParams, ToSchema)]
+#[into_params(parameter_in = Path)]
+pub struct TerritoryUuid {
+    pub uuid: String,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize, ToSchema)]
+pub struct PostalCodeRange {
+    pub from: String,
+    pub to: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, Validate, ToSchema)]
+pub struct Territory {
+    pub uuid: Option<String>,
+    #[validate(length(min = 2, message = "Territory name does not match valid length"))]
+    pub name: String,
+    #[serde(default)]
+    pub states: Vec<String>,
+    #[serde(default)]
+    pub municipalities: Vec<String>,
+    #[serde(default)]
+    #[validate(custom(function = "validate_postal_code_ranges"))]
+    pub postal_code_ranges: Vec<PostalCodeRange>,
+    pub assigned_to: Option<String>,
+    #[serde(default)]
+    pub managers: Vec<String>,
+    pub deleted: bool,
+    pub date_created: Option<DateTime<Utc>>,
+    pub date_modified: Option<DateTime<Utc>>,
+    pub created_by: Option<String>,
+    pub modified_by: Option<String>,
+}
+
+impl Territory {
+    pub fn new(uuid: String, territory: Territory) -> Territory {
+        Territory {
+            uuid: Some(uuid),
+            name: territory.name.trim().to_string(),
+            ..territory
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct TerritoryScope {
+    pub territories: Vec<Territory>,
+    pub managed_uuids: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct TerritoryReportQuery {
+    pub lead_type: Option<LeadType>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+pub struct TerritoryStageSummary {
+    pub code: String,
+    pub name: String,
+    pub count: usize,
+    pub total: f64,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+pub struct TerritoryPipelineReport {
+    pub territory_uuid: String,
+    pub territory_name: String,
+    pub lead_type: LeadType,
+    pub stages: Vec<TerritoryStageSummary>,
+    pub count: usize,
+    pub total: f64,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct TerritoryHandlebarsError {
+    pub error: String,
+}
+
+impl TerritoryHandlebarsError {
+    pub fn new(error: String) -> TerritoryHandlebarsError {
+        TerritoryHandlebarsError { error }
+    }
+}
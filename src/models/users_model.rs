@@ -1,28 +1,37 @@
 use chrono::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use utoipa::{IntoParams, ToSchema};
 use validator::Validate;
 
-use crate::constants::validation::*;
+use crate::{
+    constants::validation::*,
+    models::locale_model::{deserialize_locale, Locale},
+    utils::{
+        i18n_utils::Translate,
+        time::{deserialize_timezone, validate_timezone},
+    },
+};
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, Serialize, IntoParams, ToSchema)]
+#[into_params(parameter_in = Path)]
 pub struct UserUuid {
     pub uuid: String,
 }
 
-#[derive(Debug, Deserialize, Serialize, Validate)]
+#[derive(Debug, Deserialize, Serialize, Validate, ToSchema)]
 pub struct User {
     pub uuid: String,
     #[validate(length(
         min = MINIMUM_NAMES_LENGTH,
         max = MAXIMUM_NAMES_LENGTH,
-        message = "<span class=\"icon is-small is-left\"><i class=\"fas fa-ban\"></i>Name does not match valid length (2-35 characters)</span>"
+        message = "validation.name_length"
     ))]
     pub name: String,
     #[validate(length(
         min = MINIMUM_NAMES_LENGTH,
         max = MAXIMUM_NAMES_LENGTH,
-        message = "<span class=\"icon is-small is-left\"><i class=\"fas fa-ban\"></i>Last Name does not match valid length (2-35 characters)</span>"
+        message = "validation.last_name_length"
     ))]
     pub last_name: String,
     #[validate(email)]
@@ -32,27 +41,32 @@ pub struct User {
     #[validate(length(
         min = MINIMUM_PASSWORD_LENGTH,
         max = MAXIMUM_PASSWORD_LENGTH,
-        message = "<span class=\"icon is-small is-left\"><i class=\"fas fa-ban\"></i>Minimum size of password is 10 characters. Max is 255</span>"
+        message = "validation.password_length"
     ))]
     pub password: Option<String>,
-    pub date_created: Option<DateTime<Local>>,
-    pub date_modified: Option<DateTime<Local>>,
+    pub date_created: Option<DateTime<Utc>>,
+    pub date_modified: Option<DateTime<Utc>>,
     pub notes: Option<String>,
     pub role_string: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_locale")]
+    pub locale: Option<Locale>,
+    #[serde(default, deserialize_with = "deserialize_timezone")]
+    #[validate(custom(function = "validate_timezone"))]
+    pub timezone: Option<String>,
 }
 
-#[derive(Debug, Deserialize, Serialize, Validate)]
+#[derive(Debug, Deserialize, Serialize, Validate, ToSchema)]
 pub struct UserFromJson {
     #[validate(length(
       min = MINIMUM_NAMES_LENGTH,
       max = MAXIMUM_NAMES_LENGTH,
-      message = "<span class=\"icon is-small is-left\"><i class=\"fas fa-ban\"></i>Name does not match valid length (2-35 characters)</span>"
+      message = "validation.name_length"
   ))]
     pub name: String,
     #[validate(length(
         min = MINIMUM_NAMES_LENGTH,
         max = MAXIMUM_NAMES_LENGTH,
-        message = "<span class=\"icon is-small is-left\"><i class=\"fas fa-ban\"></i>Last Name does not match valid length (2-35 characters)</span>"
+        message = "validation.last_name_length"
     ))]
     pub last_name: String,
     #[validate(email)]
@@ -62,14 +76,19 @@ pub struct UserFromJson {
     #[validate(length(
         min = MINIMUM_PASSWORD_LENGTH,
         max = MAXIMUM_PASSWORD_LENGTH,
-        message = "<span class=\"icon is-small is-left\"><i class=\"fas fa-ban\"></i>Minimum size of password is 10 characters. Max is 255</span>"
+        message = "validation.password_length"
     ))]
     pub password: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_locale")]
+    pub locale: Option<Locale>,
+    #[serde(default, deserialize_with = "deserialize_timezone")]
+    #[validate(custom(function = "validate_timezone"))]
+    pub timezone: Option<String>,
 }
 
 impl User {
     pub fn new(uuid: String, user: UserFromJson) -> User {
-        let date_created = Local::now();
+        let date_created = Utc::now();
         User {
             uuid,
             deleted: false,
@@ -82,14 +101,18 @@ impl User {
             notes: user.notes.clone(),
             password: user.password.clone(),
             role_string: Some(user.role.to_string()),
+            locale: user.locale.clone(),
+            timezone: user.timezone.clone(),
         }
     }
 }
 
-#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, ToSchema)]
+#[allow(clippy::upper_case_acronyms)]
 pub enum Roles {
     ADMIN,
     EDITOR,
+    MANAGER,
 }
 
 impl fmt::Display for Roles {
@@ -97,10 +120,17 @@ impl fmt::Display for Roles {
         match self {
             Roles::EDITOR => write!(f, "EDITOR"),
             Roles::ADMIN => write!(f, "ADMIN"),
+            Roles::MANAGER => write!(f, "MANAGER"),
         }
     }
 }
 
+impl Translate for Roles {
+    fn message_key(&self) -> String {
+        format!("role.{}", self)
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct RolesTag {
     pub value: Roles,
@@ -119,8 +149,14 @@ impl UserHandlebarsError {
     }
 }
 
+// Resolved on the server from the session or the api token, see role_utils.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ActingUser {
+    pub user_uuid: Option<String>,
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct UserPartials {
-    pub date_created: Option<DateTime<Local>>,
+    pub date_created: Option<DateTime<Utc>>,
     pub password: String,
 }
@@ -0,0 +1,83 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+
+use crate::{
+    models::{
+        lead_model::{LeadChannel, LeadSource},
+        sales_model::LeadType,
+    },
+    utils::phone_utils::validate_optional_phone,
+};
+
+#[derive(Debug, Deserialize, Serialize, Validate, ToSchema)]
+pub struct WebLeadForm {
+    pub lead_type: Option<LeadType>,
+    #[validate(length(min = 2, max = 80, message = "Name does not match valid length"))]
+    pub name: String,
+    #[validate(length(min = 2, max = 80, message = "Lastname does not match valid length"))]
+    pub last_name: String,
+    #[validate(email)]
+    pub email: String,
+    #[validate(custom(function = "validate_optional_phone"))]
+    pub phone: Option<String>,
+    #[validate(length(max = 120, message = "Organization name is too long"))]
+    pub organization: Option<String>,
+    #[validate(length(max = 80, message = "Specialty is too long"))]
+    pub specialty: Option<String>,
+    #[validate(length(max = 10, message = "Postal code is too long"))]
+    pub postal_code: Option<String>,
+    #[validate(length(max = 2000, message = "Message is too long"))]
+    pub message: Option<String>,
+    #[validate(length(max = 200, message = "UTM value is too long"))]
+    pub utm_source: Option<String>,
+    #[validate(length(max = 200, message = "UTM value is too long"))]
+    pub utm_medium: Option<String>,
+    #[validate(length(max = 200, message = "UTM value is too long"))]
+    pub utm_campaign: Option<String>,
+    #[validate(length(max = 200, message = "UTM value is too long"))]
+    pub utm_term: Option<String>,
+    #[validate(length(max = 200, message = "UTM value is too long"))]
+    pub utm_content: Option<String>,
+    #[validate(length(max = 2000, message = "Referrer is too long"))]
+    pub referrer: Option<String>,
+    #[validate(length(max = 2000, message = "Landing page is too long"))]
+    pub landing_page: Option<String>,
+    pub captcha_token: Option<String>,
+    // Honeypot, hidden from people with CSS so only bots fill it in.
+    pub website: Option<String>,
+}
+
+impl WebLeadForm {
+    pub fn source(&self) -> LeadSource {
+        LeadSource {
+            channel: LeadChannel::WEBFORM,
+            campaign_uuid: None,
+            utm_source: self.utm_source.clone(),
+            utm_medium: self.utm_medium.clone(),
+            utm_campaign: self.utm_campaign.clone(),
+            utm_term: self.utm_term.clone(),
+            utm_content: self.utm_content.clone(),
+            referrer: self.referrer.clone(),
+            landing_page: self.landing_page.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct WebLeadReceived {
+    pub received: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CaptchaVerification {
+    pub success: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct WebLeadWebhook {
+    pub event: String,
+    pub lead_type: LeadType,
+    pub lead_uuid: String,
+    pub lead: serde_json::Value,
+}
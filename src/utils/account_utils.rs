@@ -0,0 +1,241 @@
+use actix_web::web::Data;
+use chrono::Utc;
+use log::{error, info};
+
+use crate::{
+    db::{
+        account_db::AccountDB, clinical_db::ClinicalDB, config::Database, contact_db::ContactDB,
+        enterprise_db::EnterpriseDB, school_db::SchoolDB, stage_transition_db::StageTransitionDB,
+    },
+    error::account_error::AccountError,
+    models::{
+        account_model::{Account, AccountDetail, Contact},
+        clinical_model::Clinical,
+        enterprise_model::Enterprise,
+        sales_model::LeadType,
+        school_model::School,
+    },
+    utils::{
+        contact_utils::{
+            clinical_person, contact_from_person, enterprise_person, find_lead_contacts,
+            school_person,
+        },
+        general_utils::get_uuid,
+        lead_utils::get_lead_table,
+        pipeline_utils::{find_stage, get_pipeline},
+    },
+};
+
+struct LeadConversionSource {
+    account: Account,
+    contact: Contact,
+    sales_funnel: String,
+}
+
+fn non_empty(value: Option<String>) -> Option<String> {
+    value
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+fn enterprise_source(enterprise: Enterprise) -> LeadConversionSource {
+    let lead_uuid = enterprise.uuid.clone().unwrap_or_default();
+    let person = format!("{} {}", enterprise.name, enterprise.last_name);
+    let name = match enterprise.is_company {
+        true => non_empty(enterprise.company_name.clone()).unwrap_or(person),
+        false => person,
+    };
+
+    LeadConversionSource {
+        contact: contact_from_person(
+            &LeadType::ENTERPRISE,
+            enterprise_person(&enterprise),
+            None,
+            None,
+        ),
+        account: Account {
+            uuid: None,
+            name,
+            lead_type: LeadType::ENTERPRISE,
+            lead_uuid,
+            email: non_empty(Some(enterprise.email)),
+            phone: non_empty(enterprise.phone),
+            website: None,
+            services_offered: enterprise.services_offered,
+            tags: enterprise.tags,
+            assigned_to: enterprise.assigned_to,
+            notes: enterprise.notes,
+            deleted: false,
+            date_created: None,
+            date_modified: None,
+            created_by: None,
+            modified_by: None,
+        },
+        sales_funnel: enterprise.sales_funnel,
+    }
+}
+
+fn school_source(school: School) -> LeadConversionSource {
+    let lead_uuid = school.uuid.clone().unwrap_or_default();
+
+    LeadConversionSource {
+        contact: contact_from_person(&LeadType::SCHOOL, school_person(&school), None, None),
+        account: Account {
+            uuid: None,
+            name: school.school_name,
+            lead_type: LeadType::SCHOOL,
+            lead_uuid,
+            email: non_empty(school.school_email).or(non_empty(Some(school.email))),
+            phone: non_empty(Some(school.phone)),
+            website: non_empty(school.school_web),
+            services_offered: school.services_offered,
+            tags: school.tags,
+            assigned_to: school.assigned_to,
+            notes: school.notes,
+            deleted: false,
+            date_created: None,
+            date_modified: None,
+            created_by: None,
+            modified_by: None,
+        },
+        sales_funnel: school.sales_funnel,
+    }
+}
+
+fn clinical_source(clinical: Clinical) -> LeadConversionSource {
+    let lead_uuid = clinical.uuid.clone().unwrap_or_default();
+    let name = non_empty(clinical.clinic_name.clone())
+        .unwrap_or(format!("{} {}", clinical.name, clinical.last_name));
+
+    LeadConversionSource {
+        contact: contact_from_person(&LeadType::CLINICAL, clinical_person(&clinical), None, None),
+        account: Account {
+            uuid: None,
+            name,
+            lead_type: LeadType::CLINICAL,
+            lead_uuid,
+            email: non_empty(clinical.clinic_email).or(non_empty(clinical.email)),
+            phone: non_empty(Some(clinical.phone)),
+            website: non_empty(clinical.clinic_web),
+            services_offered: clinical.services_offered,
+            tags: clinical.tags,
+            assigned_to: clinical.assigned_to,
+            notes: clinical.notes,
+            deleted: false,
+            date_created: None,
+            date_modified: None,
+            created_by: None,
+            modified_by: None,
+        },
+        sales_funnel: clinical.sales_funnel,
+    }
+}
+
+async fn find_conversion_source(
+    db: &Data<Database>,
+    lead_type: &LeadType,
+    uuid: String,
+) -> Option<LeadConversionSource> {
+    match lead_type {
+        LeadType::ENTERPRISE => <Database as EnterpriseDB>::find_one(db, uuid)
+            .await
+            .map(enterprise_source),
+        LeadType::SCHOOL => <Database as SchoolDB>::find_one(db, uuid)
+            .await
+            .map(school_source),
+        LeadType::CLINICAL => <Database as ClinicalDB>::find_one(db, uuid)
+            .await
+            .map(clinical_source),
+    }
+}
+
+pub async fn convert_lead_to_account(
+    db: &Data<Database>,
+    lead_type: &LeadType,
+    uuid: String,
+    converted_by: Option<String>,
+) -> Result<Account, AccountError> {
+    let source = match find_conversion_source(db, lead_type, uuid.clone()).await {
+        Some(source) => source,
+        None => return Err(AccountError::LeadNotFound),
+    };
+
+    if <Database as AccountDB>::find_by_lead(db, uuid.clone())
+        .await
+        .is_some()
+    {
+        return Err(AccountError::LeadAlreadyConverted);
+    }
+
+    let stages = get_pipeline(db, lead_type).await;
+    match find_stage(&stages, &source.sales_funnel) {
+        Some(stage) if stage.is_won => {}
+        _ => return Err(AccountError::LeadNotWon),
+    }
+
+    let date_created = Utc::now();
+    let account_uuid = get_uuid();
+    let account = Account::new(
+        account_uuid.clone(),
+        Account {
+            date_created: Some(date_created),
+            date_modified: Some(date_created),
+            created_by: converted_by.clone(),
+            modified_by: converted_by.clone(),
+            ..source.account
+        },
+    );
+    let lead_contacts = match find_lead_contacts(db, uuid.clone()).await {
+        contacts if contacts.is_empty() => vec![Contact::new(
+            get_uuid(),
+            Contact {
+                date_created: Some(date_created),
+                created_by: converted_by.clone(),
+                ..source.contact
+            },
+        )],
+        contacts => contacts,
+    };
+    let contacts = lead_contacts
+        .into_iter()
+        .map(|contact| Contact {
+            account_uuid: Some(account_uuid.clone()),
+            date_modified: Some(date_created),
+            modified_by: converted_by.clone(),
+            ..contact
+        })
+        .collect();
+
+    match <Database as AccountDB>::convert_lead(db, get_lead_table(lead_type), account, contacts)
+        .await
+    {
+        Some(account) => {
+            info!("Converted {} lead {} into an account", lead_type, uuid);
+            Ok(account)
+        }
+        None => {
+            error!("Unable to convert {} lead {}", lead_type, uuid);
+            Err(AccountError::AccountCreationFailure)
+        }
+    }
+}
+
+pub async fn find_account_detail(db: &Data<Database>, uuid: String) -> Option<AccountDetail> {
+    let account = <Database as AccountDB>::find_one(db, uuid.clone()).await?;
+    let contacts = <Database as ContactDB>::find_by_account(db, uuid)
+        .await
+        .unwrap_or_default();
+    let history = <Database as StageTransitionDB>::find_by_lead(
+        db,
+        account.lead_type.clone(),
+        account.lead_uuid.clone(),
+    )
+    .await
+    .unwrap_or_default();
+
+    Some(AccountDetail {
+        account,
+        contacts,
+        history,
+    })
+}
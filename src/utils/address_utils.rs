@@ -0,0 +1,140 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+use validator::ValidationError;
+
+use crate::models::address_model::{Address, AddressStateTag};
+
+pub const DEFAULT_ADDRESS_COUNTRY: &str = "MX";
+
+pub const MEXICAN_STATES: [&str; 32] = [
+    "Aguascalientes",
+    "Baja California",
+    "Baja California Sur",
+    "Campeche",
+    "Chiapas",
+    "Chihuahua",
+    "Ciudad de México",
+    "Coahuila",
+    "Colima",
+    "Durango",
+    "Estado de México",
+    "Guanajuato",
+    "Guerrero",
+    "Hidalgo",
+    "Jalisco",
+    "Michoacán",
+    "Morelos",
+    "Nayarit",
+    "Nuevo León",
+    "Oaxaca",
+    "Puebla",
+    "Querétaro",
+    "Quintana Roo",
+    "San Luis Potosí",
+    "Sinaloa",
+    "Sonora",
+    "Tabasco",
+    "Tamaulipas",
+    "Tlaxcala",
+    "Veracruz",
+    "Yucatán",
+    "Zacatecas",
+];
+
+lazy_static! {
+    static ref MEXICAN_POSTAL_CODE: Regex = Regex::new(r"^(0[1-9]|[1-9][0-9])[0-9]{3}$").unwrap();
+}
+
+fn non_empty(value: Option<String>) -> Option<String> {
+    value
+        .map(|v| v.split_whitespace().collect::<Vec<&str>>().join(" "))
+        .filter(|v| !v.is_empty())
+}
+
+fn is_mexican(address: &Address) -> bool {
+    match address.country.as_deref() {
+        Some(country) => country.eq_ignore_ascii_case(DEFAULT_ADDRESS_COUNTRY),
+        None => true,
+    }
+}
+
+pub fn find_mexican_state(state: &str) -> Option<&'static str> {
+    MEXICAN_STATES
+        .iter()
+        .find(|mexican_state| mexican_state.to_lowercase() == state.trim().to_lowercase())
+        .copied()
+}
+
+pub fn is_mexican_postal_code(postal_code: &str) -> bool {
+    MEXICAN_POSTAL_CODE.is_match(postal_code)
+}
+
+pub fn is_empty_address(address: &Address) -> bool {
+    address.street.is_none()
+        && address.neighborhood.is_none()
+        && address.municipality.is_none()
+        && address.state.is_none()
+        && address.postal_code.is_none()
+}
+
+pub fn normalize_address(address: Address) -> Address {
+    let mut normalized = Address {
+        street: non_empty(address.street),
+        neighborhood: non_empty(address.neighborhood),
+        municipality: non_empty(address.municipality),
+        state: non_empty(address.state),
+        postal_code: non_empty(address.postal_code).map(|code| code.replace(' ', "")),
+        country: non_empty(address.country).map(|country| country.to_uppercase()),
+    };
+
+    if is_empty_address(&normalized) {
+        return Address::default();
+    }
+
+    if normalized.country.is_none() {
+        normalized.country = Some(DEFAULT_ADDRESS_COUNTRY.to_string());
+    }
+
+    if is_mexican(&normalized) {
+        normalized.state = match normalized.state {
+            Some(state) => find_mexican_state(&state)
+                .map(|mexican_state| mexican_state.to_string())
+                .or(Some(state)),
+            None => None,
+        };
+    }
+
+    normalized
+}
+
+pub fn validate_address(address: &Address) -> Result<(), ValidationError> {
+    if !is_mexican(address) {
+        return Ok(());
+    }
+
+    if let Some(postal_code) = address.postal_code.as_deref() {
+        let postal_code = postal_code.replace(' ', "");
+
+        if !postal_code.is_empty() && !is_mexican_postal_code(&postal_code) {
+            return Err(ValidationError::new("postal_code"));
+        }
+    }
+
+    match address.state.as_deref() {
+        Some(state) if !state.trim().is_empty() && find_mexican_state(state).is_none() => {
+            Err(ValidationError::new("state"))
+        }
+        _ => Ok(()),
+    }
+}
+
+pub fn get_state_tags(selected_state: Option<&str>) -> Vec<AddressStateTag> {
+    MEXICAN_STATES
+        .iter()
+        .map(|state| AddressStateTag {
+            value: state.to_string(),
+            text: state.to_string(),
+            selected: selected_state == Some(*state),
+        })
+        .collect()
+}
@@ -0,0 +1,198 @@
+use actix_web::{http::Method, web::Data};
+use log::warn;
+use rand::{distributions::Alphanumeric, thread_rng, Rng};
+use validator::ValidationError;
+
+use crate::{
+    db::{api_token_db::ApiTokenDB, config::Database},
+    error::api_token_error::ApiTokenError,
+    models::api_token_model::{TokenAccess, TokenScope},
+    utils::{api_utils::API_V1_PREFIX, pwd::pwd_verify, role_utils::is_active_user},
+};
+
+pub const API_TOKEN_PREFIX: &str = "yay";
+pub const API_TOKEN_SECRET_LENGTH: usize = 40;
+pub const API_TOKEN_RESOURCES: [&str; 20] = [
+    "accounts",
+    "attachments",
+    "campaigns",
+    "clinical",
+    "contacts",
+    "email_templates",
+    "enterprises",
+    "leads",
+    "meetings",
+    "notifications",
+    "pipeline_stages",
+    "quotes",
+    "reports",
+    "schools",
+    "scoring_rules",
+    "segments",
+    "services",
+    "tags",
+    "territories",
+    "users",
+];
+const API_TOKEN_SEGMENT: &str = "api_tokens";
+
+pub fn validate_token_scopes(scopes: &[TokenScope]) -> Result<(), ValidationError> {
+    for (index, scope) in scopes.iter().enumerate() {
+        if !API_TOKEN_RESOURCES.contains(&scope.resource.as_str()) {
+            return Err(ValidationError::new("token_scope"));
+        }
+        if scopes[..index]
+            .iter()
+            .any(|other| other.resource == scope.resource)
+        {
+            return Err(ValidationError::new("token_scope"));
+        }
+    }
+
+    Ok(())
+}
+
+pub fn generate_token(token_uuid: &str) -> String {
+    let secret: String = thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(API_TOKEN_SECRET_LENGTH)
+        .map(char::from)
+        .collect();
+
+    format!("{}_{}_{}", API_TOKEN_PREFIX, token_uuid, secret)
+}
+
+pub fn get_token_prefix(token: &str) -> String {
+    token.chars().take(API_TOKEN_PREFIX.len() + 9).collect()
+}
+
+fn parse_token_uuid(token: &str) -> Option<&str> {
+    let (uuid, secret) = token
+        .strip_prefix(API_TOKEN_PREFIX)?
+        .strip_prefix('_')?
+        .split_once('_')?;
+
+    if uuid.is_empty() || secret.len() != API_TOKEN_SECRET_LENGTH {
+        return None;
+    }
+
+    Some(uuid)
+}
+
+pub fn required_access(method: &Method) -> TokenAccess {
+    match *method {
+        Method::GET | Method::HEAD | Method::OPTIONS => TokenAccess::READ,
+        _ => TokenAccess::WRITE,
+    }
+}
+
+pub fn token_resource(path: &str) -> String {
+    let path = path.strip_prefix(API_V1_PREFIX).unwrap_or(path);
+    let mut segments = path.split('/').filter(|segment| !segment.is_empty());
+
+    if segments.clone().any(|segment| segment == API_TOKEN_SEGMENT) {
+        return API_TOKEN_SEGMENT.to_string();
+    }
+
+    segments.next().unwrap_or_default().to_string()
+}
+
+pub fn has_scope(scopes: &[TokenScope], resource: &str, access: TokenAccess) -> bool {
+    scopes.iter().any(|scope| {
+        scope.resource == resource && (scope.access == TokenAccess::WRITE || access == scope.access)
+    })
+}
+
+pub async fn authenticate_token(
+    db: &Data<Database>,
+    token: &str,
+    resource: &str,
+    access: TokenAccess,
+) -> Result<String, ApiTokenError> {
+    let token_uuid = parse_token_uuid(token).ok_or(ApiTokenError::ApiTokenUnauthorized)?;
+
+    let api_token = match <Database as ApiTokenDB>::find_one(db, token_uuid.to_string()).await {
+        Some(api_token) if !api_token.revoked => api_token,
+        _ => return Err(ApiTokenError::ApiTokenUnauthorized),
+    };
+
+    if !pwd_verify(token, &api_token.token_hash)
+        || !is_active_user(db, Some(api_token.user_uuid.clone())).await
+    {
+        return Err(ApiTokenError::ApiTokenUnauthorized);
+    }
+
+    if !has_scope(&api_token.scopes, resource, access) {
+        warn!(
+            "Api token {} lacks {} access to {}",
+            &api_token.token_prefix, access, resource
+        );
+        return Err(ApiTokenError::ApiTokenScopeMissing);
+    }
+
+    <Database as ApiTokenDB>::touch_last_used(db, token_uuid.to_string()).await;
+
+    Ok(api_token.user_uuid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scope(resource: &str, access: TokenAccess) -> TokenScope {
+        TokenScope {
+            resource: resource.to_string(),
+            access,
+        }
+    }
+
+    #[test]
+    fn parses_the_uuid_of_generated_tokens() {
+        let token = generate_token("abc12345");
+
+        assert_eq!(parse_token_uuid(&token), Some("abc12345"));
+        assert_eq!(get_token_prefix(&token), "yay_abc12345");
+    }
+
+    #[test]
+    fn rejects_malformed_tokens() {
+        let secret = "a".repeat(API_TOKEN_SECRET_LENGTH);
+
+        assert_eq!(parse_token_uuid(&format!("foo_abc123_{}", secret)), None);
+        assert_eq!(parse_token_uuid(&format!("yay__{}", secret)), None);
+        assert_eq!(parse_token_uuid("yay_abc123_short"), None);
+        assert_eq!(parse_token_uuid("yay_abc123"), None);
+        assert_eq!(parse_token_uuid(""), None);
+    }
+
+    #[test]
+    fn finds_the_resource_of_api_paths() {
+        assert_eq!(token_resource("/api/v1/enterprises/abc"), "enterprises");
+        assert_eq!(token_resource("/leads/enterprise/abc/stage"), "leads");
+        assert_eq!(token_resource("/api/v1/users/abc/api_tokens"), "api_tokens");
+    }
+
+    #[test]
+    fn write_scopes_also_grant_read() {
+        let scopes = vec![
+            scope("quotes", TokenAccess::WRITE),
+            scope("services", TokenAccess::READ),
+        ];
+
+        assert!(has_scope(&scopes, "quotes", TokenAccess::READ));
+        assert!(has_scope(&scopes, "services", TokenAccess::READ));
+        assert!(!has_scope(&scopes, "services", TokenAccess::WRITE));
+        assert!(!has_scope(&scopes, "users", TokenAccess::READ));
+    }
+
+    #[test]
+    fn rejects_unknown_and_repeated_scopes() {
+        assert!(validate_token_scopes(&[scope("quotes", TokenAccess::READ)]).is_ok());
+        assert!(validate_token_scopes(&[scope("api_tokens", TokenAccess::READ)]).is_err());
+        assert!(validate_token_scopes(&[
+            scope("quotes", TokenAccess::READ),
+            scope("quotes", TokenAccess::WRITE),
+        ])
+        .is_err());
+    }
+}
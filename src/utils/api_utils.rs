@@ -0,0 +1,203 @@
+use std::{
+    future::{ready, Future, Ready},
+    pin::Pin,
+    rc::Rc,
+};
+
+use actix_web::{
+    body::EitherBody,
+    dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
+    http::{
+        header::{HeaderName, HeaderValue, AUTHORIZATION},
+        Method,
+    },
+    web::Data,
+    Error, HttpMessage, HttpResponse, ResponseError,
+};
+use log::warn;
+
+use crate::{
+    db::config::Database,
+    error::{api_error::ApiError, api_token_error::ApiTokenError},
+    utils::{
+        api_token_utils::{authenticate_token, required_access, token_resource},
+        general_utils::get_uuid,
+        role_utils::{find_request_user, AuthenticatedUser},
+    },
+};
+
+pub const API_V1_PREFIX: &str = "/api/v1";
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+tokio::task_local! {
+    static REQUEST_ID: String;
+}
+
+pub fn current_request_id() -> Option<String> {
+    REQUEST_ID.try_with(|request_id| request_id.clone()).ok()
+}
+
+pub fn with_request_id<S, B>(
+    req: ServiceRequest,
+    srv: &S,
+) -> impl Future<Output = Result<ServiceResponse<B>, Error>>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+{
+    let request_id = req
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .filter(|value| !value.is_empty() && value.len() <= 64)
+        .map(str::to_string)
+        .unwrap_or_else(get_uuid);
+
+    let response = REQUEST_ID.scope(request_id.clone(), srv.call(req));
+
+    async move {
+        let mut res = response.await?;
+        if let Ok(value) = HeaderValue::from_str(&request_id) {
+            res.headers_mut()
+                .insert(HeaderName::from_static(REQUEST_ID_HEADER), value);
+        }
+        Ok(res)
+    }
+}
+
+pub fn deprecated_alias<S, B>(
+    req: ServiceRequest,
+    srv: &S,
+) -> impl Future<Output = Result<ServiceResponse<B>, Error>>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+{
+    let response = srv.call(req);
+
+    async move {
+        let mut res = response.await?;
+        if res.request().match_pattern().is_none() {
+            return Ok(res);
+        }
+
+        let successor = format!("{}{}", API_V1_PREFIX, res.request().path());
+        warn!(
+            "Deprecated API path {} {}, use {}",
+            res.request().method(),
+            res.request().path(),
+            &successor
+        );
+
+        res.headers_mut().insert(
+            HeaderName::from_static("deprecation"),
+            HeaderValue::from_static("true"),
+        );
+        if let Ok(value) =
+            HeaderValue::from_str(&format!("<{}>; rel=\"successor-version\"", successor))
+        {
+            res.headers_mut()
+                .insert(HeaderName::from_static("link"), value);
+        }
+        Ok(res)
+    }
+}
+
+pub struct BearerAuth;
+
+impl<S, B> Transform<S, ServiceRequest> for BearerAuth
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Transform = BearerAuthMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(BearerAuthMiddleware {
+            service: Rc::new(service),
+        }))
+    }
+}
+
+pub struct BearerAuthMiddleware<S> {
+    service: Rc<S>,
+}
+
+impl<S, B> Service<ServiceRequest> for BearerAuthMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>>>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = Rc::clone(&self.service);
+
+        Box::pin(async move {
+            let token = match get_bearer_token(&req) {
+                Some(token) => token,
+                None => {
+                    // Without a token only a browser with a live session gets through.
+                    if is_public_route(&req) || find_request_user(req.request()).await.is_some() {
+                        let res = service.call(req).await?;
+                        return Ok(res.map_into_left_body());
+                    }
+
+                    let error = ApiTokenError::ApiTokenUnauthorized;
+                    let response =
+                        HttpResponse::build(error.status_code()).json(ApiError::new(error));
+                    return Ok(req.into_response(response).map_into_right_body());
+                }
+            };
+
+            let authenticated = match req.app_data::<Data<Database>>() {
+                Some(db) => {
+                    let resource = token_resource(req.path());
+                    let access = required_access(req.method());
+                    authenticate_token(db, &token, &resource, access).await
+                }
+                None => Err(ApiTokenError::ApiTokenUnauthorized),
+            };
+
+            match authenticated {
+                Ok(user_uuid) => {
+                    req.extensions_mut().insert(AuthenticatedUser(user_uuid));
+                    let res = service.call(req).await?;
+                    Ok(res.map_into_left_body())
+                }
+                Err(error) => {
+                    let response =
+                        HttpResponse::build(error.status_code()).json(ApiError::new(error));
+                    Ok(req.into_response(response).map_into_right_body())
+                }
+            }
+        })
+    }
+}
+
+// The login form, the public web lead form and the calendar feed, which carries
+// its own secret in the path.
+fn is_public_route(req: &ServiceRequest) -> bool {
+    let path = req.path().strip_prefix(API_V1_PREFIX).unwrap_or(req.path());
+
+    match *req.method() {
+        Method::POST => path == "/sessions" || path == "/web_leads",
+        Method::GET => path.starts_with("/calendar/") && path.ends_with(".ics"),
+        _ => false,
+    }
+}
+
+fn get_bearer_token(req: &ServiceRequest) -> Option<String> {
+    req.headers()
+        .get(AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Bearer ")
+        .map(|token| token.trim().to_string())
+}
@@ -0,0 +1,137 @@
+use log::{error, warn};
+use std::{
+    fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+use crate::constants::connection::set_environment_variable;
+
+const DEFAULT_MAX_BYTES: usize = 10 * 1024 * 1024;
+const SNIFF_BYTES: usize = 512;
+
+pub fn get_attachments_path() -> PathBuf {
+    let media_path = set_environment_variable("MEDIA_PATH", "./media/");
+    let attachments_path = PathBuf::from(set_environment_variable(
+        "ATTACHMENTS_PATH",
+        &format!("{}attachments", media_path),
+    ));
+
+    if let Err(e) = fs::create_dir_all(&attachments_path) {
+        error!(
+            "Unable to create attachments directory {}:: {}",
+            attachments_path.display(),
+            e
+        );
+    }
+
+    attachments_path
+}
+
+pub fn get_attachment_max_bytes() -> usize {
+    match set_environment_variable("ATTACHMENT_MAX_BYTES", &DEFAULT_MAX_BYTES.to_string())
+        .parse::<usize>()
+    {
+        Ok(max_bytes) => max_bytes,
+        Err(e) => {
+            warn!("Invalid ATTACHMENT_MAX_BYTES: {}", e);
+            DEFAULT_MAX_BYTES
+        }
+    }
+}
+
+pub fn sniff_mime_type(bytes: &[u8]) -> Option<&'static str> {
+    match bytes {
+        [b'%', b'P', b'D', b'F', ..] => Some("application/pdf"),
+        [0x89, b'P', b'N', b'G', ..] => Some("image/png"),
+        [0xFF, 0xD8, 0xFF, ..] => Some("image/jpeg"),
+        [b'G', b'I', b'F', b'8', ..] => Some("image/gif"),
+        [b'R', b'I', b'F', b'F', _, _, _, _, b'W', b'E', b'B', b'P', ..] => Some("image/webp"),
+        [b'P', b'K', 0x03, 0x04, ..] => Some("application/zip"),
+        [0xD0, 0xCF, 0x11, 0xE0, ..] => Some("application/msword"),
+        _ if !bytes.is_empty() && is_utf8_text(bytes) => Some("text/plain"),
+        _ => None,
+    }
+}
+
+// Only a prefix of the file is sniffed, so a multibyte character cut at the end
+// still counts as text.
+fn is_utf8_text(bytes: &[u8]) -> bool {
+    match std::str::from_utf8(bytes) {
+        Ok(_) => true,
+        Err(e) => e.error_len().is_none(),
+    }
+}
+
+pub fn get_office_mime_type(file_name: &str, sniffed_mime: &'static str) -> &'static str {
+    let extension = Path::new(file_name)
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .unwrap_or_default()
+        .to_lowercase();
+
+    match (sniffed_mime, extension.as_str()) {
+        ("application/zip", "docx") => {
+            "application/vnd.openxmlformats-officedocument.wordprocessingml.document"
+        }
+        ("application/zip", "xlsx") => {
+            "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"
+        }
+        ("application/zip", "pptx") => {
+            "application/vnd.openxmlformats-officedocument.presentationml.presentation"
+        }
+        ("application/msword", "xls") => "application/vnd.ms-excel",
+        ("text/plain", "csv") => "text/csv",
+        _ => sniffed_mime,
+    }
+}
+
+pub fn read_file_header(path: &Path) -> io::Result<Vec<u8>> {
+    let mut header = Vec::with_capacity(SNIFF_BYTES);
+    fs::File::open(path)?
+        .take(SNIFF_BYTES as u64)
+        .read_to_end(&mut header)?;
+
+    Ok(header)
+}
+
+pub fn sanitize_file_name(file_name: &str) -> String {
+    let base_name = Path::new(file_name)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or("attachment");
+
+    let sanitized: String = base_name
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || matches!(c, '.' | '-' | '_' | ' ') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+
+    match sanitized.trim() {
+        "" => "attachment".to_string(),
+        trimmed => trimmed.to_string(),
+    }
+}
+
+pub fn store_attachment_file(temp_path: &Path, storage_name: &str) -> io::Result<PathBuf> {
+    let target_path = get_attachments_path().join(storage_name);
+
+    if fs::rename(temp_path, &target_path).is_err() {
+        fs::copy(temp_path, &target_path)?;
+    }
+
+    Ok(target_path)
+}
+
+pub fn format_file_size(size: u64) -> String {
+    match size {
+        s if s >= 1024 * 1024 => format!("{:.1} MB", s as f64 / (1024.0 * 1024.0)),
+        s if s >= 1024 => format!("{:.1} KB", s as f64 / 1024.0),
+        s => format!("{} B", s),
+    }
+}
@@ -0,0 +1,268 @@
+use actix_web::web::Data;
+use chrono::Utc;
+use log::{error, info};
+use serde_json::{json, Value};
+
+use crate::{
+    db::{config::Database, lead_db::LeadDB, segment_db::SegmentDB},
+    models::{
+        lead_model::{
+            BulkAction, BulkLeadRequest, BulkLeadResponse, BulkLeadResult, LeadSummary,
+            StageTransition,
+        },
+        sales_model::LeadType,
+    },
+    utils::{
+        general_utils::get_uuid,
+        lead_utils::{find_filtered_summaries, find_lead_summaries, get_lead_table},
+        notification_utils::{notify_bulk_reassign, notify_bulk_stage_change},
+        pipeline_utils::{find_stage, get_pipeline},
+        role_utils::is_active_user,
+        scoring_utils::rescore_leads,
+        service_utils::get_service_catalog,
+        territory_utils::find_territory_scope,
+    },
+};
+
+const MAX_BULK_RECORDS: usize = 500;
+
+pub async fn resolve_bulk_uuids(
+    db: &Data<Database>,
+    lead_type: &LeadType,
+    uuids: Vec<String>,
+    segment: Option<String>,
+    user_uuid: Option<String>,
+) -> Result<Vec<String>, String> {
+    let mut resolved: Vec<String> = match segment {
+        Some(segment_uuid) => match <Database as SegmentDB>::find_one(db, segment_uuid).await {
+            Some(segment) if segment.lead_type == *lead_type => {
+                find_filtered_summaries(db, lead_type, &segment.filter, user_uuid.clone())
+                    .await
+                    .into_iter()
+                    .map(|lead| lead.uuid)
+                    .collect()
+            }
+            Some(segment) => {
+                return Err(format!(
+                    "El segmento {} es de tipo {}",
+                    segment.name, segment.lead_type
+                ))
+            }
+            None => return Err("No se encontró el segmento".to_string()),
+        },
+        None => uuids,
+    };
+
+    if find_territory_scope(db, user_uuid.clone()).await.is_some() {
+        let in_scope: Vec<String> = find_lead_summaries(db, lead_type, user_uuid)
+            .await
+            .into_iter()
+            .map(|lead| lead.uuid)
+            .collect();
+        resolved.retain(|uuid| in_scope.contains(uuid));
+    }
+
+    resolved.sort();
+    resolved.dedup();
+
+    match resolved.len() {
+        0 => Err("Selecciona al menos un registro".to_string()),
+        n if n > MAX_BULK_RECORDS => Err(format!(
+            "Máximo {} registros por acción masiva",
+            MAX_BULK_RECORDS
+        )),
+        _ => Ok(resolved),
+    }
+}
+
+async fn get_bulk_value(
+    db: &Data<Database>,
+    lead_type: &LeadType,
+    request: &BulkLeadRequest,
+) -> Result<Value, String> {
+    match request.action {
+        BulkAction::SetStage => {
+            let stages = get_pipeline(db, lead_type).await;
+            match request.sales_funnel.as_deref() {
+                Some(code) if find_stage(&stages, code).is_some() => Ok(json!(code)),
+                Some(code) => Err(format!("Etapa inválida: {}", code)),
+                None => Err("Selecciona una etapa".to_string()),
+            }
+        }
+        BulkAction::AddServices | BulkAction::RemoveServices => {
+            let catalog = get_service_catalog(db).await;
+            let services = request.services.clone().unwrap_or_default();
+
+            if services.is_empty() {
+                return Err("Selecciona al menos un servicio".to_string());
+            }
+            match services.iter().find(|service_uuid| {
+                !catalog
+                    .iter()
+                    .any(|service| service.uuid.as_deref() == Some(service_uuid.as_str()))
+            }) {
+                Some(unknown) => Err(format!("Servicio inválido: {}", unknown)),
+                None => Ok(json!(services)),
+            }
+        }
+        BulkAction::Reassign => {
+            if is_active_user(db, request.assigned_to.clone()).await {
+                Ok(json!(request.assigned_to))
+            } else {
+                Err("Selecciona un usuario activo".to_string())
+            }
+        }
+        BulkAction::Delete => Ok(Value::Null),
+    }
+}
+
+pub async fn run_bulk_action(
+    db: &Data<Database>,
+    lead_type: &LeadType,
+    request: BulkLeadRequest,
+) -> Result<BulkLeadResponse, String> {
+    let uuids = resolve_bulk_uuids(
+        db,
+        lead_type,
+        request.uuids.clone(),
+        request.segment.clone(),
+        request.modified_by.clone(),
+    )
+    .await?;
+    let value = get_bulk_value(db, lead_type, &request).await?;
+    let table_name = get_lead_table(lead_type);
+
+    let targets = <Database as LeadDB>::find_bulk_targets(db, table_name, uuids.clone())
+        .await
+        .unwrap_or_default();
+
+    let mut results: Vec<BulkLeadResult> = Vec::with_capacity(uuids.len());
+    let mut to_update: Vec<String> = Vec::new();
+    let mut transitions: Vec<StageTransition> = Vec::new();
+
+    for uuid in uuids {
+        let skip_reason = match targets.iter().find(|target| target.uuid == uuid) {
+            None => Some("No encontrado"),
+            Some(target) if target.deleted => Some("El registro está borrado"),
+            Some(target)
+                if request.action == BulkAction::SetStage
+                    && request.sales_funnel.as_deref() == Some(target.sales_funnel.as_str()) =>
+            {
+                Some("Ya está en esa etapa")
+            }
+            Some(target) => {
+                if request.action == BulkAction::SetStage {
+                    let transition = StageTransition {
+                        uuid: None,
+                        lead_type: lead_type.clone(),
+                        lead_uuid: uuid.clone(),
+                        from_stage: target.sales_funnel.clone(),
+                        to_stage: request.sales_funnel.clone().unwrap_or_default(),
+                        changed_by: request.modified_by.clone(),
+                        date_created: Some(Utc::now()),
+                    };
+                    transitions.push(StageTransition::new(get_uuid(), transition));
+                }
+                None
+            }
+        };
+
+        match skip_reason {
+            Some(message) => results.push(BulkLeadResult {
+                uuid,
+                success: false,
+                message: message.to_string(),
+            }),
+            None => to_update.push(uuid),
+        }
+    }
+
+    let committed = match to_update.is_empty() {
+        true => true,
+        false => <Database as LeadDB>::apply_bulk_update(
+            db,
+            table_name,
+            &request.action,
+            value,
+            to_update.clone(),
+            transitions,
+            request.modified_by.clone(),
+        )
+        .await
+        .is_some(),
+    };
+
+    if !committed {
+        error!(
+            "Bulk {:?} on {} was not applied",
+            &request.action, table_name
+        );
+    } else if request.action != BulkAction::Delete {
+        rescore_leads(db, lead_type, to_update.clone()).await;
+    }
+
+    if committed {
+        match request.action {
+            BulkAction::Reassign => {
+                notify_bulk_reassign(
+                    db,
+                    lead_type,
+                    request.assigned_to.clone(),
+                    to_update.len(),
+                    request.modified_by.clone(),
+                )
+                .await
+            }
+            BulkAction::SetStage => {
+                notify_bulk_stage_change(
+                    db,
+                    lead_type,
+                    &find_bulk_summaries(db, lead_type, &to_update).await,
+                    request.sales_funnel.as_deref().unwrap_or_default(),
+                    request.modified_by.clone(),
+                )
+                .await
+            }
+            _ => {}
+        }
+    }
+
+    for uuid in to_update {
+        results.push(BulkLeadResult {
+            uuid,
+            success: committed,
+            message: match committed {
+                true => "Actualizado".to_string(),
+                false => "La transacción no se aplicó".to_string(),
+            },
+        });
+    }
+
+    let updated = results.iter().filter(|result| result.success).count();
+    info!(
+        "Bulk {:?} on {}: {} updated, {} failed",
+        &request.action,
+        table_name,
+        updated,
+        results.len() - updated
+    );
+
+    Ok(BulkLeadResponse {
+        action: request.action,
+        updated,
+        failed: results.len() - updated,
+        results,
+    })
+}
+
+pub async fn find_bulk_summaries(
+    db: &Data<Database>,
+    lead_type: &LeadType,
+    uuids: &[String],
+) -> Vec<LeadSummary> {
+    find_lead_summaries(db, lead_type, None)
+        .await
+        .into_iter()
+        .filter(|lead| uuids.contains(&lead.uuid))
+        .collect()
+}
@@ -0,0 +1,195 @@
+use actix_web::web::Data;
+
+use crate::{
+    db::{campaign_db::CampaignDB, config::Database, quote_db::QuoteDB},
+    models::{
+        campaign_model::{AttributionReport, AttributionRow, Campaign},
+        lead_model::{LeadChannel, LeadChannelTag, LeadSummary},
+        sales_model::LeadType,
+    },
+    utils::{
+        kanban_utils::get_lead_value, lead_utils::find_lead_summaries,
+        pipeline_utils::get_pipeline, quote_utils::round_currency,
+        service_utils::get_service_catalog,
+    },
+};
+
+pub const LEAD_CHANNELS: [LeadChannel; 5] = [
+    LeadChannel::REFERRAL,
+    LeadChannel::WEBFORM,
+    LeadChannel::COLDCALL,
+    LeadChannel::EVENT,
+    LeadChannel::IMPORT,
+];
+
+pub fn create_lead_channel_tags(channel: Option<&LeadChannel>) -> Vec<LeadChannelTag> {
+    LEAD_CHANNELS
+        .into_iter()
+        .map(|value| LeadChannelTag {
+            text: value.to_string(),
+            selected: channel == Some(&value),
+            value,
+        })
+        .collect()
+}
+
+pub fn normalize_campaign(campaign: Campaign) -> Campaign {
+    Campaign {
+        name: campaign.name.trim().to_string(),
+        code: campaign
+            .code
+            .map(|code| code.trim().to_lowercase())
+            .filter(|code| !code.is_empty()),
+        notes: campaign.notes.trim().to_string(),
+        ..campaign
+    }
+}
+
+pub fn get_campaign_errors(campaign: &Campaign, campaigns: &[Campaign]) -> Vec<String> {
+    let mut key_errors_vec: Vec<String> = vec![];
+
+    if let (Some(start_date), Some(end_date)) = (campaign.start_date, campaign.end_date) {
+        if end_date < start_date {
+            key_errors_vec.push("La campaña termina antes de empezar".to_string());
+        }
+    }
+
+    if let Some(code) = &campaign.code {
+        let is_taken = campaigns
+            .iter()
+            .any(|other| other.uuid != campaign.uuid && other.code.as_ref() == Some(code));
+        if is_taken {
+            key_errors_vec.push(format!("El código '{}' ya es de otra campaña", code));
+        }
+    }
+
+    key_errors_vec
+}
+
+pub async fn find_campaign_by_code(db: &Data<Database>, code: Option<&str>) -> Option<Campaign> {
+    let code = code?.trim().to_lowercase();
+
+    <Database as CampaignDB>::find_all_active(db)
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .find(|campaign| campaign.code.as_deref() == Some(code.as_str()))
+}
+
+fn add_to_row(rows: &mut Vec<AttributionRow>, key: Option<String>, name: String, win: Option<f64>) {
+    let row = match rows.iter_mut().position(|row| row.key == key) {
+        Some(index) => &mut rows[index],
+        None => {
+            rows.push(AttributionRow {
+                key,
+                name,
+                leads: 0,
+                wins: 0,
+                win_rate: 0.0,
+                win_value: 0.0,
+                budget: None,
+            });
+            rows.last_mut().unwrap()
+        }
+    };
+
+    row.leads += 1;
+    if let Some(value) = win {
+        row.wins += 1;
+        row.win_value += value;
+    }
+}
+
+fn finish_rows(rows: Vec<AttributionRow>) -> Vec<AttributionRow> {
+    rows.into_iter()
+        .map(|row| AttributionRow {
+            win_rate: match row.leads {
+                0 => 0.0,
+                leads => round_currency(row.wins as f64 * 100.0 / leads as f64),
+            },
+            win_value: round_currency(row.win_value),
+            ..row
+        })
+        .collect()
+}
+
+// Wins are the leads sitting in a won stage of their pipeline, valued like the
+// kanban board does: latest quote total or the price of the offered services.
+pub async fn build_attribution_report(
+    db: &Data<Database>,
+    lead_types: Vec<LeadType>,
+    user_uuid: Option<String>,
+) -> AttributionReport {
+    let campaigns = <Database as CampaignDB>::find_all_active(db)
+        .await
+        .unwrap_or_default();
+    let services = get_service_catalog(db).await;
+    let quotes = <Database as QuoteDB>::find_all_active(db)
+        .await
+        .unwrap_or_default();
+
+    let mut campaign_rows: Vec<AttributionRow> = campaigns
+        .iter()
+        .map(|campaign| AttributionRow {
+            key: campaign.uuid.clone(),
+            name: campaign.name.clone(),
+            leads: 0,
+            wins: 0,
+            win_rate: 0.0,
+            win_value: 0.0,
+            budget: Some(campaign.budget),
+        })
+        .collect();
+    let mut source_rows: Vec<AttributionRow> = vec![];
+
+    for lead_type in lead_types {
+        let won_stages: Vec<String> = get_pipeline(db, &lead_type)
+            .await
+            .into_iter()
+            .filter(|stage| stage.is_won)
+            .map(|stage| stage.code)
+            .collect();
+        let leads: Vec<LeadSummary> = find_lead_summaries(db, &lead_type, user_uuid.clone()).await;
+
+        for lead in leads {
+            let win = match won_stages.contains(&lead.sales_funnel) {
+                true => Some(get_lead_value(&lead, &services, &quotes)),
+                false => None,
+            };
+
+            let channel = lead.source.as_ref().map(|source| source.channel.clone());
+            add_to_row(
+                &mut source_rows,
+                channel.as_ref().map(|channel| format!("{:?}", channel)),
+                match &channel {
+                    Some(channel) => channel.to_string(),
+                    None => "Sin origen".to_string(),
+                },
+                win,
+            );
+
+            let campaign_uuid = lead
+                .source
+                .as_ref()
+                .and_then(|source| source.campaign_uuid.clone())
+                .filter(|uuid| campaigns.iter().any(|c| c.uuid.as_ref() == Some(uuid)));
+            add_to_row(
+                &mut campaign_rows,
+                campaign_uuid,
+                "Sin campaña".to_string(),
+                win,
+            );
+        }
+    }
+
+    let campaign_rows = finish_rows(campaign_rows);
+    let source_rows = finish_rows(source_rows);
+
+    AttributionReport {
+        leads: source_rows.iter().map(|row| row.leads).sum(),
+        wins: source_rows.iter().map(|row| row.wins).sum(),
+        win_value: round_currency(source_rows.iter().map(|row| row.win_value).sum()),
+        campaigns: campaign_rows,
+        sources: source_rows,
+    }
+}
@@ -0,0 +1,218 @@
+use actix_web::web::Data;
+use chrono::Utc;
+use log::{error, info};
+
+use crate::{
+    db::{config::Database, contact_db::ContactDB, lead_db::LeadDB},
+    models::{
+        account_model::Contact, clinical_model::Clinical, enterprise_model::Enterprise,
+        lead_model::LeadPerson, sales_model::LeadType, school_model::School,
+    },
+    utils::{
+        general_utils::{get_lead_type_tags, get_uuid},
+        lead_utils::get_lead_table,
+    },
+};
+
+fn non_empty(value: Option<String>) -> Option<String> {
+    value
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+pub fn enterprise_person(enterprise: &Enterprise) -> LeadPerson {
+    LeadPerson {
+        uuid: enterprise.uuid.clone().unwrap_or_default(),
+        name: enterprise.name.clone(),
+        last_name: enterprise.last_name.clone(),
+        email: Some(enterprise.email.clone()),
+        phone: enterprise.phone.clone(),
+    }
+}
+
+pub fn school_person(school: &School) -> LeadPerson {
+    LeadPerson {
+        uuid: school.uuid.clone().unwrap_or_default(),
+        name: school.name.clone(),
+        last_name: school.last_name.clone(),
+        email: Some(school.email.clone()),
+        phone: Some(school.phone.clone()),
+    }
+}
+
+pub fn clinical_person(clinical: &Clinical) -> LeadPerson {
+    LeadPerson {
+        uuid: clinical.uuid.clone().unwrap_or_default(),
+        name: clinical.name.clone(),
+        last_name: clinical.last_name.clone(),
+        email: clinical.email.clone(),
+        phone: Some(clinical.phone.clone()),
+    }
+}
+
+pub fn contact_from_person(
+    lead_type: &LeadType,
+    person: LeadPerson,
+    account_uuid: Option<String>,
+    created_by: Option<String>,
+) -> Contact {
+    let date_created = Utc::now();
+
+    Contact {
+        uuid: None,
+        account_uuid,
+        lead_type: Some(lead_type.clone()),
+        lead_uuid: Some(person.uuid),
+        name: person.name,
+        last_name: person.last_name,
+        role: None,
+        email: non_empty(person.email),
+        phone: non_empty(person.phone),
+        is_primary: true,
+        deleted: false,
+        date_created: Some(date_created),
+        date_modified: Some(date_created),
+        created_by: created_by.clone(),
+        modified_by: created_by,
+    }
+}
+
+pub async fn find_lead_contacts(db: &Data<Database>, lead_uuid: String) -> Vec<Contact> {
+    <Database as ContactDB>::find_by_lead(db, lead_uuid)
+        .await
+        .unwrap_or_default()
+}
+
+pub async fn sync_primary_contact(
+    db: &Data<Database>,
+    lead_type: &LeadType,
+    person: LeadPerson,
+    account_uuid: Option<String>,
+    modified_by: Option<String>,
+) {
+    let contacts = find_lead_contacts(db, person.uuid.clone()).await;
+
+    let saved = match contacts.into_iter().find(|contact| contact.is_primary) {
+        Some(primary) => {
+            let email = non_empty(person.email);
+            let phone = non_empty(person.phone);
+
+            if primary.name == person.name
+                && primary.last_name == person.last_name
+                && primary.email == email
+                && primary.phone == phone
+            {
+                return;
+            }
+
+            <Database as ContactDB>::update_one(
+                db,
+                Contact {
+                    name: person.name,
+                    last_name: person.last_name,
+                    email,
+                    phone,
+                    date_modified: Some(Utc::now()),
+                    modified_by,
+                    ..primary
+                },
+            )
+            .await
+        }
+        None => {
+            let contact = contact_from_person(lead_type, person, account_uuid, modified_by);
+            <Database as ContactDB>::add_one(db, Contact::new(get_uuid(), contact)).await
+        }
+    };
+
+    if saved.is_none() {
+        error!("Unable to sync the primary contact of a {} lead", lead_type);
+    }
+}
+
+pub async fn keep_single_primary(db: &Data<Database>, contact: &Contact) {
+    if !contact.is_primary {
+        return;
+    }
+
+    let contact_uuid = contact.uuid.clone().unwrap_or_default();
+    let updated = match (contact.lead_uuid.clone(), contact.account_uuid.clone()) {
+        (Some(lead_uuid), _) => {
+            <Database as ContactDB>::unset_lead_primary(db, lead_uuid, contact_uuid).await
+        }
+        (None, Some(account_uuid)) => {
+            <Database as ContactDB>::unset_primary(db, account_uuid, contact_uuid).await
+        }
+        (None, None) => true,
+    };
+
+    if !updated {
+        error!("Unable to keep a single primary contact");
+    }
+}
+
+pub async fn sync_lead_person(db: &Data<Database>, contact: &Contact) {
+    if let (true, Some(lead_type), Some(lead_uuid)) = (
+        contact.is_primary,
+        contact.lead_type.clone(),
+        contact.lead_uuid.clone(),
+    ) {
+        let person = LeadPerson {
+            uuid: lead_uuid,
+            name: contact.name.clone(),
+            last_name: contact.last_name.clone(),
+            email: contact.email.clone(),
+            phone: contact.phone.clone(),
+        };
+
+        if !<Database as LeadDB>::set_person(db, get_lead_table(&lead_type), person).await {
+            error!(
+                "Unable to copy the primary contact into the {} lead",
+                lead_type
+            );
+        }
+    }
+}
+
+pub async fn migrate_lead_contacts(db: &Data<Database>) -> Result<(), String> {
+    let mut failed_leads = 0;
+
+    for lead_type_tag in get_lead_type_tags() {
+        let lead_type = lead_type_tag.value;
+        let table_name = get_lead_table(&lead_type);
+        let people =
+            match <Database as ContactDB>::find_leads_without_contacts(db, table_name).await {
+                Some(people) => people,
+                None => return Err(format!("Unable to read leads from {}", table_name)),
+            };
+        let mut migrated_leads = 0;
+
+        for person in people {
+            let contact = contact_from_person(&lead_type, person, None, None);
+
+            if <Database as ContactDB>::add_one(db, Contact::new(get_uuid(), contact))
+                .await
+                .is_some()
+            {
+                migrated_leads += 1;
+            } else {
+                failed_leads += 1;
+            }
+        }
+
+        if migrated_leads > 0 {
+            info!(
+                "Migrated contacts of {} records in {}",
+                migrated_leads, table_name
+            );
+        }
+    }
+
+    match failed_leads {
+        0 => Ok(()),
+        _ => Err(format!(
+            "Unable to migrate contacts of {} leads",
+            failed_leads
+        )),
+    }
+}
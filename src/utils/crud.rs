@@ -1,10 +1,15 @@
 use actix_web::web::Data;
 use log::error;
 
+use chrono::Utc;
 use serde::{de::DeserializeOwned, Serialize};
-use surrealdb::Error;
+use surrealdb::{method::Stream, opt::PatchOp, Error};
 
-use crate::db::config::Database;
+use crate::{
+    db::config::Database,
+    models::lead_model::{LeadFilter, LeadSort},
+    utils::tag_utils::parse_tag_list,
+};
 
 pub async fn util_find_all<T: DeserializeOwned>(
     db: &Data<Database>,
@@ -21,6 +26,21 @@ pub async fn util_find_all<T: DeserializeOwned>(
     }
 }
 
+pub async fn util_live_table<T: DeserializeOwned>(
+    db: &Data<Database>,
+    table_name: &str,
+) -> Option<Stream<Vec<T>>> {
+    let live_t: Result<Stream<Vec<T>>, Error> = db.client.select(table_name).live().await;
+
+    match live_t {
+        Ok(stream) => Some(stream),
+        Err(e) => {
+            error!("Error {}.live:: {:?}", &table_name, e);
+            None
+        }
+    }
+}
+
 pub async fn util_find_one<T: DeserializeOwned>(
     db: &Data<Database>,
     uuid: String,
@@ -116,6 +136,115 @@ pub async fn util_find_all_non_deleted<T: DeserializeOwned + Serialize>(
     }
 }
 
+pub async fn util_find_filtered<T: DeserializeOwned>(
+    db: &Data<Database>,
+    table_name: &str,
+    filter: &LeadFilter,
+) -> Option<Vec<T>> {
+    let tags = parse_tag_list(filter.tag.as_deref().unwrap_or_default());
+    let sales_funnel = filter.sales_funnel.clone().unwrap_or_default();
+    let service = filter.service.clone().unwrap_or_default();
+    let state = filter.state.clone().unwrap_or_default();
+    let municipality = filter.municipality.clone().unwrap_or_default();
+
+    let mut conditions = vec!["deleted = false"];
+    if !tags.is_empty() {
+        conditions.push("tags CONTAINSALL $tags");
+    }
+    if !sales_funnel.is_empty() {
+        conditions.push("sales_funnel = $sales_funnel");
+    }
+    if !service.is_empty() {
+        conditions.push("services_offered CONTAINS $service");
+    }
+    if !state.is_empty() {
+        conditions.push("address.state = $state");
+    }
+    if !municipality.is_empty() {
+        conditions.push(
+            "string::lowercase(address.municipality ?? '') = string::lowercase($municipality)",
+        );
+    }
+    if filter.min_score.is_some() {
+        conditions.push("score >= $min_score");
+    }
+    if filter.max_score.is_some() {
+        conditions.push("score <= $max_score");
+    }
+
+    let order_by = match filter.sort {
+        Some(LeadSort::ScoreDesc) => " ORDER BY score DESC",
+        Some(LeadSort::ScoreAsc) => " ORDER BY score ASC",
+        None => "",
+    };
+
+    let surreal_query = format!(
+        "SELECT * FROM {} WHERE {}{}",
+        table_name,
+        conditions.join(" AND "),
+        order_by
+    );
+
+    let query_t_result = db
+        .client
+        .query(surreal_query)
+        .bind(("tags", tags))
+        .bind(("sales_funnel", sales_funnel))
+        .bind(("service", service))
+        .bind(("state", state))
+        .bind(("municipality", municipality))
+        .bind(("min_score", filter.min_score.unwrap_or_default()))
+        .bind(("max_score", filter.max_score.unwrap_or_default()))
+        .await;
+
+    match query_t_result {
+        Ok(mut response) => match response.take(0) {
+            Ok(filtered_t_records) => Some(filtered_t_records),
+            Err(e) => {
+                error!(
+                    "Failed to retrieve filtered records from {}:: {}",
+                    table_name, e
+                );
+                None
+            }
+        },
+        Err(e) => {
+            error!(
+                "Failed to retrieve filtered records from {}:: {}",
+                table_name, e
+            );
+            None
+        }
+    }
+}
+
+pub async fn util_patch_one<T, V>(
+    db: &Data<Database>,
+    uuid: String,
+    table_name: &str,
+    field_path: &str,
+    value: V,
+) -> Option<T>
+where
+    T: DeserializeOwned,
+    V: Serialize + 'static,
+{
+    let patched_t: Result<Option<T>, Error> = db
+        .client
+        .update((table_name, uuid))
+        .patch(PatchOp::replace(field_path, value))
+        .patch(PatchOp::replace("/date_modified", Utc::now()))
+        .await;
+
+    match patched_t {
+        Ok(t_record) => t_record,
+        Err(e) => {
+            error!("Error {}.patch_one {}:: {:?}", table_name, field_path, e);
+            None
+        }
+    }
+}
+
 // pub async fn util_query_table<T: DeserializeOwned + Serialize>(
 //     db: &Database,
 //     table_name: &str,
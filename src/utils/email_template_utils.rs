@@ -0,0 +1,98 @@
+use handlebars::{Handlebars, RenderError};
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde_json::{json, Value};
+
+use crate::models::{
+    clinical_model::Clinical,
+    email_template_model::RenderedEmail,
+    enterprise_model::Enterprise,
+    sales_model::LeadType,
+    school_model::School,
+};
+
+lazy_static! {
+    static ref TEMPLATE_VARIABLE: Regex =
+        Regex::new(r"\{\{\{?~?\s*(?:[#^]\s*(?:if|unless|each|with)\s+)?(\.\./)*([A-Za-z_][A-Za-z0-9_]*)")
+            .unwrap();
+}
+
+const RESERVED_WORDS: [&str; 6] = ["else", "this", "if", "unless", "each", "with"];
+
+pub fn get_sample_data(lead_type: &LeadType) -> Value {
+    match lead_type {
+        LeadType::ENTERPRISE => json!(Enterprise::sample()),
+        LeadType::SCHOOL => json!(School::sample()),
+        LeadType::CLINICAL => json!(Clinical::sample()),
+    }
+}
+
+pub fn get_template_variables(template: &str) -> Vec<String> {
+    let mut variables: Vec<String> = Vec::new();
+
+    for captures in TEMPLATE_VARIABLE.captures_iter(template) {
+        let variable = captures[2].to_string();
+
+        if !RESERVED_WORDS.contains(&variable.as_str()) && !variables.contains(&variable) {
+            variables.push(variable);
+        }
+    }
+
+    variables
+}
+
+pub fn get_template_variable_errors(
+    subject: &str,
+    html_body: &str,
+    text_body: &str,
+    lead_type: &LeadType,
+) -> Vec<String> {
+    let mut key_errors_vec: Vec<String> = Vec::new();
+    let sample_data = get_sample_data(lead_type);
+
+    let template_parts = [
+        ("subject", subject),
+        ("html_body", html_body),
+        ("text_body", text_body),
+    ];
+
+    for (part_name, part) in template_parts {
+        if let Err(e) = Handlebars::new().render_template(part, &sample_data) {
+            key_errors_vec.push(format!(" '{}' no es una plantilla válida: {}", part_name, e));
+            continue;
+        }
+
+        for variable in get_template_variables(part) {
+            if sample_data.get(&variable).is_none() {
+                key_errors_vec.push(format!(
+                    " '{}' usa la variable desconocida '{}' para {}",
+                    part_name, variable, lead_type
+                ));
+            }
+        }
+    }
+
+    key_errors_vec
+}
+
+pub fn render_email(
+    subject: &str,
+    html_body: &str,
+    text_body: &str,
+    data: &Value,
+) -> Result<RenderedEmail, RenderError> {
+    let mut handlebars = Handlebars::new();
+    handlebars.set_strict_mode(true);
+
+    let html_body = handlebars.render_template(html_body, data)?;
+
+    handlebars.register_escape_fn(handlebars::no_escape);
+    let subject = handlebars.render_template(subject, data)?;
+    let text_body = handlebars.render_template(text_body, data)?;
+
+    Ok(RenderedEmail {
+        subject,
+        html_body,
+        text_body,
+    })
+}
@@ -2,12 +2,16 @@
 use rand::{thread_rng, Rng};
 use uuid::Uuid;
 
-use crate::models::{
-    sales_model::{
-        SalesFunnel, SalesFunnelTag, SchoolLevel, SchoolLevelTag, ServicesOffered,
-        ServicesOfferedTag,
+use crate::{
+    models::{
+        pipeline_model::PipelineStage,
+        sales_model::{
+            LeadType, LeadTypeTag, SalesFunnelTag, SchoolLevel, SchoolLevelTag, ServicesOfferedTag,
+        },
+        service_model::Service,
+        users_model::{Roles, RolesTag},
     },
-    users_model::{Roles, RolesTag},
+    utils::pipeline_utils::get_stage_label,
 };
 
 handlebars_helper!(str_equal: |s1: String, s2: String| s1 == s2);
@@ -33,6 +37,11 @@ pub fn get_roles_tag() -> Vec<RolesTag> {
             text: Roles::EDITOR.to_string(),
             selected: false,
         },
+        RolesTag {
+            value: Roles::MANAGER,
+            text: Roles::MANAGER.to_string(),
+            selected: false,
+        },
         RolesTag {
             value: Roles::ADMIN,
             text: Roles::ADMIN.to_string(),
@@ -43,91 +52,43 @@ pub fn get_roles_tag() -> Vec<RolesTag> {
     roles_tag
 }
 
-pub fn get_options_and_services() -> (Vec<ServicesOfferedTag>, Vec<SalesFunnelTag>) {
-    let services_tag: Vec<ServicesOfferedTag> = vec![
-        ServicesOfferedTag {
-            value: ServicesOffered::BRANDING,
-            text: ServicesOffered::BRANDING.to_string(),
+pub fn get_services_tag(services: Vec<Service>) -> Vec<ServicesOfferedTag> {
+    services
+        .into_iter()
+        .map(|service| ServicesOfferedTag {
+            value: service.uuid.unwrap_or_default(),
+            text: service.name,
             selected: false,
-            icon: Some("fas fa-copyright".to_string()),
-        },
-        ServicesOfferedTag {
-            value: ServicesOffered::WEBSERVICES,
-            text: ServicesOffered::WEBSERVICES.to_string(),
-            selected: false,
-            icon: Some("fas fa-globe-americas".to_string()),
-        },
-        ServicesOfferedTag {
-            value: ServicesOffered::DIGITALSTRATEGY,
-            text: ServicesOffered::DIGITALSTRATEGY.to_string(),
-            selected: false,
-            icon: Some("fas fa-robot".to_string()),
-        },
-        ServicesOfferedTag {
-            value: ServicesOffered::ATTRACTIONOFNEWCLIENTS,
-            text: ServicesOffered::ATTRACTIONOFNEWCLIENTS.to_string(),
-            selected: false,
-            icon: Some("fas fa-magnet".to_string()),
-        },
-        ServicesOfferedTag {
-            value: ServicesOffered::SALESMANAGEMENT,
-            text: ServicesOffered::SALESMANAGEMENT.to_string(),
-            selected: false,
-            icon: Some("far fa-money-bill-alt".to_string()),
-        },
-    ];
+            icon: service.icon,
+            price: service.price,
+        })
+        .collect()
+}
 
-    let funnel_tag: Vec<SalesFunnelTag> = vec![
-        SalesFunnelTag {
-            value: SalesFunnel::LOST,
-            text: SalesFunnel::LOST.to_string(),
-            selected: false,
-            icon: Some("fas fa-trash".to_string()),
-            css: Some("is-lost".to_string()),
-        },
-        SalesFunnelTag {
-            value: SalesFunnel::PROSPECT,
-            text: SalesFunnel::PROSPECT.to_string(),
+pub fn get_funnel_tags(stages: Vec<PipelineStage>) -> Vec<SalesFunnelTag> {
+    stages
+        .into_iter()
+        .map(|stage| SalesFunnelTag {
+            text: get_stage_label(&stage),
+            value: stage.code,
             selected: false,
-            icon: Some("fas fa-binoculars".to_string()),
-            css: Some("is-prospect".to_string()),
-        },
-        SalesFunnelTag {
-            value: SalesFunnel::RESEARCH,
-            text: SalesFunnel::RESEARCH.to_string(),
-            selected: false,
-            icon: Some("fas fa-user-secret".to_owned()),
-            css: Some("is-research".to_string()),
-        },
-        SalesFunnelTag {
-            value: SalesFunnel::NEED,
-            text: SalesFunnel::NEED.to_string(),
-            selected: false,
-            icon: Some("fas fa-drumstick-bite".to_string()),
-            css: Some("has-background-primary-100 has-text-primary-100-invert".to_string()),
-        },
-        SalesFunnelTag {
-            value: SalesFunnel::QUOTE,
-            text: SalesFunnel::QUOTE.to_string(),
-            selected: false,
-            icon: Some("fas fa-money-check-alt".to_string()),
-            css: Some("is-quote".to_string()),
-        },
-        SalesFunnelTag {
-            value: SalesFunnel::NEGOTIATION,
-            text: SalesFunnel::NEGOTIATION.to_string(),
-            selected: false,
-            icon: Some("fas fa-people-arrows".to_string()),
-            css: Some("is-negotiation".to_string()),
-        },
-        SalesFunnelTag {
-            value: SalesFunnel::WIN,
-            text: SalesFunnel::WIN.to_string(),
-            selected: false,
-            icon: Some("fas fa-handshake".to_string()),
-            css: Some("is-win".to_string()),
-        },
-    ];
+            icon: stage.icon,
+            css: stage.css,
+            probability: stage.probability,
+        })
+        .collect()
+}
+
+pub fn get_options_and_services(
+    services: Vec<Service>,
+    stages: Vec<PipelineStage>,
+) -> (Vec<ServicesOfferedTag>, Vec<SalesFunnelTag>) {
+    let active_services: Vec<Service> = services
+        .into_iter()
+        .filter(|service| service.active)
+        .collect();
+    let services_tag = get_services_tag(active_services);
+    let funnel_tag = get_funnel_tags(stages);
 
     (services_tag, funnel_tag)
 }
@@ -173,14 +134,25 @@ pub fn get_school_level_tags() -> Vec<SchoolLevelTag> {
 }
 
 pub fn create_option_tags_info_for_services_and_funnel(
-    enterprise_services_offered: Vec<ServicesOffered>,
-    enterprise_sales_funnel: SalesFunnel,
+    services: Vec<Service>,
+    stages: Vec<PipelineStage>,
+    enterprise_services_offered: Vec<String>,
+    enterprise_sales_funnel: String,
 ) -> (Vec<ServicesOfferedTag>, Vec<SalesFunnelTag>) {
-    let (mut services_tag, mut funnel_tag) = get_options_and_services();
-
     let services_offered = enterprise_services_offered;
     let sales_funnel = enterprise_sales_funnel;
 
+    let available_services: Vec<Service> = services
+        .into_iter()
+        .filter(|service| match &service.uuid {
+            Some(uuid) => service.active || services_offered.contains(uuid),
+            None => false,
+        })
+        .collect();
+
+    let mut funnel_tag = get_funnel_tags(stages);
+    let mut services_tag = get_services_tag(available_services);
+
     for service in &mut services_tag {
         if services_offered.contains(&service.value) {
             service.selected = true;
@@ -208,6 +180,38 @@ pub fn create_role_tags_for_users(user_role: Roles) -> Vec<RolesTag> {
     roles_tag
 }
 
+pub fn get_lead_type_tags() -> Vec<LeadTypeTag> {
+    vec![
+        LeadTypeTag {
+            value: LeadType::ENTERPRISE,
+            text: LeadType::ENTERPRISE.to_string(),
+            selected: false,
+        },
+        LeadTypeTag {
+            value: LeadType::SCHOOL,
+            text: LeadType::SCHOOL.to_string(),
+            selected: false,
+        },
+        LeadTypeTag {
+            value: LeadType::CLINICAL,
+            text: LeadType::CLINICAL.to_string(),
+            selected: false,
+        },
+    ]
+}
+
+pub fn create_lead_type_tags(lead_type: LeadType) -> Vec<LeadTypeTag> {
+    let mut lead_type_tags = get_lead_type_tags();
+
+    for tag in &mut lead_type_tags {
+        if lead_type == tag.value {
+            tag.selected = true;
+        }
+    }
+
+    lead_type_tags
+}
+
 pub fn create_school_level_tags(school_level: Vec<SchoolLevel>) -> Vec<SchoolLevelTag> {
     let mut level_tag = get_school_level_tags();
 
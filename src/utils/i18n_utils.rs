@@ -0,0 +1,366 @@
+use std::{cmp::Ordering, collections::HashMap};
+
+use actix_web::{dev::Payload, http::header::ACCEPT_LANGUAGE, FromRequest, HttpRequest};
+use futures::future::LocalBoxFuture;
+use handlebars::{html_escape, Context, Handlebars, Helper, HelperResult, Output, RenderContext};
+use lazy_static::lazy_static;
+use log::warn;
+
+use crate::{
+    constants::connection::set_environment_variable,
+    models::locale_model::{Locale, LocaleTag},
+    utils::role_utils::find_request_user,
+};
+
+// (key, español, english)
+const MESSAGES: &[(&str, &str, &str)] = &[
+    // Enum labels
+    ("lead_type.ENTERPRISE", "Empresa", "Company"),
+    ("lead_type.SCHOOL", "Escuela", "School"),
+    ("lead_type.CLINICAL", "Clínica", "Clinic"),
+    ("school_level.NURSERY", "Guardería", "Nursery"),
+    ("school_level.KINDER", "Kinder", "Kindergarten"),
+    ("school_level.ELEMENTARY", "Primaria", "Elementary school"),
+    ("school_level.MIDDLESCHOOL", "Secundaria", "Middle school"),
+    ("school_level.HIGHSCHOOL", "Preparatoria", "High school"),
+    ("school_level.TECHNICALSCHOOL", "Escuela Técnica", "Technical school"),
+    ("school_level.UNIVERSITY", "Universidad", "University"),
+    ("lead_channel.REFERRAL", "Referido", "Referral"),
+    ("lead_channel.WEBFORM", "Formulario web", "Web form"),
+    ("lead_channel.COLDCALL", "Llamada en frío", "Cold call"),
+    ("lead_channel.EVENT", "Evento", "Event"),
+    ("lead_channel.IMPORT", "Importación", "Import"),
+    ("notification_kind.LeadAssigned", "Lead asignado", "Lead assigned"),
+    ("notification_kind.StageChanged", "Cambio de etapa", "Stage changed"),
+    ("meeting_kind.CALL", "Llamada", "Call"),
+    ("meeting_kind.MEETING", "Reunión", "Meeting"),
+    ("meeting_kind.VIDEO", "Videollamada", "Video call"),
+    ("role.ADMIN", "Administrador", "Administrator"),
+    ("role.EDITOR", "Editor", "Editor"),
+    ("role.MANAGER", "Gerente", "Manager"),
+    // Dates
+    ("month.1", "Enero", "January"),
+    ("month.2", "Febrero", "February"),
+    ("month.3", "Marzo", "March"),
+    ("month.4", "Abril", "April"),
+    ("month.5", "Mayo", "May"),
+    ("month.6", "Junio", "June"),
+    ("month.7", "Julio", "July"),
+    ("month.8", "Agosto", "August"),
+    ("month.9", "Septiembre", "September"),
+    ("month.10", "Octubre", "October"),
+    ("month.11", "Noviembre", "November"),
+    ("month.12", "Diciembre", "December"),
+    ("weekday.mon", "Lun", "Mon"),
+    ("weekday.tue", "Mar", "Tue"),
+    ("weekday.wed", "Mié", "Wed"),
+    ("weekday.thu", "Jue", "Thu"),
+    ("weekday.fri", "Vie", "Fri"),
+    ("weekday.sat", "Sáb", "Sat"),
+    ("weekday.sun", "Dom", "Sun"),
+    (
+        "date.long",
+        "{weekday}, {day} de {month} de {year}, {hour}:{minute}",
+        "{weekday}, {month} {day}, {year}, {hour}:{minute}",
+    ),
+    ("date.month", "{month} {year}", "{month} {year}"),
+    // Validation
+    (
+        "validation.invalid_format",
+        " '{field}' no cumple con el formato de {code}",
+        " '{field}' does not match the {code} format",
+    ),
+    (
+        "validation.name_length",
+        "El nombre debe tener entre 2 y 35 caracteres",
+        "Name does not match valid length (2-35 characters)",
+    ),
+    (
+        "validation.last_name_length",
+        "El apellido debe tener entre 2 y 35 caracteres",
+        "Last name does not match valid length (2-35 characters)",
+    ),
+    (
+        "validation.password_length",
+        "La contraseña debe tener entre 10 y 255 caracteres",
+        "Minimum size of password is 10 characters. Max is 255",
+    ),
+    (
+        "validation.timezone",
+        "La zona horaria no es válida",
+        "Unknown time zone",
+    ),
+    // Common
+    ("common.cancel", "Cancelar", "Cancel"),
+    ("common.delete", "Borrar", "Delete"),
+    ("common.notes", "Notas", "Notes"),
+    ("common.submit", "Guardar", "Save"),
+    // Leads
+    ("lead.name", "Nombre", "Name"),
+    ("lead.last_name", "Apellido", "Last name"),
+    ("lead.contact", "Contacto", "Contact"),
+    ("lead.contact_date", "Fecha de Contacto", "Contact date"),
+    ("lead.contact_details", "Datos de Contacto", "Contact details"),
+    ("lead.email", "Correo-e del Contacto", "Contact e-mail"),
+    ("lead.phone", "Teléfono de contacto", "Contact phone"),
+    ("lead.phone_title", "Teléfono", "Phone"),
+    ("lead.address", "Dirección", "Address"),
+    ("lead.tags", "Etiquetas", "Tags"),
+    ("lead.source", "Origen", "Source"),
+    ("lead.funnel", "Funnel", "Funnel"),
+    ("lead.stage", "Avance", "Stage"),
+    ("lead.offered", "Ofrecimos", "Offered"),
+    ("lead.services_offered", "Servicios Ofrecidos", "Services offered"),
+    ("lead.score", "Puntuación", "Score"),
+    ("lead.yes_no", "Sí / No", "Yes / No"),
+    ("lead.edit", "Editar", "Edit"),
+    ("lead.quote", "Cotizar", "Quote"),
+    ("lead.select_all", "Seleccionar todos", "Select all"),
+    ("lead.notes_placeholder", "Escribe tus notas", "Enter your message"),
+    ("lead.instagram_placeholder", "Instagram de la empresa", "Enter the company instagram"),
+    ("lead.facebook_placeholder", "Dirección de Facebook", "Enter the facebook address"),
+    ("lead.twitter_placeholder", "Twitter de la empresa", "Enter the company twitter"),
+    ("lead.tiktok_placeholder", "TikTok de la empresa", "Enter the company tiktok"),
+    (
+        "lead.linkedin_placeholder",
+        "Dirección o usuario de LinkedIn",
+        "Enter linkedIn address or handle",
+    ),
+    ("enterprise.is_company", "¿Es una empresa?", "Is it a company?"),
+    ("enterprise.company_name", "Nombre compañía", "Company name"),
+    ("enterprise.line_of_business", "Giro de la empresa", "Line of business"),
+    ("school.name", "Nombre de la Escuela", "School name"),
+    ("school.email", "Correo-e de la Escuela", "School e-mail"),
+    ("school.website", "Página Web de la Escuela", "School website"),
+    ("school.contact", "Contacto de la escuela", "School contact"),
+    ("school.contact_name", "Nombre del Contacto", "Contact name"),
+    ("school.levels", "Niveles Escolares", "School levels"),
+    ("clinical.new", "Médico", "Doctor"),
+    ("clinical.is_hospital", "¿Es Hospital o Clínica?", "Is it a hospital or clinic?"),
+    ("clinical.name", "Nombre de Clínica/Hospital", "Clinic/hospital name"),
+    ("clinical.specialty", "Especialidad", "Specialty"),
+    ("clinical.website", "Web de la Clínica", "Clinic website"),
+    // Users
+    ("user.name", "Nombre", "First name"),
+    ("user.last_name", "Apellido", "Last name"),
+    ("user.email", "e-mail", "E-mail"),
+    ("user.email_placeholder", "Correo electrónico del usuario", "Enter user's e-mail"),
+    ("user.password", "Contraseña", "Password"),
+    ("user.notes_placeholder", "Escriba notas para este usuario", "Write notes about this user"),
+    ("user.locale", "Idioma", "Language"),
+    ("user.locale_auto", "Automático (navegador)", "Automatic (browser)"),
+    ("user.timezone", "Zona horaria", "Time zone"),
+    ("user.timezone_default", "Predeterminada ({timezone})", "Default ({timezone})"),
+    // Sessions
+    ("session.login", "Iniciar sesión", "Sign in"),
+    ("session.logout", "Cerrar sesión", "Sign out"),
+    ("session.email", "Correo electrónico", "E-mail"),
+    ("session.password", "Contraseña", "Password"),
+    // Notifications
+    ("notification.title", "Notificaciones", "Notifications"),
+    ("notification.mark_read", "Marcar como leída", "Mark as read"),
+    ("notification.mark_all_read", "Marcar todas como leídas", "Mark all as read"),
+    ("notification.empty", "Sin notificaciones", "No notifications"),
+    ("notification.event", "Evento", "Event"),
+    ("notification.in_app", "En la aplicación", "In the app"),
+    ("notification.email", "Por correo", "By e-mail"),
+    ("notification.save", "Guardar preferencias", "Save preferences"),
+    // Meetings
+    ("meeting.title", "Reuniones", "Meetings"),
+    ("meeting.open_calendar", "Ver calendario", "Open calendar"),
+    ("meeting.title_placeholder", "Presentación de propuesta", "Proposal presentation"),
+    ("meeting.start", "Inicio", "Start"),
+    ("meeting.end", "Fin", "End"),
+    ("meeting.location", "Lugar o enlace", "Place or link"),
+    ("meeting.attendees", "Participantes", "Attendees"),
+    ("meeting.invite_lead", "Enviar invitación al lead", "Send an invitation to the lead"),
+    ("meeting.schedule", "Agendar", "Schedule"),
+    ("meeting.date", "Fecha", "Date"),
+    ("meeting.kind", "Tipo", "Type"),
+    ("meeting.name", "Título", "Title"),
+    ("meeting.delete_confirm", "¿Borrar la reunión {title}?", "Delete the meeting {title}?"),
+    ("meeting.empty", "Sin reuniones agendadas.", "No meetings scheduled."),
+    ("calendar.previous", "Anterior", "Previous"),
+    ("calendar.today", "Hoy", "Today"),
+    ("calendar.next", "Siguiente", "Next"),
+    ("calendar.month", "Mes", "Month"),
+    ("calendar.week", "Semana", "Week"),
+    ("calendar.week_title", "Semana del {from} al {to}", "Week of {from} to {to}"),
+    (
+        "calendar.login_required",
+        "Inicia sesión para ver tu calendario",
+        "Sign in to see your calendar",
+    ),
+    (
+        "feed.title",
+        "Suscribirse desde Google Calendar u Outlook",
+        "Subscribe from Google Calendar or Outlook",
+    ),
+    (
+        "feed.help",
+        "Agrega esta dirección como calendario por URL. Es secreta: quien la tenga puede ver tus reuniones.",
+        "Add this address as a calendar from URL. Keep it secret: anyone who has it can see your meetings.",
+    ),
+    ("feed.copy", "Copiar", "Copy"),
+    ("feed.regenerate", "Generar nueva", "Generate a new one"),
+    (
+        "feed.regenerate_confirm",
+        "La dirección actual dejará de funcionar en los calendarios suscritos. ¿Generar una nueva?",
+        "The current address will stop working in subscribed calendars. Generate a new one?",
+    ),
+    (
+        "feed.unavailable",
+        "No se pudo generar la dirección del calendario.",
+        "The calendar address could not be generated.",
+    ),
+];
+
+lazy_static! {
+    static ref CATALOG: HashMap<&'static str, (&'static str, &'static str)> = MESSAGES
+        .iter()
+        .map(|(key, es, en)| (*key, (*es, *en)))
+        .collect();
+    static ref DEFAULT_LOCALE: Locale = {
+        let code = set_environment_variable("DEFAULT_LOCALE", "es");
+        Locale::from_code(&code).unwrap_or_else(|| {
+            warn!("Invalid DEFAULT_LOCALE {}, using es", code);
+            Locale::ES
+        })
+    };
+}
+
+pub const LOCALES: [Locale; 2] = [Locale::ES, Locale::EN];
+
+pub fn get_default_locale() -> Locale {
+    DEFAULT_LOCALE.clone()
+}
+
+pub fn has_translation(key: &str) -> bool {
+    CATALOG.contains_key(key)
+}
+
+pub fn translate(locale: &Locale, key: &str) -> String {
+    match CATALOG.get(key) {
+        Some((_, en)) if *locale == Locale::EN && !en.is_empty() => en.to_string(),
+        Some((es, _)) => es.to_string(),
+        None => {
+            warn!("Missing translation for {}", key);
+            key.to_string()
+        }
+    }
+}
+
+pub fn translate_with(locale: &Locale, key: &str, values: &[(&str, &str)]) -> String {
+    values
+        .iter()
+        .fold(translate(locale, key), |message, (name, value)| {
+            message.replace(&format!("{{{}}}", name), value)
+        })
+}
+
+// Enums shown to people implement this instead of hard-coding a language in Display.
+pub trait Translate {
+    fn message_key(&self) -> String;
+
+    fn label(&self, locale: &Locale) -> String {
+        translate(locale, &self.message_key())
+    }
+}
+
+pub fn create_locale_tags(locale: Option<Locale>) -> Vec<LocaleTag> {
+    LOCALES
+        .iter()
+        .map(|value| LocaleTag {
+            value: value.clone(),
+            text: value.to_string(),
+            selected: locale.as_ref() == Some(value),
+        })
+        .collect()
+}
+
+// "en-US,en;q=0.9,es;q=0.8" -> the supported language with the highest weight.
+pub fn parse_accept_language(header: &str) -> Option<Locale> {
+    let mut languages: Vec<(f32, Locale)> = header
+        .split(',')
+        .filter_map(|language| {
+            let mut parts = language.trim().split(';');
+            let tag = parts.next()?.trim();
+            let weight = parts
+                .find_map(|part| part.trim().strip_prefix("q="))
+                .and_then(|weight| weight.parse::<f32>().ok())
+                .unwrap_or(1.0);
+            let locale = Locale::from_code(tag.split('-').next()?)?;
+            Some((weight, locale))
+        })
+        .filter(|(weight, _)| *weight > 0.0)
+        .collect();
+
+    languages.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(Ordering::Equal));
+    languages.into_iter().next().map(|(_, locale)| locale)
+}
+
+// The acting user's preference wins, then the browser, then DEFAULT_LOCALE.
+pub async fn resolve_locale(req: &HttpRequest) -> Locale {
+    if let Some(locale) = find_request_user(req).await.and_then(|user| user.locale) {
+        return locale;
+    }
+
+    req.headers()
+        .get(ACCEPT_LANGUAGE)
+        .and_then(|header| header.to_str().ok())
+        .and_then(parse_accept_language)
+        .unwrap_or_else(get_default_locale)
+}
+
+#[derive(Debug)]
+pub struct RequestLocale(pub Locale);
+
+impl FromRequest for RequestLocale {
+    type Error = actix_web::Error;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        let req = req.clone();
+        Box::pin(async move { Ok(RequestLocale(resolve_locale(&req).await)) })
+    }
+}
+
+// {{t "meeting.delete_confirm" title=m.title}} using the "locale" of the template data.
+fn translate_helper<'reg, 'rc>(
+    h: &Helper<'rc>,
+    _: &'reg Handlebars<'reg>,
+    ctx: &'rc Context,
+    _: &mut RenderContext<'reg, 'rc>,
+    out: &mut dyn Output,
+) -> HelperResult {
+    let key = h
+        .param(0)
+        .and_then(|param| param.value().as_str())
+        .unwrap_or_default();
+    let locale = ctx
+        .data()
+        .get("locale")
+        .and_then(|locale| locale.as_str())
+        .and_then(Locale::from_code)
+        .unwrap_or_else(get_default_locale);
+
+    let values: Vec<(&str, String)> = h
+        .hash()
+        .iter()
+        .map(|(name, value)| match value.value().as_str() {
+            Some(text) => (*name, text.to_string()),
+            None => (*name, value.value().to_string()),
+        })
+        .collect();
+    let values: Vec<(&str, &str)> = values
+        .iter()
+        .map(|(name, value)| (*name, value.as_str()))
+        .collect();
+
+    out.write(&html_escape(&translate_with(&locale, key, &values)))?;
+    Ok(())
+}
+
+pub fn register_i18n_helpers(handlebars: &mut Handlebars) {
+    handlebars.register_helper("t", Box::new(translate_helper));
+}
@@ -0,0 +1,77 @@
+use crate::{
+    models::{
+        lead_model::{KanbanCard, KanbanColumn, LeadSummary},
+        pipeline_model::PipelineStage,
+        quote_model::Quote,
+        service_model::Service,
+    },
+    utils::{
+        general_utils::{create_option_tags_info_for_services_and_funnel, get_funnel_tags},
+        quote_utils::round_currency,
+        service_utils::get_service_price,
+    },
+};
+
+pub fn get_lead_value(lead: &LeadSummary, services: &[Service], quotes: &[Quote]) -> f64 {
+    let latest_quote = quotes
+        .iter()
+        .filter(|quote| quote.lead_uuid == lead.uuid)
+        .max_by_key(|quote| quote.date_created);
+
+    match latest_quote.and_then(|quote| quote.total) {
+        Some(total) => total,
+        None => lead
+            .services_offered
+            .iter()
+            .map(|service_uuid| get_service_price(services, service_uuid))
+            .sum(),
+    }
+}
+
+pub fn build_kanban_board(
+    stages: Vec<PipelineStage>,
+    leads: Vec<LeadSummary>,
+    services: Vec<Service>,
+    quotes: Vec<Quote>,
+) -> Vec<KanbanColumn> {
+    let mut columns: Vec<KanbanColumn> = get_funnel_tags(stages)
+        .into_iter()
+        .map(|stage| KanbanColumn {
+            stage,
+            cards: vec![],
+            count: 0,
+            total: 0.0,
+        })
+        .collect();
+
+    for lead in leads {
+        let value = get_lead_value(&lead, &services, &quotes);
+        let (services_tag, _) = create_option_tags_info_for_services_and_funnel(
+            services.clone(),
+            vec![],
+            lead.services_offered.clone(),
+            lead.sales_funnel.clone(),
+        );
+
+        if let Some(column) = columns
+            .iter_mut()
+            .find(|column| column.stage.value == lead.sales_funnel)
+        {
+            column.cards.push(KanbanCard {
+                lead,
+                services_tag: services_tag
+                    .into_iter()
+                    .filter(|service| service.selected)
+                    .collect(),
+                value: round_currency(value),
+            });
+        }
+    }
+
+    for column in &mut columns {
+        column.count = column.cards.len();
+        column.total = round_currency(column.cards.iter().map(|card| card.value).sum());
+    }
+
+    columns
+}
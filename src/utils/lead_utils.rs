@@ -0,0 +1,255 @@
+use actix_web::web::Data;
+use chrono::Utc;
+use log::error;
+
+use crate::{
+    db::{
+        clinical_db::{ClinicalDB, CLINICAL_TABLE},
+        config::Database,
+        enterprise_db::{EnterpriseDB, ENTERPRISE_TABLE},
+        school_db::{SchoolDB, SCHOOL_TABLE},
+        stage_transition_db::StageTransitionDB,
+    },
+    models::{
+        clinical_model::Clinical,
+        enterprise_model::Enterprise,
+        lead_model::{LeadFilter, LeadSummary, StageTransition},
+        sales_model::LeadType,
+        school_model::School,
+    },
+    utils::{
+        crud::util_patch_one, general_utils::get_uuid, notification_utils::notify_lead_changes,
+        scoring_utils::rescore_lead, territory_utils::scope_leads,
+    },
+};
+
+pub fn get_lead_table(lead_type: &LeadType) -> &'static str {
+    match lead_type {
+        LeadType::ENTERPRISE => ENTERPRISE_TABLE,
+        LeadType::SCHOOL => SCHOOL_TABLE,
+        LeadType::CLINICAL => CLINICAL_TABLE,
+    }
+}
+
+pub fn get_lead_table_path(lead_type: &LeadType) -> &'static str {
+    match lead_type {
+        LeadType::ENTERPRISE => "/htmx/enterprise/table",
+        LeadType::SCHOOL => "/htmx/schools/table",
+        LeadType::CLINICAL => "/htmx/clinics/table",
+    }
+}
+
+pub fn get_lead_edit_path(lead_type: &LeadType) -> &'static str {
+    match lead_type {
+        LeadType::ENTERPRISE => "/htmx/enterprise/edit",
+        LeadType::SCHOOL => "/htmx/schools/edit",
+        LeadType::CLINICAL => "/clinics/edit",
+    }
+}
+
+pub fn enterprise_summary(enterprise: Enterprise) -> LeadSummary {
+    LeadSummary {
+        uuid: enterprise.uuid.unwrap_or_default(),
+        lead_type: LeadType::ENTERPRISE,
+        name: format!("{} {}", enterprise.name, enterprise.last_name),
+        company: enterprise.company_name,
+        email: Some(enterprise.email),
+        phone: enterprise.phone,
+        sales_funnel: enterprise.sales_funnel,
+        services_offered: enterprise.services_offered,
+        tags: enterprise.tags,
+        assigned_to: enterprise.assigned_to,
+        score: enterprise.score,
+        account_uuid: enterprise.account_uuid,
+        address: enterprise.address,
+        source: enterprise.source,
+    }
+}
+
+pub fn school_summary(school: School) -> LeadSummary {
+    LeadSummary {
+        uuid: school.uuid.unwrap_or_default(),
+        lead_type: LeadType::SCHOOL,
+        name: format!("{} {}", school.name, school.last_name),
+        company: Some(school.school_name),
+        email: Some(school.email),
+        phone: Some(school.phone),
+        sales_funnel: school.sales_funnel,
+        services_offered: school.services_offered,
+        tags: school.tags,
+        assigned_to: school.assigned_to,
+        score: school.score,
+        account_uuid: school.account_uuid,
+        address: school.address,
+        source: school.source,
+    }
+}
+
+pub fn clinical_summary(clinical: Clinical) -> LeadSummary {
+    LeadSummary {
+        uuid: clinical.uuid.unwrap_or_default(),
+        lead_type: LeadType::CLINICAL,
+        name: format!("{} {}", clinical.name, clinical.last_name),
+        company: clinical.clinic_name,
+        email: clinical.email,
+        phone: Some(clinical.phone),
+        sales_funnel: clinical.sales_funnel,
+        services_offered: clinical.services_offered,
+        tags: clinical.tags,
+        assigned_to: clinical.assigned_to,
+        score: clinical.score,
+        account_uuid: clinical.account_uuid,
+        address: clinical.address,
+        source: clinical.source,
+    }
+}
+
+pub async fn find_lead_summary(
+    db: &Data<Database>,
+    lead_type: &LeadType,
+    uuid: String,
+) -> Option<LeadSummary> {
+    match lead_type {
+        LeadType::ENTERPRISE => <Database as EnterpriseDB>::find_one(db, uuid)
+            .await
+            .map(enterprise_summary),
+        LeadType::SCHOOL => <Database as SchoolDB>::find_one(db, uuid)
+            .await
+            .map(school_summary),
+        LeadType::CLINICAL => <Database as ClinicalDB>::find_one(db, uuid)
+            .await
+            .map(clinical_summary),
+    }
+}
+
+pub async fn set_lead_sales_funnel(
+    db: &Data<Database>,
+    lead_type: &LeadType,
+    uuid: String,
+    sales_funnel: String,
+) -> Option<LeadSummary> {
+    let table_name = get_lead_table(lead_type);
+
+    match lead_type {
+        LeadType::ENTERPRISE => util_patch_one::<Enterprise, String>(
+            db,
+            uuid,
+            table_name,
+            "/sales_funnel",
+            sales_funnel,
+        )
+        .await
+        .map(enterprise_summary),
+        LeadType::SCHOOL => {
+            util_patch_one::<School, String>(db, uuid, table_name, "/sales_funnel", sales_funnel)
+                .await
+                .map(school_summary)
+        }
+        LeadType::CLINICAL => {
+            util_patch_one::<Clinical, String>(db, uuid, table_name, "/sales_funnel", sales_funnel)
+                .await
+                .map(clinical_summary)
+        }
+    }
+}
+
+pub async fn find_lead_summaries(
+    db: &Data<Database>,
+    lead_type: &LeadType,
+    user_uuid: Option<String>,
+) -> Vec<LeadSummary> {
+    let leads = match lead_type {
+        LeadType::ENTERPRISE => <Database as EnterpriseDB>::find_all_active(db)
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .map(enterprise_summary)
+            .collect(),
+        LeadType::SCHOOL => <Database as SchoolDB>::find_all_active(db)
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .map(school_summary)
+            .collect(),
+        LeadType::CLINICAL => <Database as ClinicalDB>::find_all_non_deleted(db)
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .map(clinical_summary)
+            .collect(),
+    };
+
+    scope_leads(db, user_uuid, leads, |lead| &lead.address).await
+}
+
+pub async fn find_filtered_summaries(
+    db: &Data<Database>,
+    lead_type: &LeadType,
+    filter: &LeadFilter,
+    user_uuid: Option<String>,
+) -> Vec<LeadSummary> {
+    let leads = match lead_type {
+        LeadType::ENTERPRISE => <Database as EnterpriseDB>::find_filtered(db, filter)
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .map(enterprise_summary)
+            .collect(),
+        LeadType::SCHOOL => <Database as SchoolDB>::find_filtered(db, filter)
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .map(school_summary)
+            .collect(),
+        LeadType::CLINICAL => <Database as ClinicalDB>::find_filtered(db, filter)
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .map(clinical_summary)
+            .collect(),
+    };
+
+    scope_leads(db, user_uuid, leads, |lead| &lead.address).await
+}
+
+pub async fn move_lead_to_stage(
+    db: &Data<Database>,
+    lead_type: &LeadType,
+    uuid: String,
+    to_stage: String,
+    changed_by: Option<String>,
+) -> Option<LeadSummary> {
+    let lead = find_lead_summary(db, lead_type, uuid.clone()).await?;
+
+    if lead.sales_funnel == to_stage {
+        return Some(lead);
+    }
+
+    let moved_lead = set_lead_sales_funnel(db, lead_type, uuid.clone(), to_stage.clone()).await?;
+
+    let transition = StageTransition {
+        uuid: None,
+        lead_type: lead_type.clone(),
+        lead_uuid: uuid,
+        from_stage: lead.sales_funnel.clone(),
+        to_stage,
+        changed_by: changed_by.clone(),
+        date_created: Some(Utc::now()),
+    };
+
+    if <Database as StageTransitionDB>::add_one(db, StageTransition::new(get_uuid(), transition))
+        .await
+        .is_none()
+    {
+        error!("Unable to record stage transition for {}", &moved_lead.uuid);
+    }
+
+    notify_lead_changes(db, Some(&lead), &moved_lead, changed_by).await;
+
+    let score = rescore_lead(db, lead_type, moved_lead.uuid.clone()).await;
+
+    Some(LeadSummary {
+        score: score.unwrap_or(moved_lead.score),
+        ..moved_lead
+    })
+}
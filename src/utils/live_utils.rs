@@ -0,0 +1,112 @@
+use std::time::Duration;
+
+use actix_web::{
+    rt::{
+        spawn,
+        time::{sleep, timeout},
+    },
+    web::{Bytes, Data},
+};
+use futures::{stream, Stream, StreamExt};
+use lazy_static::lazy_static;
+use log::{error, info, warn};
+use surrealdb::Action;
+use tokio::sync::broadcast::{self, error::RecvError, Sender};
+
+use crate::{
+    db::config::Database,
+    models::{
+        live_model::{LiveAction, LiveEvent, LiveRecord},
+        sales_model::LeadType,
+    },
+    utils::{crud::util_live_table, lead_utils::get_lead_table},
+};
+
+pub const LIVE_EVENT_CAPACITY: usize = 256;
+pub const LIVE_KEEP_ALIVE_SECONDS: u64 = 15;
+pub const LIVE_RETRY_SECONDS: u64 = 5;
+
+lazy_static! {
+    static ref LIVE_EVENTS: Sender<LiveEvent> = broadcast::channel(LIVE_EVENT_CAPACITY).0;
+}
+
+pub fn publish_live_event(event: LiveEvent) {
+    // Sending only fails when no browser is connected, which is fine.
+    let _ = LIVE_EVENTS.send(event);
+}
+
+fn to_live_action(action: Action) -> Option<LiveAction> {
+    match action {
+        Action::Create => Some(LiveAction::CREATE),
+        Action::Update => Some(LiveAction::UPDATE),
+        Action::Delete => Some(LiveAction::DELETE),
+        _ => None,
+    }
+}
+
+async fn watch_lead_table(db: Data<Database>, lead_type: LeadType) {
+    let table_name = get_lead_table(&lead_type);
+
+    loop {
+        if let Some(mut notifications) = util_live_table::<LiveRecord>(&db, table_name).await {
+            info!("Watching {} for live updates", table_name);
+
+            while let Some(notification) = notifications.next().await {
+                match notification {
+                    Ok(notification) => {
+                        if let Some(action) = to_live_action(notification.action) {
+                            publish_live_event(LiveEvent {
+                                lead_type: lead_type.clone(),
+                                action,
+                                uuid: notification.data.uuid,
+                            });
+                        }
+                    }
+                    Err(e) => error!("Error {}.live notification:: {:?}", table_name, e),
+                }
+            }
+            warn!("Live query on {} ended", table_name);
+        }
+
+        sleep(Duration::from_secs(LIVE_RETRY_SECONDS)).await;
+    }
+}
+
+pub fn watch_lead_tables(db: Data<Database>) {
+    for lead_type in [LeadType::ENTERPRISE, LeadType::SCHOOL, LeadType::CLINICAL] {
+        spawn(watch_lead_table(db.clone(), lead_type));
+    }
+}
+
+fn format_live_event(event: &LiveEvent) -> String {
+    match serde_json::to_string(event) {
+        Ok(data) => format!("event: lead_changed\ndata: {}\n\n", data),
+        Err(e) => {
+            error!("Couldn't serialize live event:: {}", e);
+            String::new()
+        }
+    }
+}
+
+// Comments keep proxies from closing an idle connection, and a browser that
+// fell behind is told to resync instead of replaying the missed events.
+pub fn live_event_stream() -> impl Stream<Item = Result<Bytes, actix_web::Error>> {
+    stream::unfold(LIVE_EVENTS.subscribe(), |mut receiver| async move {
+        let frame = match timeout(
+            Duration::from_secs(LIVE_KEEP_ALIVE_SECONDS),
+            receiver.recv(),
+        )
+        .await
+        {
+            Ok(Ok(event)) => format_live_event(&event),
+            Ok(Err(RecvError::Lagged(skipped))) => {
+                warn!("Live events client skipped {} events", skipped);
+                "event: resync\ndata: {}\n\n".to_string()
+            }
+            Ok(Err(RecvError::Closed)) => return None,
+            Err(_) => ": keep-alive\n\n".to_string(),
+        };
+
+        Some((Ok(Bytes::from(frame)), receiver))
+    })
+}
@@ -0,0 +1,112 @@
+use lettre::{
+    message::{header::ContentType, Attachment, MultiPart, SinglePart},
+    transport::smtp::authentication::Credentials,
+    AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor,
+};
+use log::{error, info, warn};
+
+use crate::{
+    constants::connection::set_environment_variable, models::email_template_model::RenderedEmail,
+};
+
+const DEFAULT_SMTP_PORT: u16 = 587;
+
+pub fn is_mail_enabled() -> bool {
+    !set_environment_variable("SMTP_HOST", "").is_empty()
+}
+
+fn get_smtp_port() -> u16 {
+    match set_environment_variable("SMTP_PORT", &DEFAULT_SMTP_PORT.to_string()).parse::<u16>() {
+        Ok(port) => port,
+        Err(e) => {
+            warn!("Invalid SMTP_PORT: {}", e);
+            DEFAULT_SMTP_PORT
+        }
+    }
+}
+
+pub async fn send_email(to: &str, email: &RenderedEmail) -> bool {
+    let body = MultiPart::alternative_plain_html(email.text_body.clone(), email.html_body.clone());
+    deliver_email(to, &email.subject, body).await
+}
+
+// Calendar clients offer to add the event when the invite comes both as an
+// inline text/calendar part and as an .ics attachment.
+pub async fn send_calendar_invite(to: &str, email: &RenderedEmail, ics: &str) -> bool {
+    let calendar_type = match ContentType::parse("text/calendar; charset=UTF-8; method=REQUEST") {
+        Ok(content_type) => content_type,
+        Err(e) => {
+            error!("Invalid calendar content type:: {}", e);
+            return false;
+        }
+    };
+
+    let body = MultiPart::mixed()
+        .multipart(
+            MultiPart::alternative()
+                .singlepart(SinglePart::plain(email.text_body.clone()))
+                .singlepart(SinglePart::html(email.html_body.clone()))
+                .singlepart(
+                    SinglePart::builder()
+                        .header(calendar_type.clone())
+                        .body(ics.to_string()),
+                ),
+        )
+        .singlepart(Attachment::new("invite.ics".to_string()).body(ics.to_string(), calendar_type));
+
+    deliver_email(to, &email.subject, body).await
+}
+
+async fn deliver_email(to: &str, subject: &str, body: MultiPart) -> bool {
+    let smtp_host = set_environment_variable("SMTP_HOST", "");
+    if smtp_host.is_empty() {
+        warn!("SMTP_HOST is not set, email to {} not sent", to);
+        return false;
+    }
+
+    let from = set_environment_variable("SMTP_FROM", "YAY LEADS CRM <no-reply@yayleads.mx>");
+    let message = match (from.parse(), to.parse()) {
+        (Ok(from), Ok(to)) => Message::builder()
+            .from(from)
+            .to(to)
+            .subject(subject)
+            .multipart(body),
+        _ => {
+            error!("Invalid email address, from: {} to: {}", &from, to);
+            return false;
+        }
+    };
+
+    let message = match message {
+        Ok(message) => message,
+        Err(e) => {
+            error!("Couldn't build email to {}:: {}", to, e);
+            return false;
+        }
+    };
+
+    let mailer = match AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&smtp_host) {
+        Ok(builder) => builder
+            .port(get_smtp_port())
+            .credentials(Credentials::new(
+                set_environment_variable("SMTP_USER", ""),
+                set_environment_variable("SMTP_PASSWORD", ""),
+            ))
+            .build(),
+        Err(e) => {
+            error!("Couldn't connect to SMTP_HOST {}:: {}", &smtp_host, e);
+            return false;
+        }
+    };
+
+    match mailer.send(message).await {
+        Ok(_) => {
+            info!("Email sent to {}", to);
+            true
+        }
+        Err(e) => {
+            error!("Couldn't send email to {}:: {}", to, e);
+            false
+        }
+    }
+}
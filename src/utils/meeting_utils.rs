@@ -0,0 +1,454 @@
+use actix_web::{rt::spawn, web::Data};
+use chrono::{DateTime, Datelike, Duration, Months, NaiveDate, NaiveTime, Utc};
+use chrono_tz::Tz;
+use handlebars::html_escape;
+use log::info;
+use rand::{distributions::Alphanumeric, thread_rng, Rng};
+
+use crate::{
+    db::{config::Database, meeting_db::MeetingDB, users_db::UsersDB},
+    models::{
+        email_template_model::RenderedEmail,
+        lead_model::LeadSummary,
+        locale_model::Locale,
+        meeting_model::{CalendarFeed, CalendarView, Meeting, MeetingKind, MeetingKindTag},
+        users_model::User,
+    },
+    utils::{
+        api_utils::API_V1_PREFIX,
+        env::{set_env_vars, ConfVars},
+        i18n_utils::Translate,
+        lead_utils::find_lead_summary,
+        mail_utils::{is_mail_enabled, send_calendar_invite},
+        time::{format_date_in_language, get_default_timezone, get_user_timezone, local_to_utc},
+    },
+};
+
+pub const MEETING_KINDS: [MeetingKind; 3] =
+    [MeetingKind::CALL, MeetingKind::MEETING, MeetingKind::VIDEO];
+pub const CALENDAR_FEED_SECRET_LENGTH: usize = 40;
+const CALENDAR_FEED_PAST_DAYS: i64 = 90;
+const ICS_PRODID: &str = "-//YAY LEADS//CRM//ES";
+const ICS_LINE_OCTETS: usize = 75;
+
+pub fn create_meeting_kind_tags(
+    kind: Option<&MeetingKind>,
+    locale: &Locale,
+) -> Vec<MeetingKindTag> {
+    MEETING_KINDS
+        .into_iter()
+        .map(|value| MeetingKindTag {
+            text: value.label(locale),
+            selected: kind == Some(&value),
+            value,
+        })
+        .collect()
+}
+
+pub fn normalize_meeting(meeting: Meeting) -> Meeting {
+    let mut attendees: Vec<String> = meeting
+        .attendees
+        .iter()
+        .map(|attendee| attendee.trim().to_string())
+        .filter(|attendee| !attendee.is_empty() && Some(attendee) != meeting.organizer.as_ref())
+        .collect();
+    attendees.sort();
+    attendees.dedup();
+
+    Meeting {
+        title: meeting.title.trim().to_string(),
+        location: meeting.location.trim().to_string(),
+        notes: meeting.notes.trim().to_string(),
+        attendees,
+        ..meeting
+    }
+}
+
+pub fn get_meeting_errors(meeting: &Meeting, users: &[User]) -> Vec<String> {
+    let mut key_errors_vec: Vec<String> = vec![];
+
+    if meeting.end_date <= meeting.start_date {
+        key_errors_vec.push("La reunión termina antes de empezar".to_string());
+    }
+
+    let participants = meeting.organizer.iter().chain(meeting.attendees.iter());
+    for participant in participants {
+        if !users.iter().any(|user| &user.uuid == participant) {
+            key_errors_vec.push(format!("El usuario '{}' no existe", participant));
+        }
+    }
+
+    key_errors_vec
+}
+
+pub fn is_participant(meeting: &Meeting, user_uuid: &str) -> bool {
+    meeting.organizer.as_deref() == Some(user_uuid)
+        || meeting
+            .attendees
+            .iter()
+            .any(|attendee| attendee == user_uuid)
+}
+
+pub fn is_in_range(meeting: &Meeting, from: &DateTime<Utc>, to: &DateTime<Utc>) -> bool {
+    meeting.start_date < *to && meeting.end_date > *from
+}
+
+// Months start on the monday of their first week and end on the sunday of
+// their last one, so the grid is always made of whole weeks.
+pub fn get_calendar_range(view: &CalendarView, date: NaiveDate) -> (NaiveDate, NaiveDate) {
+    match view {
+        CalendarView::MONTH => {
+            let first_day = date.with_day(1).unwrap_or(date);
+            let last_day = first_day
+                .checked_add_months(Months::new(1))
+                .map(|next_month| next_month - Duration::days(1))
+                .unwrap_or(first_day);
+            (
+                first_day - Duration::days(first_day.weekday().num_days_from_monday() as i64),
+                last_day + Duration::days(6 - last_day.weekday().num_days_from_monday() as i64),
+            )
+        }
+        CalendarView::WEEK => {
+            let monday = date - Duration::days(date.weekday().num_days_from_monday() as i64);
+            (monday, monday + Duration::days(6))
+        }
+    }
+}
+
+pub fn shift_calendar_date(view: &CalendarView, date: NaiveDate, forward: bool) -> NaiveDate {
+    match (view, forward) {
+        (CalendarView::MONTH, true) => date.with_day(1).unwrap_or(date) + Months::new(1),
+        (CalendarView::MONTH, false) => date.with_day(1).unwrap_or(date) - Months::new(1),
+        (CalendarView::WEEK, true) => date + Duration::days(7),
+        (CalendarView::WEEK, false) => date - Duration::days(7),
+    }
+}
+
+pub fn get_local_datetime(date: NaiveDate, timezone: &Tz) -> DateTime<Utc> {
+    local_to_utc(date.and_time(NaiveTime::MIN), timezone)
+}
+
+pub fn get_user_name(users: &[User], user_uuid: &str) -> String {
+    match users.iter().find(|user| user.uuid == user_uuid) {
+        Some(user) => format!("{} {}", user.name, user.last_name),
+        None => user_uuid.to_string(),
+    }
+}
+
+pub fn generate_feed_secret() -> String {
+    thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(CALENDAR_FEED_SECRET_LENGTH)
+        .map(char::from)
+        .collect()
+}
+
+// The first request creates the feed, after that the url only changes when
+// the user asks for a new one, which breaks every subscription to the old one.
+pub async fn get_calendar_feed(
+    db: &Data<Database>,
+    user_uuid: String,
+    regenerate: bool,
+) -> Option<CalendarFeed> {
+    if !regenerate {
+        if let Some(feed) = <Database as MeetingDB>::find_feed(db, user_uuid.clone()).await {
+            return Some(feed);
+        }
+    }
+
+    let feed = CalendarFeed {
+        uuid: Some(user_uuid),
+        secret: generate_feed_secret(),
+        date_created: Some(Utc::now()),
+    };
+    <Database as MeetingDB>::update_feed(db, feed).await
+}
+
+pub fn get_calendar_feed_url(user_uuid: &str, secret: &str) -> String {
+    let cf: ConfVars = set_env_vars();
+    format!(
+        "{}://{}{}{}/calendar/{}/{}.ics",
+        cf.server_protocol,
+        cf.hbs_target_address,
+        cf.hbs_target_port,
+        API_V1_PREFIX,
+        user_uuid,
+        secret
+    )
+}
+
+fn format_ics_date(date: &DateTime<Utc>) -> String {
+    date.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+fn escape_ics_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+// Parameter values can't be escaped, only quoted.
+fn quote_ics_param(text: &str) -> String {
+    format!("\"{}\"", text.replace(['"', '\r', '\n'], ""))
+}
+
+// RFC 5545 content lines are at most 75 octets, longer ones go on in the next
+// line after a leading space.
+fn fold_ics_line(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len());
+    let mut octets = 0;
+
+    for c in line.chars() {
+        if octets + c.len_utf8() > ICS_LINE_OCTETS {
+            folded.push_str("\r\n ");
+            octets = 1;
+        }
+        folded.push(c);
+        octets += c.len_utf8();
+    }
+
+    folded
+}
+
+fn ics_user(property: &str, user: &User, is_attendee: bool) -> String {
+    let attendee_params = match is_attendee {
+        true => ";ROLE=REQ-PARTICIPANT;PARTSTAT=NEEDS-ACTION;RSVP=TRUE",
+        false => "",
+    };
+    format!(
+        "{};CN={}{}:mailto:{}",
+        property,
+        quote_ics_param(&format!("{} {}", user.name, user.last_name)),
+        attendee_params,
+        user.email
+    )
+}
+
+fn get_lead_name(lead: &LeadSummary) -> String {
+    match &lead.company {
+        Some(company) if !company.trim().is_empty() => company.clone(),
+        _ => lead.name.clone(),
+    }
+}
+
+fn get_invited_lead_email(meeting: &Meeting, lead: Option<&LeadSummary>) -> Option<String> {
+    match meeting.invite_lead {
+        true => lead
+            .and_then(|lead| lead.email.clone())
+            .filter(|email| !email.trim().is_empty()),
+        false => None,
+    }
+}
+
+pub fn build_ics_event(
+    meeting: &Meeting,
+    users: &[User],
+    lead: Option<&LeadSummary>,
+) -> Vec<String> {
+    let mut description = match lead {
+        Some(lead) => format!("{} con {}", meeting.kind, get_lead_name(lead)),
+        None => meeting.kind.to_string(),
+    };
+    if !meeting.notes.is_empty() {
+        description = format!("{}\n\n{}", description, meeting.notes);
+    }
+
+    let mut lines = vec![
+        "BEGIN:VEVENT".to_string(),
+        format!(
+            "UID:{}@yayleads.mx",
+            meeting.uuid.clone().unwrap_or_default()
+        ),
+        format!("DTSTAMP:{}", format_ics_date(&Utc::now())),
+        format!("DTSTART:{}", format_ics_date(&meeting.start_date)),
+        format!("DTEND:{}", format_ics_date(&meeting.end_date)),
+        format!("SUMMARY:{}", escape_ics_text(&meeting.title)),
+        format!("DESCRIPTION:{}", escape_ics_text(&description)),
+    ];
+
+    if let Some(date_modified) = &meeting.date_modified {
+        lines.push(format!("LAST-MODIFIED:{}", format_ics_date(date_modified)));
+    }
+    if !meeting.location.is_empty() {
+        lines.push(format!("LOCATION:{}", escape_ics_text(&meeting.location)));
+    }
+
+    let find_user = |uuid: &String| users.iter().find(|user| &user.uuid == uuid);
+    if let Some(organizer) = meeting.organizer.as_ref().and_then(find_user) {
+        lines.push(ics_user("ORGANIZER", organizer, false));
+    }
+    for attendee in meeting.attendees.iter().filter_map(find_user) {
+        lines.push(ics_user("ATTENDEE", attendee, true));
+    }
+    if let Some(email) = get_invited_lead_email(meeting, lead) {
+        let name = lead.map(get_lead_name).unwrap_or_default();
+        lines.push(format!(
+            "ATTENDEE;CN={};ROLE=REQ-PARTICIPANT;PARTSTAT=NEEDS-ACTION;RSVP=TRUE:mailto:{}",
+            quote_ics_param(&name),
+            email
+        ));
+    }
+
+    lines.push("STATUS:CONFIRMED".to_string());
+    lines.push("END:VEVENT".to_string());
+    lines
+}
+
+pub fn build_icalendar(
+    method: &str,
+    calendar_name: Option<&str>,
+    events: Vec<Vec<String>>,
+) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        format!("PRODID:{}", ICS_PRODID),
+        "CALSCALE:GREGORIAN".to_string(),
+        format!("METHOD:{}", method),
+    ];
+    if let Some(name) = calendar_name {
+        lines.push(format!("X-WR-CALNAME:{}", escape_ics_text(name)));
+    }
+    lines.extend(events.into_iter().flatten());
+    lines.push("END:VCALENDAR".to_string());
+
+    lines
+        .iter()
+        .map(|line| fold_ics_line(line))
+        .collect::<Vec<String>>()
+        .join("\r\n")
+        + "\r\n"
+}
+
+pub async fn build_calendar_feed(db: &Data<Database>, user: &User) -> String {
+    let users = <Database as UsersDB>::find_all_non_deleted(db)
+        .await
+        .unwrap_or_default();
+    let since = Utc::now() - Duration::days(CALENDAR_FEED_PAST_DAYS);
+
+    let mut events: Vec<Vec<String>> = vec![];
+    let meetings = <Database as MeetingDB>::find_by_user(db, user.uuid.clone())
+        .await
+        .unwrap_or_default();
+    for meeting in meetings.iter().filter(|meeting| meeting.end_date > since) {
+        let lead = find_lead_summary(db, &meeting.lead_type, meeting.lead_uuid.clone()).await;
+        events.push(build_ics_event(meeting, &users, lead.as_ref()));
+    }
+
+    let calendar_name = format!("YAY CRM - {} {}", user.name, user.last_name);
+    build_icalendar("PUBLISH", Some(&calendar_name), events)
+}
+
+// Only the attendees get the invitation, the organizer already has the
+// meeting in the calendar feed.
+pub async fn send_meeting_invites(db: &Data<Database>, meeting: &Meeting) {
+    if !is_mail_enabled() {
+        return;
+    }
+
+    let users = <Database as UsersDB>::find_all_non_deleted(db)
+        .await
+        .unwrap_or_default();
+    let lead = find_lead_summary(db, &meeting.lead_type, meeting.lead_uuid.clone()).await;
+
+    let mut recipients: Vec<String> = meeting
+        .attendees
+        .iter()
+        .filter_map(|attendee| users.iter().find(|user| &user.uuid == attendee))
+        .map(|user| user.email.clone())
+        .collect();
+    recipients.extend(get_invited_lead_email(meeting, lead.as_ref()));
+    if recipients.is_empty() {
+        return;
+    }
+
+    let ics = build_icalendar(
+        "REQUEST",
+        None,
+        vec![build_ics_event(meeting, &users, lead.as_ref())],
+    );
+    let organizer = match &meeting.organizer {
+        Some(organizer) => get_user_name(&users, organizer),
+        None => "YAY LEADS CRM".to_string(),
+    };
+    // Attendees may live elsewhere, so the time goes out in the organizer's zone, named.
+    let timezone = meeting
+        .organizer
+        .as_ref()
+        .and_then(|organizer| users.iter().find(|user| &user.uuid == organizer))
+        .map(get_user_timezone)
+        .unwrap_or_else(get_default_timezone);
+    let date = format!(
+        "{} ({})",
+        format_date_in_language(&meeting.start_date, &Locale::ES, &timezone),
+        timezone.name()
+    );
+    let email = RenderedEmail {
+        subject: format!("Invitación: {}", meeting.title),
+        html_body: format!(
+            "<p>{} te invita a <strong>{}</strong>.</p><p>{}</p>",
+            html_escape(&organizer),
+            html_escape(&meeting.title),
+            date
+        ),
+        text_body: format!("{} te invita a {}.\n\n{}", organizer, meeting.title, date),
+    };
+
+    // Sending is slow, the meeting is saved without waiting for it.
+    let meeting_uuid = meeting.uuid.clone().unwrap_or_default();
+    spawn(async move {
+        for to in recipients {
+            if !send_calendar_invite(&to, &email, &ics).await {
+                info!(
+                    "Invitation to meeting {} was not sent to {}",
+                    meeting_uuid, to
+                );
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[test]
+    fn escapes_ics_text() {
+        assert_eq!(
+            escape_ics_text("Demo; precios, \\ notas\r\nsegunda\nlínea"),
+            r"Demo\; precios\, \\ notas\nsegunda\nlínea"
+        );
+    }
+
+    #[test]
+    fn quotes_ics_params_without_breaking_the_line() {
+        assert_eq!(quote_ics_param("Ana \"Gómez\"\r\n"), "\"Ana Gómez\"");
+    }
+
+    #[test]
+    fn formats_ics_dates_in_utc() {
+        let date = Utc.with_ymd_and_hms(2024, 3, 5, 17, 30, 0).unwrap();
+
+        assert_eq!(format_ics_date(&date), "20240305T173000Z");
+    }
+
+    #[test]
+    fn folds_long_lines_at_75_octets() {
+        let line = format!("DESCRIPTION:{}", "ñandú ".repeat(40));
+        let folded = fold_ics_line(&line);
+
+        assert!(folded
+            .split("\r\n")
+            .all(|content_line| content_line.len() <= ICS_LINE_OCTETS));
+        assert_eq!(folded.replace("\r\n ", ""), line);
+    }
+
+    #[test]
+    fn keeps_short_lines_as_they_are() {
+        assert_eq!(fold_ics_line("SUMMARY:Demo"), "SUMMARY:Demo");
+    }
+}
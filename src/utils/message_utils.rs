@@ -1,6 +1,14 @@
 use validator::ValidationErrors;
 
-pub fn get_validation_errors(validation_errors: &ValidationErrors) -> Vec<String> {
+use crate::{
+    models::locale_model::Locale,
+    utils::i18n_utils::{has_translation, translate, translate_with},
+};
+
+pub fn get_localized_validation_errors(
+    validation_errors: &ValidationErrors,
+    locale: &Locale,
+) -> Vec<String> {
     let mut key_errors_vec: Vec<String> = Vec::new();
     let validation_errors = ValidationErrors::field_errors(validation_errors);
 
@@ -8,10 +16,17 @@ pub fn get_validation_errors(validation_errors: &ValidationErrors) -> Vec<String
         for this_error in errors {
             let error_code = this_error.code.to_string();
 
-            key_errors_vec.push(format!(
-                " '{}' no cumple con el formato de {}",
-                key, error_code
-            ));
+            // Messages that are catalog keys get translated, anything else falls back to the code.
+            let message = match &this_error.message {
+                Some(message) if has_translation(message) => translate(locale, message),
+                _ => translate_with(
+                    locale,
+                    "validation.invalid_format",
+                    &[("field", key), ("code", &error_code)],
+                ),
+            };
+
+            key_errors_vec.push(message);
         }
     }
 
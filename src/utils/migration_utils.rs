@@ -0,0 +1,41 @@
+use std::future::Future;
+
+use actix_web::web::Data;
+use chrono::Utc;
+use log::{error, info};
+
+use crate::{
+    db::{config::Database, migration_db::MigrationDB},
+    models::migration_model::Migration,
+};
+
+// Data migrations scan whole tables, so each one runs until it succeeds once and
+// is skipped from then on. A failed run is retried on the next start.
+pub async fn run_once<F>(db: &Data<Database>, name: &str, migration: F)
+where
+    F: Future<Output = Result<(), String>>,
+{
+    if <Database as MigrationDB>::find_one(db, name.to_string())
+        .await
+        .is_some()
+    {
+        return;
+    }
+
+    info!("Running migration {}", name);
+    if let Err(e) = migration.await {
+        error!("Migration {} failed, it will run again:: {}", name, e);
+        return;
+    }
+
+    let record = Migration {
+        name: name.to_string(),
+        date_created: Utc::now(),
+    };
+    if <Database as MigrationDB>::add_one(db, record)
+        .await
+        .is_none()
+    {
+        error!("Unable to record migration {}", name);
+    }
+}
@@ -1,7 +1,38 @@
+pub mod account_utils;
+pub mod address_utils;
+pub mod api_token_utils;
+pub mod api_utils;
+pub mod attachment_utils;
+pub mod bulk_utils;
+pub mod campaign_utils;
+pub mod contact_utils;
 pub mod crud;
+pub mod email_template_utils;
 pub mod env;
 pub mod fs_utils;
 pub mod general_utils;
+pub mod i18n_utils;
+pub mod kanban_utils;
+pub mod lead_utils;
+pub mod live_utils;
+pub mod mail_utils;
+pub mod meeting_utils;
 pub mod message_utils;
+pub mod migration_utils;
+pub mod notification_utils;
+pub mod pdf_utils;
+pub mod phone_utils;
+pub mod pipeline_utils;
 pub mod pwd;
+pub mod quote_utils;
+pub mod role_utils;
+pub mod scoring_utils;
+pub mod segment_utils;
+pub mod service_utils;
+pub mod session_utils;
+pub mod social_utils;
+pub mod tag_utils;
+pub mod territory_utils;
 pub mod time;
+pub mod web_lead_utils;
+pub mod webhook_utils;
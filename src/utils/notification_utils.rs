@@ -0,0 +1,244 @@
+use actix_web::{rt::spawn, web::Data};
+use chrono::Utc;
+use handlebars::html_escape;
+use log::{error, info};
+
+use crate::{
+    db::{config::Database, notification_db::NotificationDB, users_db::UsersDB},
+    models::{
+        email_template_model::RenderedEmail,
+        lead_model::LeadSummary,
+        notification_model::{Notification, NotificationKind, NotificationPreference},
+        sales_model::LeadType,
+    },
+    utils::{
+        general_utils::get_uuid,
+        mail_utils::{is_mail_enabled, send_email},
+        pipeline_utils::{find_stage, get_pipeline, get_stage_label},
+    },
+};
+
+pub const NOTIFICATION_KINDS: [NotificationKind; 2] = [
+    NotificationKind::LeadAssigned,
+    NotificationKind::StageChanged,
+];
+
+fn default_preference(kind: NotificationKind) -> NotificationPreference {
+    NotificationPreference {
+        kind,
+        in_app: true,
+        email: false,
+    }
+}
+
+// Always one preference per kind, in a stable order, so kinds added later get
+// the defaults for users that saved their preferences before.
+pub fn normalize_preferences(
+    preferences: Vec<NotificationPreference>,
+) -> Vec<NotificationPreference> {
+    NOTIFICATION_KINDS
+        .into_iter()
+        .map(|kind| {
+            preferences
+                .iter()
+                .find(|preference| preference.kind == kind)
+                .cloned()
+                .unwrap_or(default_preference(kind))
+        })
+        .collect()
+}
+
+pub async fn get_notification_preferences(
+    db: &Data<Database>,
+    user_uuid: String,
+) -> Vec<NotificationPreference> {
+    let stored = <Database as NotificationDB>::find_preferences(db, user_uuid)
+        .await
+        .map(|stored| stored.preferences)
+        .unwrap_or_default();
+
+    normalize_preferences(stored)
+}
+
+fn get_lead_name(lead: &LeadSummary) -> String {
+    match &lead.company {
+        Some(company) if !company.trim().is_empty() => company.clone(),
+        _ => lead.name.clone(),
+    }
+}
+
+pub async fn notify_user(
+    db: &Data<Database>,
+    user_uuid: String,
+    kind: NotificationKind,
+    message: String,
+    lead: Option<(LeadType, String)>,
+    created_by: Option<String>,
+) {
+    let user = match <Database as UsersDB>::find_one(db, user_uuid.clone()).await {
+        Some(user) if !user.deleted => user,
+        _ => return,
+    };
+
+    let preference = get_notification_preferences(db, user_uuid.clone())
+        .await
+        .into_iter()
+        .find(|preference| preference.kind == kind)
+        .unwrap_or(default_preference(kind.clone()));
+
+    if preference.in_app {
+        let (lead_type, lead_uuid) = match lead {
+            Some((lead_type, lead_uuid)) => (Some(lead_type), Some(lead_uuid)),
+            None => (None, None),
+        };
+        let notification = Notification {
+            uuid: None,
+            user_uuid,
+            kind: kind.clone(),
+            message: message.clone(),
+            lead_type,
+            lead_uuid,
+            read: false,
+            date_read: None,
+            date_created: Some(Utc::now()),
+            created_by,
+        };
+
+        if <Database as NotificationDB>::add_one(db, Notification::new(get_uuid(), notification))
+            .await
+            .is_none()
+        {
+            error!("Unable to store {:?} notification for {}", kind, user.uuid);
+        }
+    }
+
+    if preference.email && is_mail_enabled() {
+        let email = RenderedEmail {
+            subject: format!("{}: {}", kind, message),
+            html_body: format!(
+                "<p>Hola {},</p><p>{}</p>",
+                html_escape(&user.name),
+                html_escape(&message)
+            ),
+            text_body: format!("Hola {},\n\n{}", user.name, message),
+        };
+        // Sending is slow, the change that raised the notification doesn't wait for it.
+        spawn(async move {
+            if !send_email(&user.email, &email).await {
+                info!("Notification email to {} was not sent", user.uuid);
+            }
+        });
+    }
+}
+
+async fn get_stage_name(db: &Data<Database>, lead_type: &LeadType, code: &str) -> String {
+    match find_stage(&get_pipeline(db, lead_type).await, code) {
+        Some(stage) => get_stage_label(stage),
+        None => code.to_string(),
+    }
+}
+
+// Nobody is notified about their own changes.
+pub async fn notify_lead_changes(
+    db: &Data<Database>,
+    before: Option<&LeadSummary>,
+    after: &LeadSummary,
+    changed_by: Option<String>,
+) {
+    let owner = match &after.assigned_to {
+        Some(owner) if Some(owner) != changed_by.as_ref() => owner.clone(),
+        _ => return,
+    };
+    let lead = Some((after.lead_type.clone(), after.uuid.clone()));
+
+    if before.and_then(|lead| lead.assigned_to.as_ref()) != Some(&owner) {
+        let message = format!("Se te asignó el lead {}", get_lead_name(after));
+        notify_user(
+            db,
+            owner,
+            NotificationKind::LeadAssigned,
+            message,
+            lead,
+            changed_by,
+        )
+        .await;
+        return;
+    }
+
+    if let Some(before) = before.filter(|before| before.sales_funnel != after.sales_funnel) {
+        let message = format!(
+            "El lead {} pasó de {} a {}",
+            get_lead_name(after),
+            get_stage_name(db, &after.lead_type, &before.sales_funnel).await,
+            get_stage_name(db, &after.lead_type, &after.sales_funnel).await,
+        );
+        notify_user(
+            db,
+            owner,
+            NotificationKind::StageChanged,
+            message,
+            lead,
+            changed_by,
+        )
+        .await;
+    }
+}
+
+pub async fn notify_bulk_reassign(
+    db: &Data<Database>,
+    lead_type: &LeadType,
+    owner: Option<String>,
+    count: usize,
+    changed_by: Option<String>,
+) {
+    let owner = match owner {
+        Some(owner) if count > 0 && Some(&owner) != changed_by.as_ref() => owner,
+        _ => return,
+    };
+
+    let message = format!("Se te asignaron {} leads de tipo {}", count, lead_type);
+    notify_user(
+        db,
+        owner,
+        NotificationKind::LeadAssigned,
+        message,
+        None,
+        changed_by,
+    )
+    .await;
+}
+
+pub async fn notify_bulk_stage_change(
+    db: &Data<Database>,
+    lead_type: &LeadType,
+    leads: &[LeadSummary],
+    to_stage: &str,
+    changed_by: Option<String>,
+) {
+    let stage_name = get_stage_name(db, lead_type, to_stage).await;
+
+    let mut owners: Vec<&String> = leads
+        .iter()
+        .filter_map(|lead| lead.assigned_to.as_ref())
+        .filter(|owner| Some(*owner) != changed_by.as_ref())
+        .collect();
+    owners.sort();
+    owners.dedup();
+
+    for owner in owners {
+        let count = leads
+            .iter()
+            .filter(|lead| lead.assigned_to.as_ref() == Some(owner))
+            .count();
+        let message = format!("{} de tus leads pasaron a {}", count, stage_name);
+        notify_user(
+            db,
+            owner.clone(),
+            NotificationKind::StageChanged,
+            message,
+            None,
+            changed_by.clone(),
+        )
+        .await;
+    }
+}
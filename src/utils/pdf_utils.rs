@@ -0,0 +1,148 @@
+use std::fmt::Write;
+
+pub const PAGE_WIDTH: f32 = 595.0;
+pub const PAGE_HEIGHT: f32 = 842.0;
+
+#[derive(Debug, Clone)]
+pub struct PdfText {
+    pub x: f32,
+    pub y: f32,
+    pub size: f32,
+    pub bold: bool,
+    pub text: String,
+}
+
+#[derive(Debug, Default)]
+pub struct PdfDocument {
+    pub pages: Vec<Vec<PdfText>>,
+}
+
+impl PdfDocument {
+    pub fn new() -> PdfDocument {
+        PdfDocument { pages: vec![vec![]] }
+    }
+
+    pub fn add_page(&mut self) {
+        self.pages.push(vec![]);
+    }
+
+    pub fn text(&mut self, x: f32, y: f32, size: f32, bold: bool, text: &str) {
+        if let Some(page) = self.pages.last_mut() {
+            page.push(PdfText {
+                x,
+                y,
+                size,
+                bold,
+                text: text.to_string(),
+            });
+        }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut objects: Vec<Vec<u8>> = Vec::new();
+        let page_count = self.pages.len();
+
+        let kids: Vec<String> = (0..page_count)
+            .map(|i| format!("{} 0 R", 5 + i * 2))
+            .collect();
+
+        objects.push(b"<< /Type /Catalog /Pages 2 0 R >>".to_vec());
+        objects.push(
+            format!(
+                "<< /Type /Pages /Kids [{}] /Count {} >>",
+                kids.join(" "),
+                page_count
+            )
+            .into_bytes(),
+        );
+        objects.push(
+            b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>"
+                .to_vec(),
+        );
+        objects.push(
+            b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica-Bold /Encoding /WinAnsiEncoding >>"
+                .to_vec(),
+        );
+
+        for (i, page) in self.pages.iter().enumerate() {
+            let content = page_content(page);
+            objects.push(
+                format!(
+                    "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] /Resources << /Font << /F1 3 0 R /F2 4 0 R >> >> /Contents {} 0 R >>",
+                    PAGE_WIDTH,
+                    PAGE_HEIGHT,
+                    6 + i * 2
+                )
+                .into_bytes(),
+            );
+
+            let mut stream = format!("<< /Length {} >>\nstream\n", content.len()).into_bytes();
+            stream.extend_from_slice(&content);
+            stream.extend_from_slice(b"\nendstream");
+            objects.push(stream);
+        }
+
+        let mut pdf: Vec<u8> = b"%PDF-1.4\n%\xE2\xE3\xCF\xD3\n".to_vec();
+        let mut offsets: Vec<usize> = Vec::new();
+
+        for (i, object) in objects.iter().enumerate() {
+            offsets.push(pdf.len());
+            pdf.extend_from_slice(format!("{} 0 obj\n", i + 1).as_bytes());
+            pdf.extend_from_slice(object);
+            pdf.extend_from_slice(b"\nendobj\n");
+        }
+
+        let xref_offset = pdf.len();
+        let mut xref = format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1);
+        for offset in offsets {
+            let _ = writeln!(xref, "{:010} 00000 n ", offset);
+        }
+        let _ = write!(
+            xref,
+            "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+            objects.len() + 1,
+            xref_offset
+        );
+        pdf.extend_from_slice(xref.as_bytes());
+
+        pdf
+    }
+}
+
+fn page_content(page: &[PdfText]) -> Vec<u8> {
+    let mut content: Vec<u8> = Vec::new();
+
+    for item in page {
+        let font = if item.bold { "F2" } else { "F1" };
+        content.extend_from_slice(
+            format!(
+                "BT /{} {} Tf {} {} Td (",
+                font, item.size, item.x, item.y
+            )
+            .as_bytes(),
+        );
+        content.extend_from_slice(&encode_pdf_text(&item.text));
+        content.extend_from_slice(b") Tj ET\n");
+    }
+
+    content
+}
+
+fn encode_pdf_text(text: &str) -> Vec<u8> {
+    let mut encoded: Vec<u8> = Vec::new();
+
+    for c in text.chars() {
+        match c {
+            '(' | ')' | '\\' => {
+                encoded.push(b'\\');
+                encoded.push(c as u8);
+            }
+            '€' => encoded.push(0x80),
+            c if (c as u32) < 0x20 => encoded.push(b' '),
+            c if (c as u32) < 0x100 => encoded.push(c as u32 as u8),
+            _ => encoded.push(b'?'),
+        }
+    }
+
+    encoded
+}
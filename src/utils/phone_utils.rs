@@ -0,0 +1,162 @@
+use actix_web::web::Data;
+use log::{info, warn};
+use phonenumber::{country::Id, Mode};
+use validator::ValidationError;
+
+use crate::{
+    constants::connection::set_environment_variable,
+    db::{account_db::ACCOUNT_TABLE, config::Database, contact_db::CONTACT_TABLE, lead_db::LeadDB},
+    utils::{general_utils::get_lead_type_tags, lead_utils::get_lead_table},
+};
+
+const DEFAULT_PHONE_COUNTRY: Id = Id::MX;
+
+pub fn get_default_phone_country() -> Id {
+    let country = set_environment_variable("PHONE_DEFAULT_COUNTRY", "MX");
+
+    match country.trim().to_uppercase().parse::<Id>() {
+        Ok(id) => id,
+        Err(_) => {
+            warn!(
+                "Invalid PHONE_DEFAULT_COUNTRY {}, using {:?}",
+                country, DEFAULT_PHONE_COUNTRY
+            );
+            DEFAULT_PHONE_COUNTRY
+        }
+    }
+}
+
+pub fn normalize_phone(raw_phone: &str) -> Option<String> {
+    let number = phonenumber::parse(Some(get_default_phone_country()), raw_phone.trim()).ok()?;
+
+    match phonenumber::is_valid(&number) {
+        true => Some(number.format().mode(Mode::E164).to_string()),
+        false => None,
+    }
+}
+
+pub fn normalize_required_phone(raw_phone: String) -> String {
+    normalize_phone(&raw_phone).unwrap_or(raw_phone)
+}
+
+pub fn normalize_optional_phone(raw_phone: Option<String>) -> Option<String> {
+    match raw_phone {
+        Some(phone) if phone.trim().is_empty() => None,
+        Some(phone) => Some(normalize_required_phone(phone)),
+        None => None,
+    }
+}
+
+pub fn validate_phone(phone: &str) -> Result<(), ValidationError> {
+    if phone.trim().is_empty() || normalize_phone(phone).is_some() {
+        return Ok(());
+    }
+
+    Err(ValidationError::new("phone"))
+}
+
+pub fn validate_optional_phone(phone: &Option<String>) -> Result<(), ValidationError> {
+    match phone {
+        Some(phone) => validate_phone(phone),
+        None => Ok(()),
+    }
+}
+
+pub async fn normalize_existing_phones(db: &Data<Database>) -> Result<(), String> {
+    let lead_tables: Vec<&str> = get_lead_type_tags()
+        .iter()
+        .map(|lead_type_tag| get_lead_table(&lead_type_tag.value))
+        .collect();
+
+    let mut failed_records = 0;
+
+    for table_name in [lead_tables, vec![CONTACT_TABLE, ACCOUNT_TABLE]].concat() {
+        let records = match <Database as LeadDB>::find_phones(db, table_name).await {
+            Some(records) => records,
+            None => return Err(format!("Unable to read phones from {}", table_name)),
+        };
+        let mut normalized_records = 0;
+
+        for record in records {
+            let phone = match record.phone {
+                Some(phone) if !phone.trim().is_empty() => phone,
+                _ => continue,
+            };
+
+            match normalize_phone(&phone) {
+                Some(normalized) if normalized != phone => {
+                    if <Database as LeadDB>::set_phone(db, table_name, record.uuid, normalized)
+                        .await
+                    {
+                        normalized_records += 1;
+                    } else {
+                        failed_records += 1;
+                    }
+                }
+                Some(_) => {}
+                None => warn!(
+                    "Unable to normalize phone of {} in {}",
+                    record.uuid, table_name
+                ),
+            }
+        }
+
+        if normalized_records > 0 {
+            info!(
+                "Normalized phones of {} records in {}",
+                normalized_records, table_name
+            );
+        }
+    }
+
+    match failed_records {
+        0 => Ok(()),
+        _ => Err(format!(
+            "Unable to save {} normalized phones",
+            failed_records
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_local_numbers_to_e164() {
+        assert_eq!(
+            normalize_phone("33 1234 5678").as_deref(),
+            Some("+523312345678")
+        );
+        assert_eq!(
+            normalize_phone(" (55) 1234-5678 ").as_deref(),
+            Some("+525512345678")
+        );
+    }
+
+    #[test]
+    fn keeps_the_country_of_international_numbers() {
+        assert_eq!(
+            normalize_phone("+1 202 456 1111").as_deref(),
+            Some("+12024561111")
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_numbers() {
+        assert_eq!(normalize_phone("12345"), None);
+        assert_eq!(normalize_phone("no tengo"), None);
+        assert!(validate_phone("12345").is_err());
+        assert!(validate_phone("").is_ok());
+    }
+
+    #[test]
+    fn keeps_unparseable_phones_and_drops_blank_ones() {
+        assert_eq!(normalize_required_phone("12345".to_string()), "12345");
+        assert_eq!(normalize_optional_phone(Some("  ".to_string())), None);
+        assert_eq!(
+            normalize_optional_phone(Some("3312345678".to_string())).as_deref(),
+            Some("+523312345678")
+        );
+    }
+}
@@ -0,0 +1,168 @@
+use std::fmt::Display;
+
+use actix_web::{web::Data, HttpResponse};
+use chrono::Utc;
+use log::{error, info, warn};
+
+use crate::{
+    db::{config::Database, pipeline_db::PipelineDB},
+    error::api_error::ApiError,
+    models::{pipeline_model::PipelineStage, sales_model::LeadType},
+    utils::general_utils::{get_lead_type_tags, get_uuid},
+};
+
+pub const PROSPECT_STAGE: &str = "PROSPECT";
+pub const QUOTE_STAGE: &str = "QUOTE";
+
+const DEFAULT_STAGES: [(&str, &str, u8, &str, &str, bool, bool); 7] = [
+    ("LOST", "Perdido", 0, "fas fa-trash", "is-lost", false, true),
+    (
+        "PROSPECT",
+        "Prospecto",
+        0,
+        "fas fa-binoculars",
+        "is-prospect",
+        false,
+        false,
+    ),
+    (
+        "RESEARCH",
+        "Investigación",
+        10,
+        "fas fa-user-secret",
+        "is-research",
+        false,
+        false,
+    ),
+    (
+        "NEED",
+        "Necesidad",
+        20,
+        "fas fa-drumstick-bite",
+        "has-background-primary-100 has-text-primary-100-invert",
+        false,
+        false,
+    ),
+    (
+        "QUOTE",
+        "Cotización",
+        50,
+        "fas fa-money-check-alt",
+        "is-quote",
+        false,
+        false,
+    ),
+    (
+        "NEGOTIATION",
+        "Negociación",
+        80,
+        "fas fa-people-arrows",
+        "is-negotiation",
+        false,
+        false,
+    ),
+    (
+        "WIN",
+        "Ganado",
+        100,
+        "fas fa-handshake",
+        "is-win",
+        true,
+        false,
+    ),
+];
+
+pub fn get_stage_label(stage: &PipelineStage) -> String {
+    format!("{} {}%", stage.name, stage.probability)
+}
+
+pub fn find_stage<'a>(stages: &'a [PipelineStage], code: &str) -> Option<&'a PipelineStage> {
+    stages.iter().find(|stage| stage.code == code)
+}
+
+pub async fn get_pipeline(db: &Data<Database>, lead_type: &LeadType) -> Vec<PipelineStage> {
+    match Database::find_by_lead_type(db, lead_type.clone()).await {
+        Some(stages) => stages,
+        None => {
+            warn!("No pipeline found for {}", lead_type);
+            vec![]
+        }
+    }
+}
+
+// The 400 every lead controller answers when sales_funnel isn't a stage of its pipeline.
+pub async fn check_lead_stage(
+    db: &Data<Database>,
+    lead_type: &LeadType,
+    code: &str,
+    invalid_stage: impl Display,
+) -> Option<HttpResponse> {
+    if find_stage(&get_pipeline(db, lead_type).await, code).is_some() {
+        return None;
+    }
+
+    error!("Stage {} is not part of the {} pipeline", code, lead_type);
+    Some(
+        HttpResponse::BadRequest()
+            .insert_header((
+                "HX-Trigger",
+                format!(
+                    "{{ \"page_error\": {:?} }}",
+                    format!("Etapa inválida: {}", code)
+                ),
+            ))
+            .json(ApiError::new(invalid_stage)),
+    )
+}
+
+pub async fn seed_default_pipelines(db: &Data<Database>) -> Result<(), String> {
+    let mut failed_stages = 0;
+
+    for lead_type_tag in get_lead_type_tags() {
+        let lead_type = lead_type_tag.value;
+
+        match Database::find_by_lead_type(db, lead_type.clone()).await {
+            Some(stages) if stages.is_empty() => {
+                info!("Creating default pipeline for {}", &lead_type);
+                let date_created = Utc::now();
+
+                for (position, (code, name, probability, icon, css, is_won, is_lost)) in
+                    DEFAULT_STAGES.into_iter().enumerate()
+                {
+                    let stage = PipelineStage {
+                        uuid: None,
+                        lead_type: lead_type.clone(),
+                        code: code.to_string(),
+                        name: name.to_string(),
+                        probability,
+                        position: position as u32,
+                        icon: Some(icon.to_string()),
+                        css: Some(css.to_string()),
+                        is_won,
+                        is_lost,
+                        deleted: false,
+                        date_created: Some(date_created),
+                        date_modified: Some(date_created),
+                        created_by: None,
+                        modified_by: None,
+                    };
+
+                    if Database::add_one(db, PipelineStage::new(get_uuid(), stage))
+                        .await
+                        .is_none()
+                    {
+                        error!("Unable to create stage {} for {}", code, &lead_type);
+                        failed_stages += 1;
+                    }
+                }
+            }
+            Some(_) => {}
+            None => return Err(format!("Unable to read pipeline for {}", &lead_type)),
+        }
+    }
+
+    match failed_stages {
+        0 => Ok(()),
+        _ => Err(format!("Unable to create {} default stages", failed_stages)),
+    }
+}
@@ -1,9 +1,12 @@
 use argon2::{
-    password_hash::{rand_core::OsRng, Error, PasswordHasher, SaltString},
+    password_hash::{
+        rand_core::OsRng, Error, PasswordHash, PasswordHasher, PasswordVerifier, SaltString,
+    },
     Argon2,
 };
 // use chrono::Local;
 // use log::info;
+use log::warn;
 
 // use crate::models::users_model::User;
 
@@ -17,6 +20,18 @@ pub fn pwd_hasher(my_password: String) -> Result<String, Error> {
     Ok(password_hash)
 }
 
+pub fn pwd_verify(my_password: &str, password_hash: &str) -> bool {
+    match PasswordHash::new(password_hash) {
+        Ok(parsed_hash) => Argon2::default()
+            .verify_password(my_password.as_bytes(), &parsed_hash)
+            .is_ok(),
+        Err(e) => {
+            warn!("Stored password hash is invalid:: {}", e);
+            false
+        }
+    }
+}
+
 // pub fn hash_user_password(
 //     cloned_pwd_from_json: Option<String>,
 //     user_in_db: Option<User>,
@@ -0,0 +1,375 @@
+use chrono_tz::Tz;
+use log::warn;
+
+use crate::{
+    constants::connection::set_environment_variable,
+    models::{
+        locale_model::Locale,
+        quote_model::{Quote, QuoteLineItem},
+        service_model::Service,
+    },
+    utils::{
+        pdf_utils::{PdfDocument, PAGE_HEIGHT},
+        service_utils::{get_service_name, get_service_price},
+        time::format_date_in_language,
+    },
+};
+
+const PDF_MARGIN: f32 = 50.0;
+const PDF_LINE_HEIGHT: f32 = 16.0;
+
+pub fn round_currency(amount: f64) -> f64 {
+    (amount * 100.0).round() / 100.0
+}
+
+pub fn format_currency(amount: f64) -> String {
+    let cents = (round_currency(amount.abs()) * 100.0).round() as u64;
+    let integer_part = (cents / 100).to_string();
+
+    let mut grouped = String::new();
+    for (i, digit) in integer_part.chars().enumerate() {
+        if i > 0 && (integer_part.len() - i).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+
+    let sign = if amount < 0.0 { "-" } else { "" };
+    format!("{}${}.{:02}", sign, grouped, cents % 100)
+}
+
+pub fn get_quote_tax_percent() -> f64 {
+    match set_environment_variable("QUOTE_TAX_PERCENT", "16").parse::<f64>() {
+        Ok(tax) => tax,
+        Err(e) => {
+            warn!("Invalid QUOTE_TAX_PERCENT: {}", e);
+            16.0
+        }
+    }
+}
+
+// Quotes only take services that are still offered, an unknown uuid would be quoted at $0.
+pub fn get_quote_errors(quote: &Quote, services: &[Service]) -> Vec<String> {
+    quote
+        .line_items
+        .iter()
+        .filter(|line_item| {
+            !services.iter().any(|service| {
+                service.active && service.uuid.as_deref() == Some(line_item.service.as_str())
+            })
+        })
+        .map(|line_item| format!("Servicio inválido: {}", line_item.service))
+        .collect()
+}
+
+pub fn build_quote_line_item(line_item: QuoteLineItem, services: &[Service]) -> QuoteLineItem {
+    let unit_price = match line_item.unit_price {
+        Some(price) => price,
+        None => get_service_price(services, &line_item.service),
+    };
+    let description = match line_item.description.clone() {
+        Some(description) if !description.trim().is_empty() => description,
+        _ => get_service_name(services, &line_item.service),
+    };
+    let discount_percent = line_item.discount_percent.unwrap_or(0.0);
+    let gross = unit_price * line_item.quantity as f64;
+
+    QuoteLineItem {
+        description: Some(description),
+        unit_price: Some(round_currency(unit_price)),
+        discount_percent: Some(discount_percent),
+        line_total: Some(round_currency(gross * (1.0 - discount_percent / 100.0))),
+        ..line_item
+    }
+}
+
+pub fn build_quote(quote: Quote, services: &[Service]) -> Quote {
+    let line_items: Vec<QuoteLineItem> = quote
+        .line_items
+        .iter()
+        .cloned()
+        .map(|line_item| build_quote_line_item(line_item, services))
+        .collect();
+
+    let subtotal: f64 = line_items
+        .iter()
+        .map(|item| item.unit_price.unwrap_or(0.0) * item.quantity as f64)
+        .sum();
+    let lines_total: f64 = line_items
+        .iter()
+        .map(|item| item.line_total.unwrap_or(0.0))
+        .sum();
+
+    let discount_percent = quote.discount_percent.unwrap_or(0.0);
+    let tax_percent = match quote.tax_percent {
+        Some(tax) => tax,
+        None => get_quote_tax_percent(),
+    };
+
+    let taxable = lines_total * (1.0 - discount_percent / 100.0);
+    let tax_total = taxable * tax_percent / 100.0;
+
+    Quote {
+        line_items,
+        discount_percent: Some(discount_percent),
+        tax_percent: Some(tax_percent),
+        currency: match quote.currency.clone() {
+            Some(currency) => Some(currency),
+            None => Some(set_environment_variable("QUOTE_CURRENCY", "MXN")),
+        },
+        subtotal: Some(round_currency(subtotal)),
+        discount_total: Some(round_currency(subtotal - taxable)),
+        tax_total: Some(round_currency(tax_total)),
+        total: Some(round_currency(taxable + tax_total)),
+        ..quote
+    }
+}
+
+pub fn render_quote_pdf(quote: &Quote, timezone: &Tz) -> Vec<u8> {
+    let mut pdf = PdfDocument::new();
+    let mut y = PAGE_HEIGHT - PDF_MARGIN;
+    let currency = quote.currency.clone().unwrap_or_default();
+
+    pdf.text(PDF_MARGIN, y, 18.0, true, "COTIZACIÓN");
+    pdf.text(
+        380.0,
+        y,
+        12.0,
+        true,
+        &quote.quote_number.clone().unwrap_or_default(),
+    );
+    y -= PDF_LINE_HEIGHT * 1.5;
+
+    if let Some(date_created) = quote.date_created {
+        pdf.text(
+            PDF_MARGIN,
+            y,
+            10.0,
+            false,
+            &format!(
+                "Fecha: {}",
+                format_date_in_language(&date_created, &Locale::ES, timezone)
+            ),
+        );
+        y -= PDF_LINE_HEIGHT;
+    }
+
+    if let Some(valid_until) = quote.valid_until {
+        pdf.text(
+            PDF_MARGIN,
+            y,
+            10.0,
+            false,
+            &format!(
+                "Vigencia: {}",
+                format_date_in_language(&valid_until, &Locale::ES, timezone)
+            ),
+        );
+        y -= PDF_LINE_HEIGHT;
+    }
+
+    if let Some(lead) = &quote.lead {
+        y -= PDF_LINE_HEIGHT / 2.0;
+        pdf.text(PDF_MARGIN, y, 11.0, true, "Para:");
+        y -= PDF_LINE_HEIGHT;
+        pdf.text(PDF_MARGIN, y, 10.0, false, &lead.name);
+        y -= PDF_LINE_HEIGHT;
+
+        for line in [&lead.company, &lead.email, &lead.phone].into_iter().flatten() {
+            pdf.text(PDF_MARGIN, y, 10.0, false, line);
+            y -= PDF_LINE_HEIGHT;
+        }
+    }
+
+    y -= PDF_LINE_HEIGHT;
+    let header = |pdf: &mut PdfDocument, y: f32| {
+        pdf.text(PDF_MARGIN, y, 10.0, true, "Concepto");
+        pdf.text(300.0, y, 10.0, true, "Cant.");
+        pdf.text(350.0, y, 10.0, true, "Precio");
+        pdf.text(430.0, y, 10.0, true, "Desc.");
+        pdf.text(480.0, y, 10.0, true, "Importe");
+    };
+    header(&mut pdf, y);
+    y -= PDF_LINE_HEIGHT;
+
+    for item in &quote.line_items {
+        if y < PDF_MARGIN + PDF_LINE_HEIGHT * 6.0 {
+            pdf.add_page();
+            y = PAGE_HEIGHT - PDF_MARGIN;
+            header(&mut pdf, y);
+            y -= PDF_LINE_HEIGHT;
+        }
+
+        let description: String = item
+            .description
+            .clone()
+            .unwrap_or_default()
+            .chars()
+            .take(45)
+            .collect();
+
+        pdf.text(PDF_MARGIN, y, 10.0, false, &description);
+        pdf.text(300.0, y, 10.0, false, &item.quantity.to_string());
+        pdf.text(
+            350.0,
+            y,
+            10.0,
+            false,
+            &format_currency(item.unit_price.unwrap_or(0.0)),
+        );
+        pdf.text(
+            430.0,
+            y,
+            10.0,
+            false,
+            &format!("{}%", item.discount_percent.unwrap_or(0.0)),
+        );
+        pdf.text(
+            480.0,
+            y,
+            10.0,
+            false,
+            &format_currency(item.line_total.unwrap_or(0.0)),
+        );
+        y -= PDF_LINE_HEIGHT;
+    }
+
+    y -= PDF_LINE_HEIGHT;
+    let tax_label = format!("IVA {}%", quote.tax_percent.unwrap_or(0.0));
+    let totals = [
+        ("Subtotal", quote.subtotal),
+        ("Descuento", quote.discount_total),
+        (tax_label.as_str(), quote.tax_total),
+    ];
+
+    for (label, amount) in totals {
+        pdf.text(380.0, y, 10.0, false, label);
+        pdf.text(480.0, y, 10.0, false, &format_currency(amount.unwrap_or(0.0)));
+        y -= PDF_LINE_HEIGHT;
+    }
+
+    pdf.text(380.0, y, 11.0, true, &format!("Total {}", currency));
+    pdf.text(
+        480.0,
+        y,
+        11.0,
+        true,
+        &format_currency(quote.total.unwrap_or(0.0)),
+    );
+    y -= PDF_LINE_HEIGHT * 2.0;
+
+    if let Some(notes) = &quote.notes {
+        for line in notes.lines() {
+            if y < PDF_MARGIN {
+                pdf.add_page();
+                y = PAGE_HEIGHT - PDF_MARGIN;
+            }
+            pdf.text(PDF_MARGIN, y, 9.0, false, line);
+            y -= PDF_LINE_HEIGHT;
+        }
+    }
+
+    pdf.to_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::sales_model::LeadType;
+
+    fn service(uuid: &str, price: f64, active: bool) -> Service {
+        Service {
+            uuid: Some(uuid.to_string()),
+            name: format!("Servicio {}", uuid),
+            description: None,
+            icon: None,
+            price,
+            active,
+            legacy_code: None,
+            deleted: false,
+            date_created: None,
+            date_modified: None,
+            created_by: None,
+            modified_by: None,
+        }
+    }
+
+    fn line_item(service: &str, quantity: u32) -> QuoteLineItem {
+        QuoteLineItem {
+            service: service.to_string(),
+            description: None,
+            quantity,
+            unit_price: None,
+            discount_percent: None,
+            line_total: None,
+        }
+    }
+
+    #[test]
+    fn rounds_currency_to_cents() {
+        assert_eq!(round_currency(10.006), 10.01);
+        assert_eq!(round_currency(10.004), 10.0);
+        assert_eq!(round_currency(-3.456), -3.46);
+    }
+
+    #[test]
+    fn formats_currency_with_thousands_separators() {
+        assert_eq!(format_currency(0.0), "$0.00");
+        assert_eq!(format_currency(999.5), "$999.50");
+        assert_eq!(format_currency(1234567.891), "$1,234,567.89");
+        assert_eq!(format_currency(-1500.0), "-$1,500.00");
+    }
+
+    #[test]
+    fn line_items_fall_back_to_catalog_price_and_name() {
+        let services = vec![service("web", 1500.0, true)];
+        let item = build_quote_line_item(
+            QuoteLineItem {
+                discount_percent: Some(10.0),
+                ..line_item("web", 3)
+            },
+            &services,
+        );
+
+        assert_eq!(item.unit_price, Some(1500.0));
+        assert_eq!(item.description.as_deref(), Some("Servicio web"));
+        assert_eq!(item.line_total, Some(4050.0));
+    }
+
+    #[test]
+    fn rejects_unknown_and_inactive_services() {
+        let services = vec![service("web", 1500.0, true), service("seo", 800.0, false)];
+        let quote = Quote {
+            uuid: None,
+            quote_number: None,
+            lead_type: LeadType::ENTERPRISE,
+            lead_uuid: "lead".to_string(),
+            lead: None,
+            line_items: vec![
+                line_item("web", 1),
+                line_item("seo", 1),
+                line_item("ads", 1),
+            ],
+            discount_percent: None,
+            tax_percent: None,
+            currency: None,
+            subtotal: None,
+            discount_total: None,
+            tax_total: None,
+            total: None,
+            notes: None,
+            valid_until: None,
+            advance_funnel: None,
+            deleted: false,
+            date_created: None,
+            date_modified: None,
+            created_by: None,
+            modified_by: None,
+        };
+
+        assert_eq!(
+            get_quote_errors(&quote, &services),
+            vec!["Servicio inválido: seo", "Servicio inválido: ads"]
+        );
+    }
+}
@@ -0,0 +1,136 @@
+use actix_web::{dev::Payload, web::Data, FromRequest, HttpMessage, HttpRequest};
+use futures::future::LocalBoxFuture;
+use log::{info, warn};
+
+use crate::{
+    constants::connection::set_environment_variable,
+    db::{config::Database, users_db::UsersDB},
+    models::users_model::{ActingUser, Roles, User, UserFromJson},
+    utils::{
+        general_utils::get_uuid,
+        pwd::pwd_hasher,
+        session_utils::{find_session_user_uuid, get_session_uuid},
+    },
+};
+
+// Put in the request extensions once the caller's identity was checked on the server.
+#[derive(Clone, Debug)]
+pub struct AuthenticatedUser(pub String);
+
+pub async fn is_admin(db: &Data<Database>, user_uuid: Option<String>) -> bool {
+    let uuid = match user_uuid {
+        Some(this_uuid) => this_uuid,
+        None => {
+            warn!("No user uuid provided for an ADMIN only action");
+            return false;
+        }
+    };
+
+    match <Database as UsersDB>::find_one(db, uuid.clone()).await {
+        Some(user) => !user.deleted && user.role == Roles::ADMIN,
+        None => {
+            warn!("User {} not found for an ADMIN only action", &uuid);
+            false
+        }
+    }
+}
+
+pub async fn is_active_user(db: &Data<Database>, user_uuid: Option<String>) -> bool {
+    let uuid = match user_uuid {
+        Some(this_uuid) => this_uuid,
+        None => {
+            warn!("No user uuid provided for an authenticated action");
+            return false;
+        }
+    };
+
+    match <Database as UsersDB>::find_one(db, uuid.clone()).await {
+        Some(user) => !user.deleted,
+        None => {
+            warn!("User {} not found for an authenticated action", &uuid);
+            false
+        }
+    }
+}
+
+// An api token request acts as the token owner, a browser as the owner of its
+// session. Nothing the client sends in the body or query string counts.
+pub async fn find_request_user_uuid(req: &HttpRequest) -> Option<String> {
+    if let Some(AuthenticatedUser(user_uuid)) = req.extensions().get::<AuthenticatedUser>() {
+        return Some(user_uuid.clone());
+    }
+
+    let session_uuid = get_session_uuid(req)?;
+    let db = req.app_data::<Data<Database>>()?;
+    let user_uuid = find_session_user_uuid(db, session_uuid).await?;
+
+    req.extensions_mut()
+        .insert(AuthenticatedUser(user_uuid.clone()));
+    Some(user_uuid)
+}
+
+pub async fn find_request_user(req: &HttpRequest) -> Option<User> {
+    let user_uuid = find_request_user_uuid(req).await?;
+    let db = req.app_data::<Data<Database>>()?;
+
+    <Database as UsersDB>::find_one(db, user_uuid)
+        .await
+        .filter(|user| !user.deleted)
+}
+
+// Creating users takes an ADMIN, so a fresh install gets its first one from
+// ADMIN_EMAIL and ADMIN_PASSWORD.
+pub async fn seed_first_admin(db: &Data<Database>) -> Result<(), String> {
+    let users = match <Database as UsersDB>::find_all_non_deleted(db).await {
+        Some(users) => users,
+        None => return Err("Unable to read users".to_string()),
+    };
+    if users.iter().any(|user| user.role == Roles::ADMIN) {
+        return Ok(());
+    }
+
+    let email = set_environment_variable("ADMIN_EMAIL", "")
+        .trim()
+        .to_lowercase();
+    let password = set_environment_variable("ADMIN_PASSWORD", "");
+    if email.is_empty() || password.is_empty() {
+        return Err("There is no ADMIN user, set ADMIN_EMAIL and ADMIN_PASSWORD".to_string());
+    }
+
+    let hashed_password = match pwd_hasher(password) {
+        Ok(hashed_password) => hashed_password,
+        Err(e) => return Err(format!("Unable to hash ADMIN_PASSWORD:: {}", e)),
+    };
+    let admin = UserFromJson {
+        name: "Admin".to_string(),
+        last_name: "Yay".to_string(),
+        email: email.clone(),
+        role: Roles::ADMIN,
+        notes: None,
+        password: Some(hashed_password),
+        locale: None,
+        timezone: None,
+    };
+
+    match <Database as UsersDB>::add_one(db, User::new(get_uuid(), admin)).await {
+        Some(_) => {
+            info!("Created the first ADMIN user {}", &email);
+            Ok(())
+        }
+        None => Err(format!("Unable to create the ADMIN user {}", &email)),
+    }
+}
+
+impl FromRequest for ActingUser {
+    type Error = actix_web::Error;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        let req = req.clone();
+        Box::pin(async move {
+            Ok(ActingUser {
+                user_uuid: find_request_user_uuid(&req).await,
+            })
+        })
+    }
+}
@@ -0,0 +1,395 @@
+use std::time::Duration;
+
+use actix_web::{rt::time::sleep, web::Data};
+use chrono::{DateTime, Utc};
+use log::{error, info, warn};
+
+use crate::{
+    constants::connection::set_environment_variable,
+    db::{
+        clinical_db::ClinicalDB, config::Database, enterprise_db::EnterpriseDB, lead_db::LeadDB,
+        school_db::SchoolDB, scoring_db::ScoringDB,
+    },
+    models::{
+        clinical_model::Clinical,
+        enterprise_model::Enterprise,
+        pipeline_model::PipelineStage,
+        sales_model::LeadType,
+        school_model::School,
+        scoring_model::{ScoringCriterion, ScoringFacts, ScoringRule},
+    },
+    utils::{
+        general_utils::{get_lead_type_tags, get_uuid},
+        lead_utils::get_lead_table,
+        pipeline_utils::{find_stage, get_pipeline},
+        time::get_default_timezone,
+    },
+};
+
+const DEFAULT_SCORING_HOUR: u32 = 3;
+
+const DEFAULT_RULES: [(&str, ScoringCriterion, i32, Option<i32>); 7] = [
+    ("Tiene correo", ScoringCriterion::HasEmail, 10, None),
+    ("Tiene teléfono", ScoringCriterion::HasPhone, 5, None),
+    ("Tiene sitio web", ScoringCriterion::HasWebsite, 5, None),
+    (
+        "Presencia en redes sociales",
+        ScoringCriterion::SocialProfile,
+        3,
+        Some(15),
+    ),
+    (
+        "Peso de la etapa",
+        ScoringCriterion::StageProbability,
+        30,
+        None,
+    ),
+    (
+        "Días sin actividad",
+        ScoringCriterion::DaysSinceActivity,
+        -1,
+        Some(30),
+    ),
+    (
+        "Servicios de interés",
+        ScoringCriterion::ServiceCount,
+        2,
+        Some(10),
+    ),
+];
+
+pub const SCORING_CRITERIA: [ScoringCriterion; 8] = [
+    ScoringCriterion::HasEmail,
+    ScoringCriterion::HasPhone,
+    ScoringCriterion::HasWebsite,
+    ScoringCriterion::SocialProfile,
+    ScoringCriterion::StageProbability,
+    ScoringCriterion::DaysSinceActivity,
+    ScoringCriterion::ServiceCount,
+    ScoringCriterion::HasTag,
+];
+
+pub fn get_criterion_label(criterion: &ScoringCriterion) -> &'static str {
+    match criterion {
+        ScoringCriterion::HasEmail => "Tiene correo",
+        ScoringCriterion::HasPhone => "Tiene teléfono",
+        ScoringCriterion::HasWebsite => "Tiene sitio web",
+        ScoringCriterion::SocialProfile => "Por cada red social",
+        ScoringCriterion::StageProbability => "Peso de la etapa (% de probabilidad)",
+        ScoringCriterion::DaysSinceActivity => "Por cada día sin actividad",
+        ScoringCriterion::ServiceCount => "Por cada servicio de interés",
+        ScoringCriterion::HasTag => "Tiene la etiqueta",
+    }
+}
+
+fn is_filled(value: &Option<String>) -> bool {
+    value
+        .as_deref()
+        .map(|text| !text.trim().is_empty())
+        .unwrap_or(false)
+}
+
+fn count_social_profiles(profiles: [&Option<String>; 5]) -> i32 {
+    profiles
+        .into_iter()
+        .filter(|profile| is_filled(profile))
+        .count() as i32
+}
+
+fn apply_limit(points: i32, limit: Option<i32>) -> i32 {
+    match limit {
+        Some(limit) => points.clamp(-limit, limit),
+        None => points,
+    }
+}
+
+pub fn enterprise_facts(enterprise: &Enterprise) -> ScoringFacts {
+    ScoringFacts {
+        has_email: !enterprise.email.trim().is_empty(),
+        has_phone: is_filled(&enterprise.phone),
+        has_website: false,
+        social_profiles: count_social_profiles([
+            &enterprise.fb,
+            &enterprise.instagram,
+            &enterprise.linked_in,
+            &enterprise.tik_tok,
+            &enterprise.twitter,
+        ]),
+        sales_funnel: enterprise.sales_funnel.clone(),
+        services: enterprise.services_offered.len() as i32,
+        tags: enterprise.tags.clone(),
+        last_activity: enterprise.date_modified.or(enterprise.date_created),
+    }
+}
+
+pub fn school_facts(school: &School) -> ScoringFacts {
+    ScoringFacts {
+        has_email: !school.email.trim().is_empty() || is_filled(&school.school_email),
+        has_phone: !school.phone.trim().is_empty(),
+        has_website: is_filled(&school.school_web),
+        social_profiles: count_social_profiles([
+            &school.fb,
+            &school.instagram,
+            &school.linked_in,
+            &school.tik_tok,
+            &school.twitter,
+        ]),
+        sales_funnel: school.sales_funnel.clone(),
+        services: school.services_offered.len() as i32,
+        tags: school.tags.clone(),
+        last_activity: school.date_modified.or(school.date_created),
+    }
+}
+
+pub fn clinical_facts(clinical: &Clinical) -> ScoringFacts {
+    ScoringFacts {
+        has_email: is_filled(&clinical.email) || is_filled(&clinical.clinic_email),
+        has_phone: !clinical.phone.trim().is_empty(),
+        has_website: is_filled(&clinical.clinic_web),
+        social_profiles: count_social_profiles([
+            &clinical.fb,
+            &clinical.instagram,
+            &clinical.linked_in,
+            &clinical.tik_tok,
+            &clinical.twitter,
+        ]),
+        sales_funnel: clinical.sales_funnel.clone(),
+        services: clinical.services_offered.len() as i32,
+        tags: clinical.tags.clone(),
+        last_activity: clinical.date_modified.or(clinical.date_created),
+    }
+}
+
+pub fn calculate_score(
+    facts: &ScoringFacts,
+    rules: &[ScoringRule],
+    stages: &[PipelineStage],
+    now: DateTime<Utc>,
+) -> i32 {
+    rules
+        .iter()
+        .map(|rule| match rule.criterion {
+            ScoringCriterion::HasEmail if facts.has_email => rule.points,
+            ScoringCriterion::HasPhone if facts.has_phone => rule.points,
+            ScoringCriterion::HasWebsite if facts.has_website => rule.points,
+            ScoringCriterion::SocialProfile => {
+                apply_limit(facts.social_profiles * rule.points, rule.limit)
+            }
+            ScoringCriterion::StageProbability => find_stage(stages, &facts.sales_funnel)
+                .map(|stage| stage.probability as i32 * rule.points / 100)
+                .unwrap_or(0),
+            ScoringCriterion::DaysSinceActivity => match facts.last_activity {
+                Some(last_activity) => {
+                    let idle_days = (now - last_activity).num_days().max(0) as i32;
+                    apply_limit(idle_days.saturating_mul(rule.points), rule.limit)
+                }
+                None => 0,
+            },
+            ScoringCriterion::ServiceCount => apply_limit(facts.services * rule.points, rule.limit),
+            ScoringCriterion::HasTag => match rule.value.as_deref() {
+                Some(tag) if facts.tags.iter().any(|lead_tag| lead_tag == tag) => rule.points,
+                _ => 0,
+            },
+            _ => 0,
+        })
+        .sum()
+}
+
+pub async fn get_active_rules(db: &Data<Database>, lead_type: &LeadType) -> Vec<ScoringRule> {
+    <Database as ScoringDB>::find_all_non_deleted(db)
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|rule| {
+            rule.active
+                && rule
+                    .lead_type
+                    .as_ref()
+                    .map(|rule_lead_type| rule_lead_type == lead_type)
+                    .unwrap_or(true)
+        })
+        .collect()
+}
+
+pub async fn compute_lead_score(
+    db: &Data<Database>,
+    lead_type: &LeadType,
+    facts: &ScoringFacts,
+) -> i32 {
+    let rules = get_active_rules(db, lead_type).await;
+    let stages = get_pipeline(db, lead_type).await;
+
+    calculate_score(facts, &rules, &stages, Utc::now())
+}
+
+pub async fn rescore_lead(db: &Data<Database>, lead_type: &LeadType, uuid: String) -> Option<i32> {
+    let (facts, stored_score) = match lead_type {
+        LeadType::ENTERPRISE => <Database as EnterpriseDB>::find_one(db, uuid.clone())
+            .await
+            .map(|enterprise| (enterprise_facts(&enterprise), enterprise.score))?,
+        LeadType::SCHOOL => <Database as SchoolDB>::find_one(db, uuid.clone())
+            .await
+            .map(|school| (school_facts(&school), school.score))?,
+        LeadType::CLINICAL => <Database as ClinicalDB>::find_one(db, uuid.clone())
+            .await
+            .map(|clinical| (clinical_facts(&clinical), clinical.score))?,
+    };
+
+    let score = compute_lead_score(db, lead_type, &facts).await;
+
+    if score != stored_score
+        && !<Database as LeadDB>::set_score(db, get_lead_table(lead_type), uuid, score).await
+    {
+        return None;
+    }
+
+    Some(score)
+}
+
+pub async fn rescore_leads(db: &Data<Database>, lead_type: &LeadType, uuids: Vec<String>) {
+    for uuid in uuids {
+        if rescore_lead(db, lead_type, uuid.clone()).await.is_none() {
+            warn!("Unable to rescore {} lead {}", lead_type, uuid);
+        }
+    }
+}
+
+pub async fn rescore_all_leads(db: &Data<Database>) -> usize {
+    let now = Utc::now();
+    let mut updated = 0;
+
+    for lead_type_tag in get_lead_type_tags() {
+        let lead_type = lead_type_tag.value;
+        let rules = get_active_rules(db, &lead_type).await;
+        let stages = get_pipeline(db, &lead_type).await;
+
+        let leads: Vec<(String, ScoringFacts, i32)> = match lead_type {
+            LeadType::ENTERPRISE => <Database as EnterpriseDB>::find_all_active(db)
+                .await
+                .unwrap_or_default()
+                .into_iter()
+                .map(|lead| {
+                    (
+                        lead.uuid.clone().unwrap_or_default(),
+                        enterprise_facts(&lead),
+                        lead.score,
+                    )
+                })
+                .collect(),
+            LeadType::SCHOOL => <Database as SchoolDB>::find_all_active(db)
+                .await
+                .unwrap_or_default()
+                .into_iter()
+                .map(|lead| {
+                    (
+                        lead.uuid.clone().unwrap_or_default(),
+                        school_facts(&lead),
+                        lead.score,
+                    )
+                })
+                .collect(),
+            LeadType::CLINICAL => <Database as ClinicalDB>::find_all_non_deleted(db)
+                .await
+                .unwrap_or_default()
+                .into_iter()
+                .map(|lead| {
+                    (
+                        lead.uuid.clone().unwrap_or_default(),
+                        clinical_facts(&lead),
+                        lead.score,
+                    )
+                })
+                .collect(),
+        };
+
+        for (uuid, facts, stored_score) in leads {
+            let score = calculate_score(&facts, &rules, &stages, now);
+
+            if score != stored_score
+                && <Database as LeadDB>::set_score(db, get_lead_table(&lead_type), uuid, score)
+                    .await
+            {
+                updated += 1;
+            }
+        }
+    }
+
+    updated
+}
+
+pub async fn seed_default_scoring_rules(db: &Data<Database>) {
+    match <Database as ScoringDB>::find_all_non_deleted(db).await {
+        Some(rules) if rules.is_empty() => {
+            info!("Creating default scoring rules");
+            let date_created = Utc::now();
+
+            for (name, criterion, points, limit) in DEFAULT_RULES {
+                let rule = ScoringRule {
+                    uuid: None,
+                    name: name.to_string(),
+                    criterion,
+                    value: None,
+                    points,
+                    limit,
+                    lead_type: None,
+                    active: true,
+                    deleted: false,
+                    date_created: Some(date_created),
+                    date_modified: Some(date_created),
+                    created_by: None,
+                    modified_by: None,
+                };
+
+                if <Database as ScoringDB>::add_one(db, ScoringRule::new(get_uuid(), rule))
+                    .await
+                    .is_none()
+                {
+                    error!("Unable to create default scoring rule {}", name);
+                }
+            }
+        }
+        Some(_) => info!("Scoring rules already configured"),
+        None => warn!("Unable to read scoring rules"),
+    }
+}
+
+pub fn get_scoring_hour() -> u32 {
+    match set_environment_variable("SCORING_HOUR", &DEFAULT_SCORING_HOUR.to_string()).parse::<u32>()
+    {
+        Ok(hour) if hour < 24 => hour,
+        _ => {
+            warn!("Invalid SCORING_HOUR, using {}", DEFAULT_SCORING_HOUR);
+            DEFAULT_SCORING_HOUR
+        }
+    }
+}
+
+// SCORING_HOUR is a wall clock hour in DEFAULT_TIMEZONE.
+fn time_until_next_run(now: DateTime<Utc>, hour: u32) -> Duration {
+    let now_naive = now.with_timezone(&get_default_timezone()).naive_local();
+    let mut next_run = now_naive
+        .date()
+        .and_hms_opt(hour, 0, 0)
+        .unwrap_or(now_naive);
+
+    if next_run <= now_naive {
+        next_run += chrono::Duration::days(1);
+    }
+
+    (next_run - now_naive)
+        .to_std()
+        .unwrap_or(Duration::from_secs(60 * 60))
+}
+
+pub async fn schedule_nightly_scoring(db: Data<Database>) {
+    let hour = get_scoring_hour();
+    info!("Rescored {} leads on startup", rescore_all_leads(&db).await);
+
+    loop {
+        sleep(time_until_next_run(Utc::now(), hour)).await;
+        info!(
+            "Nightly scoring updated {} leads",
+            rescore_all_leads(&db).await
+        );
+    }
+}
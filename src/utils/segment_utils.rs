@@ -0,0 +1,105 @@
+use crate::{
+    models::{
+        lead_model::{LeadFilter, LeadSummary},
+        pipeline_model::PipelineStage,
+        service_model::Service,
+    },
+    utils::{
+        address_utils::find_mexican_state, pipeline_utils::find_stage,
+        service_utils::get_service_name, tag_utils::parse_tag_list,
+    },
+};
+
+const CSV_HEADER: [&str; 12] = [
+    "uuid",
+    "tipo",
+    "nombre",
+    "empresa",
+    "email",
+    "telefono",
+    "etapa",
+    "servicios",
+    "etiquetas",
+    "puntuacion",
+    "estado",
+    "municipio",
+];
+
+fn non_empty(value: Option<String>) -> Option<String> {
+    value
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+pub fn normalize_segment_filter(filter: LeadFilter) -> LeadFilter {
+    let tags = parse_tag_list(filter.tag.as_deref().unwrap_or_default());
+
+    LeadFilter {
+        tag: match tags.is_empty() {
+            true => None,
+            false => Some(tags.join(",")),
+        },
+        sales_funnel: non_empty(filter.sales_funnel),
+        service: non_empty(filter.service),
+        state: non_empty(filter.state).map(|state| match find_mexican_state(&state) {
+            Some(mexican_state) => mexican_state.to_string(),
+            None => state,
+        }),
+        municipality: non_empty(filter.municipality),
+        ..filter
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+pub fn leads_to_csv(
+    leads: &[LeadSummary],
+    services: &[Service],
+    stages: &[PipelineStage],
+) -> String {
+    let mut csv = CSV_HEADER.join(",");
+    csv.push_str("\r\n");
+
+    for lead in leads {
+        let stage_name = match find_stage(stages, &lead.sales_funnel) {
+            Some(stage) => stage.name.clone(),
+            None => lead.sales_funnel.clone(),
+        };
+        let service_names: Vec<String> = lead
+            .services_offered
+            .iter()
+            .map(|service_uuid| get_service_name(services, service_uuid))
+            .collect();
+
+        let row = [
+            lead.uuid.clone(),
+            lead.lead_type.to_string(),
+            lead.name.clone(),
+            lead.company.clone().unwrap_or_default(),
+            lead.email.clone().unwrap_or_default(),
+            lead.phone.clone().unwrap_or_default(),
+            stage_name,
+            service_names.join("; "),
+            lead.tags.join("; "),
+            lead.score.to_string(),
+            lead.address.state.clone().unwrap_or_default(),
+            lead.address.municipality.clone().unwrap_or_default(),
+        ];
+
+        csv.push_str(
+            &row.iter()
+                .map(|field| csv_field(field))
+                .collect::<Vec<String>>()
+                .join(","),
+        );
+        csv.push_str("\r\n");
+    }
+
+    csv
+}
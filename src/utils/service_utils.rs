@@ -0,0 +1,167 @@
+use actix_web::web::Data;
+use chrono::Utc;
+use log::{info, warn};
+
+use crate::{
+    constants::connection::set_environment_variable,
+    db::{
+        clinical_db::CLINICAL_TABLE, config::Database, enterprise_db::ENTERPRISE_TABLE,
+        school_db::SCHOOL_TABLE, service_db::ServiceDB,
+    },
+    models::service_model::Service,
+    utils::general_utils::get_uuid,
+};
+
+const LEGACY_SERVICES: [(&str, &str, &str); 5] = [
+    ("BRANDING", "BRANDING", "fas fa-copyright"),
+    ("WEBSERVICES", "WEB SERVICES", "fas fa-globe-americas"),
+    ("DIGITALSTRATEGY", "DIGITAL STRATEGY", "fas fa-robot"),
+    (
+        "ATTRACTIONOFNEWCLIENTS",
+        "ATTRACTION OF NEW CLIENTS",
+        "fas fa-magnet",
+    ),
+    (
+        "SALESMANAGEMENT",
+        "SALES MANAGEMENT",
+        "far fa-money-bill-alt",
+    ),
+];
+
+pub async fn get_service_catalog(db: &Data<Database>) -> Vec<Service> {
+    match <Database as ServiceDB>::find_all_active(db).await {
+        Some(services) => services,
+        None => {
+            warn!("Service catalog is empty");
+            vec![]
+        }
+    }
+}
+
+pub fn get_service_price(services: &[Service], service_uuid: &str) -> f64 {
+    match services
+        .iter()
+        .find(|service| service.uuid.as_deref() == Some(service_uuid))
+    {
+        Some(service) => service.price,
+        None => {
+            warn!("No price found for service {}", service_uuid);
+            0.0
+        }
+    }
+}
+
+pub fn get_service_name(services: &[Service], service_uuid: &str) -> String {
+    match services
+        .iter()
+        .find(|service| service.uuid.as_deref() == Some(service_uuid))
+    {
+        Some(service) => service.name.clone(),
+        None => service_uuid.to_string(),
+    }
+}
+
+fn get_legacy_price(legacy_code: &str) -> f64 {
+    let env_var = format!("SERVICE_PRICE_{}", legacy_code);
+
+    match set_environment_variable(&env_var, "0").parse::<f64>() {
+        Ok(price) => price,
+        Err(e) => {
+            warn!("Invalid price in {}: {}", &env_var, e);
+            0.0
+        }
+    }
+}
+
+async fn find_or_create_legacy_service(
+    db: &Data<Database>,
+    legacy_code: &str,
+    name: &str,
+    icon: &str,
+) -> Option<Service> {
+    if let Some(service) = Database::find_by_legacy_code(db, legacy_code.to_string()).await {
+        return Some(service);
+    }
+
+    let date_created = Utc::now();
+    let legacy_service = Service {
+        uuid: None,
+        name: name.to_string(),
+        description: None,
+        icon: Some(icon.to_string()),
+        price: get_legacy_price(legacy_code),
+        active: true,
+        legacy_code: Some(legacy_code.to_string()),
+        deleted: false,
+        date_created: Some(date_created),
+        date_modified: Some(date_created),
+        created_by: None,
+        modified_by: None,
+    };
+
+    info!("Creating service {} from legacy value", legacy_code);
+    <Database as ServiceDB>::add_one(db, Service::new(get_uuid(), legacy_service)).await
+}
+
+pub async fn migrate_legacy_services(db: &Data<Database>) -> Result<(), String> {
+    let mut legacy_ids: Vec<(String, String)> = Vec::new();
+
+    for (legacy_code, name, icon) in LEGACY_SERVICES {
+        match find_or_create_legacy_service(db, legacy_code, name, icon).await {
+            Some(Service {
+                uuid: Some(uuid), ..
+            }) => legacy_ids.push((legacy_code.to_string(), uuid)),
+            _ => return Err(format!("Unable to migrate legacy service {}", legacy_code)),
+        }
+    }
+
+    let mut failed_leads = 0;
+
+    for table_name in [ENTERPRISE_TABLE, SCHOOL_TABLE, CLINICAL_TABLE] {
+        let leads = match Database::find_lead_services(db, table_name).await {
+            Some(leads) => leads,
+            None => return Err(format!("Unable to read services from {}", table_name)),
+        };
+        let mut migrated_leads = 0;
+
+        for lead in leads {
+            let services_offered: Vec<String> = lead
+                .services_offered
+                .iter()
+                .map(|service| {
+                    match legacy_ids
+                        .iter()
+                        .find(|(legacy_code, _)| legacy_code == service)
+                    {
+                        Some((_, uuid)) => uuid.clone(),
+                        None => service.clone(),
+                    }
+                })
+                .collect();
+
+            if services_offered == lead.services_offered {
+                continue;
+            }
+
+            match Database::set_lead_services(db, table_name, lead.uuid, services_offered).await {
+                Some(_) => migrated_leads += 1,
+                None => failed_leads += 1,
+            }
+        }
+
+        if migrated_leads > 0 {
+            info!(
+                "Migrated services of {} records in {}",
+                migrated_leads, table_name
+            );
+        }
+    }
+
+    match failed_leads {
+        0 => Ok(()),
+        _ => Err(format!(
+            "Unable to migrate services of {} leads",
+            failed_leads
+        )),
+    }
+}
@@ -0,0 +1,172 @@
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration as StdDuration, Instant},
+};
+
+use actix_web::{
+    cookie::{time::Duration as CookieDuration, Cookie, SameSite},
+    web::Data,
+    HttpRequest,
+};
+use chrono::{Duration, Utc};
+use lazy_static::lazy_static;
+use log::warn;
+
+use crate::{
+    constants::connection::set_environment_variable,
+    db::{config::Database, session_db::SessionDB},
+    models::session_model::Session,
+    utils::{
+        env::{set_env_vars, ConfVars},
+        general_utils::get_uuid,
+    },
+};
+
+pub const SESSION_COOKIE: &str = "yay_session";
+const DEFAULT_SESSION_HOURS: i64 = 12;
+const DEFAULT_LOGIN_ATTEMPTS: usize = 5;
+const DEFAULT_LOGIN_WINDOW_SECONDS: u64 = 900;
+
+lazy_static! {
+    static ref FAILED_LOGINS: Mutex<HashMap<String, Vec<Instant>>> = Mutex::new(HashMap::new());
+}
+
+pub fn get_session_hours() -> i64 {
+    match set_environment_variable("SESSION_HOURS", &DEFAULT_SESSION_HOURS.to_string())
+        .parse::<i64>()
+    {
+        Ok(hours) if hours > 0 => hours,
+        _ => {
+            warn!("Invalid SESSION_HOURS, using {}", DEFAULT_SESSION_HOURS);
+            DEFAULT_SESSION_HOURS
+        }
+    }
+}
+
+pub async fn create_session(db: &Data<Database>, user_uuid: String) -> Option<Session> {
+    let date_created = Utc::now();
+    let session = Session {
+        uuid: None,
+        user_uuid,
+        expires_at: date_created + Duration::hours(get_session_hours()),
+        date_created: Some(date_created),
+    };
+
+    <Database as SessionDB>::add_one(db, Session::new(get_uuid(), session)).await
+}
+
+// The session uuid is the only secret, so scripts and other sites never get to see it.
+pub fn session_cookie(session_uuid: String) -> Cookie<'static> {
+    let ConfVars {
+        server_protocol, ..
+    } = set_env_vars();
+
+    Cookie::build(SESSION_COOKIE, session_uuid)
+        .path("/")
+        .http_only(true)
+        .secure(server_protocol == "https")
+        .same_site(SameSite::Strict)
+        .max_age(CookieDuration::hours(get_session_hours()))
+        .finish()
+}
+
+pub fn expired_session_cookie() -> Cookie<'static> {
+    let mut cookie = session_cookie("".to_string());
+    cookie.make_removal();
+    cookie
+}
+
+pub fn get_session_uuid(req: &HttpRequest) -> Option<String> {
+    req.cookie(SESSION_COOKIE)
+        .map(|cookie| cookie.value().to_string())
+        .filter(|session_uuid| !session_uuid.is_empty())
+}
+
+// An expired session is dropped the first time it shows up again.
+pub async fn find_session_user_uuid(db: &Data<Database>, session_uuid: String) -> Option<String> {
+    let session = <Database as SessionDB>::find_one(db, session_uuid.clone()).await?;
+
+    if session.expires_at <= Utc::now() {
+        <Database as SessionDB>::delete_one(db, session_uuid).await;
+        return None;
+    }
+
+    Some(session.user_uuid)
+}
+
+fn get_login_attempts() -> usize {
+    match set_environment_variable("LOGIN_ATTEMPTS", &DEFAULT_LOGIN_ATTEMPTS.to_string())
+        .parse::<usize>()
+    {
+        Ok(attempts) => attempts,
+        Err(e) => {
+            warn!("Invalid LOGIN_ATTEMPTS: {}", e);
+            DEFAULT_LOGIN_ATTEMPTS
+        }
+    }
+}
+
+fn get_login_window() -> StdDuration {
+    match set_environment_variable(
+        "LOGIN_WINDOW_SECONDS",
+        &DEFAULT_LOGIN_WINDOW_SECONDS.to_string(),
+    )
+    .parse::<u64>()
+    {
+        Ok(seconds) => StdDuration::from_secs(seconds),
+        Err(e) => {
+            warn!("Invalid LOGIN_WINDOW_SECONDS: {}", e);
+            StdDuration::from_secs(DEFAULT_LOGIN_WINDOW_SECONDS)
+        }
+    }
+}
+
+// Failed logins are counted per email, so guessing one account's password is
+// slowed down no matter how many addresses the attempts come from. Returns the
+// seconds to wait once the limit was reached.
+pub fn check_login_attempts(email: &str) -> Result<(), u64> {
+    let limit = get_login_attempts();
+    let window = get_login_window();
+    let now = Instant::now();
+
+    let mut failures = match FAILED_LOGINS.lock() {
+        Ok(failures) => failures,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+
+    failures.retain(|_, email_failures| {
+        email_failures.retain(|failure| now.duration_since(*failure) < window);
+        !email_failures.is_empty()
+    });
+
+    match failures.get(email) {
+        Some(email_failures) if email_failures.len() >= limit => {
+            let oldest = email_failures.first().copied().unwrap_or(now);
+            let retry_after = window.saturating_sub(now.duration_since(oldest));
+            Err(retry_after.as_secs().max(1))
+        }
+        _ => Ok(()),
+    }
+}
+
+pub fn record_failed_login(email: &str) {
+    let mut failures = match FAILED_LOGINS.lock() {
+        Ok(failures) => failures,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+
+    failures
+        .entry(email.to_string())
+        .or_default()
+        .push(Instant::now());
+}
+
+pub fn clear_failed_logins(email: &str) {
+    let mut failures = match FAILED_LOGINS.lock() {
+        Ok(failures) => failures,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+
+    failures.remove(email);
+}
@@ -0,0 +1,338 @@
+use actix_web::web::Data;
+use lazy_static::lazy_static;
+use log::{info, warn};
+use regex::Regex;
+use validator::ValidationError;
+
+use crate::{
+    db::{config::Database, lead_db::LeadDB},
+    models::lead_model::LeadSocialProfiles,
+    utils::{general_utils::get_lead_type_tags, lead_utils::get_lead_table},
+};
+
+lazy_static! {
+    static ref FACEBOOK_HANDLE: Regex = Regex::new(r"^[A-Za-z0-9.]{5,50}$").unwrap();
+    static ref FACEBOOK_PROFILE_ID: Regex = Regex::new(r"^profile\.php\?id=([0-9]+)").unwrap();
+    static ref INSTAGRAM_HANDLE: Regex = Regex::new(r"^[A-Za-z0-9._]{1,30}$").unwrap();
+    static ref LINKED_IN_SLUG: Regex = Regex::new(r"^[A-Za-z0-9\-_%]{3,100}$").unwrap();
+    static ref TIK_TOK_HANDLE: Regex = Regex::new(r"^[A-Za-z0-9._]{2,24}$").unwrap();
+    static ref TWITTER_HANDLE: Regex = Regex::new(r"^[A-Za-z0-9_]{1,15}$").unwrap();
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum SocialNetwork {
+    Facebook,
+    Instagram,
+    LinkedIn,
+    TikTok,
+    Twitter,
+}
+
+fn get_hosts(network: SocialNetwork) -> &'static [&'static str] {
+    match network {
+        SocialNetwork::Facebook => &["facebook.com", "m.facebook.com", "fb.com"],
+        SocialNetwork::Instagram => &["instagram.com"],
+        SocialNetwork::LinkedIn => &["linkedin.com", "mx.linkedin.com"],
+        SocialNetwork::TikTok => &["tiktok.com", "m.tiktok.com"],
+        SocialNetwork::Twitter => &["x.com", "twitter.com", "mobile.twitter.com"],
+    }
+}
+
+fn split_profile_url(network: SocialNetwork, raw: &str) -> Option<String> {
+    let without_scheme = raw
+        .trim_start_matches("https://")
+        .trim_start_matches("http://");
+    let (host, path) = without_scheme
+        .split_once('/')
+        .unwrap_or((without_scheme, ""));
+    let host = host.to_lowercase();
+    let host = host.trim_start_matches("www.");
+
+    match get_hosts(network).contains(&host) {
+        true => Some(path.trim_end_matches('/').to_string()),
+        false => None,
+    }
+}
+
+fn is_profile_url(raw: &str) -> bool {
+    raw.contains("://") || raw.contains(".com/") || raw.starts_with("www.")
+}
+
+fn first_segment(path: &str) -> &str {
+    path.split(['/', '?', '#']).next().unwrap_or_default()
+}
+
+fn normalize_facebook(raw: &str) -> Option<String> {
+    let path = match is_profile_url(raw) {
+        true => split_profile_url(SocialNetwork::Facebook, raw)?,
+        false => raw.trim_start_matches('@').to_string(),
+    };
+
+    if let Some(profile_id) = FACEBOOK_PROFILE_ID.captures(&path) {
+        return Some(format!(
+            "https://www.facebook.com/profile.php?id={}",
+            &profile_id[1]
+        ));
+    }
+
+    let handle = first_segment(&path);
+    match FACEBOOK_HANDLE.is_match(handle) {
+        true => Some(format!("https://www.facebook.com/{}", handle)),
+        false => None,
+    }
+}
+
+fn normalize_instagram(raw: &str) -> Option<String> {
+    let path = match is_profile_url(raw) {
+        true => split_profile_url(SocialNetwork::Instagram, raw)?,
+        false => raw.trim_start_matches('@').to_string(),
+    };
+
+    let handle = first_segment(&path);
+    match INSTAGRAM_HANDLE.is_match(handle) {
+        true => Some(format!(
+            "https://www.instagram.com/{}",
+            handle.to_lowercase()
+        )),
+        false => None,
+    }
+}
+
+fn normalize_linked_in(raw: &str) -> Option<String> {
+    let path = match is_profile_url(raw) {
+        true => split_profile_url(SocialNetwork::LinkedIn, raw)?,
+        false if raw.contains('/') => raw.trim_start_matches('/').to_string(),
+        false => format!("in/{}", raw.trim_start_matches('@')),
+    };
+
+    let mut segments = path.split(['/', '?', '#']);
+    let kind = segments.next().unwrap_or_default();
+    let slug = segments.next().unwrap_or_default();
+
+    match ["in", "company", "school"].contains(&kind) && LINKED_IN_SLUG.is_match(slug) {
+        true => Some(format!("https://www.linkedin.com/{}/{}", kind, slug)),
+        false => None,
+    }
+}
+
+fn normalize_tik_tok(raw: &str) -> Option<String> {
+    let path = match is_profile_url(raw) {
+        true => split_profile_url(SocialNetwork::TikTok, raw)?,
+        false => raw.to_string(),
+    };
+
+    let handle = first_segment(&path).trim_start_matches('@');
+    match TIK_TOK_HANDLE.is_match(handle) {
+        true => Some(format!("https://www.tiktok.com/@{}", handle.to_lowercase())),
+        false => None,
+    }
+}
+
+fn normalize_twitter(raw: &str) -> Option<String> {
+    let path = match is_profile_url(raw) {
+        true => split_profile_url(SocialNetwork::Twitter, raw)?,
+        false => raw.trim_start_matches('@').to_string(),
+    };
+
+    let handle = first_segment(&path);
+    match TWITTER_HANDLE.is_match(handle) {
+        true => Some(format!("https://x.com/{}", handle)),
+        false => None,
+    }
+}
+
+pub fn normalize_social_profile(network: SocialNetwork, raw_profile: &str) -> Option<String> {
+    let raw = raw_profile.trim();
+
+    match network {
+        SocialNetwork::Facebook => normalize_facebook(raw),
+        SocialNetwork::Instagram => normalize_instagram(raw),
+        SocialNetwork::LinkedIn => normalize_linked_in(raw),
+        SocialNetwork::TikTok => normalize_tik_tok(raw),
+        SocialNetwork::Twitter => normalize_twitter(raw),
+    }
+}
+
+pub fn normalize_optional_social_profile(
+    network: SocialNetwork,
+    raw_profile: Option<String>,
+) -> Option<String> {
+    match raw_profile {
+        Some(profile) if profile.trim().is_empty() => None,
+        Some(profile) => Some(normalize_social_profile(network, &profile).unwrap_or(profile)),
+        None => None,
+    }
+}
+
+fn validate_social_profile(
+    network: SocialNetwork,
+    raw_profile: &Option<String>,
+) -> Result<(), ValidationError> {
+    match raw_profile {
+        Some(profile)
+            if !profile.trim().is_empty()
+                && normalize_social_profile(network, profile).is_none() =>
+        {
+            Err(ValidationError::new("social_profile"))
+        }
+        _ => Ok(()),
+    }
+}
+
+pub fn validate_facebook(profile: &Option<String>) -> Result<(), ValidationError> {
+    validate_social_profile(SocialNetwork::Facebook, profile)
+}
+
+pub fn validate_instagram(profile: &Option<String>) -> Result<(), ValidationError> {
+    validate_social_profile(SocialNetwork::Instagram, profile)
+}
+
+pub fn validate_linked_in(profile: &Option<String>) -> Result<(), ValidationError> {
+    validate_social_profile(SocialNetwork::LinkedIn, profile)
+}
+
+pub fn validate_tik_tok(profile: &Option<String>) -> Result<(), ValidationError> {
+    validate_social_profile(SocialNetwork::TikTok, profile)
+}
+
+pub fn validate_twitter(profile: &Option<String>) -> Result<(), ValidationError> {
+    validate_social_profile(SocialNetwork::Twitter, profile)
+}
+
+pub fn normalize_social_profiles(profiles: LeadSocialProfiles) -> LeadSocialProfiles {
+    LeadSocialProfiles {
+        fb: normalize_optional_social_profile(SocialNetwork::Facebook, profiles.fb),
+        instagram: normalize_optional_social_profile(SocialNetwork::Instagram, profiles.instagram),
+        linked_in: normalize_optional_social_profile(SocialNetwork::LinkedIn, profiles.linked_in),
+        tik_tok: normalize_optional_social_profile(SocialNetwork::TikTok, profiles.tik_tok),
+        twitter: normalize_optional_social_profile(SocialNetwork::Twitter, profiles.twitter),
+        ..profiles
+    }
+}
+
+pub async fn normalize_existing_social_profiles(db: &Data<Database>) -> Result<(), String> {
+    let mut failed_records = 0;
+
+    for lead_type_tag in get_lead_type_tags() {
+        let table_name = get_lead_table(&lead_type_tag.value);
+        let records = match <Database as LeadDB>::find_social_profiles(db, table_name).await {
+            Some(records) => records,
+            None => {
+                return Err(format!(
+                    "Unable to read social profiles from {}",
+                    table_name
+                ))
+            }
+        };
+        let mut normalized_records = 0;
+
+        for record in records {
+            let normalized = normalize_social_profiles(record.clone());
+
+            if normalized == record {
+                continue;
+            }
+
+            if <Database as LeadDB>::set_social_profiles(db, table_name, normalized).await {
+                normalized_records += 1;
+            } else {
+                warn!(
+                    "Unable to normalize social profiles of {} in {}",
+                    record.uuid, table_name
+                );
+                failed_records += 1;
+            }
+        }
+
+        if normalized_records > 0 {
+            info!(
+                "Normalized social profiles of {} records in {}",
+                normalized_records, table_name
+            );
+        }
+    }
+
+    match failed_records {
+        0 => Ok(()),
+        _ => Err(format!(
+            "Unable to save {} normalized social profiles",
+            failed_records
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn normalize(network: SocialNetwork, raw: &str) -> Option<String> {
+        normalize_social_profile(network, raw)
+    }
+
+    #[test]
+    fn normalizes_handles_and_urls_to_canonical_urls() {
+        assert_eq!(
+            normalize(
+                SocialNetwork::Facebook,
+                "https://m.facebook.com/yay.crm/?ref=bookmarks"
+            )
+            .as_deref(),
+            Some("https://www.facebook.com/yay.crm")
+        );
+        assert_eq!(
+            normalize(SocialNetwork::Instagram, "@Yay_CRM").as_deref(),
+            Some("https://www.instagram.com/yay_crm")
+        );
+        assert_eq!(
+            normalize(SocialNetwork::LinkedIn, "linkedin.com/company/yay-crm/").as_deref(),
+            Some("https://www.linkedin.com/company/yay-crm")
+        );
+        assert_eq!(
+            normalize(
+                SocialNetwork::TikTok,
+                "https://www.tiktok.com/@YayCRM?lang=es"
+            )
+            .as_deref(),
+            Some("https://www.tiktok.com/@yaycrm")
+        );
+        assert_eq!(
+            normalize(SocialNetwork::Twitter, "http://twitter.com/yay_crm").as_deref(),
+            Some("https://x.com/yay_crm")
+        );
+    }
+
+    #[test]
+    fn keeps_facebook_profile_ids() {
+        assert_eq!(
+            normalize(
+                SocialNetwork::Facebook,
+                "www.facebook.com/profile.php?id=100012345678&sk=about"
+            )
+            .as_deref(),
+            Some("https://www.facebook.com/profile.php?id=100012345678")
+        );
+    }
+
+    #[test]
+    fn bare_linked_in_handles_are_personal_profiles() {
+        assert_eq!(
+            normalize(SocialNetwork::LinkedIn, "ana-gomez").as_deref(),
+            Some("https://www.linkedin.com/in/ana-gomez")
+        );
+    }
+
+    #[test]
+    fn rejects_urls_of_other_networks_and_invalid_handles() {
+        assert_eq!(
+            normalize(SocialNetwork::Instagram, "https://www.facebook.com/yay.crm"),
+            None
+        );
+        assert_eq!(
+            normalize(SocialNetwork::Twitter, "a_handle_that_is_too_long"),
+            None
+        );
+        assert_eq!(normalize(SocialNetwork::LinkedIn, "/jobs/view/123"), None);
+        assert!(validate_twitter(&Some("no vale".to_string())).is_err());
+        assert!(validate_twitter(&Some(" ".to_string())).is_ok());
+    }
+}
@@ -0,0 +1,88 @@
+use actix_web::web::Data;
+use chrono::Utc;
+use log::{error, info};
+
+use crate::{
+    db::{
+        config::Database,
+        tag_db::{TagDB, TAG_TABLE},
+    },
+    models::tag_model::Tag,
+    utils::{crud::util_patch_one, general_utils::get_uuid},
+};
+
+const MAX_TAG_LENGTH: usize = 40;
+
+pub fn normalize_tag(tag: &str) -> Option<String> {
+    let normalized: String = tag
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+        .to_lowercase()
+        .chars()
+        .take(MAX_TAG_LENGTH)
+        .collect();
+
+    match normalized.trim() {
+        "" => None,
+        trimmed => Some(trimmed.to_string()),
+    }
+}
+
+pub fn normalize_tags(tags: Vec<String>) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::with_capacity(tags.len());
+
+    for tag in tags.iter().filter_map(|tag| normalize_tag(tag)) {
+        if !normalized.contains(&tag) {
+            normalized.push(tag);
+        }
+    }
+
+    normalized
+}
+
+pub fn parse_tag_list(tags: &str) -> Vec<String> {
+    normalize_tags(tags.split(',').map(String::from).collect())
+}
+
+pub async fn register_tags(db: &Data<Database>, tags: &[String], created_by: Option<String>) {
+    for name in tags {
+        match <Database as TagDB>::find_by_name(db, name.clone()).await {
+            Some(tag) if !tag.deleted => continue,
+            Some(tag) => {
+                info!("Restoring deleted tag {}", name);
+                let restored: Option<Tag> = util_patch_one(
+                    db,
+                    tag.uuid.unwrap_or_default(),
+                    TAG_TABLE,
+                    "/deleted",
+                    false,
+                )
+                .await;
+                if restored.is_none() {
+                    error!("Unable to restore tag {}", name);
+                }
+                continue;
+            }
+            None => info!("Registering new tag {}", name),
+        }
+
+        let tag_uuid = get_uuid();
+        let new_tag = Tag::new(
+            tag_uuid,
+            Tag {
+                uuid: None,
+                name: name.clone(),
+                deleted: false,
+                date_created: Some(Utc::now()),
+                date_modified: Some(Utc::now()),
+                created_by: created_by.clone(),
+                modified_by: created_by.clone(),
+            },
+        );
+
+        if <Database as TagDB>::add_one(db, new_tag).await.is_none() {
+            error!("Unable to register tag {}", name);
+        }
+    }
+}
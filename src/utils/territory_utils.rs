@@ -0,0 +1,392 @@
+use actix_web::web::Data;
+use validator::ValidationError;
+
+use crate::{
+    db::{config::Database, quote_db::QuoteDB, territory_db::TerritoryDB, users_db::UsersDB},
+    models::{
+        address_model::Address,
+        lead_model::LeadSummary,
+        sales_model::LeadType,
+        territory_model::{
+            PostalCodeRange, Territory, TerritoryPipelineReport, TerritoryScope,
+            TerritoryStageSummary,
+        },
+        users_model::Roles,
+    },
+    utils::{
+        address_utils::{find_mexican_state, is_mexican_postal_code},
+        kanban_utils::build_kanban_board,
+        lead_utils::find_lead_summaries,
+        pipeline_utils::get_pipeline,
+        quote_utils::round_currency,
+        service_utils::get_service_catalog,
+    },
+};
+
+const POSTAL_CODE_RANK: u8 = 0;
+const MUNICIPALITY_RANK: u8 = 1;
+const STATE_RANK: u8 = 2;
+
+fn collapse_whitespace(value: &str) -> String {
+    value.split_whitespace().collect::<Vec<&str>>().join(" ")
+}
+
+fn dedupe_ignore_case(values: Vec<String>) -> Vec<String> {
+    let mut unique: Vec<String> = vec![];
+
+    for value in values {
+        if !value.is_empty()
+            && !unique
+                .iter()
+                .any(|v| v.to_lowercase() == value.to_lowercase())
+        {
+            unique.push(value);
+        }
+    }
+
+    unique
+}
+
+fn parse_postal_code(postal_code: &str) -> Option<u32> {
+    match is_mexican_postal_code(postal_code) {
+        true => postal_code.parse().ok(),
+        false => None,
+    }
+}
+
+pub fn validate_postal_code_ranges(ranges: &[PostalCodeRange]) -> Result<(), ValidationError> {
+    for range in ranges {
+        match (parse_postal_code(&range.from), parse_postal_code(&range.to)) {
+            (Some(from), Some(to)) if from <= to => continue,
+            _ => return Err(ValidationError::new("postal_code_range")),
+        }
+    }
+
+    Ok(())
+}
+
+pub fn normalize_territory(territory: Territory) -> Territory {
+    let states = territory
+        .states
+        .iter()
+        .map(|state| match find_mexican_state(state) {
+            Some(mexican_state) => mexican_state.to_string(),
+            None => collapse_whitespace(state),
+        })
+        .collect();
+    let municipalities = territory
+        .municipalities
+        .iter()
+        .map(|municipality| collapse_whitespace(municipality))
+        .collect();
+    let postal_code_ranges = territory
+        .postal_code_ranges
+        .iter()
+        .map(|range| {
+            let from = range.from.replace(' ', "");
+            let to = range.to.replace(' ', "");
+            PostalCodeRange {
+                to: if to.is_empty() { from.clone() } else { to },
+                from,
+            }
+        })
+        .filter(|range| !range.from.is_empty())
+        .collect();
+    let managers = territory
+        .managers
+        .iter()
+        .map(|manager| manager.trim().to_string())
+        .collect();
+
+    Territory {
+        states: dedupe_ignore_case(states),
+        municipalities: dedupe_ignore_case(municipalities),
+        postal_code_ranges,
+        managers: dedupe_ignore_case(managers),
+        assigned_to: territory.assigned_to.filter(|rep| !rep.trim().is_empty()),
+        ..territory
+    }
+}
+
+pub fn get_territory_errors(territory: &Territory) -> Vec<String> {
+    let mut key_errors_vec: Vec<String> = vec![];
+
+    for state in &territory.states {
+        if find_mexican_state(state).is_none() {
+            key_errors_vec.push(format!("Estado desconocido: {}", state));
+        }
+    }
+
+    if territory.states.is_empty()
+        && territory.municipalities.is_empty()
+        && territory.postal_code_ranges.is_empty()
+    {
+        key_errors_vec
+            .push("Un territorio necesita estados, municipios o rangos de C.P.".to_string());
+    }
+
+    key_errors_vec
+}
+
+pub fn territory_match_rank(territory: &Territory, address: &Address) -> Option<u8> {
+    let postal_code = address.postal_code.as_deref().and_then(parse_postal_code);
+    if let Some(code) = postal_code {
+        let in_range = territory.postal_code_ranges.iter().any(|range| {
+            match (parse_postal_code(&range.from), parse_postal_code(&range.to)) {
+                (Some(from), Some(to)) => (from..=to).contains(&code),
+                _ => false,
+            }
+        });
+        if in_range {
+            return Some(POSTAL_CODE_RANK);
+        }
+    }
+
+    if let Some(municipality) = address.municipality.as_deref() {
+        if territory
+            .municipalities
+            .iter()
+            .any(|m| m.to_lowercase() == municipality.to_lowercase())
+        {
+            return Some(MUNICIPALITY_RANK);
+        }
+    }
+
+    match address.state.as_deref() {
+        Some(state) if territory.states.iter().any(|s| s == state) => Some(STATE_RANK),
+        _ => None,
+    }
+}
+
+pub fn find_address_territory<'a>(
+    territories: &'a [Territory],
+    address: &Address,
+) -> Option<&'a Territory> {
+    territories
+        .iter()
+        .filter_map(|territory| {
+            territory_match_rank(territory, address).map(|rank| (rank, territory))
+        })
+        .min_by_key(|(rank, _)| *rank)
+        .map(|(_, territory)| territory)
+}
+
+pub async fn assign_lead_owner(
+    db: &Data<Database>,
+    assigned_to: Option<String>,
+    address: &Address,
+    created_by: Option<String>,
+) -> Option<String> {
+    if assigned_to.is_some() {
+        return assigned_to;
+    }
+
+    let territories = <Database as TerritoryDB>::find_all_active(db)
+        .await
+        .unwrap_or_default();
+
+    find_address_territory(&territories, address)
+        .and_then(|territory| territory.assigned_to.clone())
+        .or(created_by)
+}
+
+pub async fn find_territory_scope(
+    db: &Data<Database>,
+    user_uuid: Option<String>,
+) -> Option<TerritoryScope> {
+    let user = <Database as UsersDB>::find_one(db, user_uuid?).await?;
+    if user.deleted || user.role != Roles::MANAGER {
+        return None;
+    }
+
+    let territories = <Database as TerritoryDB>::find_all_active(db)
+        .await
+        .unwrap_or_default();
+    let managed_uuids = territories
+        .iter()
+        .filter(|territory| territory.managers.contains(&user.uuid))
+        .filter_map(|territory| territory.uuid.clone())
+        .collect();
+
+    Some(TerritoryScope {
+        territories,
+        managed_uuids,
+    })
+}
+
+pub fn is_in_scope(scope: &TerritoryScope, address: &Address) -> bool {
+    match find_address_territory(&scope.territories, address) {
+        Some(territory) => territory
+            .uuid
+            .as_ref()
+            .is_some_and(|uuid| scope.managed_uuids.contains(uuid)),
+        None => false,
+    }
+}
+
+pub async fn is_lead_in_scope(
+    db: &Data<Database>,
+    user_uuid: Option<String>,
+    address: &Address,
+) -> bool {
+    match find_territory_scope(db, user_uuid).await {
+        Some(scope) => is_in_scope(&scope, address),
+        None => true,
+    }
+}
+
+pub async fn scope_leads<T, F>(
+    db: &Data<Database>,
+    user_uuid: Option<String>,
+    leads: Vec<T>,
+    get_address: F,
+) -> Vec<T>
+where
+    T: Send,
+    F: Fn(&T) -> &Address + Send,
+{
+    match find_territory_scope(db, user_uuid).await {
+        Some(scope) => leads
+            .into_iter()
+            .filter(|lead| is_in_scope(&scope, get_address(lead)))
+            .collect(),
+        None => leads,
+    }
+}
+
+pub async fn build_territory_reports(
+    db: &Data<Database>,
+    territory: &Territory,
+    lead_types: Vec<LeadType>,
+) -> Vec<TerritoryPipelineReport> {
+    let territories = <Database as TerritoryDB>::find_all_active(db)
+        .await
+        .unwrap_or_default();
+    let services = get_service_catalog(db).await;
+    let quotes = <Database as QuoteDB>::find_all_active(db)
+        .await
+        .unwrap_or_default();
+    let territory_uuid = territory.uuid.clone().unwrap_or_default();
+
+    let mut reports = vec![];
+    for lead_type in lead_types {
+        let leads: Vec<LeadSummary> = find_lead_summaries(db, &lead_type, None)
+            .await
+            .into_iter()
+            .filter(|lead| {
+                find_address_territory(&territories, &lead.address)
+                    .is_some_and(|found| found.uuid.as_deref() == Some(territory_uuid.as_str()))
+            })
+            .collect();
+
+        let columns = build_kanban_board(
+            get_pipeline(db, &lead_type).await,
+            leads,
+            services.clone(),
+            quotes.clone(),
+        );
+        let stages: Vec<TerritoryStageSummary> = columns
+            .into_iter()
+            .map(|column| TerritoryStageSummary {
+                code: column.stage.value,
+                name: column.stage.text,
+                count: column.count,
+                total: column.total,
+            })
+            .collect();
+
+        reports.push(TerritoryPipelineReport {
+            territory_uuid: territory_uuid.clone(),
+            territory_name: territory.name.clone(),
+            lead_type,
+            count: stages.iter().map(|stage| stage.count).sum(),
+            total: round_currency(stages.iter().map(|stage| stage.total).sum()),
+            stages,
+        });
+    }
+
+    reports
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn range(from: &str, to: &str) -> PostalCodeRange {
+        PostalCodeRange {
+            from: from.to_string(),
+            to: to.to_string(),
+        }
+    }
+
+    fn territory(uuid: &str, states: &[&str], ranges: Vec<PostalCodeRange>) -> Territory {
+        Territory {
+            uuid: Some(uuid.to_string()),
+            name: uuid.to_string(),
+            states: states.iter().map(|state| state.to_string()).collect(),
+            municipalities: vec![],
+            postal_code_ranges: ranges,
+            assigned_to: None,
+            managers: vec![],
+            deleted: false,
+            date_created: None,
+            date_modified: None,
+            created_by: None,
+            modified_by: None,
+        }
+    }
+
+    fn address(state: &str, postal_code: &str) -> Address {
+        Address {
+            state: Some(state.to_string()),
+            postal_code: Some(postal_code.to_string()),
+            ..Address::default()
+        }
+    }
+
+    #[test]
+    fn validates_postal_code_ranges() {
+        assert!(validate_postal_code_ranges(&[range("44100", "44199")]).is_ok());
+        assert!(validate_postal_code_ranges(&[range("44100", "44100")]).is_ok());
+        assert!(validate_postal_code_ranges(&[range("44199", "44100")]).is_err());
+        assert!(validate_postal_code_ranges(&[range("441", "44199")]).is_err());
+    }
+
+    #[test]
+    fn postal_code_ranges_include_both_ends() {
+        let guadalajara = territory("gdl", &[], vec![range("44100", "44199")]);
+
+        for code in ["44100", "44150", "44199"] {
+            assert_eq!(
+                territory_match_rank(&guadalajara, &address("Jalisco", code)),
+                Some(POSTAL_CODE_RANK)
+            );
+        }
+        assert_eq!(
+            territory_match_rank(&guadalajara, &address("Jalisco", "44200")),
+            None
+        );
+    }
+
+    #[test]
+    fn postal_code_match_wins_over_state() {
+        let territories = vec![
+            territory("jalisco", &["Jalisco"], vec![]),
+            territory("gdl", &[], vec![range("44100", "44199")]),
+        ];
+
+        let found = find_address_territory(&territories, &address("Jalisco", "44130"));
+        assert_eq!(found.and_then(|t| t.uuid.as_deref()), Some("gdl"));
+
+        let found = find_address_territory(&territories, &address("Jalisco", "45000"));
+        assert_eq!(found.and_then(|t| t.uuid.as_deref()), Some("jalisco"));
+    }
+
+    #[test]
+    fn single_postal_codes_become_one_code_ranges() {
+        let normalized = normalize_territory(territory("gdl", &[], vec![range(" 44 100", "")]));
+
+        assert_eq!(normalized.postal_code_ranges[0].from, "44100");
+        assert_eq!(normalized.postal_code_ranges[0].to, "44100");
+    }
+}
@@ -1,69 +1,187 @@
+use actix_web::{dev::Payload, FromRequest, HttpRequest};
 use chrono::{prelude::*, DateTime};
-use std::collections::HashMap;
-
-pub fn format_date_in_language(date: &DateTime<Local>, lang: &str) -> String {
-    let months_en = vec![
-        "January",
-        "February",
-        "March",
-        "April",
-        "May",
-        "June",
-        "July",
-        "August",
-        "September",
-        "October",
-        "November",
-        "December",
-    ];
-
-    let months_es = vec![
-        "Enero",
-        "Febrero",
-        "Marzo",
-        "Abril",
-        "Mayo",
-        "Junio",
-        "Julio",
-        "Agosto",
-        "Septiembre",
-        "Octubre",
-        "Noviembre",
-        "Diciembre",
-    ];
-
-    let mut days_es: HashMap<String, String> = HashMap::new();
-    days_es.insert("Mon".to_string(), "Lun".to_string());
-    days_es.insert("Tue".to_string(), "Mar".to_string());
-    days_es.insert("Wed".to_string(), "Mié".to_string());
-    days_es.insert("Thu".to_string(), "Jue".to_string());
-    days_es.insert("Fri".to_string(), "Vie".to_string());
-    days_es.insert("Sat".to_string(), "Sab".to_string());
-    days_es.insert("Sun".to_string(), "Dom".to_string());
-
-    let mut months_map = HashMap::new();
-    months_map.insert("en", months_en.clone());
-    months_map.insert("es", months_es);
-
-    let month = date.month() as usize - 1;
-    let month_name = months_map.get(lang).unwrap_or(&months_en)[month];
-
-    let weekday_name = if lang == "en" {
-        &date.weekday().to_string()
-    } else {
-        match days_es.get(&date.weekday().to_string()) {
-            Some(day) => &day,
-            None => &date.weekday().to_string(),
-        }
+use chrono_tz::{Tz, TZ_VARIANTS};
+use futures::future::LocalBoxFuture;
+use lazy_static::lazy_static;
+use log::warn;
+use serde::{Deserialize, Deserializer};
+use validator::ValidationError;
+
+use crate::{
+    constants::connection::set_environment_variable,
+    models::{
+        locale_model::{Locale, TimezoneTag},
+        users_model::User,
+    },
+    utils::{
+        i18n_utils::{translate, translate_with},
+        role_utils::find_request_user,
+    },
+};
+
+pub const TIMEZONE_HEADER: &str = "X-Timezone";
+const DEFAULT_TIMEZONE_NAME: &str = "America/Mexico_City";
+
+lazy_static! {
+    static ref DEFAULT_TIMEZONE: Tz = {
+        let name = set_environment_variable("DEFAULT_TIMEZONE", DEFAULT_TIMEZONE_NAME);
+        parse_timezone(&name).unwrap_or_else(|| {
+            warn!(
+                "Invalid DEFAULT_TIMEZONE {}, using {}",
+                name, DEFAULT_TIMEZONE_NAME
+            );
+            Tz::America__Mexico_City
+        })
     };
+}
+
+pub fn get_default_timezone() -> Tz {
+    *DEFAULT_TIMEZONE
+}
+
+pub fn parse_timezone(name: &str) -> Option<Tz> {
+    name.trim().parse::<Tz>().ok()
+}
+
+pub fn get_user_timezone(user: &User) -> Tz {
+    user.timezone
+        .as_deref()
+        .and_then(parse_timezone)
+        .unwrap_or_else(get_default_timezone)
+}
+
+pub fn validate_timezone(timezone: &Option<String>) -> Result<(), ValidationError> {
+    match timezone.as_deref().map(parse_timezone) {
+        Some(None) => {
+            Err(ValidationError::new("timezone").with_message("validation.timezone".into()))
+        }
+        _ => Ok(()),
+    }
+}
+
+// Forms send "" for "use the default zone".
+pub fn deserialize_timezone<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let timezone: Option<String> = Option::deserialize(deserializer)?;
+    Ok(timezone
+        .map(|timezone| timezone.trim().to_string())
+        .filter(|timezone| !timezone.is_empty()))
+}
+
+pub fn create_timezone_tags(timezone: Option<&str>) -> Vec<TimezoneTag> {
+    TZ_VARIANTS
+        .iter()
+        .map(|tz| TimezoneTag {
+            value: tz.name().to_string(),
+            text: tz.name().replace('_', " "),
+            selected: timezone == Some(tz.name()),
+        })
+        .collect()
+}
+
+// Ambiguous times (DST fall back) take the first occurrence, skipped ones
+// (DST spring forward) move to the next valid hour.
+pub fn local_to_utc(datetime: NaiveDateTime, timezone: &Tz) -> DateTime<Utc> {
+    match timezone.from_local_datetime(&datetime).earliest() {
+        Some(local) => local.with_timezone(&Utc),
+        None => local_to_utc(datetime + chrono::Duration::hours(1), timezone),
+    }
+}
+
+// The offset is required, a value without one can't be told apart from a time
+// in some other viewer's zone. Browsers send toISOString() values.
+pub fn deserialize_optional_datetime<'de, D>(
+    deserializer: D,
+) -> Result<Option<DateTime<Utc>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value: Option<String> = Option::deserialize(deserializer)?;
 
-    format!(
-        "{}, {} de {} de {}, {}:{}",
-        weekday_name,
-        date.day().to_string(),
-        month_name,
-        date.year(),
-        date.hour(),
-        date.minute(),
+    match value.as_deref().map(str::trim) {
+        None | Some("") => Ok(None),
+        Some(value) => match DateTime::parse_from_rfc3339(value) {
+            Ok(datetime) => Ok(Some(datetime.with_timezone(&Utc))),
+            Err(_) => Err(serde::de::Error::custom(format!(
+                "invalid date {}, expected RFC 3339 with an offset",
+                value
+            ))),
+        },
+    }
+}
+
+pub fn get_month_name(month: u32, locale: &Locale) -> String {
+    translate(locale, &format!("month.{}", month))
+}
+
+pub fn get_weekday_name(weekday: Weekday, locale: &Locale) -> String {
+    translate(
+        locale,
+        &format!("weekday.{}", weekday.to_string().to_lowercase()),
+    )
+}
+
+pub fn format_month_in_language(date: &NaiveDate, locale: &Locale) -> String {
+    translate_with(
+        locale,
+        "date.month",
+        &[
+            ("month", &get_month_name(date.month(), locale)),
+            ("year", &date.year().to_string()),
+        ],
+    )
+}
+
+pub fn format_time(date: &DateTime<Utc>, timezone: &Tz) -> String {
+    date.with_timezone(timezone).format("%H:%M").to_string()
+}
+
+pub fn format_date_in_language(date: &DateTime<Utc>, locale: &Locale, timezone: &Tz) -> String {
+    let date = date.with_timezone(timezone);
+
+    translate_with(
+        locale,
+        "date.long",
+        &[
+            ("weekday", &get_weekday_name(date.weekday(), locale)),
+            ("day", &date.day().to_string()),
+            ("month", &get_month_name(date.month(), locale)),
+            ("year", &date.year().to_string()),
+            ("hour", &date.hour().to_string()),
+            ("minute", &format!("{:02}", date.minute())),
+        ],
     )
 }
+
+// The acting user's zone wins, then the one the browser reports, then DEFAULT_TIMEZONE.
+pub async fn resolve_timezone(req: &HttpRequest) -> Tz {
+    if let Some(timezone) = find_request_user(req)
+        .await
+        .and_then(|user| user.timezone)
+        .as_deref()
+        .and_then(parse_timezone)
+    {
+        return timezone;
+    }
+
+    req.headers()
+        .get(TIMEZONE_HEADER)
+        .and_then(|header| header.to_str().ok())
+        .and_then(parse_timezone)
+        .unwrap_or_else(get_default_timezone)
+}
+
+#[derive(Debug)]
+pub struct RequestTimezone(pub Tz);
+
+impl FromRequest for RequestTimezone {
+    type Error = actix_web::Error;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        let req = req.clone();
+        Box::pin(async move { Ok(RequestTimezone(resolve_timezone(&req).await)) })
+    }
+}
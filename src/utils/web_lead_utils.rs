@@ -0,0 +1,447 @@
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use actix_web::{web::Data, HttpRequest};
+use chrono::Utc;
+use lazy_static::lazy_static;
+use log::{error, info, warn};
+use serde_json::{json, Value};
+use validator::{Validate, ValidationErrors};
+
+use crate::{
+    constants::connection::set_environment_variable,
+    db::{
+        clinical_db::ClinicalDB, config::Database, email_template_db::EmailTemplateDB,
+        enterprise_db::EnterpriseDB, school_db::SchoolDB,
+    },
+    models::{
+        address_model::Address,
+        clinical_model::Clinical,
+        email_template_model::RenderedEmail,
+        enterprise_model::Enterprise,
+        lead_model::LeadSource,
+        sales_model::LeadType,
+        school_model::School,
+        web_lead_model::{CaptchaVerification, WebLeadForm, WebLeadWebhook},
+    },
+    utils::{
+        address_utils::normalize_address,
+        campaign_utils::find_campaign_by_code,
+        contact_utils::{clinical_person, enterprise_person, school_person, sync_primary_contact},
+        email_template_utils::render_email,
+        general_utils::get_uuid,
+        lead_utils::{clinical_summary, enterprise_summary, school_summary},
+        mail_utils::{is_mail_enabled, send_email},
+        notification_utils::notify_lead_changes,
+        phone_utils::{normalize_optional_phone, normalize_required_phone},
+        pipeline_utils::PROSPECT_STAGE,
+        scoring_utils::{clinical_facts, compute_lead_score, enterprise_facts, school_facts},
+        territory_utils::assign_lead_owner,
+        webhook_utils::post_webhook,
+    },
+};
+
+const DEFAULT_RATE_LIMIT: usize = 5;
+const DEFAULT_RATE_WINDOW_SECONDS: u64 = 600;
+const DEFAULT_CAPTCHA_VERIFY_URL: &str = "https://www.google.com/recaptcha/api/siteverify";
+const DEFAULT_CONFIRMATION_SUBJECT: &str = "Gracias por contactarnos";
+const DEFAULT_CONFIRMATION_HTML: &str = "<p>Hola {{name}},</p><p>Recibimos tu mensaje, un asesor se pondrá en contacto contigo muy pronto.</p>";
+const DEFAULT_CONFIRMATION_TEXT: &str =
+    "Hola {{name}},\n\nRecibimos tu mensaje, un asesor se pondrá en contacto contigo muy pronto.";
+
+lazy_static! {
+    static ref WEB_LEAD_HITS: Mutex<HashMap<String, Vec<Instant>>> = Mutex::new(HashMap::new());
+}
+
+fn get_rate_limit() -> usize {
+    match set_environment_variable("WEB_LEAD_RATE_LIMIT", &DEFAULT_RATE_LIMIT.to_string())
+        .parse::<usize>()
+    {
+        Ok(limit) => limit,
+        Err(e) => {
+            warn!("Invalid WEB_LEAD_RATE_LIMIT: {}", e);
+            DEFAULT_RATE_LIMIT
+        }
+    }
+}
+
+fn get_rate_window() -> Duration {
+    match set_environment_variable(
+        "WEB_LEAD_RATE_WINDOW_SECONDS",
+        &DEFAULT_RATE_WINDOW_SECONDS.to_string(),
+    )
+    .parse::<u64>()
+    {
+        Ok(seconds) => Duration::from_secs(seconds),
+        Err(e) => {
+            warn!("Invalid WEB_LEAD_RATE_WINDOW_SECONDS: {}", e);
+            Duration::from_secs(DEFAULT_RATE_WINDOW_SECONDS)
+        }
+    }
+}
+
+fn is_proxy_trusted() -> bool {
+    match set_environment_variable("TRUSTED_PROXY", "false").parse::<bool>() {
+        Ok(trusted) => trusted,
+        Err(e) => {
+            warn!("Invalid TRUSTED_PROXY: {}", e);
+            false
+        }
+    }
+}
+
+// Forwarded headers are set by the client, they are only read when a proxy we run
+// in front of the app overwrites them. Otherwise the socket address is the client.
+pub fn get_client_ip(req: &HttpRequest) -> Option<String> {
+    match is_proxy_trusted() {
+        true => req
+            .connection_info()
+            .realip_remote_addr()
+            .map(|addr| addr.to_string()),
+        false => req.peer_addr().map(|addr| addr.ip().to_string()),
+    }
+}
+
+// Returns the seconds the client has to wait when it went over the limit.
+pub fn check_rate_limit(client: &str) -> Result<(), u64> {
+    let limit = get_rate_limit();
+    let window = get_rate_window();
+    let now = Instant::now();
+
+    let mut hits = match WEB_LEAD_HITS.lock() {
+        Ok(hits) => hits,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+
+    hits.retain(|_, client_hits| {
+        client_hits.retain(|hit| now.duration_since(*hit) < window);
+        !client_hits.is_empty()
+    });
+
+    let client_hits = hits.entry(client.to_string()).or_default();
+    if client_hits.len() >= limit {
+        let oldest = client_hits.first().copied().unwrap_or(now);
+        let retry_after = window.saturating_sub(now.duration_since(oldest));
+        return Err(retry_after.as_secs().max(1));
+    }
+
+    client_hits.push(now);
+    Ok(())
+}
+
+pub fn is_honeypot_filled(form: &WebLeadForm) -> bool {
+    form.website
+        .as_ref()
+        .is_some_and(|website| !website.trim().is_empty())
+}
+
+pub async fn verify_captcha(token: Option<&str>, remote_ip: Option<&str>) -> bool {
+    let secret = set_environment_variable("WEB_LEAD_CAPTCHA_SECRET", "");
+    if secret.is_empty() {
+        return true;
+    }
+
+    let token = match token.map(str::trim).filter(|token| !token.is_empty()) {
+        Some(token) => token,
+        None => return false,
+    };
+
+    let verify_url =
+        set_environment_variable("WEB_LEAD_CAPTCHA_VERIFY_URL", DEFAULT_CAPTCHA_VERIFY_URL);
+    let mut params = vec![("secret", secret.as_str()), ("response", token)];
+    if let Some(remote_ip) = remote_ip {
+        params.push(("remoteip", remote_ip));
+    }
+
+    let response = match reqwest::Client::new()
+        .post(&verify_url)
+        .form(&params)
+        .send()
+        .await
+    {
+        Ok(response) => response,
+        Err(e) => {
+            error!("Couldn't verify captcha:: {}", e);
+            return false;
+        }
+    };
+
+    match response.json::<CaptchaVerification>().await {
+        Ok(verification) => verification.success,
+        Err(e) => {
+            error!("Invalid captcha verification response:: {}", e);
+            false
+        }
+    }
+}
+
+fn non_empty(value: Option<String>) -> Option<String> {
+    value
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+fn web_lead_address(form: &WebLeadForm) -> Address {
+    normalize_address(Address {
+        postal_code: form.postal_code.clone(),
+        ..Address::default()
+    })
+}
+
+pub fn web_lead_to_enterprise(form: &WebLeadForm) -> Enterprise {
+    let date_created = Utc::now();
+    let company_name = non_empty(form.organization.clone());
+
+    Enterprise {
+        uuid: None,
+        name: form.name.trim().to_string(),
+        last_name: form.last_name.trim().to_string(),
+        is_company: company_name.is_some(),
+        company_name,
+        line_of_business: None,
+        phone: normalize_optional_phone(non_empty(form.phone.clone())),
+        email: form.email.trim().to_string(),
+        deleted: false,
+        fb: None,
+        instagram: None,
+        linked_in: None,
+        tik_tok: None,
+        twitter: None,
+        first_contact_date: Some(date_created),
+        sales_funnel: PROSPECT_STAGE.to_string(),
+        notes: non_empty(form.message.clone()).unwrap_or_default(),
+        services_offered: vec![],
+        tags: vec![],
+        assigned_to: None,
+        score: 0,
+        account_uuid: None,
+        address: web_lead_address(form),
+        source: Some(form.source()),
+        resolution: None,
+        date_created: Some(date_created),
+        date_modified: Some(date_created),
+        created_by: None,
+        modified_by: None,
+    }
+}
+
+pub fn web_lead_to_school(form: &WebLeadForm) -> School {
+    let date_created = Utc::now();
+
+    School {
+        uuid: None,
+        name: form.name.trim().to_string(),
+        last_name: form.last_name.trim().to_string(),
+        school_name: non_empty(form.organization.clone()).unwrap_or_default(),
+        school_web: None,
+        school_email: None,
+        school_level: vec![],
+        email: form.email.trim().to_string(),
+        phone: normalize_required_phone(non_empty(form.phone.clone()).unwrap_or_default()),
+        deleted: false,
+        fb: None,
+        instagram: None,
+        linked_in: None,
+        tik_tok: None,
+        twitter: None,
+        first_contact_date: Some(date_created),
+        sales_funnel: PROSPECT_STAGE.to_string(),
+        notes: non_empty(form.message.clone()).unwrap_or_default(),
+        services_offered: vec![],
+        tags: vec![],
+        assigned_to: None,
+        score: 0,
+        account_uuid: None,
+        address: web_lead_address(form),
+        source: Some(form.source()),
+        date_created: Some(date_created),
+        date_modified: Some(date_created),
+        created_by: None,
+        modified_by: None,
+    }
+}
+
+pub fn web_lead_to_clinical(form: &WebLeadForm) -> Clinical {
+    let date_created = Utc::now();
+    let clinic_name = non_empty(form.organization.clone());
+
+    Clinical {
+        uuid: None,
+        name: form.name.trim().to_string(),
+        last_name: form.last_name.trim().to_string(),
+        is_company: clinic_name.is_some(),
+        clinic_name,
+        clinic_web: None,
+        clinic_email: None,
+        specialty: non_empty(form.specialty.clone()).unwrap_or_default(),
+        email: Some(form.email.trim().to_string()),
+        phone: normalize_required_phone(non_empty(form.phone.clone()).unwrap_or_default()),
+        deleted: false,
+        fb: None,
+        instagram: None,
+        linked_in: None,
+        tik_tok: None,
+        twitter: None,
+        first_contact_date: Some(date_created),
+        sales_funnel: PROSPECT_STAGE.to_string(),
+        notes: non_empty(form.message.clone()).unwrap_or_default(),
+        services_offered: vec![],
+        tags: vec![],
+        assigned_to: None,
+        score: 0,
+        account_uuid: None,
+        address: web_lead_address(form),
+        source: Some(form.source()),
+        date_created: Some(date_created),
+        date_modified: Some(date_created),
+        created_by: None,
+        modified_by: None,
+    }
+}
+
+// Saves the lead the same way the lead APIs do, web leads have no creator so
+// the owner only comes from the matching territory.
+pub async fn save_web_lead(
+    db: &Data<Database>,
+    lead_type: &LeadType,
+    form: &WebLeadForm,
+) -> Result<Option<(String, Value)>, ValidationErrors> {
+    let campaign_uuid = find_campaign_by_code(db, form.utm_campaign.as_deref())
+        .await
+        .and_then(|campaign| campaign.uuid);
+    let source = Some(LeadSource {
+        campaign_uuid,
+        ..form.source()
+    });
+
+    match lead_type {
+        LeadType::ENTERPRISE => {
+            let mut enterprise = web_lead_to_enterprise(form);
+            enterprise.validate()?;
+            enterprise.source = source;
+            enterprise.assigned_to = assign_lead_owner(db, None, &enterprise.address, None).await;
+            enterprise.score =
+                compute_lead_score(db, lead_type, &enterprise_facts(&enterprise)).await;
+
+            let saved =
+                <Database as EnterpriseDB>::add_one(db, Enterprise::new(get_uuid(), enterprise))
+                    .await;
+            if let Some(enterprise) = &saved {
+                sync_primary_contact(db, lead_type, enterprise_person(enterprise), None, None)
+                    .await;
+                notify_lead_changes(db, None, &enterprise_summary(enterprise.clone()), None).await;
+            }
+            Ok(saved.map(|enterprise| {
+                (
+                    enterprise.uuid.clone().unwrap_or_default(),
+                    json!(enterprise),
+                )
+            }))
+        }
+        LeadType::SCHOOL => {
+            let mut school = web_lead_to_school(form);
+            school.validate()?;
+            school.source = source;
+            school.assigned_to = assign_lead_owner(db, None, &school.address, None).await;
+            school.score = compute_lead_score(db, lead_type, &school_facts(&school)).await;
+
+            let saved = <Database as SchoolDB>::add_one(db, School::new(get_uuid(), school)).await;
+            if let Some(school) = &saved {
+                sync_primary_contact(db, lead_type, school_person(school), None, None).await;
+                notify_lead_changes(db, None, &school_summary(school.clone()), None).await;
+            }
+            Ok(saved.map(|school| (school.uuid.clone().unwrap_or_default(), json!(school))))
+        }
+        LeadType::CLINICAL => {
+            let mut clinical = web_lead_to_clinical(form);
+            clinical.validate()?;
+            clinical.source = source;
+            clinical.assigned_to = assign_lead_owner(db, None, &clinical.address, None).await;
+            clinical.score = compute_lead_score(db, lead_type, &clinical_facts(&clinical)).await;
+
+            let saved =
+                <Database as ClinicalDB>::add_one(db, Clinical::new(get_uuid(), clinical)).await;
+            if let Some(clinical) = &saved {
+                sync_primary_contact(db, lead_type, clinical_person(clinical), None, None).await;
+                notify_lead_changes(db, None, &clinical_summary(clinical.clone()), None).await;
+            }
+            Ok(saved.map(|clinical| (clinical.uuid.clone().unwrap_or_default(), json!(clinical))))
+        }
+    }
+}
+
+async fn render_confirmation(
+    db: &Data<Database>,
+    lead_type: &LeadType,
+    lead: &Value,
+) -> Option<RenderedEmail> {
+    let template_key = set_environment_variable("WEB_LEAD_EMAIL_TEMPLATE", "");
+    let template = match template_key.is_empty() {
+        true => None,
+        false => <Database as EmailTemplateDB>::find_all_current(db)
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .find(|template| {
+                template.template_key.as_deref() == Some(template_key.as_str())
+                    && &template.lead_type == lead_type
+            }),
+    };
+
+    let rendered = match &template {
+        Some(template) => render_email(
+            &template.subject,
+            &template.html_body,
+            &template.text_body,
+            lead,
+        ),
+        None => render_email(
+            DEFAULT_CONFIRMATION_SUBJECT,
+            DEFAULT_CONFIRMATION_HTML,
+            DEFAULT_CONFIRMATION_TEXT,
+            lead,
+        ),
+    };
+
+    match rendered {
+        Ok(email) => Some(email),
+        Err(e) => {
+            error!("Couldn't render web lead confirmation:: {}", e);
+            None
+        }
+    }
+}
+
+pub async fn notify_web_lead(
+    db: Data<Database>,
+    lead_type: LeadType,
+    lead_uuid: String,
+    lead: Value,
+    email: String,
+) {
+    let webhook_url = set_environment_variable("WEB_LEAD_WEBHOOK_URL", "");
+    if !webhook_url.is_empty() {
+        let payload = WebLeadWebhook {
+            event: "web_lead.created".to_string(),
+            lead_type: lead_type.clone(),
+            lead_uuid: lead_uuid.clone(),
+            lead: lead.clone(),
+        };
+        post_webhook(&webhook_url, &payload).await;
+    }
+
+    if !is_mail_enabled() {
+        info!(
+            "Mail is disabled, no confirmation for web lead {}",
+            &lead_uuid
+        );
+        return;
+    }
+
+    if let Some(confirmation) = render_confirmation(&db, &lead_type, &lead).await {
+        send_email(&email, &confirmation).await;
+    }
+}
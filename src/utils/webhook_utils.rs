@@ -0,0 +1,34 @@
+use std::time::Duration;
+
+use log::{error, info};
+use serde::Serialize;
+
+const WEBHOOK_TIMEOUT_SECONDS: u64 = 10;
+
+pub async fn post_webhook<T: Serialize>(url: &str, payload: &T) -> bool {
+    let client = match reqwest::Client::builder()
+        .timeout(Duration::from_secs(WEBHOOK_TIMEOUT_SECONDS))
+        .build()
+    {
+        Ok(client) => client,
+        Err(e) => {
+            error!("Couldn't build webhook client:: {}", e);
+            return false;
+        }
+    };
+
+    match client.post(url).json(payload).send().await {
+        Ok(response) if response.status().is_success() => {
+            info!("Webhook delivered to {}", url);
+            true
+        }
+        Ok(response) => {
+            error!("Webhook {} answered {}", url, response.status());
+            false
+        }
+        Err(e) => {
+            error!("Couldn't deliver webhook to {}:: {}", url, e);
+            false
+        }
+    }
+}